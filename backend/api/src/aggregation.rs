@@ -33,6 +33,66 @@ async fn run_aggregation(pool: &PgPool) -> Result<(), sqlx::Error> {
     // to ensure we always capture the freshest data.
     let rows_affected = sqlx::query(
         r#"
+        WITH recent AS (
+            SELECT * FROM analytics_events
+            WHERE created_at >= CURRENT_DATE - INTERVAL '1 day'
+        ),
+        daily AS (
+            SELECT
+                contract_id,
+                DATE(created_at) AS agg_date,
+
+                -- deployment counts
+                COUNT(*) FILTER (WHERE event_type = 'contract_deployed') AS deployment_count,
+                COUNT(DISTINCT user_address) FILTER (WHERE event_type = 'contract_deployed') AS unique_deployers,
+
+                -- other event counts
+                COUNT(*) FILTER (WHERE event_type = 'contract_verified') AS verification_count,
+                COUNT(*) FILTER (WHERE event_type = 'contract_published') AS publish_count,
+                COUNT(*) FILTER (WHERE event_type = 'version_created') AS version_count,
+
+                -- totals
+                COUNT(*) AS total_events,
+                COUNT(DISTINCT user_address) AS unique_users
+            FROM recent
+            GROUP BY contract_id, DATE(created_at)
+        ),
+        -- network breakdown as JSON object
+        networks AS (
+            SELECT contract_id, agg_date, jsonb_object_agg(network, cnt) AS breakdown
+            FROM (
+                SELECT contract_id, DATE(created_at) AS agg_date, network::text AS network, COUNT(*) AS cnt
+                FROM recent
+                WHERE network IS NOT NULL
+                GROUP BY contract_id, DATE(created_at), network
+            ) n
+            GROUP BY contract_id, agg_date
+        ),
+        -- top users as JSON array (top 10)
+        users AS (
+            SELECT
+                contract_id,
+                agg_date,
+                jsonb_agg(
+                    jsonb_build_object('address', user_address, 'count', cnt)
+                    ORDER BY cnt DESC
+                ) AS top
+            FROM (
+                SELECT
+                    contract_id,
+                    DATE(created_at) AS agg_date,
+                    user_address,
+                    COUNT(*) AS cnt,
+                    ROW_NUMBER() OVER (
+                        PARTITION BY contract_id, DATE(created_at) ORDER BY COUNT(*) DESC
+                    ) AS rank
+                FROM recent
+                WHERE user_address IS NOT NULL
+                GROUP BY contract_id, DATE(created_at), user_address
+            ) u
+            WHERE rank <= 10
+            GROUP BY contract_id, agg_date
+        )
         INSERT INTO analytics_daily_aggregates (
             contract_id, date,
             deployment_count, unique_deployers,
@@ -41,63 +101,15 @@ async fn run_aggregation(pool: &PgPool) -> Result<(), sqlx::Error> {
             network_breakdown, top_users
         )
         SELECT
-            e.contract_id,
-            DATE(e.created_at) AS agg_date,
-
-            -- deployment counts
-            COUNT(*) FILTER (WHERE e.event_type = 'contract_deployed') AS deployment_count,
-            COUNT(DISTINCT e.user_address) FILTER (WHERE e.event_type = 'contract_deployed') AS unique_deployers,
-
-            -- other event counts
-            COUNT(*) FILTER (WHERE e.event_type = 'contract_verified') AS verification_count,
-            COUNT(*) FILTER (WHERE e.event_type = 'contract_published') AS publish_count,
-            COUNT(*) FILTER (WHERE e.event_type = 'version_created') AS version_count,
-
-            -- totals
-            COUNT(*) AS total_events,
-            COUNT(DISTINCT e.user_address) AS unique_users,
-
-            -- network breakdown as JSON object
-            COALESCE(
-                jsonb_object_agg(
-                    COALESCE(e.network::text, 'unknown'),
-                    sub.net_count
-                ) FILTER (WHERE sub.net_count IS NOT NULL),
-                '{}'::jsonb
-            ) AS network_breakdown,
-
-            -- top users as JSON array (top 10)
-            COALESCE(
-                (
-                    SELECT jsonb_agg(
-                        jsonb_build_object('address', tu.user_address, 'count', tu.cnt)
-                        ORDER BY tu.cnt DESC
-                    )
-                    FROM (
-                        SELECT e2.user_address, COUNT(*) AS cnt
-                        FROM analytics_events e2
-                        WHERE e2.contract_id = e.contract_id
-                          AND DATE(e2.created_at) = DATE(e.created_at)
-                          AND e2.user_address IS NOT NULL
-                        GROUP BY e2.user_address
-                        ORDER BY cnt DESC
-                        LIMIT 10
-                    ) tu
-                ),
-                '[]'::jsonb
-            ) AS top_users
-
-        FROM analytics_events e
-        LEFT JOIN LATERAL (
-            SELECT e.network, COUNT(*) AS net_count
-            FROM analytics_events e3
-            WHERE e3.contract_id = e.contract_id
-              AND DATE(e3.created_at) = DATE(e.created_at)
-              AND e3.network IS NOT NULL
-            GROUP BY e3.network
-        ) sub ON true
-        WHERE e.created_at >= CURRENT_DATE - INTERVAL '1 day'
-        GROUP BY e.contract_id, DATE(e.created_at)
+            d.contract_id, d.agg_date,
+            d.deployment_count, d.unique_deployers,
+            d.verification_count, d.publish_count, d.version_count,
+            d.total_events, d.unique_users,
+            COALESCE(n.breakdown, '{}'::jsonb),
+            COALESCE(u.top, '[]'::jsonb)
+        FROM daily d
+        LEFT JOIN networks n USING (contract_id, agg_date)
+        LEFT JOIN users u USING (contract_id, agg_date)
 
         ON CONFLICT (contract_id, date) DO UPDATE SET
             deployment_count    = EXCLUDED.deployment_count,
@@ -0,0 +1,439 @@
+// api/src/artifact_handlers.rs
+//
+// Artifact endpoints backed by S3-compatible object storage.
+//
+// Routes (registered in artifact_routes.rs):
+//   POST /api/artifacts/uploads                – owner/auditor: start an upload, returns a pre-signed PUT
+//   POST /api/artifacts/uploads/:id/complete   – uploader: confirm the stored object matches its hash
+//
+// Audit report uploads are started by the filing auditor (API key); every other
+// kind by the contract's publisher (session).
+//   GET  /api/artifacts/:kind/:sha256          – pre-signed GET for a completed artifact
+//   GET  /api/contracts/:id/wasm               – pre-signed GET for a contract's wasm
+//
+// Wasm and source bundles are public.  Audit report PDFs are only handed out
+// to the audited contract's owner, the auditor who filed them and admins.
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use shared::{
+    ArtifactKind, ArtifactUpload, ArtifactUploadResponse, ArtifactUploadStatus,
+    CreateArtifactUploadRequest, PresignedUrl,
+};
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::{
+    audit_report_handlers::authenticate_auditor,
+    auth::{bearer_token, require_admin, require_contract_owner, require_publisher},
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    object_storage::{hex_sha256_to_base64, ObjectStorage, PresignedRequest},
+    quota::{self, QuotaKind},
+    state::AppState,
+    wasm_analysis::analyze_upload,
+};
+
+fn map_json_rejection(err: JsonRejection) -> ApiError {
+    ApiError::bad_request(
+        "InvalidRequest",
+        format!("Invalid JSON payload: {}", err.body_text()),
+    )
+}
+
+fn storage(state: &AppState) -> ApiResult<Arc<ObjectStorage>> {
+    state.storage.clone().ok_or_else(|| {
+        ApiError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "ObjectStorageNotConfigured",
+            "Artifact storage is not configured on this registry",
+        )
+    })
+}
+
+fn to_presigned_url(req: PresignedRequest) -> PresignedUrl {
+    PresignedUrl {
+        method: req.method,
+        url: req.url,
+        headers: req.headers.into_iter().collect(),
+        expires_at: req.expires_at,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/artifacts/uploads
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Register an upload and hand back a pre-signed PUT bound to the declared hash.
+pub async fn create_upload(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    payload: Result<Json<CreateArtifactUploadRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<ArtifactUploadResponse>)> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let storage = storage(&state)?;
+
+    let sha256 = req.sha256.trim().to_ascii_lowercase();
+    hex_sha256_to_base64(&sha256).map_err(|msg| ApiError::bad_request("InvalidSha256", msg))?;
+    if req.size_bytes <= 0 {
+        return Err(ApiError::bad_request(
+            "InvalidSize",
+            "size_bytes must be greater than zero",
+        ));
+    }
+
+    let (publisher_id, auditor_id) = if req.kind == ArtifactKind::AuditReport {
+        let auditor = authenticate_auditor(&state, &headers).await?;
+        let publisher_id: Uuid =
+            sqlx::query_scalar("SELECT publisher_id FROM contracts WHERE id = $1")
+                .bind(req.contract_id)
+                .fetch_optional(&state.db)
+                .await
+                .map_err(|err| db_internal_error("check contract exists", err))?
+                .ok_or_else(|| {
+                    ApiError::not_found(
+                        "ContractNotFound",
+                        format!("No contract found with ID: {}", req.contract_id),
+                    )
+                })?;
+        (publisher_id, Some(auditor.id))
+    } else {
+        let owner = require_contract_owner(&state, &headers, req.contract_id).await?;
+        (owner.publisher_id, None)
+    };
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin artifact upload", err))?;
+
+    // Holds the publisher row until the upload is recorded
+    if req.kind == ArtifactKind::Wasm {
+        quota::enforce(
+            &mut tx,
+            publisher_id,
+            &[(QuotaKind::WasmStorageBytes, req.size_bytes)],
+        )
+        .await?;
+    }
+
+    let object_key = ObjectStorage::artifact_key(&req.kind.to_string(), &sha256);
+    let presigned = storage
+        .presign_upload(&object_key, &sha256)
+        .map_err(|msg| ApiError::bad_request("InvalidSha256", msg))?;
+
+    let upload: ArtifactUpload = sqlx::query_as(
+        "INSERT INTO artifact_uploads
+             (contract_id, kind, object_key, sha256, size_bytes, publisher_id, auditor_id, upload_expires_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+         RETURNING *",
+    )
+    .bind(req.contract_id)
+    .bind(req.kind)
+    .bind(&object_key)
+    .bind(&sha256)
+    .bind(req.size_bytes)
+    .bind(auditor_id.is_none().then_some(publisher_id))
+    .bind(auditor_id)
+    .bind(presigned.expires_at)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("create artifact upload", err))?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit artifact upload", err))?;
+
+    tracing::info!(upload_id = %upload.id, kind = %req.kind, "artifact upload started");
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ArtifactUploadResponse {
+            upload,
+            upload_url: to_presigned_url(presigned),
+        }),
+    ))
+}
+
+/// Only whoever started an upload may act on it.  Uploads from before
+/// uploaders were recorded fall back to the contract's publisher.
+async fn authorize_uploader(
+    state: &AppState,
+    headers: &HeaderMap,
+    upload: &ArtifactUpload,
+) -> ApiResult<()> {
+    match (upload.auditor_id, upload.publisher_id) {
+        (Some(auditor_id), _) => {
+            if authenticate_auditor(state, headers).await?.id != auditor_id {
+                return Err(ApiError::new(
+                    StatusCode::FORBIDDEN,
+                    "NotUploader",
+                    "Only the auditor who started this upload can complete it",
+                ));
+            }
+        }
+        (None, Some(publisher_id)) => {
+            require_publisher(state, headers, publisher_id).await?;
+        }
+        (None, None) => {
+            require_contract_owner(state, headers, upload.contract_id).await?;
+        }
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/artifacts/uploads/:id/complete
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Confirm the object landed in the bucket with the declared size and hash.
+pub async fn complete_upload(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<ArtifactUpload>> {
+    let storage = storage(&state)?;
+
+    let upload: ArtifactUpload = sqlx::query_as("SELECT * FROM artifact_uploads WHERE id = $1")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| match err {
+            sqlx::Error::RowNotFound => ApiError::not_found(
+                "UploadNotFound",
+                format!("No artifact upload found with ID: {}", id),
+            ),
+            _ => db_internal_error("fetch artifact upload", err),
+        })?;
+    authorize_uploader(&state, &headers, &upload).await?;
+
+    if upload.status == ArtifactUploadStatus::Completed {
+        return Ok(Json(upload));
+    }
+
+    let metadata = storage.head_object(&upload.object_key).await.map_err(|err| {
+        tracing::error!(upload_id = %id, error = ?err, "object storage HEAD failed");
+        ApiError::new(
+            StatusCode::BAD_GATEWAY,
+            "ObjectStorageUnavailable",
+            "Could not reach artifact storage",
+        )
+    })?;
+
+    let Some(metadata) = metadata else {
+        return Err(ApiError::bad_request(
+            "UploadIncomplete",
+            "The artifact has not been uploaded yet",
+        ));
+    };
+
+    let expected_checksum = hex_sha256_to_base64(&upload.sha256).map_err(ApiError::internal)?;
+    let failure = if metadata.size_bytes != Some(upload.size_bytes) {
+        Some(format!(
+            "size mismatch: declared {} bytes, stored {:?}",
+            upload.size_bytes, metadata.size_bytes
+        ))
+    } else if metadata.checksum_sha256.as_deref() != Some(expected_checksum.as_str()) {
+        Some("stored object does not match the declared sha256".to_string())
+    } else {
+        None
+    };
+
+    let (status, error_message) = match &failure {
+        Some(reason) => (ArtifactUploadStatus::Failed, Some(reason.clone())),
+        None => (ArtifactUploadStatus::Completed, None),
+    };
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin artifact completion", err))?;
+
+    // A pending upload stops counting against the wasm quota once its URL
+    // expires, so a late completion has to fit the quota again
+    let expired = upload
+        .upload_expires_at
+        .is_none_or(|expires_at| expires_at <= Utc::now());
+    if failure.is_none() && upload.kind == ArtifactKind::Wasm && expired {
+        if let Some(publisher_id) = upload.publisher_id {
+            quota::enforce(
+                &mut tx,
+                publisher_id,
+                &[(QuotaKind::WasmStorageBytes, upload.size_bytes)],
+            )
+            .await?;
+        }
+    }
+
+    let updated: ArtifactUpload = sqlx::query_as(
+        "UPDATE artifact_uploads
+            SET status = $2,
+                error_message = $3,
+                completed_at = CASE WHEN $2 = 'completed'::artifact_upload_status THEN NOW() ELSE NULL END
+          WHERE id = $1
+          RETURNING *",
+    )
+    .bind(id)
+    .bind(&status)
+    .bind(&error_message)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("complete artifact upload", err))?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit artifact completion", err))?;
+
+    if let Some(reason) = failure {
+        tracing::warn!(upload_id = %id, reason = %reason, "artifact upload failed validation");
+        return Err(ApiError::bad_request("ArtifactHashMismatch", reason));
+    }
+
+    tracing::info!(upload_id = %id, "artifact upload completed");
+
+    // Feature detection is advisory; a wasm the parser rejects still uploads
+    if updated.kind == ArtifactKind::Wasm {
+        match analyze_upload(&state.db, &storage, &updated.object_key, &updated.sha256).await {
+            Ok(features) => tracing::info!(
+                upload_id = %id,
+                min_protocol_version = ?features.min_protocol_version,
+                "wasm features recorded"
+            ),
+            Err(err) => tracing::warn!(upload_id = %id, "wasm feature detection failed: {:#}", err),
+        }
+    }
+
+    Ok(Json(updated))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/artifacts/:kind/:sha256
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Pre-signed download URL for a completed artifact, addressed by content hash.
+pub async fn get_artifact(
+    State(state): State<AppState>,
+    Path((kind, sha256)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> ApiResult<Json<PresignedUrl>> {
+    let kind = match kind.as_str() {
+        "wasm" => ArtifactKind::Wasm,
+        "source_bundle" => ArtifactKind::SourceBundle,
+        "audit_report" => ArtifactKind::AuditReport,
+        other => {
+            return Err(ApiError::bad_request(
+                "InvalidArtifactKind",
+                format!("Unknown artifact kind: {}", other),
+            ))
+        }
+    };
+    let sha256 = sha256.to_ascii_lowercase();
+    if kind == ArtifactKind::AuditReport {
+        authorize_audit_report_download(&state, &headers, &sha256).await?;
+    }
+    presigned_download(&state, kind, &sha256).await
+}
+
+/// Allow an audit report download by an admin, the auditor who filed the
+/// report, or the owner of a contract it was filed for.
+async fn authorize_audit_report_download(
+    state: &AppState,
+    headers: &HeaderMap,
+    sha256: &str,
+) -> ApiResult<()> {
+    if bearer_token(headers).is_none() {
+        return Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "Unauthorized",
+            "Audit reports require signing in",
+        ));
+    }
+    if require_admin(&state.auth, headers).is_ok() {
+        return Ok(());
+    }
+
+    let filings: Vec<(Uuid, Option<Uuid>)> = sqlx::query_as(
+        "SELECT contract_id, auditor_id FROM artifact_uploads
+          WHERE kind = 'audit_report' AND sha256 = $1 AND status = 'completed'",
+    )
+    .bind(sha256)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("look up audit report filings", err))?;
+
+    if let Ok(auditor) = authenticate_auditor(state, headers).await {
+        if filings.iter().any(|(_, filed_by)| *filed_by == Some(auditor.id)) {
+            return Ok(());
+        }
+    }
+    for (contract_id, _) in &filings {
+        if require_contract_owner(state, headers, *contract_id).await.is_ok() {
+            return Ok(());
+        }
+    }
+
+    Err(ApiError::new(
+        StatusCode::FORBIDDEN,
+        "Forbidden",
+        "Audit reports are only available to the contract owner, the filing auditor and admins",
+    ))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/contracts/:id/wasm
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Pre-signed download URL for the wasm matching a contract's on-chain hash.
+pub async fn get_contract_wasm(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<PresignedUrl>> {
+    let wasm_hash: String = sqlx::query_scalar("SELECT wasm_hash FROM contracts WHERE id = $1")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| match err {
+            sqlx::Error::RowNotFound => ApiError::not_found(
+                "ContractNotFound",
+                format!("No contract found with ID: {}", id),
+            ),
+            _ => db_internal_error("get contract wasm hash", err),
+        })?;
+
+    presigned_download(&state, ArtifactKind::Wasm, &wasm_hash.to_ascii_lowercase()).await
+}
+
+async fn presigned_download(
+    state: &AppState,
+    kind: ArtifactKind,
+    sha256: &str,
+) -> ApiResult<Json<PresignedUrl>> {
+    let storage = storage(state)?;
+
+    let object_key: Option<String> = sqlx::query_scalar(
+        "SELECT object_key FROM artifact_uploads
+          WHERE kind = $1 AND sha256 = $2 AND status = 'completed'
+          ORDER BY completed_at DESC
+          LIMIT 1",
+    )
+    .bind(kind)
+    .bind(sha256)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("look up artifact", err))?;
+
+    let object_key = object_key.ok_or_else(|| {
+        ApiError::not_found(
+            "ArtifactNotFound",
+            format!("No stored {} artifact with sha256 {}", kind, sha256),
+        )
+    })?;
+
+    Ok(Json(to_presigned_url(storage.presign_download(&object_key))))
+}
@@ -0,0 +1,33 @@
+// api/src/artifact_routes.rs
+// Artifact upload/download routes (pre-signed object storage URLs).
+
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::{artifact_handlers, state::AppState};
+
+pub fn artifact_routes() -> Router<AppState> {
+    Router::new()
+        // Start a direct-to-bucket upload
+        .route(
+            "/api/artifacts/uploads",
+            post(artifact_handlers::create_upload),
+        )
+        // Validate the uploaded object against its declared hash
+        .route(
+            "/api/artifacts/uploads/:id/complete",
+            post(artifact_handlers::complete_upload),
+        )
+        // Content-addressed download; audit reports need the owner, auditor or an admin
+        .route(
+            "/api/artifacts/:kind/:sha256",
+            get(artifact_handlers::get_artifact),
+        )
+        // Download the wasm backing a registry contract
+        .route(
+            "/api/contracts/:id/wasm",
+            get(artifact_handlers::get_contract_wasm),
+        )
+}
@@ -0,0 +1,62 @@
+// api/src/attestation_handlers.rs
+//
+// Signed verification attestations.
+//
+// Routes (registered in attestation_routes.rs):
+//   GET /api/contracts/:id/attestation – latest signed attestation of the contract
+//
+// Attestations are written by the verifier after a successful verification.
+// The response carries the exact signed message and the signer's public key,
+// so anyone can check the Ed25519 signature without trusting this API; the
+// key should be compared with the one the registry publishes.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use shared::{attestation, AttestationResponse, VerificationAttestation};
+use uuid::Uuid;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    state::AppState,
+};
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/contracts/:id/attestation
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn get_attestation(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<AttestationResponse>> {
+    let attestation: VerificationAttestation = sqlx::query_as(
+        "SELECT * FROM verification_attestations
+          WHERE contract_id = $1
+          ORDER BY signed_at DESC
+          LIMIT 1",
+    )
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("get verification attestation", err))?
+    .ok_or_else(|| {
+        ApiError::not_found(
+            "AttestationNotFound",
+            format!("No verification attestation for contract {}", id),
+        )
+    })?;
+
+    let message = attestation::message(
+        &attestation.onchain_contract_id,
+        &attestation.network,
+        &attestation.wasm_hash,
+        attestation.source_commit.as_deref(),
+        attestation.signed_at,
+    );
+    Ok(Json(AttestationResponse {
+        attestation,
+        algorithm: attestation::ALGORITHM.to_string(),
+        message,
+    }))
+}
@@ -0,0 +1,13 @@
+// api/src/attestation_routes.rs
+// Verification attestation routes.
+
+use axum::{routing::get, Router};
+
+use crate::{attestation_handlers, state::AppState};
+
+pub fn attestation_routes() -> Router<AppState> {
+    Router::new().route(
+        "/api/contracts/:id/attestation",
+        get(attestation_handlers::get_attestation),
+    )
+}
@@ -0,0 +1,173 @@
+// api/src/audit_findings.rs
+//
+// Published JSON Schema for structured audit findings, and parsing of
+// uploaded findings into per-check statuses.
+//
+// Auditors submit one finding per checklist item they assessed; items not
+// mentioned stay `pending` on the resulting audit.
+
+use std::collections::HashSet;
+
+use jsonschema::JSONSchema;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+pub const FINDINGS_SCHEMA_VERSION: &str = "1.0";
+
+/// The findings schema served at GET /api/audit-reports/schema.
+pub fn findings_schema() -> Value {
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "Soroban Registry audit findings",
+        "type": "object",
+        "required": ["schema_version", "findings"],
+        "additionalProperties": false,
+        "properties": {
+            "schema_version": { "const": FINDINGS_SCHEMA_VERSION },
+            "summary": { "type": "string", "maxLength": 10000 },
+            "audit_date": { "type": "string", "format": "date-time" },
+            "findings": {
+                "type": "array",
+                "minItems": 1,
+                "items": {
+                    "type": "object",
+                    "required": ["check_id", "status"],
+                    "additionalProperties": false,
+                    "properties": {
+                        "check_id": { "type": "string", "minLength": 1 },
+                        "status": { "enum": ["passed", "failed", "not_applicable"] },
+                        "title": { "type": "string" },
+                        "description": { "type": "string" },
+                        "evidence": { "type": "string" }
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingStatus {
+    Passed,
+    Failed,
+    NotApplicable,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Finding {
+    pub check_id: String,
+    pub status: FindingStatus,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub evidence: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FindingsDocument {
+    pub schema_version: String,
+    pub summary: Option<String>,
+    pub audit_date: Option<chrono::DateTime<chrono::Utc>>,
+    pub findings: Vec<Finding>,
+}
+
+/// Validate `raw` against the findings schema and the known checklist ids.
+///
+/// Every problem is returned so auditors can fix a report in one pass.
+pub fn parse_findings(
+    raw: &Value,
+    known_check_ids: &HashSet<String>,
+) -> Result<FindingsDocument, Vec<String>> {
+    let schema = findings_schema();
+    let compiled = JSONSchema::compile(&schema).map_err(|err| vec![err.to_string()])?;
+    if let Err(errors) = compiled.validate(raw) {
+        return Err(errors
+            .map(|err| {
+                let path = err.instance_path.to_string();
+                if path.is_empty() {
+                    err.to_string()
+                } else {
+                    format!("{}: {}", path, err)
+                }
+            })
+            .collect());
+    }
+
+    let doc: FindingsDocument =
+        serde_json::from_value(raw.clone()).map_err(|err| vec![err.to_string()])?;
+
+    let mut errors = Vec::new();
+    let mut seen = HashSet::new();
+    for (i, finding) in doc.findings.iter().enumerate() {
+        if !known_check_ids.contains(&finding.check_id) {
+            errors.push(format!(
+                "/findings/{}/check_id: unknown checklist item '{}'",
+                i, finding.check_id
+            ));
+        }
+        if !seen.insert(finding.check_id.as_str()) {
+            errors.push(format!(
+                "/findings/{}/check_id: duplicate finding for '{}'",
+                i, finding.check_id
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(doc)
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn known() -> HashSet<String> {
+        ["IV-001", "AC-001"].iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_conforming_findings() {
+        let raw = json!({
+            "schema_version": "1.0",
+            "summary": "No critical issues",
+            "audit_date": "2024-06-01T00:00:00Z",
+            "findings": [
+                { "check_id": "IV-001", "status": "passed" },
+                { "check_id": "AC-001", "status": "failed", "evidence": "missing require_auth" }
+            ]
+        });
+        let doc = parse_findings(&raw, &known()).unwrap();
+        assert_eq!(doc.findings.len(), 2);
+        assert_eq!(doc.findings[1].status, FindingStatus::Failed);
+        assert!(doc.audit_date.is_some());
+    }
+
+    #[test]
+    fn rejects_schema_violations() {
+        let raw = json!({
+            "schema_version": "0.9",
+            "findings": [{ "check_id": "IV-001", "status": "maybe" }]
+        });
+        let errors = parse_findings(&raw, &known()).unwrap_err();
+        assert!(errors.len() >= 2);
+    }
+
+    #[test]
+    fn rejects_unknown_and_duplicate_checks() {
+        let raw = json!({
+            "schema_version": "1.0",
+            "findings": [
+                { "check_id": "IV-001", "status": "passed" },
+                { "check_id": "IV-001", "status": "failed" },
+                { "check_id": "XX-999", "status": "passed" }
+            ]
+        });
+        let errors = parse_findings(&raw, &known()).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().any(|e| e.contains("duplicate")));
+        assert!(errors.iter().any(|e| e.contains("XX-999")));
+    }
+}
@@ -7,7 +7,6 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
-use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::{
@@ -16,8 +15,8 @@ use crate::{
     scoring::{build_markdown_report, calculate_scores, score_badge},
     state::AppState,
 };
-use shared::models::{
-    AuditCheckRow, AuditRecord, AuditResponse, CategoryScore, CheckStatus, CheckWithStatus,
+use crate::models::{
+    AuditCheckRow, AuditRecord, AuditResponse, CheckStatus, CheckWithStatus,
     ContractSecuritySummary, CreateAuditRequest, DetectionMethod, ExportRequest,
     UpdateCheckRequest,
 };
@@ -0,0 +1,329 @@
+// api/src/audit_report_handlers.rs
+//
+// Third-party audit report intake.
+//
+// Routes (registered in audit_report_routes.rs):
+//   POST /api/auditors                    – admin: register an auditor, returns its API key once
+//   GET  /api/audit-reports/schema        – published JSON Schema for structured findings
+//   GET  /api/contracts/:id/audit-reports – reports on file for a contract
+//   POST /api/contracts/:id/audit-reports – auditor: submit a report
+//
+// Submitting a report expects the PDF to have been uploaded first as an
+// `audit_report` artifact (POST /api/artifacts/uploads) and names the contract
+// version that was audited.  The findings become a new security audit whose
+// checks are populated from the report, after which the audit score and the
+// contract's trust score are recomputed.
+
+use std::collections::{HashMap, HashSet};
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use rand::RngCore;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use shared::{
+    ArtifactKind, ArtifactUpload, ArtifactUploadStatus, AuditReport, AuditReportResponse, Auditor,
+    RegisterAuditorRequest, RegisterAuditorResponse, SubmitAuditReportRequest,
+};
+use uuid::Uuid;
+
+use crate::{
+    audit_findings::{findings_schema, parse_findings, FindingStatus},
+    auth::{bearer_token, optional_credential, require_admin, AUDITOR_CREDENTIAL},
+    checklist::all_checks,
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    health_monitor::refresh_contract_health,
+    models::{AuditCheckRow, CheckStatus},
+    scoring::calculate_scores,
+    state::AppState,
+};
+
+fn map_json_rejection(err: JsonRejection) -> ApiError {
+    ApiError::bad_request(
+        "InvalidRequest",
+        format!("Invalid JSON payload: {}", err.body_text()),
+    )
+}
+
+fn hash_api_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// Resolve the calling auditor from its bearer API key, or from a credential
+/// token exchanged for it.
+pub(crate) async fn authenticate_auditor(state: &AppState, headers: &HeaderMap) -> ApiResult<Auditor> {
+    let token = bearer_token(headers).ok_or_else(|| {
+        ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "Unauthorized",
+            "Missing auditor API key",
+        )
+    })?;
+
+    let query = match optional_credential(&state.auth, headers)? {
+        Some(claims) if claims.crd == AUDITOR_CREDENTIAL => sqlx::query_as(
+            "SELECT * FROM auditors
+              WHERE id = $1 AND left(api_key_hash, 16) = $2 AND revoked_at IS NULL",
+        )
+        .bind(claims.sub)
+        .bind(claims.key),
+        _ => sqlx::query_as(
+            "SELECT * FROM auditors WHERE api_key_hash = $1 AND revoked_at IS NULL",
+        )
+        .bind(hash_api_key(token)),
+    };
+    query
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("authenticate auditor", err))?
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::FORBIDDEN,
+                "Forbidden",
+                "API key does not belong to a registered auditor",
+            )
+        })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/auditors
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn register_auditor(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    payload: Result<Json<RegisterAuditorRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<RegisterAuditorResponse>)> {
+    require_admin(&state.auth, &headers)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+
+    if req.name.trim().is_empty() {
+        return Err(ApiError::bad_request("InvalidRequest", "name must not be empty"));
+    }
+
+    let mut raw = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut raw);
+    let api_key = format!("aud_{}", hex::encode(raw));
+
+    let auditor: Auditor = sqlx::query_as(
+        "INSERT INTO auditors (name, stellar_address, website, api_key_hash)
+         VALUES ($1, $2, $3, $4)
+         RETURNING *",
+    )
+    .bind(req.name.trim())
+    .bind(&req.stellar_address)
+    .bind(&req.website)
+    .bind(hash_api_key(&api_key))
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("register auditor", err))?;
+
+    tracing::info!(auditor_id = %auditor.id, name = %auditor.name, "auditor registered");
+    Ok((
+        StatusCode::CREATED,
+        Json(RegisterAuditorResponse { auditor, api_key }),
+    ))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/audit-reports/schema
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn get_findings_schema() -> Json<Value> {
+    Json(findings_schema())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/contracts/:id/audit-reports
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn list_audit_reports(
+    State(state): State<AppState>,
+    Path(contract_id): Path<Uuid>,
+) -> ApiResult<Json<Vec<AuditReport>>> {
+    let reports: Vec<AuditReport> = sqlx::query_as(
+        "SELECT * FROM audit_reports WHERE contract_id = $1 ORDER BY created_at DESC",
+    )
+    .bind(contract_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list audit reports", err))?;
+
+    Ok(Json(reports))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/contracts/:id/audit-reports
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn submit_audit_report(
+    State(state): State<AppState>,
+    Path(contract_id): Path<Uuid>,
+    headers: HeaderMap,
+    payload: Result<Json<SubmitAuditReportRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<AuditReportResponse>)> {
+    let auditor = authenticate_auditor(&state, &headers).await?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+
+    let checklist = all_checks();
+    let known: HashSet<String> = checklist.iter().map(|c| c.id.to_string()).collect();
+    let doc = parse_findings(&req.findings, &known).map_err(|errors| {
+        ApiError::bad_request(
+            "InvalidFindings",
+            format!("findings do not match the published schema: {}", errors.join("; ")),
+        )
+    })?;
+
+    let contract_version_id: Uuid = sqlx::query_scalar(
+        "SELECT id FROM contract_versions WHERE contract_id = $1 AND version = $2",
+    )
+    .bind(contract_id)
+    .bind(&req.version)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("get audited version", err))?
+    .ok_or_else(|| {
+        ApiError::not_found(
+            "VersionNotFound",
+            format!("No version {} found for contract {}", req.version, contract_id),
+        )
+    })?;
+
+    // The PDF must already be stored and hash-verified for this contract, by this auditor
+    let upload: ArtifactUpload = sqlx::query_as(
+        "SELECT * FROM artifact_uploads WHERE id = $1 AND contract_id = $2 AND auditor_id = $3",
+    )
+    .bind(req.artifact_upload_id)
+    .bind(contract_id)
+    .bind(auditor.id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("get audit report upload", err))?
+    .ok_or_else(|| {
+        ApiError::not_found(
+            "UploadNotFound",
+            format!("No upload {} for contract {}", req.artifact_upload_id, contract_id),
+        )
+    })?;
+
+    if upload.kind != ArtifactKind::AuditReport || upload.status != ArtifactUploadStatus::Completed
+    {
+        return Err(ApiError::bad_request(
+            "ReportNotUploaded",
+            "artifact_upload_id must reference a completed audit_report upload",
+        ));
+    }
+
+    let findings_by_check: HashMap<&str, _> = doc
+        .findings
+        .iter()
+        .map(|f| (f.check_id.as_str(), f))
+        .collect();
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin transaction", err))?;
+
+    let audit_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO security_audits (contract_id, auditor, audit_date, overall_score, summary)
+         VALUES ($1, $2, COALESCE($3, NOW()), 0.0, $4)
+         RETURNING id",
+    )
+    .bind(contract_id)
+    .bind(&auditor.name)
+    .bind(doc.audit_date)
+    .bind(&doc.summary)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("create security audit", err))?;
+
+    for item in &checklist {
+        let finding = findings_by_check.get(item.id.as_str());
+        let status = match finding.map(|f| f.status) {
+            Some(FindingStatus::Passed) => CheckStatus::Passed,
+            Some(FindingStatus::Failed) => CheckStatus::Failed,
+            Some(FindingStatus::NotApplicable) => CheckStatus::NotApplicable,
+            None => CheckStatus::Pending,
+        };
+        let notes = finding.and_then(|f| match (&f.title, &f.description) {
+            (Some(title), Some(desc)) => Some(format!("{}: {}", title, desc)),
+            (Some(text), None) | (None, Some(text)) => Some(text.clone()),
+            (None, None) => None,
+        });
+
+        sqlx::query(
+            "INSERT INTO audit_checks (audit_id, check_id, status, notes, auto_detected, evidence)
+             VALUES ($1, $2, $3, $4, FALSE, $5)",
+        )
+        .bind(audit_id)
+        .bind(&item.id)
+        .bind(&status)
+        .bind(&notes)
+        .bind(finding.and_then(|f| f.evidence.clone()))
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("insert audit check", err))?;
+    }
+
+    let report: AuditReport = sqlx::query_as(
+        "INSERT INTO audit_reports
+             (contract_id, contract_version_id, audit_id, auditor_id, artifact_upload_id,
+              findings, schema_version)
+         VALUES ($1, $2, $3, $4, $5, $6, $7)
+         RETURNING *",
+    )
+    .bind(contract_id)
+    .bind(contract_version_id)
+    .bind(audit_id)
+    .bind(auditor.id)
+    .bind(upload.id)
+    .bind(&req.findings)
+    .bind(&doc.schema_version)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("record audit report", err))?;
+
+    let check_rows: Vec<AuditCheckRow> =
+        sqlx::query_as("SELECT * FROM audit_checks WHERE audit_id = $1 ORDER BY check_id")
+            .bind(audit_id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("load audit checks", err))?;
+    let (overall_score, _) = calculate_scores(&check_rows);
+
+    sqlx::query("UPDATE security_audits SET overall_score = $1, updated_at = NOW() WHERE id = $2")
+        .bind(overall_score)
+        .bind(audit_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("update audit score", err))?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit audit report", err))?;
+
+    // Trust score refresh is best-effort; the hourly monitor catches up on failure
+    if let Err(err) = refresh_contract_health(&state.db, contract_id).await {
+        tracing::warn!(contract_id = %contract_id, error = ?err, "failed to refresh trust score");
+    }
+
+    tracing::info!(
+        report_id = %report.id,
+        contract_id = %contract_id,
+        auditor_id = %auditor.id,
+        findings = doc.findings.len(),
+        overall_score,
+        "audit report ingested"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(AuditReportResponse {
+            report,
+            overall_score,
+            mapped_checks: doc.findings.len(),
+        }),
+    ))
+}
@@ -0,0 +1,23 @@
+// api/src/audit_report_routes.rs
+// Audit report intake routes.
+
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::{audit_report_handlers, state::AppState};
+
+pub fn audit_report_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/auditors", post(audit_report_handlers::register_auditor))
+        .route(
+            "/api/audit-reports/schema",
+            get(audit_report_handlers::get_findings_schema),
+        )
+        .route(
+            "/api/contracts/:id/audit-reports",
+            get(audit_report_handlers::list_audit_reports)
+                .post(audit_report_handlers::submit_audit_report),
+        )
+}
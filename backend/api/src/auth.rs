@@ -0,0 +1,715 @@
+// api/src/auth.rs
+// Request authentication helpers.
+
+use axum::http::{header, HeaderMap, StatusCode};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use registry_config::ApiConfig;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::Sha256;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    auth_handlers::identity_publishers,
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    state::AppState,
+};
+
+/// Extract a bearer token from the `Authorization` header.
+pub fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+/// Admin token, admin identities and session signing secret, loaded once
+/// from the `[admin]` and `[sessions]` settings.
+#[derive(Clone, Default)]
+pub struct AuthKeys {
+    admin_token: Option<String>,
+    admin_identities: Vec<String>,
+    session_secret: Option<Vec<u8>>,
+}
+
+impl AuthKeys {
+    pub fn from_config(config: &ApiConfig) -> Self {
+        Self {
+            admin_token: config
+                .admin
+                .api_token
+                .as_ref()
+                .map(|t| t.expose().to_string())
+                .filter(|t| !t.is_empty()),
+            admin_identities: config.admin.identities.clone(),
+            session_secret: config
+                .sessions
+                .jwt_secret
+                .as_ref()
+                .map(|s| s.expose().as_bytes().to_vec()),
+        }
+    }
+}
+
+/// Who passed `require_admin`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdminIdentity {
+    /// The shared static token; says nothing about who is behind it
+    Token,
+    /// Session of a login identity listed in `admin.identities`, as its
+    /// stable `provider:subject`
+    Named(String),
+}
+
+impl AdminIdentity {
+    /// Require a named admin, for actions that must be attributable.
+    pub fn require_named(self) -> ApiResult<String> {
+        match self {
+            Self::Named(name) => Ok(name),
+            Self::Token => Err(ApiError::new(
+                StatusCode::FORBIDDEN,
+                "NamedAdminRequired",
+                "Sign in with an identity listed in admin.identities for this action",
+            )),
+        }
+    }
+}
+
+/// Require the static admin token (`admin.api_token`), a credential token
+/// exchanged for it, or the session of a listed admin identity
+/// (`admin.identities`).
+///
+/// Admin endpoints are disabled entirely (403) when neither is configured.
+pub fn require_admin(keys: &AuthKeys, headers: &HeaderMap) -> ApiResult<AdminIdentity> {
+    if keys.admin_token.is_none() && keys.admin_identities.is_empty() {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "AdminDisabled",
+            "Admin endpoints are not enabled on this registry",
+        ));
+    }
+
+    let Some(token) = bearer_token(headers) else {
+        return Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "Unauthorized",
+            "Missing bearer token",
+        ));
+    };
+    if let Some(expected) = keys.admin_token.as_deref() {
+        if constant_time_eq(token.as_bytes(), expected.as_bytes()) {
+            return Ok(AdminIdentity::Token);
+        }
+        if let Some(claims) = optional_credential(keys, headers)? {
+            if claims.crd == ADMIN_CREDENTIAL && claims.key == key_fingerprint(expected) {
+                return Ok(AdminIdentity::Token);
+            }
+        }
+    }
+    if !keys.admin_identities.is_empty() {
+        // Matched on the provider's subject: usernames can be changed or
+        // re-registered by someone else
+        let session = optional_session(keys, headers)?.filter(|s| !s.subject.is_empty());
+        if let Some(session) = session {
+            let label = format!("{}:{}", session.provider, session.subject);
+            if keys.admin_identities.contains(&label) {
+                return Ok(AdminIdentity::Named(label));
+            }
+        }
+    }
+    Err(ApiError::new(
+        StatusCode::FORBIDDEN,
+        "Forbidden",
+        "Invalid admin token",
+    ))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Publisher authorization
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Who is acting for a publisher in an owner-gated request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PublisherActor {
+    /// Login session of an identity linked to (or granted) the publisher
+    Session { identity_id: Uuid, label: String },
+    /// Admin impersonating the publisher
+    Impersonation { session_id: Uuid, admin: String },
+}
+
+impl PublisherActor {
+    /// The impersonation session and admin behind the request, if any.
+    pub fn impersonation(&self) -> Option<(Uuid, &str)> {
+        match self {
+            Self::Impersonation { session_id, admin } => Some((*session_id, admin.as_str())),
+            Self::Session { .. } => None,
+        }
+    }
+}
+
+/// A request authorized to act for a publisher.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublisherAuth {
+    pub publisher_id: Uuid,
+    /// The publisher's Stellar address, recorded as the owner in settings rows
+    pub address: String,
+    pub actor: PublisherActor,
+}
+
+/// Require the caller to act for `publisher_id`: a login session whose
+/// identity may publish for it, or an impersonation token scoped to it.
+/// Addresses in request bodies are never trusted for this.
+pub async fn require_publisher(
+    state: &AppState,
+    headers: &HeaderMap,
+    publisher_id: Uuid,
+) -> ApiResult<PublisherAuth> {
+    let address: String = sqlx::query_scalar("SELECT stellar_address FROM publishers WHERE id = $1")
+        .bind(publisher_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("get publisher", err))?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "PublisherNotFound",
+                format!("No publisher found with ID: {}", publisher_id),
+            )
+        })?;
+
+    let actor = if let Some(claims) = optional_impersonation(&state.auth, headers)? {
+        if claims.sub != publisher_id {
+            return Err(ApiError::new(
+                StatusCode::FORBIDDEN,
+                "NotAuthorizedPublisher",
+                "This impersonation session is scoped to another publisher",
+            ));
+        }
+        PublisherActor::Impersonation {
+            session_id: claims.sid,
+            admin: claims.act,
+        }
+    } else if let Some(session) = optional_session(&state.auth, headers)? {
+        let allowed = identity_publishers(&state.db, session.sub).await?;
+        if !allowed.contains(&publisher_id) {
+            return Err(ApiError::new(
+                StatusCode::FORBIDDEN,
+                "NotAuthorizedPublisher",
+                "This session may not act on behalf of that publisher",
+            ));
+        }
+        PublisherActor::Session {
+            identity_id: session.sub,
+            label: format!(
+                "{}:{}",
+                session.provider,
+                session.username.as_deref().unwrap_or("unknown")
+            ),
+        }
+    } else {
+        return Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "Unauthorized",
+            "Sign in as the publisher (session token) to make this change",
+        ));
+    };
+
+    Ok(PublisherAuth {
+        publisher_id,
+        address,
+        actor,
+    })
+}
+
+/// Require the caller to act for the contract's publisher (see
+/// `require_publisher`).  Used by owner-only contract settings.
+pub async fn require_contract_owner(
+    state: &AppState,
+    headers: &HeaderMap,
+    contract_id: Uuid,
+) -> ApiResult<PublisherAuth> {
+    let publisher_id: Uuid = sqlx::query_scalar("SELECT publisher_id FROM contracts WHERE id = $1")
+        .bind(contract_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("get contract owner", err))?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "ContractNotFound",
+                format!("No contract found with ID: {}", contract_id),
+            )
+        })?;
+
+    require_publisher(state, headers, publisher_id).await
+}
+
+/// Stellar addresses a session identity has proven control of: its own key
+/// for a Stellar-key login, and the address of the publisher it is linked to
+/// (linking requires a signed challenge).
+pub async fn proven_addresses(pool: &PgPool, identity_id: Uuid) -> ApiResult<Vec<String>> {
+    sqlx::query_scalar(
+        "SELECT subject FROM external_identities WHERE id = $1 AND provider = 'stellar'
+         UNION
+         SELECT p.stellar_address FROM external_identities i
+           JOIN publishers p ON p.id = i.publisher_id
+          WHERE i.id = $1",
+    )
+    .bind(identity_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|err| db_internal_error("resolve session addresses", err))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Web sessions (external login)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// How long a session issued after OIDC/GitHub login stays valid.
+pub const SESSION_TTL_HOURS: i64 = 12;
+
+/// Claims carried by a session token issued after OIDC/GitHub login.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SessionClaims {
+    /// `external_identities.id`
+    pub sub: Uuid,
+    pub provider: String,
+    /// `external_identities.subject`: the provider's stable account id
+    #[serde(default)]
+    pub subject: String,
+    pub username: Option<String>,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+/// Secret used to sign session and OAuth `state` tokens (`sessions.jwt_secret`,
+/// at least 32 bytes as checked at startup).
+///
+/// External login is disabled (403) unless a secret is set.
+pub fn session_secret(keys: &AuthKeys) -> ApiResult<&[u8]> {
+    keys.session_secret.as_deref().ok_or_else(|| {
+        ApiError::new(
+            StatusCode::FORBIDDEN,
+            "SessionsDisabled",
+            "External login is not enabled on this registry",
+        )
+    })
+}
+
+fn hmac_sha256(secret: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Encode `claims` as an HS256 JWT.
+pub fn sign_jwt<T: Serialize>(secret: &[u8], claims: &T) -> String {
+    let header = URL_SAFE_NO_PAD.encode(br#"{"alg":"HS256","typ":"JWT"}"#);
+    let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(claims).unwrap_or_default());
+    let signing_input = format!("{}.{}", header, payload);
+    let signature = URL_SAFE_NO_PAD.encode(hmac_sha256(secret, signing_input.as_bytes()));
+    format!("{}.{}", signing_input, signature)
+}
+
+/// Verify an HS256 JWT and decode its claims, rejecting tokens whose `exp`
+/// is before `now` (unix seconds).
+pub fn decode_jwt<T: DeserializeOwned>(secret: &[u8], token: &str, now: i64) -> Result<T, &'static str> {
+    let mut parts = token.split('.');
+    let (Some(header_b64), Some(payload_b64), Some(signature_b64), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err("malformed token");
+    };
+
+    let header: serde_json::Value = URL_SAFE_NO_PAD
+        .decode(header_b64)
+        .ok()
+        .and_then(|h| serde_json::from_slice(&h).ok())
+        .ok_or("malformed token header")?;
+    if header["alg"] != "HS256" {
+        return Err("unsupported token algorithm");
+    }
+
+    let signing_input = format!("{}.{}", header_b64, payload_b64);
+    let expected = hmac_sha256(secret, signing_input.as_bytes());
+    let signature = URL_SAFE_NO_PAD
+        .decode(signature_b64)
+        .map_err(|_| "malformed token signature")?;
+    if !constant_time_eq(&signature, &expected) {
+        return Err("invalid token signature");
+    }
+
+    let claims: serde_json::Value = URL_SAFE_NO_PAD
+        .decode(payload_b64)
+        .ok()
+        .and_then(|p| serde_json::from_slice(&p).ok())
+        .ok_or("malformed token payload")?;
+    match claims["exp"].as_i64() {
+        Some(exp) if exp >= now => {}
+        Some(_) => return Err("token has expired"),
+        None => return Err("token has no expiry"),
+    }
+    serde_json::from_value(claims).map_err(|_| "unexpected token claims")
+}
+
+/// Issue a session token for an external identity.
+pub fn issue_session(
+    keys: &AuthKeys,
+    identity_id: Uuid,
+    provider: &str,
+    subject: &str,
+    username: Option<String>,
+) -> ApiResult<(String, DateTime<Utc>)> {
+    let secret = session_secret(keys)?;
+    let now = Utc::now();
+    let expires_at = now + Duration::hours(SESSION_TTL_HOURS);
+    let claims = SessionClaims {
+        sub: identity_id,
+        provider: provider.to_string(),
+        subject: subject.to_string(),
+        username,
+        iat: now.timestamp(),
+        exp: expires_at.timestamp(),
+    };
+    Ok((sign_jwt(secret, &claims), expires_at))
+}
+
+/// Session claims from the bearer token, if the request carries a session
+/// token.  Other bearer tokens (admin, auditor keys, impersonation and
+/// credential tokens) yield `None`.
+pub fn optional_session(keys: &AuthKeys, headers: &HeaderMap) -> ApiResult<Option<SessionClaims>> {
+    let Some(token) = bearer_token(headers).filter(|t| {
+        t.matches('.').count() == 2 && !has_claim(t, "act") && !has_claim(t, "crd")
+    }) else {
+        return Ok(None);
+    };
+    let secret = session_secret(keys)?;
+    decode_jwt(secret, token, Utc::now().timestamp())
+        .map(Some)
+        .map_err(|reason| ApiError::new(StatusCode::UNAUTHORIZED, "InvalidSession", reason))
+}
+
+/// Require a valid session token issued by external login.
+pub fn require_session(keys: &AuthKeys, headers: &HeaderMap) -> ApiResult<SessionClaims> {
+    optional_session(keys, headers)?.ok_or_else(|| {
+        ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "Unauthorized",
+            "Missing session token",
+        )
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Admin impersonation
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Longest an impersonation token may live.
+pub const MAX_IMPERSONATION_MINUTES: i64 = 60;
+
+/// Claims of a short-lived token letting an admin act as a publisher.
+///
+/// `act` names the admin behind the request (as in RFC 8693); `sid` is the
+/// `impersonation_sessions` row, checked on every request so revocation is
+/// immediate.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ImpersonationClaims {
+    pub sid: Uuid,
+    /// Impersonated publisher
+    pub sub: Uuid,
+    pub act: String,
+    pub read_only: bool,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+/// Impersonation claims from the bearer token, if it is an impersonation token.
+pub fn optional_impersonation(
+    keys: &AuthKeys,
+    headers: &HeaderMap,
+) -> ApiResult<Option<ImpersonationClaims>> {
+    let Some(token) = bearer_token(headers).filter(|t| t.matches('.').count() == 2) else {
+        return Ok(None);
+    };
+    let Ok(secret) = session_secret(keys) else {
+        return Ok(None);
+    };
+    if !has_claim(token, "act") {
+        return Ok(None);
+    }
+    decode_jwt(secret, token, Utc::now().timestamp())
+        .map(Some)
+        .map_err(|reason| ApiError::new(StatusCode::UNAUTHORIZED, "InvalidImpersonation", reason))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Credential tokens
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub const ADMIN_CREDENTIAL: &str = "admin";
+pub const AUDITOR_CREDENTIAL: &str = "auditor";
+
+/// Claims of a session-length token standing in for the admin token or an
+/// auditor API key, so `soroban-registry login` never stores either.
+///
+/// `key` fingerprints the secret it was exchanged for: rotating the admin
+/// token or re-keying the auditor ends the token with it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CredentialClaims {
+    /// `admin` or `auditor`
+    pub crd: String,
+    /// Auditor id for auditor credentials
+    pub sub: Option<Uuid>,
+    pub key: String,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+/// Short, non-reversible fingerprint of a token or API key.  Matches the
+/// first 16 characters of the stored auditor `api_key_hash`.
+pub fn key_fingerprint(secret: &str) -> String {
+    use sha2::Digest;
+    hex::encode(Sha256::digest(secret.as_bytes()))[..16].to_string()
+}
+
+fn issue_credential(
+    keys: &AuthKeys,
+    crd: &str,
+    sub: Option<Uuid>,
+    key: String,
+) -> ApiResult<(String, DateTime<Utc>)> {
+    let secret = session_secret(keys)?;
+    let now = Utc::now();
+    let expires_at = now + Duration::hours(SESSION_TTL_HOURS);
+    let claims = CredentialClaims {
+        crd: crd.to_string(),
+        sub,
+        key,
+        iat: now.timestamp(),
+        exp: expires_at.timestamp(),
+    };
+    Ok((sign_jwt(secret, &claims), expires_at))
+}
+
+/// Credential token for the configured admin token.
+pub fn issue_admin_credential(keys: &AuthKeys) -> ApiResult<(String, DateTime<Utc>)> {
+    let admin_token = keys.admin_token.as_deref().ok_or_else(|| {
+        ApiError::new(
+            StatusCode::FORBIDDEN,
+            "AdminDisabled",
+            "Admin endpoints are not enabled on this registry",
+        )
+    })?;
+    issue_credential(keys, ADMIN_CREDENTIAL, None, key_fingerprint(admin_token))
+}
+
+/// Credential token for an auditor, given the API key it authenticated with.
+pub fn issue_auditor_credential(
+    keys: &AuthKeys,
+    auditor_id: Uuid,
+    api_key: &str,
+) -> ApiResult<(String, DateTime<Utc>)> {
+    issue_credential(keys, AUDITOR_CREDENTIAL, Some(auditor_id), key_fingerprint(api_key))
+}
+
+/// Credential claims from the bearer token, if it is a credential token.
+pub fn optional_credential(
+    keys: &AuthKeys,
+    headers: &HeaderMap,
+) -> ApiResult<Option<CredentialClaims>> {
+    let Some(token) = bearer_token(headers).filter(|t| t.matches('.').count() == 2) else {
+        return Ok(None);
+    };
+    let Ok(secret) = session_secret(keys) else {
+        return Ok(None);
+    };
+    if !has_claim(token, "crd") {
+        return Ok(None);
+    }
+    decode_jwt(secret, token, Utc::now().timestamp())
+        .map(Some)
+        .map_err(|reason| ApiError::new(StatusCode::UNAUTHORIZED, "InvalidCredentialToken", reason))
+}
+
+/// Whether a JWT carries `claim` (`act` for impersonation, `crd` for
+/// credential tokens), without verifying it.
+fn has_claim(token: &str, claim: &str) -> bool {
+    token
+        .split('.')
+        .nth(1)
+        .and_then(|payload| URL_SAFE_NO_PAD.decode(payload).ok())
+        .and_then(|payload| serde_json::from_slice::<serde_json::Value>(&payload).ok())
+        .is_some_and(|claims| claims.get(claim).is_some())
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::HeaderValue, response::IntoResponse};
+
+    #[test]
+    fn extracts_bearer_token() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(bearer_token(&headers), None);
+
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer abc123"));
+        assert_eq!(bearer_token(&headers), Some("abc123"));
+
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Basic abc123"));
+        assert_eq!(bearer_token(&headers), None);
+    }
+
+    #[test]
+    fn admin_token_comes_from_the_loaded_keys() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer admin-token"));
+
+        let disabled = AuthKeys::default();
+        assert_eq!(
+            require_admin(&disabled, &headers).unwrap_err().into_response().status(),
+            StatusCode::FORBIDDEN
+        );
+
+        let keys = AuthKeys {
+            admin_token: Some("admin-token".into()),
+            ..AuthKeys::default()
+        };
+        assert_eq!(require_admin(&keys, &headers).ok(), Some(AdminIdentity::Token));
+        assert!(session_secret(&keys).is_err());
+    }
+
+    #[test]
+    fn listed_identities_are_named_admins() {
+        let secret = b"0123456789abcdef0123456789abcdef";
+        let keys = AuthKeys {
+            admin_token: Some("admin-token".into()),
+            admin_identities: vec!["github:583231".into()],
+            session_secret: Some(secret.to_vec()),
+        };
+        let session_headers = |subject: &str, username: &str| {
+            let (token, _) =
+                issue_session(&keys, Uuid::nil(), "github", subject, Some(username.into()))
+                    .unwrap();
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                header::AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+            );
+            headers
+        };
+
+        assert_eq!(
+            require_admin(&keys, &session_headers("583231", "octocat")).ok(),
+            Some(AdminIdentity::Named("github:583231".into()))
+        );
+        // A renamed account keeps its subject
+        assert!(require_admin(&keys, &session_headers("583231", "octocat-renamed")).is_ok());
+        // Someone else registering the old username does not
+        assert!(require_admin(&keys, &session_headers("9999999", "octocat")).is_err());
+        assert!(AdminIdentity::Token.require_named().is_err());
+    }
+
+    #[test]
+    fn credential_tokens_stand_in_for_the_admin_token() {
+        let secret = b"0123456789abcdef0123456789abcdef";
+        let keys = AuthKeys {
+            admin_token: Some("admin-token".into()),
+            session_secret: Some(secret.to_vec()),
+            ..AuthKeys::default()
+        };
+        let (token, _) = issue_admin_credential(&keys).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+        );
+
+        assert_eq!(require_admin(&keys, &headers).ok(), Some(AdminIdentity::Token));
+        assert_eq!(optional_session(&keys, &headers).ok(), Some(None));
+
+        // Rotating the admin token retires credentials exchanged for the old one
+        let rotated = AuthKeys {
+            admin_token: Some("new-admin-token".into()),
+            ..keys.clone()
+        };
+        assert!(require_admin(&rotated, &headers).is_err());
+
+        let (auditor, _) = issue_auditor_credential(&keys, Uuid::nil(), "aud_key").unwrap();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", auditor)).unwrap(),
+        );
+        assert!(require_admin(&keys, &headers).is_err());
+    }
+
+    #[test]
+    fn session_jwt_round_trips_and_rejects_tampering() {
+        let secret = b"0123456789abcdef0123456789abcdef";
+        let claims = SessionClaims {
+            sub: Uuid::nil(),
+            provider: "github".into(),
+            subject: "583231".into(),
+            username: Some("octocat".into()),
+            iat: 1_000,
+            exp: 2_000,
+        };
+        let token = sign_jwt(secret, &claims);
+
+        assert_eq!(decode_jwt::<SessionClaims>(secret, &token, 1_500), Ok(claims));
+        assert_eq!(
+            decode_jwt::<SessionClaims>(secret, &token, 2_001),
+            Err("token has expired")
+        );
+        assert_eq!(
+            decode_jwt::<SessionClaims>(b"another-secret-another-secret-xx", &token, 1_500),
+            Err("invalid token signature")
+        );
+
+        let (head, _) = token.rsplit_once('.').unwrap();
+        let forged = format!("{}.{}", head, URL_SAFE_NO_PAD.encode(b"forged"));
+        assert!(decode_jwt::<SessionClaims>(secret, &forged, 1_500).is_err());
+    }
+
+    #[test]
+    fn impersonation_tokens_are_told_apart_from_sessions() {
+        let secret = b"0123456789abcdef0123456789abcdef";
+        let claims = ImpersonationClaims {
+            sid: Uuid::nil(),
+            sub: Uuid::nil(),
+            act: "support@registry".into(),
+            read_only: true,
+            iat: 1_000,
+            exp: 2_000,
+        };
+        let token = sign_jwt(secret, &claims);
+        assert!(has_claim(&token, "act"));
+        assert_eq!(decode_jwt::<ImpersonationClaims>(secret, &token, 1_500), Ok(claims));
+
+        let session = SessionClaims {
+            sub: Uuid::nil(),
+            provider: "github".into(),
+            subject: "583231".into(),
+            username: None,
+            iat: 1_000,
+            exp: 2_000,
+        };
+        assert!(!has_claim(&sign_jwt(secret, &session), "act"));
+    }
+
+    #[test]
+    fn constant_time_eq_compares_contents() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+    }
+}
@@ -0,0 +1,643 @@
+// api/src/auth_handlers.rs
+//
+// External login for the web UI (OIDC / GitHub) and publisher linking.
+//
+// Routes (registered in auth_routes.rs):
+//   GET  /api/auth/providers              – configured login providers
+//   GET  /api/auth/:provider/login        – redirect to the provider
+//   GET  /api/auth/:provider/callback     – finish login, issue a session token
+//   GET  /api/auth/session                – session: identity + publish rights
+//   GET  /api/auth/whoami                 – any credential: who is calling
+//   POST /api/auth/token                  – admin token / auditor key: credential token
+//   POST /api/auth/challenge              – key challenge for a Stellar address
+//   POST /api/auth/stellar                – signed challenge: session for that key
+//   POST /api/auth/link                   – session + signed challenge: link to a publisher
+//   GET  /api/publishers/:id/org-grants   – list org grants
+//   POST /api/publishers/:id/org-grants   – linked session: let an org's members publish
+//
+// Sessions are HS256 JWTs (see auth.rs).  A session may publish for the
+// publisher it is linked to and for every publisher that granted one of its
+// orgs.  Linking always requires a challenge signed by the publisher's key
+// (key_challenge.rs); a Stellar-key login is an identity of provider
+// `stellar` linked to its own address.  Stellar-key publishing without a
+// session is unchanged.
+
+use axum::{
+    extract::{
+        rejection::{JsonRejection, QueryRejection},
+        Path, Query, State,
+    },
+    http::{header, HeaderMap, StatusCode},
+    response::Redirect,
+    Json,
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use shared::{
+    AuthProviderInfo, AuthSessionResponse, ExternalIdentityRecord, GrantOrgRequest,
+    CredentialTokenResponse, KeyChallengeRequest, KeyChallengeResponse, KeyProof, Publisher,
+    PublisherOrgGrant, WhoAmIResponse,
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::{
+    audit_report_handlers::authenticate_auditor,
+    auth::{
+        bearer_token, decode_jwt, issue_admin_credential, issue_auditor_credential,
+        issue_session, optional_credential, optional_impersonation, optional_session,
+        require_admin, require_session, session_secret, sign_jwt, AdminIdentity,
+        ADMIN_CREDENTIAL, AUDITOR_CREDENTIAL,
+    },
+    auth_providers::AuthProvider,
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    key_challenge::{consume_nonce, issue_challenge, redeem_challenge},
+    state::AppState,
+};
+
+/// How long the user has to complete the provider's login page.
+const LOGIN_STATE_TTL_MINUTES: i64 = 10;
+
+/// Cookie binding a login's `state` to the browser that started it.
+const LOGIN_NONCE_COOKIE: &str = "registry_login_nonce";
+
+/// Provider name of identities created by Stellar-key login.
+const STELLAR_PROVIDER: &str = "stellar";
+
+fn map_json_rejection(err: JsonRejection) -> ApiError {
+    ApiError::bad_request(
+        "InvalidRequest",
+        format!("Invalid JSON payload: {}", err.body_text()),
+    )
+}
+
+fn map_query_rejection(err: QueryRejection) -> ApiError {
+    ApiError::bad_request(
+        "InvalidQuery",
+        format!("Invalid query parameters: {}", err.body_text()),
+    )
+}
+
+/// Signed OAuth `state`, binding the callback to the provider it was issued
+/// for and expiring after `LOGIN_STATE_TTL_MINUTES`.  `nonce` must match the
+/// browser's login cookie and is redeemed once.
+#[derive(Debug, Serialize, Deserialize)]
+struct LoginState {
+    provider: String,
+    nonce: Uuid,
+    exp: i64,
+}
+
+fn find_provider(state: &AppState, name: &str) -> ApiResult<Arc<dyn AuthProvider>> {
+    state
+        .auth_providers
+        .iter()
+        .find(|p| p.name() == name)
+        .cloned()
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "ProviderNotFound",
+                format!("Login provider '{}' is not configured", name),
+            )
+        })
+}
+
+/// Publishers a session identity may publish for: its direct link plus every
+/// publisher that granted one of its orgs on the same provider.
+pub(crate) async fn identity_publishers(pool: &PgPool, identity_id: Uuid) -> ApiResult<Vec<Uuid>> {
+    sqlx::query_scalar(
+        "SELECT publisher_id FROM external_identities
+          WHERE id = $1 AND publisher_id IS NOT NULL
+         UNION
+         SELECT g.publisher_id FROM publisher_org_grants g
+           JOIN external_identities i ON i.provider = g.provider AND g.org = ANY(i.orgs)
+          WHERE i.id = $1",
+    )
+    .bind(identity_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|err| db_internal_error("resolve identity publishers", err))
+}
+
+fn login_cookie(nonce: &str, max_age: i64) -> String {
+    format!(
+        "{}={}; Path=/api/auth; Max-Age={}; HttpOnly; Secure; SameSite=Lax",
+        LOGIN_NONCE_COOKIE, nonce, max_age
+    )
+}
+
+/// Value of the login nonce cookie sent with the callback.
+fn login_cookie_nonce(headers: &HeaderMap) -> Option<Uuid> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(name, _)| *name == LOGIN_NONCE_COOKIE)
+        .and_then(|(_, value)| value.parse().ok())
+}
+
+async fn fetch_identity(pool: &PgPool, id: Uuid) -> ApiResult<ExternalIdentityRecord> {
+    sqlx::query_as("SELECT * FROM external_identities WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|err| db_internal_error("get external identity", err))?
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::UNAUTHORIZED,
+                "InvalidSession",
+                "Session identity no longer exists",
+            )
+        })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/auth/providers
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn list_providers(State(state): State<AppState>) -> Json<Vec<AuthProviderInfo>> {
+    Json(
+        state
+            .auth_providers
+            .iter()
+            .map(|p| AuthProviderInfo {
+                name: p.name().to_string(),
+                kind: p.kind().to_string(),
+                login_url: format!("/api/auth/{}/login", p.name()),
+            })
+            .collect(),
+    )
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/auth/:provider/login
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn login(
+    State(state): State<AppState>,
+    Path(provider): Path<String>,
+) -> ApiResult<([(header::HeaderName, String); 1], Redirect)> {
+    let provider = find_provider(&state, &provider)?;
+    let secret = session_secret(&state.auth)?;
+
+    let nonce = Uuid::new_v4();
+    let login_state = sign_jwt(
+        secret,
+        &LoginState {
+            provider: provider.name().to_string(),
+            nonce,
+            exp: (Utc::now() + Duration::minutes(LOGIN_STATE_TTL_MINUTES)).timestamp(),
+        },
+    );
+
+    Ok((
+        [(
+            header::SET_COOKIE,
+            login_cookie(&nonce.to_string(), LOGIN_STATE_TTL_MINUTES * 60),
+        )],
+        Redirect::temporary(&provider.authorize_url(&login_state)),
+    ))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/auth/:provider/callback
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct CallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+}
+
+pub async fn callback(
+    State(state): State<AppState>,
+    Path(provider): Path<String>,
+    headers: HeaderMap,
+    params: Result<Query<CallbackQuery>, QueryRejection>,
+) -> ApiResult<([(header::HeaderName, String); 1], Json<AuthSessionResponse>)> {
+    let Query(params) = params.map_err(map_query_rejection)?;
+    let provider = find_provider(&state, &provider)?;
+
+    if let Some(error) = params.error {
+        return Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "LoginDenied",
+            format!("{} login failed: {}", provider.name(), error),
+        ));
+    }
+    let (Some(code), Some(login_state)) = (params.code, params.state) else {
+        return Err(ApiError::bad_request(
+            "InvalidQuery",
+            "code and state are required",
+        ));
+    };
+
+    let secret = session_secret(&state.auth)?;
+    let login_state: LoginState = decode_jwt(secret, &login_state, Utc::now().timestamp())
+        .map_err(|reason| ApiError::bad_request("InvalidLoginState", reason))?;
+    if login_state.provider != provider.name() {
+        return Err(ApiError::bad_request(
+            "InvalidLoginState",
+            "login state was issued for a different provider",
+        ));
+    }
+    // The state must come back to the browser that started the login, once
+    if login_cookie_nonce(&headers) != Some(login_state.nonce) {
+        return Err(ApiError::bad_request(
+            "InvalidLoginState",
+            "login state does not belong to this browser; start the login again",
+        ));
+    }
+    consume_nonce(&state.db, login_state.nonce, login_state.exp).await?;
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .unwrap_or_default();
+    let external = provider.exchange_code(&client, &code).await.map_err(|reason| {
+        tracing::warn!(provider = provider.name(), reason = %reason, "external login failed");
+        ApiError::new(StatusCode::BAD_GATEWAY, "LoginFailed", reason)
+    })?;
+
+    let identity: ExternalIdentityRecord = sqlx::query_as(
+        "INSERT INTO external_identities (provider, subject, username, email, orgs)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (provider, subject) DO UPDATE
+            SET username = EXCLUDED.username,
+                email = EXCLUDED.email,
+                orgs = EXCLUDED.orgs,
+                last_login_at = NOW()
+         RETURNING *",
+    )
+    .bind(&external.provider)
+    .bind(&external.subject)
+    .bind(&external.username)
+    .bind(&external.email)
+    .bind(&external.orgs)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("upsert external identity", err))?;
+
+    let (token, expires_at) = issue_session(
+        &state.auth,
+        identity.id,
+        &identity.provider,
+        &identity.subject,
+        identity.username.clone(),
+    )?;
+    let publishers = identity_publishers(&state.db, identity.id).await?;
+
+    tracing::info!(
+        provider = %identity.provider,
+        identity_id = %identity.id,
+        "external login succeeded"
+    );
+
+    Ok((
+        [(header::SET_COOKIE, login_cookie("", 0))],
+        Json(AuthSessionResponse {
+            token: Some(token),
+            expires_at: Some(expires_at),
+            identity,
+            publishers,
+        }),
+    ))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/auth/session
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn get_session(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<AuthSessionResponse>> {
+    let session = require_session(&state.auth, &headers)?;
+    let identity = fetch_identity(&state.db, session.sub).await?;
+    let publishers = identity_publishers(&state.db, identity.id).await?;
+
+    Ok(Json(AuthSessionResponse {
+        token: None,
+        expires_at: None,
+        identity,
+        publishers,
+    }))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/auth/whoami
+// ─────────────────────────────────────────────────────────────────────────────
+/// Identify the bearer credential: a login session, an impersonation token,
+/// the admin token or an auditor API key.  Used by `soroban-registry login` to validate tokens.
+pub async fn whoami(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<WhoAmIResponse>> {
+    if bearer_token(&headers).is_none() {
+        return Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "Unauthorized",
+            "Missing bearer token",
+        ));
+    }
+
+    // Session and impersonation tokens are only checked when sessions are enabled
+    if let Ok(Some(claims)) = optional_impersonation(&state.auth, &headers) {
+        return Ok(Json(WhoAmIResponse {
+            kind: "impersonation".to_string(),
+            name: Some(claims.act),
+            publishers: vec![claims.sub],
+        }));
+    }
+    if let Ok(Some(session)) = optional_session(&state.auth, &headers) {
+        let publishers = identity_publishers(&state.db, session.sub).await?;
+        return Ok(Json(WhoAmIResponse {
+            kind: "session".to_string(),
+            name: session.username,
+            publishers,
+        }));
+    }
+
+    if require_admin(&state.auth, &headers).is_ok() {
+        return Ok(Json(WhoAmIResponse {
+            kind: "admin".to_string(),
+            name: None,
+            publishers: Vec::new(),
+        }));
+    }
+
+    let auditor = authenticate_auditor(&state, &headers).await.map_err(|_| {
+        ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "InvalidToken",
+            "Token is not a valid session, admin token or auditor API key",
+        )
+    })?;
+    Ok(Json(WhoAmIResponse {
+        kind: "auditor".to_string(),
+        name: Some(auditor.name),
+        publishers: Vec::new(),
+    }))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/auth/token
+// ─────────────────────────────────────────────────────────────────────────────
+/// Exchange the admin token or an auditor API key for a session-length
+/// credential token, which is what `soroban-registry login` stores.  Session
+/// tokens are already short-lived and are used as they are.
+pub async fn exchange_token(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<CredentialTokenResponse>> {
+    let Some(bearer) = bearer_token(&headers) else {
+        return Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "Unauthorized",
+            "Missing bearer token",
+        ));
+    };
+
+    if matches!(require_admin(&state.auth, &headers), Ok(AdminIdentity::Token)) {
+        let (token, expires_at) = issue_admin_credential(&state.auth)?;
+        return Ok(Json(CredentialTokenResponse {
+            token,
+            expires_at,
+            kind: ADMIN_CREDENTIAL.to_string(),
+            name: None,
+        }));
+    }
+
+    // A credential token cannot be re-exchanged for an auditor: its key is only a fingerprint
+    if optional_credential(&state.auth, &headers)?.is_none() {
+        if let Ok(auditor) = authenticate_auditor(&state, &headers).await {
+            let (token, expires_at) = issue_auditor_credential(&state.auth, auditor.id, bearer)?;
+            return Ok(Json(CredentialTokenResponse {
+                token,
+                expires_at,
+                kind: AUDITOR_CREDENTIAL.to_string(),
+                name: Some(auditor.name),
+            }));
+        }
+    }
+
+    Err(ApiError::bad_request(
+        "NotExchangeable",
+        "Only the admin token or an auditor API key can be exchanged",
+    ))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/auth/challenge
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn create_challenge(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    payload: Result<Json<KeyChallengeRequest>, JsonRejection>,
+) -> ApiResult<Json<KeyChallengeResponse>> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    // Challenges requested with a session can only be redeemed by it
+    let identity = optional_session(&state.auth, &headers)?.map(|s| s.sub);
+
+    let (challenge, expires_at) = issue_challenge(&state.auth, req.address.trim(), identity)?;
+    Ok(Json(KeyChallengeResponse {
+        challenge,
+        expires_at,
+    }))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/auth/stellar
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn stellar_login(
+    State(state): State<AppState>,
+    payload: Result<Json<KeyProof>, JsonRejection>,
+) -> ApiResult<Json<AuthSessionResponse>> {
+    let Json(proof) = payload.map_err(map_json_rejection)?;
+    let challenge =
+        redeem_challenge(&state.db, &state.auth, &proof.challenge, &proof.signature).await?;
+
+    let publisher: Publisher = sqlx::query_as(
+        "INSERT INTO publishers (stellar_address) VALUES ($1)
+         ON CONFLICT (stellar_address) DO UPDATE SET stellar_address = EXCLUDED.stellar_address
+         RETURNING *",
+    )
+    .bind(&challenge.address)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("upsert publisher", err))?;
+
+    let identity: ExternalIdentityRecord = sqlx::query_as(
+        "INSERT INTO external_identities (provider, subject, username, publisher_id)
+         VALUES ($1, $2, $2, $3)
+         ON CONFLICT (provider, subject) DO UPDATE
+            SET publisher_id = EXCLUDED.publisher_id,
+                last_login_at = NOW()
+         RETURNING *",
+    )
+    .bind(STELLAR_PROVIDER)
+    .bind(&challenge.address)
+    .bind(publisher.id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("upsert stellar identity", err))?;
+
+    let (token, expires_at) = issue_session(
+        &state.auth,
+        identity.id,
+        &identity.provider,
+        &identity.subject,
+        identity.username.clone(),
+    )?;
+    let publishers = identity_publishers(&state.db, identity.id).await?;
+
+    tracing::info!(identity_id = %identity.id, publisher_id = %publisher.id, "stellar key login succeeded");
+
+    Ok(Json(AuthSessionResponse {
+        token: Some(token),
+        expires_at: Some(expires_at),
+        identity,
+        publishers,
+    }))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/auth/link
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn link_publisher(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    payload: Result<Json<KeyProof>, JsonRejection>,
+) -> ApiResult<Json<AuthSessionResponse>> {
+    let session = require_session(&state.auth, &headers)?;
+    let Json(proof) = payload.map_err(map_json_rejection)?;
+
+    // The publisher's key must have signed a challenge issued to this session
+    let challenge =
+        redeem_challenge(&state.db, &state.auth, &proof.challenge, &proof.signature).await?;
+    if challenge.identity != Some(session.sub) {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "InvalidChallenge",
+            "the challenge was not issued to this session",
+        ));
+    }
+
+    let publisher: Publisher = sqlx::query_as("SELECT * FROM publishers WHERE stellar_address = $1")
+        .bind(&challenge.address)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("get publisher", err))?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "PublisherNotFound",
+                format!("No publisher found with address: {}", challenge.address),
+            )
+        })?;
+
+    let identity: ExternalIdentityRecord = sqlx::query_as(
+        "UPDATE external_identities SET publisher_id = $2 WHERE id = $1 RETURNING *",
+    )
+    .bind(session.sub)
+    .bind(publisher.id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("link external identity", err))?
+    .ok_or_else(|| {
+        ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "InvalidSession",
+            "Session identity no longer exists",
+        )
+    })?;
+
+    tracing::info!(identity_id = %identity.id, publisher_id = %publisher.id, "identity linked to publisher");
+
+    let publishers = identity_publishers(&state.db, identity.id).await?;
+    Ok(Json(AuthSessionResponse {
+        token: None,
+        expires_at: None,
+        identity,
+        publishers,
+    }))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/publishers/:id/org-grants
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn list_org_grants(
+    State(state): State<AppState>,
+    Path(publisher_id): Path<Uuid>,
+) -> ApiResult<Json<Vec<PublisherOrgGrant>>> {
+    let grants: Vec<PublisherOrgGrant> = sqlx::query_as(
+        "SELECT * FROM publisher_org_grants WHERE publisher_id = $1 ORDER BY provider, org",
+    )
+    .bind(publisher_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list org grants", err))?;
+
+    Ok(Json(grants))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/publishers/:id/org-grants
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn grant_org(
+    State(state): State<AppState>,
+    Path(publisher_id): Path<Uuid>,
+    headers: HeaderMap,
+    payload: Result<Json<GrantOrgRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<PublisherOrgGrant>)> {
+    let session = require_session(&state.auth, &headers)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM publishers WHERE id = $1)")
+        .bind(publisher_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("get publisher", err))?;
+    if !exists {
+        return Err(ApiError::not_found(
+            "PublisherNotFound",
+            format!("No publisher found with ID: {}", publisher_id),
+        ));
+    }
+
+    // Only identities that proved the publisher's key (direct link), not
+    // members of an already granted org, may grant further orgs
+    let identity = fetch_identity(&state.db, session.sub).await?;
+    if identity.publisher_id != Some(publisher_id) {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "NotPublisher",
+            "Only an identity linked to the publisher can grant publish rights",
+        ));
+    }
+    if req.provider.trim().is_empty() || req.org.trim().is_empty() {
+        return Err(ApiError::bad_request(
+            "InvalidRequest",
+            "provider and org must not be empty",
+        ));
+    }
+
+    let grant: PublisherOrgGrant = sqlx::query_as(
+        "INSERT INTO publisher_org_grants (publisher_id, provider, org, granted_by)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (publisher_id, provider, org) DO UPDATE SET granted_by = EXCLUDED.granted_by
+         RETURNING *",
+    )
+    .bind(publisher_id)
+    .bind(req.provider.trim())
+    .bind(req.org.trim())
+    .bind(identity.id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("grant org", err))?;
+
+    tracing::info!(publisher_id = %publisher_id, provider = %grant.provider, org = %grant.org, "org grant added");
+
+    Ok((StatusCode::CREATED, Json(grant)))
+}
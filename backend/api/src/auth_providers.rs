@@ -0,0 +1,351 @@
+// api/src/auth_providers.rs
+//
+// External login providers for the web UI.
+//
+// Each provider implements the OAuth 2.0 authorization code flow: the user is
+// redirected to `authorize_url`, comes back with a `code`, and
+// `exchange_code` turns it into an `ExternalIdentity`.  Providers come from
+// the `[login.github]` and `[login.oidc]` settings (or the GITHUB_* and
+// OIDC_* variables); unset providers are simply not offered.
+
+use async_trait::async_trait;
+use registry_config::{GitHubLoginSettings, LoginSettings, OidcLoginSettings};
+use reqwest::Url;
+use serde::Deserialize;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Identity asserted by an external provider after a successful login.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalIdentity {
+    pub provider: String,
+    /// Stable provider-side user id
+    pub subject: String,
+    pub username: Option<String>,
+    pub email: Option<String>,
+    /// Organisations / groups the user belongs to, used for publisher grants
+    pub orgs: Vec<String>,
+}
+
+#[async_trait]
+pub trait AuthProvider: Send + Sync {
+    /// Name used in routes, e.g. `github` in `/api/auth/github/login`.
+    fn name(&self) -> &str;
+
+    /// `github` or `oidc`.
+    fn kind(&self) -> &'static str;
+
+    /// URL to send the user to, carrying our signed `state`.
+    fn authorize_url(&self, state: &str) -> String;
+
+    /// Exchange an authorization code for the user's identity.
+    async fn exchange_code(
+        &self,
+        client: &reqwest::Client,
+        code: &str,
+    ) -> Result<ExternalIdentity, String>;
+}
+
+/// Providers enabled in the login settings.
+pub fn configured_providers(settings: &LoginSettings) -> Vec<Arc<dyn AuthProvider>> {
+    let mut providers: Vec<Arc<dyn AuthProvider>> = Vec::new();
+    if let Some(github) = GitHubProvider::from_settings(&settings.github) {
+        providers.push(Arc::new(github));
+    }
+    if let Some(oidc) = OidcProvider::from_settings(&settings.oidc) {
+        providers.push(Arc::new(oidc));
+    }
+    providers
+}
+
+fn build_authorize_url(endpoint: &str, params: &[(&str, &str)]) -> String {
+    Url::parse_with_params(endpoint, params)
+        .map(String::from)
+        .unwrap_or_else(|_| endpoint.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+impl TokenResponse {
+    fn into_access_token(self) -> Result<String, String> {
+        match (self.access_token, self.error) {
+            (Some(token), None) => Ok(token),
+            (_, Some(error)) => Err(match self.error_description {
+                Some(desc) => format!("{}: {}", error, desc),
+                None => error,
+            }),
+            (None, None) => Err("token response has no access_token".to_string()),
+        }
+    }
+}
+
+async fn get_json(client: &reqwest::Client, url: &str, access_token: &str) -> Result<Value, String> {
+    let resp = client
+        .get(url)
+        .bearer_auth(access_token)
+        .header(reqwest::header::USER_AGENT, "soroban-registry")
+        .header(reqwest::header::ACCEPT, "application/json")
+        .send()
+        .await
+        .map_err(|e| format!("request to {} failed: {}", url, e))?;
+    if !resp.status().is_success() {
+        return Err(format!("{} returned HTTP {}", url, resp.status()));
+    }
+    resp.json().await.map_err(|e| format!("invalid JSON from {}: {}", url, e))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GitHub
+// ─────────────────────────────────────────────────────────────────────────────
+
+const GITHUB_AUTHORIZE_URL: &str = "https://github.com/login/oauth/authorize";
+const GITHUB_TOKEN_URL: &str = "https://github.com/login/oauth/access_token";
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+pub struct GitHubProvider {
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+}
+
+impl GitHubProvider {
+    pub fn from_settings(settings: &GitHubLoginSettings) -> Option<Self> {
+        Some(Self {
+            client_id: settings.client_id.clone()?,
+            client_secret: settings.client_secret.as_ref()?.expose().to_string(),
+            redirect_uri: settings.redirect_uri.clone()?,
+        })
+    }
+}
+
+#[async_trait]
+impl AuthProvider for GitHubProvider {
+    fn name(&self) -> &str {
+        "github"
+    }
+
+    fn kind(&self) -> &'static str {
+        "github"
+    }
+
+    fn authorize_url(&self, state: &str) -> String {
+        build_authorize_url(
+            GITHUB_AUTHORIZE_URL,
+            &[
+                ("client_id", &self.client_id),
+                ("redirect_uri", &self.redirect_uri),
+                ("scope", "read:user user:email read:org"),
+                ("state", state),
+            ],
+        )
+    }
+
+    async fn exchange_code(
+        &self,
+        client: &reqwest::Client,
+        code: &str,
+    ) -> Result<ExternalIdentity, String> {
+        let token: TokenResponse = client
+            .post(GITHUB_TOKEN_URL)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .form(&[
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.as_str()),
+                ("code", code),
+                ("redirect_uri", self.redirect_uri.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(|e| format!("GitHub token exchange failed: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("invalid GitHub token response: {}", e))?;
+        let access_token = token.into_access_token()?;
+
+        let user = get_json(client, &format!("{}/user", GITHUB_API_URL), &access_token).await?;
+        let orgs = get_json(client, &format!("{}/user/orgs", GITHUB_API_URL), &access_token).await?;
+
+        github_identity(&user, &orgs)
+    }
+}
+
+fn github_identity(user: &Value, orgs: &Value) -> Result<ExternalIdentity, String> {
+    let subject = user["id"]
+        .as_i64()
+        .map(|id| id.to_string())
+        .ok_or("GitHub user has no id")?;
+
+    Ok(ExternalIdentity {
+        provider: "github".to_string(),
+        subject,
+        username: user["login"].as_str().map(str::to_string),
+        email: user["email"].as_str().map(str::to_string),
+        orgs: orgs
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|org| org["login"].as_str().map(str::to_string))
+            .collect(),
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Generic OIDC
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub struct OidcProvider {
+    name: String,
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    userinfo_endpoint: String,
+    scopes: String,
+    org_claim: String,
+}
+
+impl OidcProvider {
+    pub fn from_settings(settings: &OidcLoginSettings) -> Option<Self> {
+        Some(Self {
+            name: settings.provider_name.clone(),
+            client_id: settings.client_id.clone()?,
+            client_secret: settings.client_secret.as_ref()?.expose().to_string(),
+            redirect_uri: settings.redirect_uri.clone()?,
+            authorization_endpoint: settings.authorization_endpoint.clone()?,
+            token_endpoint: settings.token_endpoint.clone()?,
+            userinfo_endpoint: settings.userinfo_endpoint.clone()?,
+            scopes: settings.scopes.clone(),
+            org_claim: settings.org_claim.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl AuthProvider for OidcProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> &'static str {
+        "oidc"
+    }
+
+    fn authorize_url(&self, state: &str) -> String {
+        build_authorize_url(
+            &self.authorization_endpoint,
+            &[
+                ("response_type", "code"),
+                ("client_id", &self.client_id),
+                ("redirect_uri", &self.redirect_uri),
+                ("scope", &self.scopes),
+                ("state", state),
+            ],
+        )
+    }
+
+    async fn exchange_code(
+        &self,
+        client: &reqwest::Client,
+        code: &str,
+    ) -> Result<ExternalIdentity, String> {
+        let token: TokenResponse = client
+            .post(&self.token_endpoint)
+            .basic_auth(&self.client_id, Some(&self.client_secret))
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", self.redirect_uri.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(|e| format!("OIDC token exchange failed: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("invalid OIDC token response: {}", e))?;
+        let access_token = token.into_access_token()?;
+
+        // Claims come from the userinfo endpoint over TLS, so the ID token's
+        // signature does not need to be checked here
+        let claims = get_json(client, &self.userinfo_endpoint, &access_token).await?;
+        oidc_identity(&self.name, &claims, &self.org_claim)
+    }
+}
+
+fn oidc_identity(provider: &str, claims: &Value, org_claim: &str) -> Result<ExternalIdentity, String> {
+    let subject = claims["sub"]
+        .as_str()
+        .ok_or("userinfo response has no `sub` claim")?
+        .to_string();
+
+    // Group claims are either a list or a single string depending on the IdP
+    let orgs = match &claims[org_claim] {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect(),
+        Value::String(group) => vec![group.clone()],
+        _ => Vec::new(),
+    };
+
+    Ok(ExternalIdentity {
+        provider: provider.to_string(),
+        subject,
+        username: claims["preferred_username"]
+            .as_str()
+            .or(claims["name"].as_str())
+            .map(str::to_string),
+        email: claims["email"].as_str().map(str::to_string),
+        orgs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn authorize_url_encodes_parameters() {
+        let url = build_authorize_url(
+            GITHUB_AUTHORIZE_URL,
+            &[("scope", "read:user read:org"), ("state", "a.b+c")],
+        );
+        assert_eq!(
+            url,
+            "https://github.com/login/oauth/authorize?scope=read%3Auser+read%3Aorg&state=a.b%2Bc"
+        );
+    }
+
+    #[test]
+    fn maps_github_user_and_orgs() {
+        let identity = github_identity(
+            &json!({ "id": 583231, "login": "octocat", "email": null }),
+            &json!([{ "login": "stellar" }, { "login": "soroban-devs" }]),
+        )
+        .unwrap();
+        assert_eq!(identity.subject, "583231");
+        assert_eq!(identity.username.as_deref(), Some("octocat"));
+        assert_eq!(identity.orgs, vec!["stellar", "soroban-devs"]);
+        assert!(github_identity(&json!({}), &json!([])).is_err());
+    }
+
+    #[test]
+    fn maps_oidc_claims_with_configurable_group_claim() {
+        let claims = json!({
+            "sub": "abc",
+            "preferred_username": "ada",
+            "email": "ada@example.com",
+            "teams": "core"
+        });
+        let identity = oidc_identity("okta", &claims, "teams").unwrap();
+        assert_eq!(identity.provider, "okta");
+        assert_eq!(identity.orgs, vec!["core"]);
+        assert!(oidc_identity("okta", &json!({ "email": "x" }), "groups").is_err());
+    }
+}
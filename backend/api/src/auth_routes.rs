@@ -0,0 +1,26 @@
+// api/src/auth_routes.rs
+// External login (OIDC / GitHub / Stellar key) and publisher grant routes.
+
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::{auth_handlers, state::AppState};
+
+pub fn auth_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/auth/providers", get(auth_handlers::list_providers))
+        .route("/api/auth/session", get(auth_handlers::get_session))
+        .route("/api/auth/whoami", get(auth_handlers::whoami))
+        .route("/api/auth/token", post(auth_handlers::exchange_token))
+        .route("/api/auth/challenge", post(auth_handlers::create_challenge))
+        .route("/api/auth/stellar", post(auth_handlers::stellar_login))
+        .route("/api/auth/link", post(auth_handlers::link_publisher))
+        .route("/api/auth/:provider/login", get(auth_handlers::login))
+        .route("/api/auth/:provider/callback", get(auth_handlers::callback))
+        .route(
+            "/api/publishers/:id/org-grants",
+            get(auth_handlers::list_org_grants).post(auth_handlers::grant_org),
+        )
+}
@@ -0,0 +1,158 @@
+// api/src/budget_handlers.rs
+//
+// Per-contract resource usage budgets.
+//
+// Routes (registered in budget_routes.rs):
+//   PUT /api/contracts/:id/budget         – owner: set CPU / fee limits
+//   GET /api/contracts/:id/budget/status  – budget, today's (UTC) usage and alerts
+//
+// Limits are evaluated by budget_monitor.rs.  Webhook URLs must be https and
+// resolve to public addresses (see webhook.rs).  They are returned to the
+// owner who set them but left out of the public status and contract history.
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    http::HeaderMap,
+    Json,
+};
+use chrono::Utc;
+use shared::{BudgetAlert, BudgetStatusResponse, ContractBudget, SetBudgetRequest};
+use uuid::Uuid;
+
+use crate::{
+    auth::require_contract_owner,
+    budget_monitor::{evaluate_budget, usage_on},
+    contract_history_handlers::log_settings_change,
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    state::AppState,
+    webhook,
+};
+
+fn map_json_rejection(err: JsonRejection) -> ApiError {
+    ApiError::bad_request(
+        "InvalidRequest",
+        format!("Invalid JSON payload: {}", err.body_text()),
+    )
+}
+
+/// A budget as anyone may see it: without the owner's webhook endpoint.
+fn without_webhook(budget: &ContractBudget) -> ContractBudget {
+    ContractBudget {
+        webhook_url: None,
+        ..budget.clone()
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// PUT /api/contracts/:id/budget
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn set_budget(
+    State(state): State<AppState>,
+    Path(contract_id): Path<Uuid>,
+    headers: HeaderMap,
+    payload: Result<Json<SetBudgetRequest>, JsonRejection>,
+) -> ApiResult<Json<ContractBudget>> {
+    let owner = require_contract_owner(&state, &headers, contract_id).await?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+
+    let limits = [
+        req.max_cpu_instructions,
+        req.max_fee_per_invocation,
+        req.max_daily_fees,
+    ];
+    if limits.iter().all(Option::is_none) {
+        return Err(ApiError::bad_request(
+            "InvalidBudget",
+            "Set at least one of max_cpu_instructions, max_fee_per_invocation, max_daily_fees",
+        ));
+    }
+    if limits.iter().flatten().any(|limit| *limit <= 0) {
+        return Err(ApiError::bad_request(
+            "InvalidBudget",
+            "Budget limits must be positive",
+        ));
+    }
+    if let Some(url) = &req.webhook_url {
+        webhook::check_webhook_url(url).await.map_err(|reason| {
+            ApiError::bad_request("InvalidBudget", format!("webhook_url: {}", reason))
+        })?;
+    }
+
+    let budget: ContractBudget = sqlx::query_as(
+        "INSERT INTO contract_budgets
+             (contract_id, max_cpu_instructions, max_fee_per_invocation, max_daily_fees, webhook_url, updated_by)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         ON CONFLICT (contract_id) DO UPDATE
+            SET max_cpu_instructions = EXCLUDED.max_cpu_instructions,
+                max_fee_per_invocation = EXCLUDED.max_fee_per_invocation,
+                max_daily_fees = EXCLUDED.max_daily_fees,
+                webhook_url = EXCLUDED.webhook_url,
+                updated_by = EXCLUDED.updated_by,
+                updated_at = NOW()
+         RETURNING *",
+    )
+    .bind(contract_id)
+    .bind(req.max_cpu_instructions)
+    .bind(req.max_fee_per_invocation)
+    .bind(req.max_daily_fees)
+    .bind(&req.webhook_url)
+    .bind(&owner.address)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("upsert contract budget", err))?;
+
+    log_settings_change(
+        &state.db,
+        contract_id,
+        &owner,
+        "budget",
+        serde_json::json!(without_webhook(&budget)),
+    )
+    .await;
+    tracing::info!(contract_id = %contract_id, "contract budget updated");
+    Ok(Json(budget))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/contracts/:id/budget/status
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn get_budget_status(
+    State(state): State<AppState>,
+    Path(contract_id): Path<Uuid>,
+) -> ApiResult<Json<BudgetStatusResponse>> {
+    let budget: ContractBudget =
+        sqlx::query_as("SELECT * FROM contract_budgets WHERE contract_id = $1")
+            .bind(contract_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("get contract budget", err))?
+            .ok_or_else(|| {
+                ApiError::not_found(
+                    "BudgetNotFound",
+                    format!("No budget configured for contract {}", contract_id),
+                )
+            })?;
+
+    let usage_today = usage_on(&state.db, contract_id, Utc::now().date_naive())
+        .await
+        .map_err(|err| db_internal_error("aggregate budget usage", err))?;
+
+    let recent_alerts: Vec<BudgetAlert> = sqlx::query_as(
+        "SELECT * FROM budget_alerts WHERE contract_id = $1 ORDER BY created_at DESC LIMIT 20",
+    )
+    .bind(contract_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list budget alerts", err))?;
+
+    let breaches = evaluate_budget(&budget, &usage_today);
+
+    Ok(Json(BudgetStatusResponse {
+        webhook_configured: budget.webhook_url.is_some(),
+        budget: without_webhook(&budget),
+        usage_today,
+        breaches,
+        recent_alerts,
+    }))
+}
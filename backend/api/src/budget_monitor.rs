@@ -0,0 +1,283 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use shared::{BudgetAlert, BudgetBreach, BudgetKind, BudgetUsage, ContractBudget};
+use sqlx::PgPool;
+use tokio::time;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::{deprecation_handlers, state::AppState, webhook};
+
+/// Webhook deliveries per alert (or deprecation notice) before giving up.
+pub(crate) const MAX_DELIVERY_ATTEMPTS: i32 = 6;
+
+/// Main loop for the budget monitor background task.
+///
+/// Every `budgets.check_interval_seconds` (5 minutes by default), compares
+/// each UTC day's invocation costs, as recorded by the indexer in
+/// `performance_metrics`, against each contract budget, raises at most one
+/// alert per budget kind per day and (re)tries webhook deliveries that are
+/// due, deprecation notices included.
+pub async fn run_budget_monitor(state: AppState, check_interval: time::Duration) {
+    info!("Starting budget monitor background task");
+
+    let mut interval = time::interval(check_interval);
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = check_budgets(&state.db).await {
+            error!("Error evaluating contract budgets: {}", e);
+        }
+        if let Err(e) = deliver_alerts(&state.db).await {
+            error!("Error delivering budget alerts: {}", e);
+        }
+        if let Err(e) = deprecation_handlers::deliver_due_notices(&state.db).await {
+            error!("Error delivering deprecation notices: {}", e);
+        }
+    }
+}
+
+async fn check_budgets(pool: &PgPool) -> Result<()> {
+    let budgets: Vec<ContractBudget> = sqlx::query_as("SELECT * FROM contract_budgets")
+        .fetch_all(pool)
+        .await?;
+    // One day for the whole pass, so usage and alerts agree at midnight
+    let today = Utc::now().date_naive();
+
+    for budget in budgets {
+        let usage = usage_on(pool, budget.contract_id, today).await?;
+
+        for breach in evaluate_budget(&budget, &usage) {
+            // One alert per contract, kind and day; re-runs are no-ops
+            let alert: Option<BudgetAlert> = sqlx::query_as(
+                "INSERT INTO budget_alerts (contract_id, kind, limit_value, observed_value, window_start)
+                 VALUES ($1, $2, $3, $4, $5)
+                 ON CONFLICT (contract_id, kind, window_start) DO NOTHING
+                 RETURNING *",
+            )
+            .bind(budget.contract_id)
+            .bind(breach.kind)
+            .bind(breach.limit)
+            .bind(breach.observed)
+            .bind(today)
+            .fetch_optional(pool)
+            .await?;
+
+            if alert.is_some() {
+                warn!(
+                    contract_id = %budget.contract_id,
+                    kind = ?breach.kind,
+                    limit = breach.limit,
+                    observed = breach.observed,
+                    "Contract budget exceeded"
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// An undelivered alert and where to deliver it.
+#[derive(sqlx::FromRow)]
+struct PendingDelivery {
+    #[sqlx(flatten)]
+    alert: BudgetAlert,
+    webhook_url: String,
+}
+
+/// Deliver every undelivered alert that is due to its budget's webhook.
+async fn deliver_alerts(pool: &PgPool) -> Result<()> {
+    let due: Vec<PendingDelivery> = sqlx::query_as(
+        "SELECT a.*, b.webhook_url
+           FROM budget_alerts a
+           JOIN contract_budgets b ON b.contract_id = a.contract_id
+          WHERE a.notified_at IS NULL
+            AND b.webhook_url IS NOT NULL
+            AND a.delivery_attempts < $1
+            AND (a.next_delivery_at IS NULL OR a.next_delivery_at <= NOW())
+          ORDER BY a.created_at",
+    )
+    .bind(MAX_DELIVERY_ATTEMPTS)
+    .fetch_all(pool)
+    .await?;
+
+    for PendingDelivery { alert, webhook_url } in due {
+        let result = notify(&webhook_url, &alert).await;
+        if let Err(reason) = &result {
+            warn!(
+                alert_id = %alert.id,
+                attempt = alert.delivery_attempts + 1,
+                "Budget webhook delivery failed: {}",
+                reason
+            );
+        }
+        record_delivery(pool, &alert, result).await?;
+    }
+
+    Ok(())
+}
+
+/// POST the alert to a webhook that passes the public-address check.
+async fn notify(url: &str, alert: &BudgetAlert) -> Result<(), String> {
+    let (url, addr) = webhook::check_webhook_url(url).await?;
+    let client = webhook::pinned_client(&url, addr).map_err(|e| e.to_string())?;
+    let payload = serde_json::json!({
+        "event": "budget_exceeded",
+        "alert": alert,
+    });
+
+    let resp = client
+        .post(url)
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        return Err(format!("webhook responded {}", resp.status()));
+    }
+    Ok(())
+}
+
+async fn record_delivery(
+    pool: &PgPool,
+    alert: &BudgetAlert,
+    result: Result<(), String>,
+) -> Result<(), sqlx::Error> {
+    let (notified_at, error, next_delivery_at) = match result {
+        Ok(()) => (Some(Utc::now()), None, None),
+        Err(reason) => (
+            None,
+            Some(reason),
+            Some(Utc::now() + retry_delay(alert.delivery_attempts)),
+        ),
+    };
+
+    sqlx::query(
+        "UPDATE budget_alerts
+            SET delivery_attempts = delivery_attempts + 1,
+                notified_at = $2,
+                last_delivery_error = $3,
+                next_delivery_at = $4
+          WHERE id = $1",
+    )
+    .bind(alert.id)
+    .bind(notified_at)
+    .bind(error)
+    .bind(next_delivery_at)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Wait before retrying after `attempts` earlier deliveries: 5 minutes,
+/// doubling each time.
+pub(crate) fn retry_delay(attempts: i32) -> Duration {
+    Duration::minutes(5 << attempts.clamp(0, 10))
+}
+
+/// Start and end of a UTC day.
+fn utc_day_bounds(day: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+    let start = day.and_time(NaiveTime::MIN).and_utc();
+    (start, start + Duration::days(1))
+}
+
+/// Aggregate a UTC day's invocation costs for a contract.
+pub async fn usage_on(
+    pool: &PgPool,
+    contract_id: Uuid,
+    day: NaiveDate,
+) -> Result<BudgetUsage, sqlx::Error> {
+    let (start, end) = utc_day_bounds(day);
+    sqlx::query_as(
+        "SELECT
+            MAX(value) FILTER (WHERE metric_type = 'cpu_instructions')::BIGINT AS max_cpu_instructions,
+            MAX(value) FILTER (WHERE metric_type = 'invocation_fee')::BIGINT   AS max_fee_per_invocation,
+            SUM(value) FILTER (WHERE metric_type = 'invocation_fee')::BIGINT   AS total_fees,
+            COUNT(*)   FILTER (WHERE metric_type = 'invocation_fee')           AS invocations
+         FROM performance_metrics
+         WHERE contract_id = $1
+           AND timestamp >= $2
+           AND timestamp < $3",
+    )
+    .bind(contract_id)
+    .bind(start)
+    .bind(end)
+    .fetch_one(pool)
+    .await
+}
+
+/// Compare observed usage against every limit set on the budget.
+pub fn evaluate_budget(budget: &ContractBudget, usage: &BudgetUsage) -> Vec<BudgetBreach> {
+    let checks = [
+        (BudgetKind::CpuInstructions, budget.max_cpu_instructions, usage.max_cpu_instructions),
+        (BudgetKind::FeePerInvocation, budget.max_fee_per_invocation, usage.max_fee_per_invocation),
+        (BudgetKind::DailyFees, budget.max_daily_fees, usage.total_fees),
+    ];
+
+    checks
+        .into_iter()
+        .filter_map(|(kind, limit, observed)| match (limit, observed) {
+            (Some(limit), Some(observed)) if observed > limit => Some(BudgetBreach {
+                kind,
+                limit,
+                observed,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budget() -> ContractBudget {
+        ContractBudget {
+            contract_id: Uuid::nil(),
+            max_cpu_instructions: Some(1_000_000),
+            max_fee_per_invocation: None,
+            max_daily_fees: Some(500_000),
+            webhook_url: None,
+            updated_by: "GOWNER".into(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn reports_only_exceeded_limits() {
+        let usage = BudgetUsage {
+            max_cpu_instructions: Some(1_500_000),
+            max_fee_per_invocation: Some(9_999_999),
+            total_fees: Some(500_000),
+            invocations: 12,
+        };
+        let breaches = evaluate_budget(&budget(), &usage);
+        assert_eq!(
+            breaches,
+            vec![BudgetBreach {
+                kind: BudgetKind::CpuInstructions,
+                limit: 1_000_000,
+                observed: 1_500_000,
+            }]
+        );
+    }
+
+    #[test]
+    fn no_usage_means_no_breach() {
+        assert!(evaluate_budget(&budget(), &BudgetUsage::default()).is_empty());
+    }
+
+    #[test]
+    fn retries_back_off_and_days_are_utc() {
+        assert_eq!(retry_delay(0), Duration::minutes(5));
+        assert_eq!(retry_delay(3), Duration::minutes(40));
+
+        let day = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        let (start, end) = utc_day_bounds(day);
+        assert_eq!(start.to_rfc3339(), "2024-06-30T00:00:00+00:00");
+        assert_eq!(end.to_rfc3339(), "2024-07-01T00:00:00+00:00");
+    }
+}
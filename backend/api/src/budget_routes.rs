@@ -0,0 +1,18 @@
+// api/src/budget_routes.rs
+// Contract resource budget routes.
+
+use axum::{
+    routing::{get, put},
+    Router,
+};
+
+use crate::{budget_handlers, state::AppState};
+
+pub fn budget_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/contracts/:id/budget", put(budget_handlers::set_budget))
+        .route(
+            "/api/contracts/:id/budget/status",
+            get(budget_handlers::get_budget_status),
+        )
+}
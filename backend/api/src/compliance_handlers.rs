@@ -0,0 +1,533 @@
+// api/src/compliance_handlers.rs
+//
+// Publisher data export and right-to-erasure workflow.
+//
+// Routes (registered in compliance_routes.rs):
+//   GET  /api/publishers/:id/data-export             – publisher or admin: all stored personal data
+//   POST /api/publishers/:id/erasure-requests        – publisher: request erasure
+//   GET  /api/erasure-requests?status=pending        – admin: list requests
+//   GET  /api/erasure-requests/:id                   – admin: request + audit trail
+//   POST /api/erasure-requests/:id/approve           – named admin: approve and erase
+//   POST /api/erasure-requests/:id/reject            – named admin: reject
+//
+// The publisher is authenticated by its session (see auth::require_publisher),
+// never by an address in the request.  Reviews are attributed to the signed-in
+// admin identity, so the shared admin token cannot approve or reject.
+//
+// Erasure never deletes contracts: the publisher's address is replaced by a
+// random pseudonym everywhere it is stored and profile fields are cleared, so
+// contract IDs, WASM hashes and version history remain verifiable.
+
+use axum::{
+    extract::{
+        rejection::{JsonRejection, QueryRejection},
+        Path, Query, State,
+    },
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use shared::{
+    CreateErasureRequest, ErasureAuditEntry, ErasureRequestStatus, ErasureRequestWithAudit,
+    Publisher, PublisherErasureRequest, ReviewErasureRequest,
+};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    auth::{require_admin, require_publisher},
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    state::AppState,
+};
+
+/// Personal data owned by the publisher row, exported by publisher id.
+const EXPORT_BY_PUBLISHER: &[(&str, &str)] = &[
+    (
+        "contracts",
+        "SELECT * FROM contracts WHERE publisher_id = $1 ORDER BY created_at",
+    ),
+    (
+        "contract_versions",
+        "SELECT v.* FROM contract_versions v JOIN contracts c ON c.id = v.contract_id
+          WHERE c.publisher_id = $1 ORDER BY v.created_at",
+    ),
+    (
+        "external_identities",
+        "SELECT * FROM external_identities WHERE publisher_id = $1 ORDER BY created_at",
+    ),
+    (
+        "org_grants",
+        "SELECT * FROM publisher_org_grants WHERE publisher_id = $1 ORDER BY created_at",
+    ),
+    (
+        "erasure_requests",
+        "SELECT * FROM publisher_erasure_requests WHERE publisher_id = $1 ORDER BY created_at",
+    ),
+];
+
+/// Records keyed by the publisher's Stellar address.  The same tables are
+/// pseudonymised on erasure.
+const ADDRESS_COLUMNS: &[(&str, &str, &str)] = &[
+    ("contract_interactions", "contract_interactions", "user_address"),
+    ("analytics_events", "analytics_events", "user_address"),
+    ("audit_log", "contract_audit_log", "changed_by"),
+    ("canary_assignments", "canary_user_assignments", "user_address"),
+    ("ab_test_assignments", "ab_test_assignments", "user_address"),
+    ("ab_test_metrics", "ab_test_metrics", "user_address"),
+    ("multisig_policies", "multisig_policies", "created_by"),
+    ("multisig_proposals", "deploy_proposals", "proposer"),
+    ("multisig_signatures", "proposal_signatures", "signer_address"),
+    ("approval_policies", "deployment_approval_policies", "created_by"),
+    ("switch_requests", "deployment_switch_requests", "requested_by"),
+    ("switch_decisions", "deployment_switch_decisions", "approver"),
+    ("budgets", "contract_budgets", "updated_by"),
+    ("event_schemas", "contract_event_schemas", "registered_by"),
+    ("deprecations", "contract_deprecations", "deprecated_by"),
+];
+
+/// Address lists that may include the publisher's Stellar address.
+const ADDRESS_ARRAY_COLUMNS: &[(&str, &str, &str)] = &[
+    ("multisig_policies_as_signer", "multisig_policies", "signer_addresses"),
+    ("approval_policies_as_approver", "deployment_approval_policies", "approvers"),
+    ("switch_requests_as_approver", "deployment_switch_requests", "approvers"),
+];
+
+fn map_json_rejection(err: JsonRejection) -> ApiError {
+    ApiError::bad_request(
+        "InvalidRequest",
+        format!("Invalid JSON payload: {}", err.body_text()),
+    )
+}
+
+fn map_query_rejection(err: QueryRejection) -> ApiError {
+    ApiError::bad_request(
+        "InvalidQuery",
+        format!("Invalid query parameters: {}", err.body_text()),
+    )
+}
+
+/// Random stand-in for an erased Stellar address.
+///
+/// Same width as a Stellar address (56 chars) so it fits existing columns.
+/// Random rather than derived from the address, so it cannot be linked back
+/// by hashing candidate addresses; erasure replaces every occurrence in one
+/// transaction, so the rows still correlate with each other.
+pub(crate) fn pseudonym() -> String {
+    let random = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    format!("ERASED{}", &random[..50])
+}
+
+async fn fetch_publisher(pool: &PgPool, id: Uuid) -> ApiResult<Publisher> {
+    sqlx::query_as("SELECT * FROM publishers WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|err| db_internal_error("get publisher", err))?
+        .ok_or_else(|| {
+            ApiError::not_found("PublisherNotFound", format!("No publisher found with ID: {}", id))
+        })
+}
+
+async fn fetch_request(pool: &PgPool, id: Uuid) -> ApiResult<PublisherErasureRequest> {
+    sqlx::query_as("SELECT * FROM publisher_erasure_requests WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|err| db_internal_error("get erasure request", err))?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "ErasureRequestNotFound",
+                format!("No erasure request found with ID: {}", id),
+            )
+        })
+}
+
+async fn record_audit(
+    tx: &mut Transaction<'_, Postgres>,
+    request_id: Uuid,
+    action: &str,
+    actor: &str,
+    detail: Option<Value>,
+) -> ApiResult<()> {
+    sqlx::query(
+        "INSERT INTO publisher_erasure_audit (request_id, action, actor, detail)
+         VALUES ($1, $2, $3, $4)",
+    )
+    .bind(request_id)
+    .bind(action)
+    .bind(actor)
+    .bind(detail)
+    .execute(&mut **tx)
+    .await
+    .map_err(|err| db_internal_error("record erasure audit", err))?;
+    Ok(())
+}
+
+async fn with_audit(pool: &PgPool, request: PublisherErasureRequest) -> ApiResult<ErasureRequestWithAudit> {
+    let audit: Vec<ErasureAuditEntry> = sqlx::query_as(
+        "SELECT * FROM publisher_erasure_audit WHERE request_id = $1 ORDER BY created_at",
+    )
+    .bind(request.id)
+    .fetch_all(pool)
+    .await
+    .map_err(|err| db_internal_error("list erasure audit", err))?;
+
+    Ok(ErasureRequestWithAudit { request, audit })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/publishers/:id/data-export
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub async fn export_publisher_data(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Value>> {
+    let publisher = fetch_publisher(&state.db, id).await?;
+
+    if require_admin(&state.auth, &headers).is_err() {
+        require_publisher(&state, &headers, id).await?;
+    }
+
+    let mut data = Map::new();
+    for (section, sql) in EXPORT_BY_PUBLISHER {
+        let rows: Value = sqlx::query_scalar(&format!(
+            "SELECT COALESCE(json_agg(t), '[]'::json) FROM ({}) t",
+            sql
+        ))
+        .bind(id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("export publisher data", err))?;
+        data.insert(section.to_string(), rows);
+    }
+    for (section, table, column) in ADDRESS_COLUMNS {
+        let rows: Value = sqlx::query_scalar(&format!(
+            "SELECT COALESCE(json_agg(t), '[]'::json) FROM (SELECT * FROM {} WHERE {} = $1) t",
+            table, column
+        ))
+        .bind(&publisher.stellar_address)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("export publisher data", err))?;
+        data.insert(section.to_string(), rows);
+    }
+    for (section, table, column) in ADDRESS_ARRAY_COLUMNS {
+        let rows: Value = sqlx::query_scalar(&format!(
+            "SELECT COALESCE(json_agg(t), '[]'::json) FROM (SELECT * FROM {} WHERE $1 = ANY({})) t",
+            table, column
+        ))
+        .bind(&publisher.stellar_address)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("export publisher data", err))?;
+        data.insert(section.to_string(), rows);
+    }
+
+    tracing::info!(publisher_id = %id, "publisher data exported");
+
+    Ok(Json(json!({
+        "generated_at": chrono::Utc::now(),
+        "publisher": publisher,
+        "data": data,
+    })))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/publishers/:id/erasure-requests
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn create_erasure_request(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    payload: Result<Json<CreateErasureRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<ErasureRequestWithAudit>)> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let publisher = fetch_publisher(&state.db, id).await?;
+    let requester = require_publisher(&state, &headers, id).await?;
+
+    if publisher.erased_at.is_some() {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "AlreadyErased",
+            "This publisher's personal data has already been erased",
+        ));
+    }
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin transaction", err))?;
+
+    let request: PublisherErasureRequest = sqlx::query_as(
+        "INSERT INTO publisher_erasure_requests (publisher_id, requested_by, reason)
+         VALUES ($1, $2, $3)
+         RETURNING *",
+    )
+    .bind(id)
+    .bind(&requester.address)
+    .bind(&req.reason)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| match err {
+        sqlx::Error::Database(ref db) if db.is_unique_violation() => ApiError::new(
+            StatusCode::CONFLICT,
+            "ErasurePending",
+            "An erasure request for this publisher is already pending",
+        ),
+        _ => db_internal_error("create erasure request", err),
+    })?;
+
+    record_audit(&mut tx, request.id, "requested", &requester.address, None).await?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit erasure request", err))?;
+
+    tracing::info!(publisher_id = %id, request_id = %request.id, "erasure requested");
+
+    Ok((StatusCode::CREATED, Json(with_audit(&state.db, request).await?)))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/erasure-requests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct ListErasureRequestsQuery {
+    pub status: Option<ErasureRequestStatus>,
+}
+
+pub async fn list_erasure_requests(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    params: Result<Query<ListErasureRequestsQuery>, QueryRejection>,
+) -> ApiResult<Json<Vec<PublisherErasureRequest>>> {
+    require_admin(&state.auth, &headers)?;
+    let Query(params) = params.map_err(map_query_rejection)?;
+
+    let requests: Vec<PublisherErasureRequest> = sqlx::query_as(
+        "SELECT * FROM publisher_erasure_requests
+         WHERE ($1::erasure_request_status IS NULL OR status = $1)
+         ORDER BY created_at DESC",
+    )
+    .bind(params.status)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list erasure requests", err))?;
+
+    Ok(Json(requests))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/erasure-requests/:id
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn get_erasure_request(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> ApiResult<Json<ErasureRequestWithAudit>> {
+    require_admin(&state.auth, &headers)?;
+    let request = fetch_request(&state.db, id).await?;
+    Ok(Json(with_audit(&state.db, request).await?))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/erasure-requests/:id/approve
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn approve_erasure_request(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    payload: Result<Json<ReviewErasureRequest>, JsonRejection>,
+) -> ApiResult<Json<ErasureRequestWithAudit>> {
+    let reviewer = require_admin(&state.auth, &headers)?.require_named()?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    review(&state, id, &reviewer, req, true).await
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/erasure-requests/:id/reject
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn reject_erasure_request(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    payload: Result<Json<ReviewErasureRequest>, JsonRejection>,
+) -> ApiResult<Json<ErasureRequestWithAudit>> {
+    let reviewer = require_admin(&state.auth, &headers)?.require_named()?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    review(&state, id, &reviewer, req, false).await
+}
+
+async fn review(
+    state: &AppState,
+    id: Uuid,
+    reviewer: &str,
+    req: ReviewErasureRequest,
+    approved: bool,
+) -> ApiResult<Json<ErasureRequestWithAudit>> {
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin transaction", err))?;
+
+    let request: PublisherErasureRequest =
+        sqlx::query_as("SELECT * FROM publisher_erasure_requests WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("lock erasure request", err))?
+            .ok_or_else(|| {
+                ApiError::not_found(
+                    "ErasureRequestNotFound",
+                    format!("No erasure request found with ID: {}", id),
+                )
+            })?;
+
+    if request.status != ErasureRequestStatus::Pending {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "ErasureRequestClosed",
+            "Erasure request has already been reviewed",
+        ));
+    }
+
+    let (status, action) = if approved {
+        (ErasureRequestStatus::Completed, "approved")
+    } else {
+        (ErasureRequestStatus::Rejected, "rejected")
+    };
+
+    sqlx::query(
+        "UPDATE publisher_erasure_requests
+            SET status = $2, reviewed_by = $3, review_note = $4, reviewed_at = NOW()
+          WHERE id = $1",
+    )
+    .bind(id)
+    .bind(status)
+    .bind(reviewer)
+    .bind(&req.note)
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("review erasure request", err))?;
+
+    record_audit(
+        &mut tx,
+        id,
+        action,
+        reviewer,
+        req.note.as_ref().map(|note| json!({ "note": note })),
+    )
+    .await?;
+
+    if approved {
+        erase_publisher(&mut tx, request.publisher_id, id).await?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit erasure review", err))?;
+
+    tracing::info!(request_id = %id, action, reviewed_by = %reviewer, "erasure request reviewed");
+
+    let request = fetch_request(&state.db, id).await?;
+    Ok(Json(with_audit(&state.db, request).await?))
+}
+
+/// Replace the publisher's address with its pseudonym everywhere and clear
+/// profile fields, recording how many rows were touched per table.
+async fn erase_publisher(
+    tx: &mut Transaction<'_, Postgres>,
+    publisher_id: Uuid,
+    request_id: Uuid,
+) -> ApiResult<()> {
+    let address: String =
+        sqlx::query_scalar("SELECT stellar_address FROM publishers WHERE id = $1 FOR UPDATE")
+            .bind(publisher_id)
+            .fetch_one(&mut **tx)
+            .await
+            .map_err(|err| db_internal_error("lock publisher", err))?;
+    let pseudonym = pseudonym();
+
+    sqlx::query(
+        "UPDATE publishers
+            SET stellar_address = $2, username = NULL, email = NULL,
+                github_url = NULL, website = NULL, erased_at = NOW()
+          WHERE id = $1",
+    )
+    .bind(publisher_id)
+    .bind(&pseudonym)
+    .execute(&mut **tx)
+    .await
+    .map_err(|err| db_internal_error("erase publisher profile", err))?;
+
+    // Linked logins carry names and emails from the provider
+    sqlx::query("DELETE FROM external_identities WHERE publisher_id = $1")
+        .bind(publisher_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(|err| db_internal_error("remove linked identities", err))?;
+
+    let erasure_tables = ADDRESS_COLUMNS
+        .iter()
+        .map(|(_, table, column)| (*table, *column))
+        .chain([
+            ("publisher_erasure_requests", "requested_by"),
+            ("publisher_erasure_audit", "actor"),
+        ]);
+
+    let mut rows = Map::new();
+    for (table, column) in erasure_tables {
+        let affected = sqlx::query(&format!(
+            "UPDATE {table} SET {column} = $2 WHERE {column} = $1"
+        ))
+        .bind(&address)
+        .bind(&pseudonym)
+        .execute(&mut **tx)
+        .await
+        .map_err(|err| db_internal_error("pseudonymise publisher address", err))?
+        .rows_affected();
+        rows.insert(format!("{table}.{column}"), json!(affected));
+    }
+    for (_, table, column) in ADDRESS_ARRAY_COLUMNS {
+        let affected = sqlx::query(&format!(
+            "UPDATE {table} SET {column} = array_replace({column}, $1, $2) WHERE $1 = ANY({column})"
+        ))
+        .bind(&address)
+        .bind(&pseudonym)
+        .execute(&mut **tx)
+        .await
+        .map_err(|err| db_internal_error("pseudonymise publisher address", err))?
+        .rows_affected();
+        rows.insert(format!("{table}.{column}"), json!(affected));
+    }
+
+    record_audit(
+        tx,
+        request_id,
+        "erased",
+        "system",
+        Some(json!({ "pseudonym": pseudonym, "rows": rows })),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pseudonym_is_random_and_address_sized() {
+        let address = "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H";
+        let first = pseudonym();
+        assert_eq!(first.len(), address.len());
+        assert!(first.starts_with("ERASED"));
+        assert_ne!(first, pseudonym());
+    }
+}
@@ -0,0 +1,37 @@
+// api/src/compliance_routes.rs
+// Publisher data export and erasure workflow routes.
+
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::{compliance_handlers, state::AppState};
+
+pub fn compliance_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/publishers/:id/data-export",
+            get(compliance_handlers::export_publisher_data),
+        )
+        .route(
+            "/api/publishers/:id/erasure-requests",
+            post(compliance_handlers::create_erasure_request),
+        )
+        .route(
+            "/api/erasure-requests",
+            get(compliance_handlers::list_erasure_requests),
+        )
+        .route(
+            "/api/erasure-requests/:id",
+            get(compliance_handlers::get_erasure_request),
+        )
+        .route(
+            "/api/erasure-requests/:id/approve",
+            post(compliance_handlers::approve_erasure_request),
+        )
+        .route(
+            "/api/erasure-requests/:id/reject",
+            post(compliance_handlers::reject_erasure_request),
+        )
+}
@@ -7,11 +7,14 @@
 //   GET  /api/contracts/:id/history/all          – paginated full history
 //   GET  /api/contracts/:id/history/export       – CSV download
 //   GET  /api/contracts/:id/versions/:v1/diff/:v2 – field-level diff
-//   POST /api/contracts/:id/rollback/:snapshot_id – admin rollback
+//   POST /api/contracts/:id/rollback/:snapshot_id – admin or owner rollback
+//
+// Entries made through admin impersonation carry the session and admin
+// alongside `changed_by` (the impersonated publisher).
 
 use axum::{
     extract::{Path, Query, State},
-    http::{header, StatusCode},
+    http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
@@ -19,6 +22,7 @@ use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::{
+    auth::{require_admin, require_contract_owner, AdminIdentity, PublisherAuth},
     error::{ApiError, ApiResult},
     state::AppState,
 };
@@ -38,7 +42,8 @@ pub async fn get_contract_history(
     verify_contract_exists(&state, contract_id).await?;
 
     let entries: Vec<ContractAuditLog> = sqlx::query_as(
-        "SELECT id, contract_id, action_type, old_value, new_value, changed_by, timestamp
+        "SELECT id, contract_id, action_type, old_value, new_value, changed_by, timestamp,
+                impersonation_session_id, impersonated_by
            FROM contract_audit_log
           WHERE contract_id = $1
           ORDER BY timestamp DESC
@@ -91,7 +96,8 @@ pub async fn get_full_history(
     .map_err(|e| db_err("count audit log", e))?;
 
     let items: Vec<ContractAuditLog> = sqlx::query_as(
-        "SELECT id, contract_id, action_type, old_value, new_value, changed_by, timestamp
+        "SELECT id, contract_id, action_type, old_value, new_value, changed_by, timestamp,
+                impersonation_session_id, impersonated_by
            FROM contract_audit_log
           WHERE contract_id = $1
           ORDER BY timestamp DESC
@@ -131,7 +137,8 @@ pub async fn export_history_csv(
         .map_err(|_| StatusCode::NOT_FOUND)?;
 
     let entries: Vec<ContractAuditLog> = sqlx::query_as(
-        "SELECT id, contract_id, action_type, old_value, new_value, changed_by, timestamp
+        "SELECT id, contract_id, action_type, old_value, new_value, changed_by, timestamp,
+                impersonation_session_id, impersonated_by
            FROM contract_audit_log
           WHERE contract_id = $1
           ORDER BY timestamp ASC",
@@ -141,7 +148,9 @@ pub async fn export_history_csv(
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    let mut csv = String::from("id,contract_id,action_type,old_value,new_value,changed_by,timestamp\n");
+    let mut csv = String::from(
+        "id,contract_id,action_type,old_value,new_value,changed_by,timestamp,impersonated_by\n",
+    );
 
     for entry in &entries {
         let old = entry
@@ -158,7 +167,7 @@ pub async fn export_history_csv(
             .replace('"', "\"\"");
 
         csv.push_str(&format!(
-            "{},{},{},\"{}\",\"{}\",{},{}\n",
+            "{},{},{},\"{}\",\"{}\",{},{},{}\n",
             entry.id,
             entry.contract_id,
             entry.action_type,
@@ -166,6 +175,7 @@ pub async fn export_history_csv(
             new,
             entry.changed_by,
             entry.timestamp.to_rfc3339(),
+            entry.impersonated_by.as_deref().unwrap_or_default(),
         ));
     }
 
@@ -235,14 +245,42 @@ pub async fn diff_versions(
 
 // ─────────────────────────────────────────────────────────────────────────────
 // POST /api/contracts/:id/rollback/:snapshot_id
-// Admin-only: restores contract to a previous snapshot.
+// Admin or contract owner: restores contract to a previous snapshot.
 // Creates a new audit log entry and a new snapshot for the rolled-back state.
 // ─────────────────────────────────────────────────────────────────────────────
 pub async fn rollback_contract(
     State(state): State<AppState>,
     Path((contract_id, snapshot_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
     Json(req): Json<RollbackRequest>,
 ) -> ApiResult<Json<serde_json::Value>> {
+    // 0. Authorise: an admin, or whoever may act for the publisher
+    let (changed_by, impersonation) = match require_admin(&state.auth, &headers) {
+        Ok(AdminIdentity::Named(name)) => (name, None),
+        Ok(AdminIdentity::Token) => {
+            let changed_by = req
+                .changed_by
+                .as_deref()
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .ok_or_else(|| {
+                    ApiError::bad_request(
+                        "ChangedByRequired",
+                        "changed_by must name who authorised a rollback made with the admin token",
+                    )
+                })?;
+            (changed_by.to_string(), None)
+        }
+        Err(_) => {
+            let owner = require_contract_owner(&state, &headers, contract_id).await?;
+            let impersonation = owner
+                .actor
+                .impersonation()
+                .map(|(session_id, admin)| (session_id, admin.to_string()));
+            (owner.address, impersonation)
+        }
+    };
+
     // 1. Load the target snapshot
     let snapshot: ContractSnapshot = sqlx::query_as(
         "SELECT id, contract_id, version_number, snapshot_data, audit_log_id, created_at
@@ -305,15 +343,19 @@ pub async fn rollback_contract(
     // 5. Write audit log entry
     let log_entry: ContractAuditLog = sqlx::query_as(
         "INSERT INTO contract_audit_log
-               (contract_id, action_type, old_value, new_value, changed_by)
-         VALUES ($1, $2, $3, $4, $5)
-         RETURNING id, contract_id, action_type, old_value, new_value, changed_by, timestamp",
+               (contract_id, action_type, old_value, new_value, changed_by,
+                impersonation_session_id, impersonated_by)
+         VALUES ($1, $2, $3, $4, $5, $6, $7)
+         RETURNING id, contract_id, action_type, old_value, new_value, changed_by, timestamp,
+                   impersonation_session_id, impersonated_by",
     )
     .bind(contract_id)
     .bind(AuditActionType::Rollback)
     .bind(&current_data)
     .bind(&snapshot.snapshot_data)
-    .bind(&req.changed_by)
+    .bind(&changed_by)
+    .bind(impersonation.as_ref().map(|(session_id, _)| *session_id))
+    .bind(impersonation.as_ref().map(|(_, admin)| admin.as_str()))
     .fetch_one(&mut *tx)
     .await
     .map_err(|e| db_err("insert rollback audit log", e))?;
@@ -347,7 +389,7 @@ pub async fn rollback_contract(
         target_snapshot = %snapshot_id,
         rolled_back_to_version = snapshot.version_number,
         new_version = next_ver,
-        changed_by = %req.changed_by,
+        changed_by = %changed_by,
         "Contract rolled back successfully"
     );
 
@@ -365,7 +407,8 @@ pub async fn rollback_contract(
 // ─────────────────────────────────────────────────────────────────────────────
 
 /// Insert one audit log entry + snapshot atomically.
-/// Called from publish_contract and any future mutation hooks.
+/// Called from publish_contract and any future mutation hooks;
+/// `impersonation` is the admin session and name behind the change, if any.
 pub async fn log_contract_change(
     db: &sqlx::PgPool,
     contract_id: Uuid,
@@ -373,14 +416,16 @@ pub async fn log_contract_change(
     old_value: Option<serde_json::Value>,
     new_value: Option<serde_json::Value>,
     changed_by: &str,
+    impersonation: Option<(Uuid, &str)>,
 ) -> Result<Uuid, sqlx::Error> {
     let mut tx = db.begin().await?;
 
     // Insert audit log row
     let (log_id,): (Uuid,) = sqlx::query_as(
         "INSERT INTO contract_audit_log
-               (contract_id, action_type, old_value, new_value, changed_by)
-         VALUES ($1, $2, $3, $4, $5)
+               (contract_id, action_type, old_value, new_value, changed_by,
+                impersonation_session_id, impersonated_by)
+         VALUES ($1, $2, $3, $4, $5, $6, $7)
          RETURNING id",
     )
     .bind(contract_id)
@@ -388,6 +433,8 @@ pub async fn log_contract_change(
     .bind(&old_value)
     .bind(&new_value)
     .bind(changed_by)
+    .bind(impersonation.map(|(session_id, _)| session_id))
+    .bind(impersonation.map(|(_, admin)| admin))
     .fetch_one(&mut *tx)
     .await?;
 
@@ -416,6 +463,42 @@ pub async fn log_contract_change(
     Ok(log_id)
 }
 
+/// Record an owner's change to a contract setting (no snapshot: settings
+/// are not part of the contract row).  Failures are logged, not returned,
+/// as the change itself has already been made.
+pub async fn log_settings_change(
+    db: &sqlx::PgPool,
+    contract_id: Uuid,
+    owner: &PublisherAuth,
+    setting: &str,
+    value: serde_json::Value,
+) {
+    let impersonation = owner.actor.impersonation();
+    let result = sqlx::query(
+        "INSERT INTO contract_audit_log
+               (contract_id, action_type, new_value, changed_by,
+                impersonation_session_id, impersonated_by)
+         VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(contract_id)
+    .bind(AuditActionType::SettingsChanged)
+    .bind(serde_json::json!({ "setting": setting, "value": value }))
+    .bind(&owner.address)
+    .bind(impersonation.map(|(session_id, _)| session_id))
+    .bind(impersonation.map(|(_, admin)| admin))
+    .execute(db)
+    .await;
+
+    if let Err(err) = result {
+        tracing::error!(
+            contract_id = %contract_id,
+            setting,
+            "failed to record settings change in the audit log: {}",
+            err
+        );
+    }
+}
+
 /// Compute a field-level diff between two JSONB objects.
 fn compute_diff(
     contract_id: Uuid,
@@ -0,0 +1,128 @@
+// api/src/dependency_handlers.rs
+//
+// Dependency resolution with conflict explanations.
+//
+// Routes (registered in dependency_routes.rs):
+//   GET /api/contracts/:id/dependencies/resolve
+//
+// Collects the contract's dependency constraints transitively and resolves
+// them against published versions (see shared::resolver).  Conflicts come
+// back as minimal traces rather than a bare "no solution".
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::Serialize;
+use shared::{resolve, Requirement, Resolution, SemVer, VersionConstraint};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    state::AppState,
+};
+
+#[derive(Debug, Serialize)]
+pub struct DependencyResolutionResponse {
+    pub contract_id: Uuid,
+    #[serde(flatten)]
+    pub resolution: Resolution,
+    /// Declared constraints that could not be parsed
+    pub invalid_constraints: Vec<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct DependencyRow {
+    requester: String,
+    dependency_name: String,
+    dependency_contract_id: Option<Uuid>,
+    version_constraint: String,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/contracts/:id/dependencies/resolve
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn resolve_dependencies(
+    State(state): State<AppState>,
+    Path(contract_id): Path<Uuid>,
+) -> ApiResult<Json<DependencyResolutionResponse>> {
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM contracts WHERE id = $1)")
+        .bind(contract_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("check contract exists", err))?;
+    if !exists {
+        return Err(ApiError::not_found(
+            "ContractNotFound",
+            format!("No contract found with ID: {}", contract_id),
+        ));
+    }
+
+    let mut requirements = Vec::new();
+    let mut invalid_constraints = Vec::new();
+    let mut package_ids: HashMap<String, Uuid> = HashMap::new();
+    let mut visited = HashSet::from([contract_id]);
+    let mut frontier = vec![contract_id];
+
+    // Walk the dependency graph breadth-first; cycles stop at visited nodes
+    while !frontier.is_empty() {
+        let rows: Vec<DependencyRow> = sqlx::query_as(
+            "SELECT c.name AS requester, d.dependency_name, d.dependency_contract_id, d.version_constraint
+               FROM contract_dependencies d
+               JOIN contracts c ON c.id = d.contract_id
+              WHERE d.contract_id = ANY($1)
+              ORDER BY c.name, d.dependency_name",
+        )
+        .bind(&frontier)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|err| db_internal_error("list contract dependencies", err))?;
+
+        frontier = Vec::new();
+        for row in rows {
+            match VersionConstraint::parse(&row.version_constraint) {
+                Some(constraint) => requirements.push(Requirement {
+                    requester: row.requester,
+                    package: row.dependency_name.clone(),
+                    constraint,
+                }),
+                None => invalid_constraints.push(format!(
+                    "{} declares an invalid constraint '{}' for {}",
+                    row.requester, row.version_constraint, row.dependency_name
+                )),
+            }
+
+            if let Some(dep_id) = row.dependency_contract_id {
+                package_ids.insert(row.dependency_name, dep_id);
+                if visited.insert(dep_id) {
+                    frontier.push(dep_id);
+                }
+            }
+        }
+    }
+
+    let mut available: HashMap<String, Vec<SemVer>> = HashMap::new();
+    for (package, id) in &package_ids {
+        let versions: Vec<String> =
+            sqlx::query_scalar("SELECT version FROM contract_versions WHERE contract_id = $1")
+                .bind(id)
+                .fetch_all(&state.db)
+                .await
+                .map_err(|err| db_internal_error("list dependency versions", err))?;
+        available.insert(
+            package.clone(),
+            versions
+                .iter()
+                .filter_map(|v| SemVer::parse(v.trim_start_matches('v')))
+                .collect(),
+        );
+    }
+
+    Ok(Json(DependencyResolutionResponse {
+        contract_id,
+        resolution: resolve(&requirements, &available),
+        invalid_constraints,
+    }))
+}
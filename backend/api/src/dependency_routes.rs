@@ -0,0 +1,13 @@
+// api/src/dependency_routes.rs
+// Dependency resolution routes.
+
+use axum::{routing::get, Router};
+
+use crate::{dependency_handlers, state::AppState};
+
+pub fn dependency_routes() -> Router<AppState> {
+    Router::new().route(
+        "/api/contracts/:id/dependencies/resolve",
+        get(dependency_handlers::resolve_dependencies),
+    )
+}
@@ -0,0 +1,950 @@
+// api/src/deployment_approval_handlers.rs
+//
+// Two-person rule for blue/green deployment switches and rollbacks.
+//
+// Routes (registered in deployment_approval_routes.rs):
+//   GET    /api/contracts/:id/deployment-approval-policy  – current policy
+//   PUT    /api/contracts/:id/deployment-approval-policy  – owner: require approvals
+//   DELETE /api/contracts/:id/deployment-approval-policy  – owner: remove requirement
+//   GET    /api/deployments/switch-requests               – list queued switches
+//   GET    /api/deployments/switch-requests/:id           – one request + decisions
+//   POST   /api/deployments/switch-requests/:id/approve   – approver sign-off
+//   POST   /api/deployments/switch-requests/:id/reject    – approver veto
+//
+// Contracts without a policy switch immediately.  With a policy,
+// POST /api/deployments/switch and /rollback queue a request instead; the
+// switch executes once `required_approvals` approvers other than the
+// requester have approved.  A single rejection closes the request.
+//
+// Requesters and approvers are Stellar addresses the caller's session has
+// proven control of (Stellar-key login or a linked publisher, see auth.rs).
+// Only the contract owner or a configured approver may queue a request.  One
+// session can prove several addresses, so self-approval and repeat decisions
+// are checked per login identity as well as per address; a request whose
+// requester identity has since been erased can no longer be decided.
+// Decisions on a request are serialised by locking its row, and a contract
+// has at most one pending request (a partial unique index).
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use shared::{
+    Contract, ContractDeployment, DecideSwitchRequest, DeploymentAction,
+    DeploymentApprovalPolicy, DeploymentEnvironment, DeploymentStatus, DeploymentSwitch,
+    DeploymentSwitchRequest, SetApprovalPolicyRequest, SwitchDecision, SwitchRequestStatus,
+    SwitchRequestWithDecisions,
+};
+use sqlx::{Connection, PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    auth::{proven_addresses, require_contract_owner, require_session},
+    contract_history_handlers::log_settings_change,
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    state::AppState,
+};
+
+fn map_json_rejection(err: JsonRejection) -> ApiError {
+    ApiError::bad_request(
+        "InvalidRequest",
+        format!("Invalid JSON payload: {}", err.body_text()),
+    )
+}
+
+/// Approver set and threshold a queued request is evaluated against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApproverSet {
+    pub approvers: Vec<String>,
+    pub required: i32,
+    pub expiry_seconds: i32,
+}
+
+/// Check an approver list and threshold are usable.
+fn validate_approver_set(approvers: &[String], required: i32) -> Result<(), String> {
+    if required < 1 {
+        return Err("required_approvals must be at least 1".to_string());
+    }
+    if approvers.iter().any(|a| a.trim().is_empty()) {
+        return Err("approver addresses must not be empty".to_string());
+    }
+    let mut unique: Vec<&String> = approvers.iter().collect();
+    unique.sort();
+    unique.dedup();
+    if unique.len() != approvers.len() {
+        return Err("approver addresses must be unique".to_string());
+    }
+    if approvers.len() < required as usize {
+        return Err(format!(
+            "required_approvals ({}) exceeds the number of approvers ({})",
+            required,
+            approvers.len()
+        ));
+    }
+    Ok(())
+}
+
+fn env_name(env: &DeploymentEnvironment) -> &'static str {
+    match env {
+        DeploymentEnvironment::Blue => "blue",
+        DeploymentEnvironment::Green => "green",
+    }
+}
+
+fn status_name(status: SwitchRequestStatus) -> &'static str {
+    match status {
+        SwitchRequestStatus::Pending => "pending",
+        SwitchRequestStatus::Executed => "executed",
+        SwitchRequestStatus::Rejected => "rejected",
+        SwitchRequestStatus::Expired => "expired",
+        SwitchRequestStatus::Failed => "failed",
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+fn evaluate(approvals: usize, rejections: usize, required: i32) -> Outcome {
+    if rejections > 0 {
+        Outcome::Rejected
+    } else if approvals >= required.max(1) as usize {
+        Outcome::Approved
+    } else {
+        Outcome::Pending
+    }
+}
+
+/// The address a caller acts as: `claimed` if the session proved it,
+/// otherwise its only proven address accepted by `eligible`.
+fn pick_address(
+    proven: &[String],
+    claimed: Option<&str>,
+    eligible: impl Fn(&str) -> bool,
+) -> Result<String, &'static str> {
+    if let Some(claimed) = claimed.map(str::trim) {
+        return proven
+            .iter()
+            .find(|a| a.as_str() == claimed)
+            .cloned()
+            .ok_or("this session has not proven control of that address");
+    }
+    let mut candidates = proven.iter().filter(|a| eligible(a));
+    match (candidates.next(), candidates.next()) {
+        (Some(address), None) => Ok(address.clone()),
+        (None, _) => Err("this session has not proven control of an eligible Stellar address"),
+        (Some(_), Some(_)) => Err("this session controls several eligible addresses; name one"),
+    }
+}
+
+/// Whether a decision comes from the requester: the same address, or the
+/// same login identity acting through another of its addresses.
+fn is_self_decision(
+    requested_by: &str,
+    requested_by_identity: Uuid,
+    approver: &str,
+    identity_id: Uuid,
+) -> bool {
+    approver == requested_by || requested_by_identity == identity_id
+}
+
+/// Resolve the login identity of the authenticated caller and the Stellar
+/// address it acts as.
+async fn caller_address(
+    state: &AppState,
+    headers: &HeaderMap,
+    claimed: Option<&str>,
+    eligible: impl Fn(&str) -> bool,
+) -> ApiResult<(Uuid, String)> {
+    let session = require_session(&state.auth, headers)?;
+    let proven = proven_addresses(&state.db, session.sub).await?;
+    let address = pick_address(&proven, claimed, eligible)
+        .map_err(|reason| ApiError::new(StatusCode::FORBIDDEN, "AddressNotProven", reason))?;
+    Ok((session.sub, address))
+}
+
+/// Stellar address of the publisher owning a contract.
+async fn owner_address(pool: &PgPool, contract_id: Uuid) -> ApiResult<String> {
+    sqlx::query_scalar(
+        "SELECT p.stellar_address FROM contracts c
+           JOIN publishers p ON p.id = c.publisher_id
+          WHERE c.id = $1",
+    )
+    .bind(contract_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|err| db_internal_error("get contract owner address", err))
+}
+
+/// Resolve the effective approver set for a contract, if approvals are required.
+pub async fn approver_set(pool: &PgPool, contract_id: Uuid) -> ApiResult<Option<ApproverSet>> {
+    let policy: Option<DeploymentApprovalPolicy> =
+        sqlx::query_as("SELECT * FROM deployment_approval_policies WHERE contract_id = $1")
+            .bind(contract_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|err| db_internal_error("get deployment approval policy", err))?;
+
+    let Some(policy) = policy else {
+        return Ok(None);
+    };
+
+    if let Some(multisig_id) = policy.multisig_policy_id {
+        let (approvers, required): (Vec<String>, i32) = sqlx::query_as(
+            "SELECT signer_addresses, threshold FROM multisig_policies WHERE id = $1",
+        )
+        .bind(multisig_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|err| db_internal_error("get multisig approver set", err))?;
+
+        return Ok(Some(ApproverSet {
+            approvers,
+            required,
+            expiry_seconds: policy.expiry_seconds,
+        }));
+    }
+
+    Ok(Some(ApproverSet {
+        approvers: policy.approvers.unwrap_or_default(),
+        required: policy.required_approvals.unwrap_or(1),
+        expiry_seconds: policy.expiry_seconds,
+    }))
+}
+
+/// Queue a switch or rollback for approval.  The requester must be signed
+/// in as the contract owner or one of its approvers; `requested_by` picks
+/// one of its proven addresses.
+pub async fn queue_switch_request(
+    state: &AppState,
+    headers: &HeaderMap,
+    contract_id: Uuid,
+    action: DeploymentAction,
+    force: bool,
+    requested_by: Option<&str>,
+    set: &ApproverSet,
+) -> ApiResult<DeploymentSwitchRequest> {
+    let pool = &state.db;
+    let owner = owner_address(pool, contract_id).await?;
+    let may_request = |a: &str| a == owner || set.approvers.iter().any(|approver| approver == a);
+    let (identity_id, requested_by) =
+        caller_address(state, headers, requested_by, may_request).await?;
+    if !may_request(&requested_by) {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "NotOwnerOrApprover",
+            format!(
+                "{} is neither the owner nor an approver of this contract",
+                requested_by
+            ),
+        ));
+    }
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin transaction for switch request", err))?;
+
+    // A lapsed request stays pending until touched; close it so it does not
+    // hold the contract's one pending slot
+    sqlx::query(
+        "UPDATE deployment_switch_requests
+            SET status = 'expired', resolved_at = NOW()
+          WHERE contract_id = $1 AND status = 'pending' AND expires_at <= NOW()",
+    )
+    .bind(contract_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("expire lapsed switch requests", err))?;
+
+    // idx_switch_requests_one_pending allows one pending request per contract
+    let request: DeploymentSwitchRequest = sqlx::query_as(
+        "INSERT INTO deployment_switch_requests
+             (contract_id, action, force, requested_by, requested_by_identity,
+              approvers, required_approvals, expires_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+         RETURNING *",
+    )
+    .bind(contract_id)
+    .bind(action)
+    .bind(force)
+    .bind(&requested_by)
+    .bind(identity_id)
+    .bind(&set.approvers)
+    .bind(set.required)
+    .bind(Utc::now() + Duration::seconds(set.expiry_seconds as i64))
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| match err {
+        sqlx::Error::Database(ref db) if db.is_unique_violation() => ApiError::new(
+            StatusCode::CONFLICT,
+            "SwitchAlreadyPending",
+            "A switch request for this contract is already awaiting approval",
+        ),
+        _ => db_internal_error("queue switch request", err),
+    })?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit switch request", err))?;
+
+    tracing::info!(
+        request_id = %request.id,
+        contract_id = %contract_id,
+        action = ?action,
+        required = set.required,
+        "deployment switch queued for approval"
+    );
+
+    Ok(request)
+}
+
+/// Flip the active blue/green environment and record the switch.
+///
+/// A plain switch requires the green deployment to be in `testing` with at
+/// least 3 passed health checks unless `force` is set; a rollback only needs
+/// the other environment to exist.
+pub async fn perform_switch(
+    pool: &PgPool,
+    contract_id: Uuid,
+    action: DeploymentAction,
+    force: bool,
+    switched_by: Option<&str>,
+) -> ApiResult<DeploymentSwitch> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin transaction for switch", err))?;
+
+    let switch = switch_in(&mut tx, contract_id, action, force, switched_by).await?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit deployment switch", err))?;
+
+    Ok(switch)
+}
+
+/// `perform_switch` on a connection that is already in a transaction.
+async fn switch_in(
+    tx: &mut PgConnection,
+    contract_id: Uuid,
+    action: DeploymentAction,
+    force: bool,
+    switched_by: Option<&str>,
+) -> ApiResult<DeploymentSwitch> {
+    let active_deployment: Option<ContractDeployment> = sqlx::query_as(
+        "SELECT * FROM contract_deployments
+         WHERE contract_id = $1 AND status = 'active'",
+    )
+    .bind(contract_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("get active deployment", err))?;
+
+    let from_env = active_deployment
+        .as_ref()
+        .map(|d| d.environment.clone())
+        .unwrap_or(match action {
+            DeploymentAction::Switch => DeploymentEnvironment::Blue,
+            DeploymentAction::Rollback => DeploymentEnvironment::Green,
+        });
+
+    let to_env = match from_env {
+        DeploymentEnvironment::Blue => DeploymentEnvironment::Green,
+        DeploymentEnvironment::Green => DeploymentEnvironment::Blue,
+    };
+
+    let target: Option<ContractDeployment> = sqlx::query_as(
+        "SELECT * FROM contract_deployments
+         WHERE contract_id = $1 AND environment = $2",
+    )
+    .bind(contract_id)
+    .bind(&to_env)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("get target deployment", err))?;
+
+    match (action, target) {
+        (DeploymentAction::Switch, Some(green)) => {
+            if !force && green.status != DeploymentStatus::Testing {
+                return Err(ApiError::bad_request(
+                    "InvalidDeploymentStatus",
+                    "Green deployment must be in testing status before switch",
+                ));
+            }
+            if !force && green.health_checks_passed < 3 {
+                return Err(ApiError::bad_request(
+                    "InsufficientHealthChecks",
+                    "Green deployment must pass at least 3 health checks before switch",
+                ));
+            }
+        }
+        (DeploymentAction::Switch, None) => {
+            return Err(ApiError::bad_request(
+                "NoGreenDeployment",
+                "No green deployment found",
+            ));
+        }
+        (DeploymentAction::Rollback, Some(_)) => {}
+        (DeploymentAction::Rollback, None) => {
+            return Err(ApiError::bad_request(
+                "NoDeploymentToRollback",
+                format!("No {} deployment found to rollback to", env_name(&to_env)),
+            ));
+        }
+    }
+
+    if let Some(ref active) = active_deployment {
+        sqlx::query("UPDATE contract_deployments SET status = 'inactive' WHERE id = $1")
+            .bind(active.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("deactivate current deployment", err))?;
+    }
+
+    sqlx::query(
+        "UPDATE contract_deployments
+         SET status = 'active', activated_at = NOW()
+         WHERE contract_id = $1 AND environment = $2",
+    )
+    .bind(contract_id)
+    .bind(&to_env)
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("activate deployment", err))?;
+
+    let switch: DeploymentSwitch = sqlx::query_as(
+        "INSERT INTO deployment_switches
+             (contract_id, from_environment, to_environment, switched_by, rollback)
+         VALUES ($1, $2, $3, $4, $5)
+         RETURNING *",
+    )
+    .bind(contract_id)
+    .bind(&from_env)
+    .bind(&to_env)
+    .bind(switched_by)
+    .bind(action == DeploymentAction::Rollback)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("record deployment switch", err))?;
+
+    Ok(switch)
+}
+
+async fn fetch_request(state: &AppState, id: Uuid) -> ApiResult<DeploymentSwitchRequest> {
+    sqlx::query_as("SELECT * FROM deployment_switch_requests WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("get switch request", err))?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "SwitchRequestNotFound",
+                format!("No switch request found with ID: {}", id),
+            )
+        })
+}
+
+async fn with_decisions(
+    state: &AppState,
+    request: DeploymentSwitchRequest,
+) -> ApiResult<SwitchRequestWithDecisions> {
+    let decisions: Vec<SwitchDecision> = sqlx::query_as(
+        "SELECT * FROM deployment_switch_decisions WHERE request_id = $1 ORDER BY created_at",
+    )
+    .bind(request.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list switch decisions", err))?;
+
+    Ok(SwitchRequestWithDecisions { request, decisions })
+}
+
+async fn set_request_status(
+    conn: &mut PgConnection,
+    id: Uuid,
+    status: SwitchRequestStatus,
+    error_message: Option<&str>,
+) -> ApiResult<DeploymentSwitchRequest> {
+    sqlx::query_as(
+        "UPDATE deployment_switch_requests
+            SET status = $2, error_message = $3, resolved_at = NOW()
+          WHERE id = $1
+          RETURNING *",
+    )
+    .bind(id)
+    .bind(status)
+    .bind(error_message)
+    .fetch_one(conn)
+    .await
+    .map_err(|err| db_internal_error("update switch request status", err))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/contracts/:id/deployment-approval-policy
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn get_approval_policy(
+    State(state): State<AppState>,
+    Path(contract_id): Path<Uuid>,
+) -> ApiResult<Json<DeploymentApprovalPolicy>> {
+    sqlx::query_as("SELECT * FROM deployment_approval_policies WHERE contract_id = $1")
+        .bind(contract_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("get deployment approval policy", err))?
+        .map(Json)
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "ApprovalPolicyNotFound",
+                format!("Contract {} does not require deployment approvals", contract_id),
+            )
+        })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// PUT /api/contracts/:id/deployment-approval-policy
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn set_approval_policy(
+    State(state): State<AppState>,
+    Path(contract_id): Path<Uuid>,
+    headers: HeaderMap,
+    payload: Result<Json<SetApprovalPolicyRequest>, JsonRejection>,
+) -> ApiResult<Json<DeploymentApprovalPolicy>> {
+    let owner = require_contract_owner(&state, &headers, contract_id).await?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+
+    let expiry_seconds = req.expiry_seconds.unwrap_or(86400);
+    if expiry_seconds < 60 {
+        return Err(ApiError::bad_request(
+            "InvalidExpiry",
+            "expiry_seconds must be at least 60",
+        ));
+    }
+
+    match (&req.multisig_policy_id, &req.approvers) {
+        (Some(_), Some(_)) => {
+            return Err(ApiError::bad_request(
+                "InvalidPolicy",
+                "Provide either multisig_policy_id or approvers, not both",
+            ));
+        }
+        (Some(policy_id), None) => {
+            let exists: bool =
+                sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM multisig_policies WHERE id = $1)")
+                    .bind(policy_id)
+                    .fetch_one(&state.db)
+                    .await
+                    .map_err(|err| db_internal_error("check multisig policy", err))?;
+            if !exists {
+                return Err(ApiError::not_found(
+                    "PolicyNotFound",
+                    format!("No multisig policy found with ID: {}", policy_id),
+                ));
+            }
+        }
+        (None, Some(approvers)) => {
+            let required = req.required_approvals.unwrap_or(1);
+            validate_approver_set(approvers, required)
+                .map_err(|msg| ApiError::bad_request("InvalidPolicy", msg))?;
+        }
+        (None, None) => {
+            return Err(ApiError::bad_request(
+                "InvalidPolicy",
+                "Provide approvers with required_approvals, or a multisig_policy_id",
+            ));
+        }
+    }
+
+    let required = req
+        .approvers
+        .as_ref()
+        .map(|_| req.required_approvals.unwrap_or(1));
+
+    let policy: DeploymentApprovalPolicy = sqlx::query_as(
+        "INSERT INTO deployment_approval_policies
+             (contract_id, approvers, required_approvals, multisig_policy_id, expiry_seconds, created_by)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         ON CONFLICT (contract_id) DO UPDATE
+            SET approvers = EXCLUDED.approvers,
+                required_approvals = EXCLUDED.required_approvals,
+                multisig_policy_id = EXCLUDED.multisig_policy_id,
+                expiry_seconds = EXCLUDED.expiry_seconds,
+                created_by = EXCLUDED.created_by,
+                updated_at = NOW()
+         RETURNING *",
+    )
+    .bind(contract_id)
+    .bind(&req.approvers)
+    .bind(required)
+    .bind(req.multisig_policy_id)
+    .bind(expiry_seconds)
+    .bind(&owner.address)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("upsert deployment approval policy", err))?;
+
+    log_settings_change(
+        &state.db,
+        contract_id,
+        &owner,
+        "deployment_approval_policy",
+        serde_json::json!(policy),
+    )
+    .await;
+    tracing::info!(contract_id = %contract_id, "deployment approval policy updated");
+    Ok(Json(policy))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// DELETE /api/contracts/:id/deployment-approval-policy
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn delete_approval_policy(
+    State(state): State<AppState>,
+    Path(contract_id): Path<Uuid>,
+    headers: HeaderMap,
+) -> ApiResult<StatusCode> {
+    let owner = require_contract_owner(&state, &headers, contract_id).await?;
+
+    sqlx::query("DELETE FROM deployment_approval_policies WHERE contract_id = $1")
+        .bind(contract_id)
+        .execute(&state.db)
+        .await
+        .map_err(|err| db_internal_error("delete deployment approval policy", err))?;
+
+    log_settings_change(
+        &state.db,
+        contract_id,
+        &owner,
+        "deployment_approval_policy",
+        serde_json::Value::Null,
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListSwitchRequestsQuery {
+    pub contract_id: Option<Uuid>,
+    pub status: Option<SwitchRequestStatus>,
+    pub limit: Option<i64>,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/deployments/switch-requests
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn list_switch_requests(
+    State(state): State<AppState>,
+    Query(query): Query<ListSwitchRequestsQuery>,
+) -> ApiResult<Json<Vec<DeploymentSwitchRequest>>> {
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+
+    let requests: Vec<DeploymentSwitchRequest> = sqlx::query_as(
+        "SELECT * FROM deployment_switch_requests
+          WHERE ($1::uuid IS NULL OR contract_id = $1)
+            AND ($2::switch_request_status IS NULL OR status = $2)
+          ORDER BY created_at DESC
+          LIMIT $3",
+    )
+    .bind(query.contract_id)
+    .bind(query.status)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list switch requests", err))?;
+
+    Ok(Json(requests))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/deployments/switch-requests/:id
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn get_switch_request(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<SwitchRequestWithDecisions>> {
+    let request = fetch_request(&state, id).await?;
+    Ok(Json(with_decisions(&state, request).await?))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/deployments/switch-requests/:id/approve
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn approve_switch_request(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    payload: Result<Json<DecideSwitchRequest>, JsonRejection>,
+) -> ApiResult<Json<SwitchRequestWithDecisions>> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    decide(state, headers, id, req, true).await
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/deployments/switch-requests/:id/reject
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn reject_switch_request(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    payload: Result<Json<DecideSwitchRequest>, JsonRejection>,
+) -> ApiResult<Json<SwitchRequestWithDecisions>> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    decide(state, headers, id, req, false).await
+}
+
+async fn decide(
+    state: AppState,
+    headers: HeaderMap,
+    id: Uuid,
+    req: DecideSwitchRequest,
+    approved: bool,
+) -> ApiResult<Json<SwitchRequestWithDecisions>> {
+    let approvers = fetch_request(&state, id).await?.approvers;
+    let (identity_id, approver) = caller_address(&state, &headers, req.approver.as_deref(), |a| {
+        approvers.iter().any(|approver| approver == a)
+    })
+    .await?;
+
+    // The row lock serialises concurrent decisions, so exactly one of them
+    // sees the threshold reached and executes the switch.
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin transaction for switch decision", err))?;
+
+    let request: DeploymentSwitchRequest =
+        sqlx::query_as("SELECT * FROM deployment_switch_requests WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("lock switch request", err))?;
+
+    if request.status != SwitchRequestStatus::Pending {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "SwitchRequestClosed",
+            format!("Switch request is already {}", status_name(request.status)),
+        ));
+    }
+
+    if request.expires_at < Utc::now() {
+        set_request_status(&mut tx, id, SwitchRequestStatus::Expired, None).await?;
+        tx.commit()
+            .await
+            .map_err(|err| db_internal_error("commit switch request expiry", err))?;
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "SwitchRequestExpired",
+            "Switch request has expired",
+        ));
+    }
+
+    if !request.approvers.contains(&approver) {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "NotAnApprover",
+            format!("{} is not an approver for this contract", approver),
+        ));
+    }
+
+    let requested_by_identity: Option<Uuid> = sqlx::query_scalar(
+        "SELECT requested_by_identity FROM deployment_switch_requests WHERE id = $1",
+    )
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("get switch requester", err))?;
+
+    // Without the requester's identity (erased, or queued before identities
+    // were recorded) a decision cannot be checked for self-approval
+    let Some(requested_by_identity) = requested_by_identity else {
+        set_request_status(
+            &mut tx,
+            id,
+            SwitchRequestStatus::Failed,
+            Some("requester identity no longer exists"),
+        )
+        .await?;
+        tx.commit()
+            .await
+            .map_err(|err| db_internal_error("commit switch request failure", err))?;
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "RequesterIdentityMissing",
+            "The requester's login identity no longer exists; queue the switch again",
+        ));
+    };
+
+    if is_self_decision(
+        &request.requested_by,
+        requested_by_identity,
+        &approver,
+        identity_id,
+    ) {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "SelfApproval",
+            "The requester cannot decide on their own switch request",
+        ));
+    }
+
+    // Unique per address and per identity: one session proving two approver
+    // addresses still gets a single decision
+    sqlx::query(
+        "INSERT INTO deployment_switch_decisions
+             (request_id, approver, identity_id, approved, comment)
+         VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(id)
+    .bind(&approver)
+    .bind(identity_id)
+    .bind(approved)
+    .bind(&req.comment)
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| match err {
+        sqlx::Error::Database(ref db) if db.is_unique_violation() => ApiError::new(
+            StatusCode::CONFLICT,
+            "AlreadyDecided",
+            "This identity has already decided on this request",
+        ),
+        _ => db_internal_error("record switch decision", err),
+    })?;
+
+    let (approvals, rejections): (i64, i64) = sqlx::query_as(
+        "SELECT COUNT(*) FILTER (WHERE approved), COUNT(*) FILTER (WHERE NOT approved)
+           FROM deployment_switch_decisions WHERE request_id = $1",
+    )
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("count switch decisions", err))?;
+
+    let request = match evaluate(approvals as usize, rejections as usize, request.required_approvals) {
+        Outcome::Pending => request,
+        Outcome::Rejected => {
+            tracing::info!(request_id = %id, approver = %approver, "switch request rejected");
+            set_request_status(&mut tx, id, SwitchRequestStatus::Rejected, None).await?
+        }
+        Outcome::Approved => {
+            // A savepoint keeps the decision when the switch itself fails
+            let mut savepoint = tx
+                .begin()
+                .await
+                .map_err(|err| db_internal_error("begin savepoint for switch", err))?;
+            let result = switch_in(
+                &mut savepoint,
+                request.contract_id,
+                request.action,
+                request.force,
+                Some(&request.requested_by),
+            )
+            .await;
+            match result {
+                Ok(switch) => {
+                    savepoint
+                        .commit()
+                        .await
+                        .map_err(|err| db_internal_error("release savepoint for switch", err))?;
+                    tracing::info!(
+                        request_id = %id,
+                        switch_id = %switch.id,
+                        "approved deployment switch executed"
+                    );
+                    set_request_status(&mut tx, id, SwitchRequestStatus::Executed, None).await?
+                }
+                Err(err) => {
+                    savepoint
+                        .rollback()
+                        .await
+                        .map_err(|err| db_internal_error("roll back savepoint for switch", err))?;
+                    tracing::warn!(request_id = %id, error = ?err, "approved deployment switch failed");
+                    set_request_status(
+                        &mut tx,
+                        id,
+                        SwitchRequestStatus::Failed,
+                        Some(err.message()),
+                    )
+                    .await?
+                }
+            }
+        }
+    };
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit switch decision", err))?;
+
+    Ok(Json(with_decisions(&state, request).await?))
+}
+
+/// Load a contract by its on-chain ID, mapping a miss to 404.
+pub async fn contract_by_onchain_id(state: &AppState, contract_id: &str) -> ApiResult<Contract> {
+    sqlx::query_as("SELECT * FROM contracts WHERE contract_id = $1")
+        .bind(contract_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("get contract for deployment", err))?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "ContractNotFound",
+                format!("Contract not found: {}", contract_id),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addrs(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn validates_approver_sets() {
+        assert!(validate_approver_set(&addrs(&["GA", "GB"]), 2).is_ok());
+        assert!(validate_approver_set(&addrs(&["GA", "GB"]), 3).is_err());
+        assert!(validate_approver_set(&addrs(&["GA", "GA"]), 1).is_err());
+        assert!(validate_approver_set(&addrs(&["GA"]), 0).is_err());
+        assert!(validate_approver_set(&addrs(&[" "]), 1).is_err());
+    }
+
+    #[test]
+    fn evaluates_decisions() {
+        assert_eq!(evaluate(0, 0, 2), Outcome::Pending);
+        assert_eq!(evaluate(1, 0, 2), Outcome::Pending);
+        assert_eq!(evaluate(2, 0, 2), Outcome::Approved);
+        assert_eq!(evaluate(5, 1, 2), Outcome::Rejected);
+    }
+
+    #[test]
+    fn self_decisions_are_caught_per_identity() {
+        let requester = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        assert!(is_self_decision("GA", requester, "GA", other));
+        // Same person approving through a second proven address
+        assert!(is_self_decision("GA", requester, "GB", requester));
+        assert!(!is_self_decision("GA", requester, "GB", other));
+    }
+
+    #[test]
+    fn picks_only_proven_addresses() {
+        let proven = addrs(&["GA", "GB"]);
+        assert_eq!(pick_address(&proven, Some("GB"), |_| true), Ok("GB".to_string()));
+        assert!(pick_address(&proven, Some("GC"), |_| true).is_err());
+        assert_eq!(pick_address(&proven, None, |a| a == "GA"), Ok("GA".to_string()));
+        assert!(pick_address(&proven, None, |_| true).is_err());
+        assert!(pick_address(&proven, None, |_| false).is_err());
+    }
+}
@@ -0,0 +1,35 @@
+// api/src/deployment_approval_routes.rs
+// Deployment approval (two-person rule) routes.
+
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::{deployment_approval_handlers, state::AppState};
+
+pub fn deployment_approval_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/contracts/:id/deployment-approval-policy",
+            get(deployment_approval_handlers::get_approval_policy)
+                .put(deployment_approval_handlers::set_approval_policy)
+                .delete(deployment_approval_handlers::delete_approval_policy),
+        )
+        .route(
+            "/api/deployments/switch-requests",
+            get(deployment_approval_handlers::list_switch_requests),
+        )
+        .route(
+            "/api/deployments/switch-requests/:id",
+            get(deployment_approval_handlers::get_switch_request),
+        )
+        .route(
+            "/api/deployments/switch-requests/:id/approve",
+            post(deployment_approval_handlers::approve_switch_request),
+        )
+        .route(
+            "/api/deployments/switch-requests/:id/reject",
+            post(deployment_approval_handlers::reject_switch_request),
+        )
+}
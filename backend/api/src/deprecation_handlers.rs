@@ -0,0 +1,452 @@
+// api/src/deprecation_handlers.rs
+//
+// Contract deprecation and notices to dependents.
+//
+// Routes (registered in deprecation_routes.rs):
+//   GET  /api/deprecations?contract_ids=                              – deprecations of many contracts
+//   GET  /api/contracts/:id/deprecation                               – deprecation, if any
+//   PUT  /api/contracts/:id/deprecation                               – owner: deprecate
+//   GET  /api/contracts/:id/deprecated-dependencies                   – deprecated direct deps
+//   GET  /api/contracts/:id/deprecation-notices                       – notices for a dependent
+//   POST /api/contracts/:id/deprecation-notices/:notice_id/acknowledge – owner: acknowledge
+//
+// Deprecating a contract records a notice for each contract that declares it
+// as a dependency and refreshes their health, whose breakdown lists
+// deprecated dependencies (see health_monitor.rs).  Each new notice is also
+// posted to the webhook the dependent's owner configured with the contract's
+// budget (contract_budgets.webhook_url, checked by webhook.rs; there is no
+// separate notice channel).  Failed deliveries are retried by the budget
+// monitor with the same backoff as budget alerts, and the outcome is kept on
+// the notice.  Owners without a webhook see notices through the notices
+// endpoint and the health breakdown.
+
+use axum::{
+    extract::{
+        rejection::{JsonRejection, QueryRejection},
+        Path, Query, State,
+    },
+    http::HeaderMap,
+    Json,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use shared::{
+    ContractDeprecation, DeprecateContractRequest, DeprecatedDependency, DeprecationNotice,
+    DeprecationResponse,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    auth::require_contract_owner,
+    budget_monitor::{retry_delay, MAX_DELIVERY_ATTEMPTS},
+    contract_history_handlers::log_settings_change,
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    health_monitor::{deprecated_dependencies, refresh_contract_health},
+    state::AppState,
+    webhook,
+};
+
+fn map_json_rejection(err: JsonRejection) -> ApiError {
+    ApiError::bad_request(
+        "InvalidRequest",
+        format!("Invalid JSON payload: {}", err.body_text()),
+    )
+}
+
+fn map_query_rejection(err: QueryRejection) -> ApiError {
+    ApiError::bad_request(
+        "InvalidQuery",
+        format!("Invalid query parameters: {}", err.body_text()),
+    )
+}
+
+/// Most contracts one batch lookup may name.
+const MAX_BATCH_CONTRACTS: usize = 200;
+
+async fn ensure_contract_exists(state: &AppState, contract_id: Uuid) -> ApiResult<()> {
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM contracts WHERE id = $1)")
+        .bind(contract_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("check contract exists", err))?;
+    if !exists {
+        return Err(ApiError::not_found(
+            "ContractNotFound",
+            format!("No contract found with ID: {}", contract_id),
+        ));
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/deprecations?contract_ids=a,b,c
+// ─────────────────────────────────────────────────────────────────────────────
+#[derive(Debug, Deserialize)]
+pub struct DeprecationsQuery {
+    /// Comma-separated contract ids
+    pub contract_ids: String,
+}
+
+/// Deprecations among the given contracts; contracts that are not deprecated
+/// are left out.  Lets `deps list` annotate a whole tree in one request.
+pub async fn list_deprecations(
+    State(state): State<AppState>,
+    params: Result<Query<DeprecationsQuery>, QueryRejection>,
+) -> ApiResult<Json<Vec<ContractDeprecation>>> {
+    let Query(params) = params.map_err(map_query_rejection)?;
+    let ids = params
+        .contract_ids
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| {
+            Uuid::parse_str(id).map_err(|_| {
+                ApiError::bad_request("InvalidContractId", format!("Not a contract id: {}", id))
+            })
+        })
+        .collect::<ApiResult<Vec<Uuid>>>()?;
+    if ids.len() > MAX_BATCH_CONTRACTS {
+        return Err(ApiError::bad_request(
+            "TooManyContracts",
+            format!("At most {} contract ids per request", MAX_BATCH_CONTRACTS),
+        ));
+    }
+
+    let deprecations: Vec<ContractDeprecation> =
+        sqlx::query_as("SELECT * FROM contract_deprecations WHERE contract_id = ANY($1)")
+            .bind(&ids)
+            .fetch_all(&state.db)
+            .await
+            .map_err(|err| db_internal_error("list contract deprecations", err))?;
+
+    Ok(Json(deprecations))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/contracts/:id/deprecation
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn get_deprecation(
+    State(state): State<AppState>,
+    Path(contract_id): Path<Uuid>,
+) -> ApiResult<Json<ContractDeprecation>> {
+    let deprecation: Option<ContractDeprecation> =
+        sqlx::query_as("SELECT * FROM contract_deprecations WHERE contract_id = $1")
+            .bind(contract_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("get contract deprecation", err))?;
+
+    deprecation.map(Json).ok_or_else(|| {
+        ApiError::not_found(
+            "NotDeprecated",
+            format!("Contract {} is not deprecated", contract_id),
+        )
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// PUT /api/contracts/:id/deprecation
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn deprecate_contract(
+    State(state): State<AppState>,
+    Path(contract_id): Path<Uuid>,
+    headers: HeaderMap,
+    payload: Result<Json<DeprecateContractRequest>, JsonRejection>,
+) -> ApiResult<Json<DeprecationResponse>> {
+    let owner = require_contract_owner(&state, &headers, contract_id).await?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+
+    let reason = req.reason.trim();
+    if reason.is_empty() {
+        return Err(ApiError::bad_request(
+            "InvalidDeprecation",
+            "A deprecation reason is required",
+        ));
+    }
+    if let Some(replacement) = req.replacement_contract_id {
+        if replacement == contract_id {
+            return Err(ApiError::bad_request(
+                "InvalidDeprecation",
+                "A contract cannot replace itself",
+            ));
+        }
+        ensure_contract_exists(&state, replacement).await?;
+    }
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin deprecation", err))?;
+
+    let deprecation: ContractDeprecation = sqlx::query_as(
+        "INSERT INTO contract_deprecations (contract_id, reason, replacement_contract_id, deprecated_by)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (contract_id) DO UPDATE SET
+             reason                  = EXCLUDED.reason,
+             replacement_contract_id = EXCLUDED.replacement_contract_id,
+             deprecated_by           = EXCLUDED.deprecated_by
+         RETURNING *",
+    )
+    .bind(contract_id)
+    .bind(reason)
+    .bind(req.replacement_contract_id)
+    .bind(&owner.address)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("deprecate contract", err))?;
+
+    // One notice per dependent; re-deprecating does not record a second one.
+    // The first delivery happens below, so the retry pass holds off until
+    // its first backoff step.
+    let notices: Vec<(Uuid, Uuid)> = sqlx::query_as(
+        "INSERT INTO deprecation_notices
+             (dependent_contract_id, deprecated_contract_id, dependency_name, next_delivery_at)
+         SELECT contract_id, dependency_contract_id, dependency_name, $2
+           FROM contract_dependencies
+          WHERE dependency_contract_id = $1
+         ON CONFLICT (dependent_contract_id, deprecated_contract_id) DO NOTHING
+         RETURNING id, dependent_contract_id",
+    )
+    .bind(contract_id)
+    .bind(Utc::now() + retry_delay(0))
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("create deprecation notices", err))?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit deprecation", err))?;
+
+    let dependents: Vec<Uuid> = notices.iter().map(|(_, dependent)| *dependent).collect();
+    let pool = state.db.clone();
+    tokio::spawn(async move {
+        for (notice_id, _) in notices {
+            deliver_notice(&pool, notice_id, 0).await;
+        }
+    });
+
+    log_settings_change(
+        &state.db,
+        contract_id,
+        &owner,
+        "deprecation",
+        serde_json::json!(deprecation),
+    )
+    .await;
+
+    tracing::info!(
+        contract_id = %contract_id,
+        notices = dependents.len(),
+        "contract deprecated"
+    );
+
+    // Dependents' trust scores now include the deprecated dependency
+    for dependent in &dependents {
+        if let Err(err) = refresh_contract_health(&state.db, *dependent).await {
+            tracing::warn!(contract_id = %dependent, "failed to refresh contract health: {}", err);
+        }
+    }
+
+    Ok(Json(DeprecationResponse {
+        deprecation,
+        notified_dependents: dependents.len() as u64,
+    }))
+}
+
+/// A notice with the webhook its dependent's owner configured, if any.
+async fn notice_target(
+    pool: &PgPool,
+    notice_id: Uuid,
+) -> Result<(DeprecationNotice, Option<String>), sqlx::Error> {
+    let notice: DeprecationNotice = sqlx::query_as(&format!("{} WHERE n.id = $1", NOTICE_SELECT))
+        .bind(notice_id)
+        .fetch_one(pool)
+        .await?;
+    let url: Option<String> =
+        sqlx::query_scalar("SELECT webhook_url FROM contract_budgets WHERE contract_id = $1")
+            .bind(notice.dependent_contract_id)
+            .fetch_optional(pool)
+            .await?
+            .flatten();
+    Ok((notice, url))
+}
+
+/// Post one notice to its dependent's webhook, if the owner configured one,
+/// and record the outcome on the notice.  `attempts` is the number of earlier
+/// deliveries, which sets the backoff before the next retry.
+async fn deliver_notice(pool: &PgPool, notice_id: Uuid, attempts: i32) {
+    let (notice, url) = match notice_target(pool, notice_id).await {
+        Ok((notice, Some(url))) => (notice, url),
+        Ok((_, None)) => return,
+        Err(err) => {
+            tracing::warn!(notice_id = %notice_id, "failed to load deprecation notice: {}", err);
+            return;
+        }
+    };
+
+    let (notified_at, error, next_delivery_at) = match notify(&url, &notice).await {
+        Ok(()) => (Some(Utc::now()), None, None),
+        Err(reason) => {
+            tracing::warn!(
+                notice_id = %notice_id,
+                attempt = attempts + 1,
+                "deprecation webhook failed: {}",
+                reason
+            );
+            (None, Some(reason), Some(Utc::now() + retry_delay(attempts)))
+        }
+    };
+    let recorded = sqlx::query(
+        "UPDATE deprecation_notices
+            SET delivery_attempts = delivery_attempts + 1,
+                notified_at = $2,
+                last_delivery_error = $3,
+                next_delivery_at = $4
+          WHERE id = $1",
+    )
+    .bind(notice_id)
+    .bind(notified_at)
+    .bind(error)
+    .bind(next_delivery_at)
+    .execute(pool)
+    .await;
+    if let Err(err) = recorded {
+        tracing::warn!(notice_id = %notice_id, "failed to record notice delivery: {}", err);
+    }
+}
+
+/// Deliver every unacknowledged notice that is due and whose dependent has a
+/// webhook, including ones created before the owner configured it.  Run on
+/// each budget monitor pass.
+pub async fn deliver_due_notices(pool: &PgPool) -> Result<(), sqlx::Error> {
+    let due: Vec<(Uuid, i32)> = sqlx::query_as(
+        "SELECT n.id, n.delivery_attempts
+           FROM deprecation_notices n
+           JOIN contract_budgets b ON b.contract_id = n.dependent_contract_id
+          WHERE n.notified_at IS NULL
+            AND n.acknowledged_at IS NULL
+            AND b.webhook_url IS NOT NULL
+            AND n.delivery_attempts < $1
+            AND (n.next_delivery_at IS NULL OR n.next_delivery_at <= NOW())
+          ORDER BY n.created_at",
+    )
+    .bind(MAX_DELIVERY_ATTEMPTS)
+    .fetch_all(pool)
+    .await?;
+
+    for (notice_id, attempts) in due {
+        deliver_notice(pool, notice_id, attempts).await;
+    }
+    Ok(())
+}
+
+async fn notify(url: &str, notice: &DeprecationNotice) -> Result<(), String> {
+    let (url, addr) = webhook::check_webhook_url(url).await?;
+    let client = webhook::pinned_client(&url, addr).map_err(|e| e.to_string())?;
+    let payload = serde_json::json!({
+        "event": "dependency_deprecated",
+        "notice": notice,
+    });
+
+    let resp = client
+        .post(url)
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        return Err(format!("webhook responded {}", resp.status()));
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/contracts/:id/deprecated-dependencies
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn list_deprecated_dependencies(
+    State(state): State<AppState>,
+    Path(contract_id): Path<Uuid>,
+) -> ApiResult<Json<Vec<DeprecatedDependency>>> {
+    ensure_contract_exists(&state, contract_id).await?;
+
+    let deps = deprecated_dependencies(&state.db, contract_id)
+        .await
+        .map_err(|err| db_internal_error("list deprecated dependencies", err))?;
+
+    Ok(Json(deps))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/contracts/:id/deprecation-notices
+// ─────────────────────────────────────────────────────────────────────────────
+const NOTICE_SELECT: &str = "
+    SELECT n.id, n.dependent_contract_id, n.dependency_name, dep.contract_id, dep.reason,
+           dep.replacement_contract_id, r.name AS replacement_name,
+           dep.created_at AS deprecated_at, n.created_at, n.acknowledged_at
+      FROM deprecation_notices n
+      JOIN contract_deprecations dep ON dep.contract_id = n.deprecated_contract_id
+      LEFT JOIN contracts r ON r.id = dep.replacement_contract_id";
+
+pub async fn list_deprecation_notices(
+    State(state): State<AppState>,
+    Path(contract_id): Path<Uuid>,
+) -> ApiResult<Json<Vec<DeprecationNotice>>> {
+    ensure_contract_exists(&state, contract_id).await?;
+
+    let notices: Vec<DeprecationNotice> = sqlx::query_as(&format!(
+        "{} WHERE n.dependent_contract_id = $1 ORDER BY n.created_at DESC",
+        NOTICE_SELECT
+    ))
+    .bind(contract_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list deprecation notices", err))?;
+
+    Ok(Json(notices))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/contracts/:id/deprecation-notices/:notice_id/acknowledge
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn acknowledge_deprecation_notice(
+    State(state): State<AppState>,
+    Path((contract_id, notice_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+) -> ApiResult<Json<DeprecationNotice>> {
+    let owner = require_contract_owner(&state, &headers, contract_id).await?;
+
+    let updated = sqlx::query(
+        "UPDATE deprecation_notices SET acknowledged_at = COALESCE(acknowledged_at, NOW())
+          WHERE id = $1 AND dependent_contract_id = $2",
+    )
+    .bind(notice_id)
+    .bind(contract_id)
+    .execute(&state.db)
+    .await
+    .map_err(|err| db_internal_error("acknowledge deprecation notice", err))?;
+    if updated.rows_affected() == 0 {
+        return Err(ApiError::not_found(
+            "NoticeNotFound",
+            format!("No deprecation notice {} for contract {}", notice_id, contract_id),
+        ));
+    }
+    log_settings_change(
+        &state.db,
+        contract_id,
+        &owner,
+        "deprecation_notice_acknowledged",
+        serde_json::json!({ "notice_id": notice_id }),
+    )
+    .await;
+
+    let notice: DeprecationNotice = sqlx::query_as(&format!("{} WHERE n.id = $1", NOTICE_SELECT))
+        .bind(notice_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("get deprecation notice", err))?;
+
+    Ok(Json(notice))
+}
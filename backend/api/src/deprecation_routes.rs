@@ -0,0 +1,30 @@
+// api/src/deprecation_routes.rs
+// Contract deprecation routes.
+
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::{deprecation_handlers, state::AppState};
+
+pub fn deprecation_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/deprecations", get(deprecation_handlers::list_deprecations))
+        .route(
+            "/api/contracts/:id/deprecation",
+            get(deprecation_handlers::get_deprecation).put(deprecation_handlers::deprecate_contract),
+        )
+        .route(
+            "/api/contracts/:id/deprecated-dependencies",
+            get(deprecation_handlers::list_deprecated_dependencies),
+        )
+        .route(
+            "/api/contracts/:id/deprecation-notices",
+            get(deprecation_handlers::list_deprecation_notices),
+        )
+        .route(
+            "/api/contracts/:id/deprecation-notices/:notice_id/acknowledge",
+            post(deprecation_handlers::acknowledge_deprecation_notice),
+        )
+}
@@ -47,6 +47,10 @@ impl ApiError {
             message,
         )
     }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
 }
 
 impl IntoResponse for ApiError {
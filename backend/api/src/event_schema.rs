@@ -0,0 +1,418 @@
+// api/src/event_schema.rs
+//
+// Contract event schemas: validation against the ABI and decoding of
+// positional event topics/data into named, typed fields.
+//
+// A schema lists constant prefix topics (usually the event name symbol)
+// followed by fields located either in the topic list or in the data payload.
+// Topic fields are read in order after the prefix; data fields are read from
+// the whole payload (one field), a vec (positional) or a map (by name).
+
+use serde_json::Value;
+use shared::{
+    DecodedContractEvent, EventFieldLocation, EventFieldSchema, EventSchemaDefinition,
+    RawContractEvent, TypedEventValue,
+};
+use std::collections::{BTreeMap, HashSet};
+
+const BUILTIN_TYPES: &[&str] = &[
+    "bool",
+    "void",
+    "u32",
+    "i32",
+    "u64",
+    "i64",
+    "u128",
+    "i128",
+    "u256",
+    "i256",
+    "timepoint",
+    "duration",
+    "bytes",
+    "string",
+    "symbol",
+    "address",
+    "muxed_address",
+];
+
+/// Names of user-defined types (structs, unions, enums, errors) in an ABI.
+fn abi_udts(abi: &Value) -> HashSet<String> {
+    abi_entries(abi)
+        .filter(|e| {
+            matches!(
+                e.get("type").and_then(Value::as_str),
+                Some("struct" | "union" | "enum" | "error")
+            )
+        })
+        .filter_map(|e| e.get("name").and_then(Value::as_str).map(str::to_string))
+        .collect()
+}
+
+fn abi_entries(abi: &Value) -> impl Iterator<Item = &Value> {
+    abi.as_array().into_iter().flatten()
+}
+
+/// Split `a, map<b, c>` on top-level commas only.
+fn split_top_level(args: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0usize, 0usize);
+    for (i, c) in args.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(args[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(args[start..].trim());
+    parts
+}
+
+/// Whether `ty` is a Soroban builtin, a generic over known types or an ABI UDT.
+fn is_known_type(ty: &str, udts: &HashSet<String>) -> bool {
+    let ty = ty.trim();
+    if BUILTIN_TYPES.contains(&ty) || udts.contains(ty) {
+        return true;
+    }
+
+    let Some((outer, rest)) = ty.split_once('<') else {
+        return false;
+    };
+    let Some(args) = rest.strip_suffix('>') else {
+        return false;
+    };
+    let args = split_top_level(args);
+
+    match (outer.trim(), args.as_slice()) {
+        ("vec" | "option", [inner]) => is_known_type(inner, udts),
+        ("map" | "result", [k, v]) => is_known_type(k, udts) && is_known_type(v, udts),
+        ("bytes_n", [n]) => n.parse::<u32>().is_ok(),
+        _ => false,
+    }
+}
+
+/// Field list declared for `event_name` in the ABI, if the ABI has event specs.
+fn abi_event<'a>(abi: &'a Value, event_name: &str) -> Option<&'a Value> {
+    abi_entries(abi).find(|e| {
+        e.get("type").and_then(Value::as_str) == Some("event")
+            && e.get("name").and_then(Value::as_str) == Some(event_name)
+    })
+}
+
+fn abi_param_location(param: &Value) -> Option<EventFieldLocation> {
+    match param.get("location").and_then(Value::as_str) {
+        Some("topic" | "topic_list") => Some(EventFieldLocation::Topic),
+        Some("data") => Some(EventFieldLocation::Data),
+        _ => None,
+    }
+}
+
+/// Validate one event schema against the contract version's ABI.
+///
+/// Returns every problem found so publishers can fix them in one round-trip.
+pub fn validate_against_abi(
+    schema: &EventSchemaDefinition,
+    abi: &Value,
+) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    let name = &schema.event_name;
+
+    if name.is_empty()
+        || name.len() > 64
+        || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        errors.push(format!(
+            "event name '{}' must be 1-64 characters of [A-Za-z0-9_]",
+            name
+        ));
+    }
+    if schema.fields.is_empty() && schema.prefix_topics.is_empty() {
+        errors.push(format!(
+            "{}: schema must declare prefix topics or fields",
+            name
+        ));
+    }
+
+    let udts = abi_udts(abi);
+    let mut seen = HashSet::new();
+    for field in &schema.fields {
+        if field.name.trim().is_empty() {
+            errors.push(format!("{}: field names must not be empty", name));
+        } else if !seen.insert(field.name.as_str()) {
+            errors.push(format!("{}.{}: duplicate field name", name, field.name));
+        }
+        if !is_known_type(&field.type_name, &udts) {
+            errors.push(format!(
+                "{}.{}: unknown type '{}' (not a Soroban builtin or a type in the ABI)",
+                name, field.name, field.type_name
+            ));
+        }
+    }
+
+    // ABIs built with event specs must agree with the registered layout
+    if let Some(spec) = abi_event(abi, name) {
+        if let Some(prefix) = spec.get("prefix_topics").and_then(Value::as_array) {
+            let prefix: Vec<&str> = prefix.iter().filter_map(Value::as_str).collect();
+            if prefix
+                != schema
+                    .prefix_topics
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+            {
+                errors.push(format!(
+                    "{}: prefix topics {:?} do not match the ABI {:?}",
+                    name, schema.prefix_topics, prefix
+                ));
+            }
+        }
+
+        let params = spec
+            .get("params")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        if params.len() != schema.fields.len() {
+            errors.push(format!(
+                "{}: ABI declares {} field(s), schema has {}",
+                name,
+                params.len(),
+                schema.fields.len()
+            ));
+        }
+        for (param, field) in params.iter().zip(&schema.fields) {
+            let abi_name = param
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let abi_type = param
+                .pointer("/value/type")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            if abi_name != field.name || abi_type != field.type_name {
+                errors.push(format!(
+                    "{}.{}: ABI declares '{}: {}' at this position",
+                    name, field.name, abi_name, abi_type
+                ));
+            }
+            if abi_param_location(param).is_some_and(|loc| loc != field.location) {
+                errors.push(format!(
+                    "{}.{}: location does not match the ABI",
+                    name, field.name
+                ));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Topics are usually decoded symbols (`"transfer"`) but some decoders wrap
+/// them as `{"symbol": "transfer"}`.
+fn topic_str(topic: &Value) -> Option<&str> {
+    match topic {
+        Value::String(s) => Some(s),
+        Value::Object(map) if map.len() == 1 => map.values().next().and_then(Value::as_str),
+        _ => None,
+    }
+}
+
+fn typed(field: &EventFieldSchema, value: Value) -> (String, TypedEventValue) {
+    (
+        field.name.clone(),
+        TypedEventValue {
+            type_name: field.type_name.clone(),
+            value,
+        },
+    )
+}
+
+/// Decode one raw event through `schema`, or `None` if it does not match.
+pub fn decode_with_schema(
+    schema: &EventSchemaDefinition,
+    event: &RawContractEvent,
+) -> Option<BTreeMap<String, TypedEventValue>> {
+    let (topic_fields, data_fields): (Vec<_>, Vec<_>) = schema
+        .fields
+        .iter()
+        .partition(|f| f.location == EventFieldLocation::Topic);
+
+    if event.topics.len() != schema.prefix_topics.len() + topic_fields.len() {
+        return None;
+    }
+    let (prefix, rest) = event.topics.split_at(schema.prefix_topics.len());
+    if !prefix
+        .iter()
+        .zip(&schema.prefix_topics)
+        .all(|(topic, expected)| topic_str(topic) == Some(expected.as_str()))
+    {
+        return None;
+    }
+
+    let mut fields: BTreeMap<String, TypedEventValue> = topic_fields
+        .iter()
+        .zip(rest)
+        .map(|(field, value)| typed(field, value.clone()))
+        .collect();
+
+    match (data_fields.as_slice(), &event.data) {
+        ([], _) => {}
+        ([field], data) => {
+            let (name, value) = typed(field, data.clone());
+            fields.insert(name, value);
+        }
+        (many, Value::Array(values)) if values.len() == many.len() => {
+            fields.extend(many.iter().zip(values).map(|(f, v)| typed(f, v.clone())));
+        }
+        (many, Value::Object(map)) => {
+            for field in many {
+                let (name, value) = typed(field, map.get(&field.name)?.clone());
+                fields.insert(name, value);
+            }
+        }
+        _ => return None,
+    }
+
+    Some(fields)
+}
+
+/// Decode events with the first matching schema; unmatched events keep only
+/// their positional values.
+pub fn decode_events(
+    schemas: &[EventSchemaDefinition],
+    events: Vec<RawContractEvent>,
+) -> Vec<DecodedContractEvent> {
+    events
+        .into_iter()
+        .map(|event| {
+            let matched = schemas
+                .iter()
+                .find_map(|s| decode_with_schema(s, &event).map(|f| (s.event_name.clone(), f)));
+            let (event_name, fields) = match matched {
+                Some((name, fields)) => (Some(name), fields),
+                None => (None, BTreeMap::new()),
+            };
+            DecodedContractEvent {
+                event_name,
+                fields,
+                topics: event.topics,
+                data: event.data,
+                ledger: event.ledger,
+                tx_hash: event.tx_hash,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn field(name: &str, ty: &str, location: EventFieldLocation) -> EventFieldSchema {
+        EventFieldSchema {
+            name: name.into(),
+            type_name: ty.into(),
+            location,
+        }
+    }
+
+    fn transfer() -> EventSchemaDefinition {
+        EventSchemaDefinition {
+            event_name: "transfer".into(),
+            prefix_topics: vec!["transfer".into()],
+            fields: vec![
+                field("from", "address", EventFieldLocation::Topic),
+                field("to", "address", EventFieldLocation::Topic),
+                field("amount", "i128", EventFieldLocation::Data),
+            ],
+        }
+    }
+
+    #[test]
+    fn accepts_builtin_generic_and_udt_types() {
+        let abi = json!([{ "type": "struct", "name": "Order" }]);
+        let mut schema = transfer();
+        schema.fields.push(field(
+            "orders",
+            "map<address, vec<Order>>",
+            EventFieldLocation::Data,
+        ));
+        schema
+            .fields
+            .push(field("hash", "bytes_n<32>", EventFieldLocation::Data));
+        assert!(validate_against_abi(&schema, &abi).is_ok());
+
+        schema
+            .fields
+            .push(field("bad", "Unknown", EventFieldLocation::Data));
+        schema
+            .fields
+            .push(field("bad", "u32", EventFieldLocation::Data));
+        let errors = validate_against_abi(&schema, &abi).unwrap_err();
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+    }
+
+    #[test]
+    fn rejects_schema_that_disagrees_with_abi_event_spec() {
+        let abi = json!([{
+            "type": "event",
+            "name": "transfer",
+            "prefix_topics": ["transfer"],
+            "params": [
+                { "name": "from", "value": { "type": "address" }, "location": "topic_list" },
+                { "name": "to", "value": { "type": "address" }, "location": "topic_list" },
+                { "name": "amount", "value": { "type": "i64" }, "location": "data" }
+            ]
+        }]);
+        let errors = validate_against_abi(&transfer(), &abi).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("amount: i64"));
+    }
+
+    #[test]
+    fn decodes_positional_values_into_named_fields() {
+        let raw = RawContractEvent {
+            topics: vec![json!({ "symbol": "transfer" }), json!("GA"), json!("GB")],
+            data: json!("100"),
+            ledger: Some(7),
+            tx_hash: None,
+        };
+        let other = RawContractEvent {
+            topics: vec![json!("mint"), json!("GA")],
+            data: json!("5"),
+            ledger: None,
+            tx_hash: None,
+        };
+
+        let decoded = decode_events(&[transfer()], vec![raw, other]);
+        assert_eq!(decoded[0].event_name.as_deref(), Some("transfer"));
+        assert_eq!(decoded[0].fields["to"].value, json!("GB"));
+        assert_eq!(decoded[0].fields["amount"].type_name, "i128");
+        assert!(decoded[1].event_name.is_none());
+        assert!(decoded[1].fields.is_empty());
+    }
+
+    #[test]
+    fn decodes_multiple_data_fields_from_map() {
+        let mut schema = transfer();
+        schema
+            .fields
+            .push(field("memo", "string", EventFieldLocation::Data));
+        let raw = RawContractEvent {
+            topics: vec![json!("transfer"), json!("GA"), json!("GB")],
+            data: json!({ "amount": "1", "memo": "hi" }),
+            ledger: None,
+            tx_hash: None,
+        };
+        let fields = decode_with_schema(&schema, &raw).unwrap();
+        assert_eq!(fields["memo"].value, json!("hi"));
+    }
+}
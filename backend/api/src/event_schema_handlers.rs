@@ -0,0 +1,218 @@
+// api/src/event_schema_handlers.rs
+//
+// Event schema registry.
+//
+// Routes (registered in event_schema_routes.rs):
+//   PUT  /api/contracts/:id/versions/:version/event-schemas – owner: register schemas
+//   GET  /api/contracts/:id/versions/:version/event-schemas – list schemas
+//   POST /api/contracts/:id/events/decode                   – name/type raw events
+//
+// Registering replaces every schema of that version.  Schemas are validated
+// against the ABI stored for the version (see event_schema.rs).
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    http::HeaderMap,
+    Json,
+};
+use shared::{
+    ContractEventSchema, DecodeEventsRequest, DecodedContractEvent, EventSchemaDefinition,
+    RegisterEventSchemasRequest,
+};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::{
+    auth::require_contract_owner,
+    contract_history_handlers::log_settings_change,
+    error::{ApiError, ApiResult},
+    event_schema::{decode_events, validate_against_abi},
+    handlers::db_internal_error,
+    state::AppState,
+};
+
+fn map_json_rejection(err: JsonRejection) -> ApiError {
+    ApiError::bad_request(
+        "InvalidRequest",
+        format!("Invalid JSON payload: {}", err.body_text()),
+    )
+}
+
+async fn load_schemas(
+    state: &AppState,
+    contract_id: Uuid,
+    version: &str,
+) -> ApiResult<Vec<ContractEventSchema>> {
+    sqlx::query_as(
+        "SELECT * FROM contract_event_schemas
+         WHERE contract_id = $1 AND version = $2
+         ORDER BY event_name",
+    )
+    .bind(contract_id)
+    .bind(version)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list event schemas", err))
+}
+
+fn to_definition(row: ContractEventSchema) -> ApiResult<EventSchemaDefinition> {
+    let fields = serde_json::from_value(row.fields).map_err(|err| {
+        ApiError::internal(format!(
+            "stored event schema '{}' is malformed: {}",
+            row.event_name, err
+        ))
+    })?;
+    Ok(EventSchemaDefinition {
+        event_name: row.event_name,
+        prefix_topics: row.prefix_topics,
+        fields,
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// PUT /api/contracts/:id/versions/:version/event-schemas
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn register_event_schemas(
+    State(state): State<AppState>,
+    Path((contract_id, version)): Path<(Uuid, String)>,
+    headers: HeaderMap,
+    payload: Result<Json<RegisterEventSchemasRequest>, JsonRejection>,
+) -> ApiResult<Json<Vec<ContractEventSchema>>> {
+    let owner = require_contract_owner(&state, &headers, contract_id).await?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+
+    let abi: serde_json::Value = sqlx::query_scalar(
+        "SELECT abi FROM contract_abis WHERE contract_id = $1 AND version = $2",
+    )
+    .bind(contract_id)
+    .bind(&version)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("get contract abi", err))?
+    .ok_or_else(|| {
+        ApiError::bad_request(
+            "AbiNotFound",
+            format!(
+                "No ABI stored for version {}; event schemas are validated against it",
+                version
+            ),
+        )
+    })?;
+
+    let mut names = HashSet::new();
+    let mut errors = Vec::new();
+    for event in &req.events {
+        if !names.insert(event.event_name.as_str()) {
+            errors.push(format!("{}: registered more than once", event.event_name));
+        }
+        if let Err(errs) = validate_against_abi(event, &abi) {
+            errors.extend(errs);
+        }
+    }
+    if !errors.is_empty() {
+        return Err(ApiError::bad_request(
+            "InvalidEventSchema",
+            format!("event schemas do not match the ABI: {}", errors.join("; ")),
+        ));
+    }
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin transaction", err))?;
+
+    sqlx::query("DELETE FROM contract_event_schemas WHERE contract_id = $1 AND version = $2")
+        .bind(contract_id)
+        .bind(&version)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("clear event schemas", err))?;
+
+    for event in &req.events {
+        sqlx::query(
+            "INSERT INTO contract_event_schemas
+                 (contract_id, version, event_name, prefix_topics, fields, registered_by)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(contract_id)
+        .bind(&version)
+        .bind(&event.event_name)
+        .bind(&event.prefix_topics)
+        .bind(serde_json::to_value(&event.fields).unwrap_or_default())
+        .bind(&owner.address)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("insert event schema", err))?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit event schemas", err))?;
+
+    let names: Vec<&str> = req.events.iter().map(|e| e.event_name.as_str()).collect();
+    log_settings_change(
+        &state.db,
+        contract_id,
+        &owner,
+        "event_schemas",
+        serde_json::json!({ "version": version, "events": names }),
+    )
+    .await;
+
+    tracing::info!(
+        contract_id = %contract_id,
+        version = %version,
+        count = req.events.len(),
+        "event schemas registered"
+    );
+
+    Ok(Json(load_schemas(&state, contract_id, &version).await?))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/contracts/:id/versions/:version/event-schemas
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn list_event_schemas(
+    State(state): State<AppState>,
+    Path((contract_id, version)): Path<(Uuid, String)>,
+) -> ApiResult<Json<Vec<ContractEventSchema>>> {
+    Ok(Json(load_schemas(&state, contract_id, &version).await?))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/contracts/:id/events/decode
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn decode_contract_events(
+    State(state): State<AppState>,
+    Path(contract_id): Path<Uuid>,
+    payload: Result<Json<DecodeEventsRequest>, JsonRejection>,
+) -> ApiResult<Json<Vec<DecodedContractEvent>>> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+
+    let version = match req.version {
+        Some(version) => version,
+        None => sqlx::query_scalar(
+            "SELECT version FROM contract_versions
+             WHERE contract_id = $1 ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(contract_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("get latest version", err))?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "VersionNotFound",
+                format!("Contract {} has no published versions", contract_id),
+            )
+        })?,
+    };
+
+    let schemas = load_schemas(&state, contract_id, &version)
+        .await?
+        .into_iter()
+        .map(to_definition)
+        .collect::<ApiResult<Vec<_>>>()?;
+
+    Ok(Json(decode_events(&schemas, req.events)))
+}
@@ -0,0 +1,22 @@
+// api/src/event_schema_routes.rs
+// Contract event schema registry routes.
+
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::{event_schema_handlers, state::AppState};
+
+pub fn event_schema_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/contracts/:id/versions/:version/event-schemas",
+            get(event_schema_handlers::list_event_schemas)
+                .put(event_schema_handlers::register_event_schemas),
+        )
+        .route(
+            "/api/contracts/:id/events/decode",
+            post(event_schema_handlers::decode_contract_events),
+        )
+}
@@ -1,5 +1,7 @@
+pub mod migrations;
+
 use axum::{
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     extract::{
         rejection::{JsonRejection, QueryRejection},
         Path, Query, State,
@@ -7,17 +9,30 @@ use axum::{
     Json,
 };
 use shared::{
-    Contract, ContractHealth, ContractSearchParams, ContractVersion, PaginatedResponse, PublishRequest, Publisher,
-    VerifyRequest,
+    AnalyticsEvent, AnalyticsEventType, AuditActionType, Contract, ContractDeployment, ContractHealth, ContractPatch, ContractSearchParams, ContractVersion,
+    PaginatedResponse,
+    DeployGreenRequest, DeploymentAction, DeploymentEnvironment, DeploymentStatus, HealthCheckRequest, PublishRequest, Publisher, PublisherAnalyticsResponse, SwitchDeploymentRequest,
+    TrustScoreDistribution, VerifyRequest,
 };
 use uuid::Uuid;
 
 use crate::{
+    analytics,
+    auth::require_publisher,
+    contract_history_handlers::log_contract_change,
+    deployment_approval_handlers::{
+        approver_set, contract_by_onchain_id, perform_switch, queue_switch_request,
+    },
     error::{ApiError, ApiResult},
+    metadata_handlers::validate_contract_metadata,
+    name_reservation,
+    query_builder::{ContractColumn, ListQuery},
+    quota::{self, QuotaKind},
+    search_handlers::escape_like,
     state::AppState,
 };
 
-fn db_internal_error(operation: &str, err: sqlx::Error) -> ApiError {
+pub(crate) fn db_internal_error(operation: &str, err: sqlx::Error) -> ApiError {
     tracing::error!(operation = operation, error = ?err, "database operation failed");
     ApiError::internal("An unexpected database error occurred")
 }
@@ -100,6 +115,24 @@ pub async fn get_stats(
     })))
 }
 
+/// Cache hit rate and lookup latencies
+pub async fn get_cache_stats(State(state): State<AppState>) -> Json<serde_json::Value> {
+    let config = state.cache.config();
+    let metrics = state.cache.metrics();
+
+    Json(serde_json::json!({
+        "enabled": config.enabled,
+        "policy": format!("{:?}", config.policy).to_lowercase(),
+        "ttl_secs": config.global_ttl.as_secs(),
+        "max_capacity": config.max_capacity,
+        "hit_rate_pct": metrics.hit_rate(),
+        "avg_cached_hit_latency_micros": metrics.avg_cached_hit_latency(),
+        "avg_cache_miss_latency_micros": metrics.avg_cache_miss_latency(),
+        "avg_uncached_latency_micros": metrics.avg_uncached_latency(),
+        "improvement_factor": metrics.improvement_factor(),
+    }))
+}
+
 /// List and search contracts
 pub async fn list_contracts(
     State(state): State<AppState>,
@@ -107,43 +140,77 @@ pub async fn list_contracts(
 ) -> ApiResult<Json<PaginatedResponse<Contract>>> {
     let Query(params) = params.map_err(map_query_rejection)?;
     let page = params.page.unwrap_or(1).max(1);
-    let page_size = params.page_size.unwrap_or(20).min(100);
+    let page_size = params.limit.unwrap_or(20).clamp(1, 100);
     let offset = (page - 1) * page_size;
 
-    // Build dynamic query based on filters
-    let mut query = String::from("SELECT * FROM contracts WHERE 1=1");
-    let mut count_query = String::from("SELECT COUNT(*) FROM contracts WHERE 1=1");
-
-    if let Some(ref q) = params.query {
-        let search_clause = format!(
-            " AND (name ILIKE '%{}%' OR description ILIKE '%{}%')",
-            q, q
+    let mut list = ListQuery::<ContractColumn>::new();
+
+    // Substring match, or a close trigram match so single-character typos
+    // in the name still find the contract
+    let search_term = params
+        .query
+        .as_deref()
+        .map(str::trim)
+        .filter(|q| !q.is_empty());
+    if let Some(term) = search_term {
+        list.search(
+            &[ContractColumn::Name, ContractColumn::Description],
+            format!("%{}%", escape_like(term)),
+            ContractColumn::Name,
+            term.to_string(),
         );
-        query.push_str(&search_clause);
-        count_query.push_str(&search_clause);
     }
 
-    if let Some(verified) = params.verified_only {
-        if verified {
-            query.push_str(" AND is_verified = true");
-            count_query.push_str(" AND is_verified = true");
-        }
+    if let Some(ref network) = params.network {
+        list.eq(ContractColumn::Network, network.clone());
+    }
+
+    if params.verified_only == Some(true) {
+        list.eq(ContractColumn::IsVerified, true);
     }
 
     if let Some(ref category) = params.category {
-        let category_clause = format!(" AND category = '{}'", category);
-        query.push_str(&category_clause);
-        count_query.push_str(&category_clause);
+        list.eq(ContractColumn::Category, category.clone());
+    }
+
+    if let Some(ref tags) = params.tags {
+        list.contains(ContractColumn::Tags, tags.clone());
     }
 
-    query.push_str(&format!(" ORDER BY created_at DESC LIMIT {} OFFSET {}", page_size, offset));
+    if let Some(tier) = params.min_tier {
+        list.gte(ContractColumn::VerificationTier, tier);
+    }
+
+    if let Some(ref raw) = params.metadata {
+        let filter: serde_json::Value = serde_json::from_str(raw).map_err(|_| {
+            ApiError::bad_request("InvalidQuery", "metadata filter must be a JSON object")
+        })?;
+        if !filter.is_object() {
+            return Err(ApiError::bad_request(
+                "InvalidQuery",
+                "metadata filter must be a JSON object",
+            ));
+        }
+        list.contains(ContractColumn::Metadata, filter);
+    }
 
-    let contracts: Vec<Contract> = sqlx::query_as(&query)
+    // Closest name matches first when searching
+    if let Some(term) = search_term {
+        list.order_by_similarity(ContractColumn::Name, term.to_string());
+    }
+    list.order_by_desc(ContractColumn::CreatedAt);
+
+    let mut contracts_query = list.select(page_size, offset);
+    let mut total_query = list.count();
+
+    let contracts: Vec<Contract> = contracts_query
+        .build_query_as()
         .fetch_all(&state.db)
         .await
         .map_err(|err| db_internal_error("list contracts", err))?;
 
-    let total: i64 = sqlx::query_scalar(&count_query)
+    let total: i64 = total_query
+        .build_query_scalar()
         .fetch_one(&state.db)
         .await
         .map_err(|err| db_internal_error("count filtered contracts", err))?;
@@ -201,45 +268,173 @@ pub async fn get_contract_versions(
     Ok(Json(versions))
 }
 
+/// Get the ABI recorded for one contract version
+pub async fn get_contract_version_abi(
+    State(state): State<AppState>,
+    Path((id, version)): Path<(Uuid, String)>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let abi: Option<serde_json::Value> = sqlx::query_scalar(
+        "SELECT abi FROM contract_abis WHERE contract_id = $1 AND version = $2",
+    )
+    .bind(id)
+    .bind(&version)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("get contract version abi", err))?;
+
+    abi.map(Json).ok_or_else(|| {
+        ApiError::not_found(
+            "AbiNotFound",
+            format!("No ABI recorded for version {} of contract {}", version, id),
+        )
+    })
+}
+
+/// Security patches applied to a contract, or whose fix shipped as one of its versions
+pub async fn get_contract_patches(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<Vec<ContractPatch>>> {
+    let patches: Vec<ContractPatch> = sqlx::query_as(
+        "SELECT p.id, p.target_version, p.severity::text AS severity, p.new_wasm_hash,
+                p.description, p.created_at, a.applied_at, v.version AS fixed_in_version
+           FROM security_patches p
+           LEFT JOIN patch_audits a ON a.patch_id = p.id AND a.contract_id = $1
+           LEFT JOIN contract_versions v
+                  ON v.contract_id = $1 AND lower(v.wasm_hash) = lower(p.new_wasm_hash)
+          WHERE a.id IS NOT NULL OR v.id IS NOT NULL
+          ORDER BY p.created_at",
+    )
+    .bind(id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list contract patches", err))?;
+
+    Ok(Json(patches))
+}
+
 /// Publish a new contract
 pub async fn publish_contract(
     State(state): State<AppState>,
+    headers: HeaderMap,
     payload: Result<Json<PublishRequest>, JsonRejection>,
 ) -> ApiResult<Json<Contract>> {
     let Json(req) = payload.map_err(map_json_rejection)?;
 
-    // First, ensure publisher exists or create one
-    let publisher: Publisher = sqlx::query_as(
-        "INSERT INTO publishers (stellar_address) VALUES ($1)
-         ON CONFLICT (stellar_address) DO UPDATE SET stellar_address = EXCLUDED.stellar_address
-         RETURNING *",
+    // Only a caller holding the publisher's key may publish as it: a session
+    // from a Stellar key login (or an identity linked to the publisher), or an
+    // admin impersonating it.  Key login registers the publisher, so unknown
+    // addresses are never created here.
+    let publisher_id: Uuid =
+        sqlx::query_scalar("SELECT id FROM publishers WHERE stellar_address = $1")
+            .bind(&req.publisher_address)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("get publisher", err))?
+            .ok_or_else(|| {
+                ApiError::not_found(
+                    "PublisherNotFound",
+                    format!(
+                        "No publisher with address {}; sign in with its Stellar key first",
+                        req.publisher_address
+                    ),
+                )
+            })?;
+    let owner = require_publisher(&state, &headers, publisher_id).await?;
+
+    let metadata = req.metadata.clone().unwrap_or_else(|| serde_json::json!({}));
+    validate_contract_metadata(&state, req.category.as_deref(), &metadata).await?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin publish", err))?;
+
+    // Holds the publisher row until the contract is inserted
+    quota::enforce(
+        &mut tx,
+        owner.publisher_id,
+        &[(QuotaKind::Contracts, 1), (QuotaKind::VersionsPerDay, 1)],
     )
-    .bind(&req.publisher_address)
-    .fetch_one(&state.db)
-    .await
-    .map_err(|err| db_internal_error("upsert publisher", err))?;
+    .await?;
+
+    // Reserved names are refused; look-alikes of other names are flagged
+    let similar_names =
+        name_reservation::check_publish(&mut tx, owner.publisher_id, &req.name).await?;
 
     // TODO: Fetch WASM hash from Stellar network
     let wasm_hash = "placeholder_hash".to_string();
 
     // Insert contract
     let contract: Contract = sqlx::query_as(
-        "INSERT INTO contracts (contract_id, wasm_hash, name, description, publisher_id, network, category, tags)
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "INSERT INTO contracts (contract_id, wasm_hash, name, description, publisher_id, network, category, tags, metadata)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
          RETURNING *",
     )
     .bind(&req.contract_id)
     .bind(&wasm_hash)
     .bind(&req.name)
     .bind(&req.description)
-    .bind(publisher.id)
+    .bind(owner.publisher_id)
     .bind(&req.network)
     .bind(&req.category)
     .bind(&req.tags)
-    .fetch_one(&state.db)
+    .bind(&metadata)
+    .fetch_one(&mut *tx)
     .await
     .map_err(|err| db_internal_error("create contract", err))?;
 
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit publish", err))?;
+
+    // The publisher dashboard now includes this contract
+    state
+        .cache
+        .invalidate(&format!("publisher:{}", owner.publisher_id), "analytics")
+        .await;
+
+    // Fire-and-forget analytics event
+    let pool = state.db.clone();
+    let (cid, net) = (contract.id, contract.network.clone());
+    let addr = owner.address.clone();
+    tokio::spawn(async move {
+        if let Err(err) = analytics::record_event(
+            &pool,
+            AnalyticsEventType::ContractPublished,
+            cid,
+            Some(&addr),
+            Some(&net),
+            None,
+        )
+        .await
+        {
+            tracing::warn!(error = ?err, "failed to record contract_published event");
+        }
+    });
+
+    // The contract exists now; a failed flag is for moderators to chase, not the publisher
+    if !similar_names.is_empty() {
+        if let Err(err) = name_reservation::flag(&state.db, &contract, &similar_names).await {
+            tracing::error!(contract_id = %contract.id, "failed to flag similar name: {:?}", err);
+        }
+    }
+
+    if let Err(err) = log_contract_change(
+        &state.db,
+        contract.id,
+        AuditActionType::ContractPublished,
+        None,
+        serde_json::to_value(&contract).ok(),
+        &owner.address,
+        owner.actor.impersonation(),
+    )
+    .await
+    {
+        tracing::error!(contract_id = %contract.id, "failed to record publish in the audit log: {}", err);
+    }
+
     Ok(Json(contract))
 }
 
@@ -257,6 +452,223 @@ pub async fn verify_contract(
     })))
 }
 
+/// Deploy a new wasm build to the green environment for testing.
+pub async fn deploy_green(
+    State(state): State<AppState>,
+    payload: Result<Json<DeployGreenRequest>, JsonRejection>,
+) -> ApiResult<Json<ContractDeployment>> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let contract = contract_by_onchain_id(&state, &req.contract_id).await?;
+
+    let deployment: ContractDeployment = sqlx::query_as(
+        "INSERT INTO contract_deployments (contract_id, environment, status, wasm_hash)
+         VALUES ($1, 'green', 'testing', $2)
+         ON CONFLICT (contract_id, environment)
+         DO UPDATE SET wasm_hash = EXCLUDED.wasm_hash, status = 'testing',
+                       deployed_at = NOW(), error_message = NULL,
+                       health_checks_passed = 0, health_checks_failed = 0
+         RETURNING *",
+    )
+    .bind(contract.id)
+    .bind(&req.wasm_hash)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("deploy green", err))?;
+
+    Ok(Json(deployment))
+}
+
+/// Record the outcome of one health check against an environment.
+///
+/// Three failed checks mark the deployment as failed.
+pub async fn report_health_check(
+    State(state): State<AppState>,
+    payload: Result<Json<HealthCheckRequest>, JsonRejection>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let contract = contract_by_onchain_id(&state, &req.contract_id).await?;
+
+    let updated = if req.passed {
+        sqlx::query(
+            "UPDATE contract_deployments
+             SET health_checks_passed = health_checks_passed + 1,
+                 last_health_check_at = NOW()
+             WHERE contract_id = $1 AND environment = $2",
+        )
+    } else {
+        sqlx::query(
+            "UPDATE contract_deployments
+             SET health_checks_failed = health_checks_failed + 1,
+                 status = CASE WHEN health_checks_failed + 1 >= 3 THEN 'failed' ELSE status END,
+                 last_health_check_at = NOW()
+             WHERE contract_id = $1 AND environment = $2",
+        )
+    }
+    .bind(contract.id)
+    .bind(&req.environment)
+    .execute(&state.db)
+    .await
+    .map_err(|err| db_internal_error("record health check", err))?;
+
+    if updated.rows_affected() == 0 {
+        return Err(ApiError::not_found(
+            "DeploymentNotFound",
+            format!("No {:?} deployment for contract {}", req.environment, req.contract_id),
+        ));
+    }
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "environment": req.environment,
+        "passed": req.passed
+    })))
+}
+
+/// Blue and green deployments of a contract, and which one is live.
+pub async fn get_deployment_status(
+    State(state): State<AppState>,
+    Path(contract_id): Path<String>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let contract = contract_by_onchain_id(&state, &contract_id).await?;
+
+    let deployments: Vec<ContractDeployment> = sqlx::query_as(
+        "SELECT * FROM contract_deployments
+         WHERE contract_id = $1
+         ORDER BY deployed_at DESC",
+    )
+    .bind(contract.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("get deployments", err))?;
+
+    let active = deployments
+        .iter()
+        .find(|d| d.status == DeploymentStatus::Active);
+    let blue = deployments
+        .iter()
+        .find(|d| matches!(d.environment, DeploymentEnvironment::Blue));
+    let green = deployments
+        .iter()
+        .find(|d| matches!(d.environment, DeploymentEnvironment::Green));
+
+    Ok(Json(serde_json::json!({
+        "contract_id": contract_id,
+        "active": active,
+        "blue": blue,
+        "green": green
+    })))
+}
+
+/// Switch traffic between blue and green deployments.
+///
+/// Contracts with a deployment approval policy get a queued switch request
+/// (202) instead of an immediate switch, raised by the signed-in caller.
+pub async fn switch_deployment(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    payload: Result<Json<SwitchDeploymentRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<serde_json::Value>)> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let force = req.force.unwrap_or(false);
+    let contract = contract_by_onchain_id(&state, &req.contract_id).await?;
+
+    if let Some(set) = approver_set(&state.db, contract.id).await? {
+        let request = queue_switch_request(
+            &state,
+            &headers,
+            contract.id,
+            DeploymentAction::Switch,
+            force,
+            req.requested_by.as_deref(),
+            &set,
+        )
+        .await?;
+        return Ok((
+            StatusCode::ACCEPTED,
+            Json(serde_json::json!({
+                "success": true,
+                "pending_approval": true,
+                "request": request,
+            })),
+        ));
+    }
+
+    let switch = perform_switch(
+        &state.db,
+        contract.id,
+        DeploymentAction::Switch,
+        force,
+        req.requested_by.as_deref(),
+    )
+    .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "success": true,
+            "switched_from": switch.from_environment,
+            "switched_to": switch.to_environment,
+            "contract_id": req.contract_id
+        })),
+    ))
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct RollbackDeploymentQuery {
+    pub requested_by: Option<String>,
+}
+
+/// Roll back to the previously active environment.
+pub async fn rollback_deployment(
+    State(state): State<AppState>,
+    Path(contract_id): Path<String>,
+    headers: HeaderMap,
+    params: Result<Query<RollbackDeploymentQuery>, QueryRejection>,
+) -> ApiResult<(StatusCode, Json<serde_json::Value>)> {
+    let Query(params) = params.map_err(map_query_rejection)?;
+    let contract = contract_by_onchain_id(&state, &contract_id).await?;
+
+    if let Some(set) = approver_set(&state.db, contract.id).await? {
+        let request = queue_switch_request(
+            &state,
+            &headers,
+            contract.id,
+            DeploymentAction::Rollback,
+            false,
+            params.requested_by.as_deref(),
+            &set,
+        )
+        .await?;
+        return Ok((
+            StatusCode::ACCEPTED,
+            Json(serde_json::json!({
+                "success": true,
+                "pending_approval": true,
+                "request": request,
+            })),
+        ));
+    }
+
+    let switch = perform_switch(
+        &state.db,
+        contract.id,
+        DeploymentAction::Rollback,
+        false,
+        params.requested_by.as_deref(),
+    )
+    .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "success": true,
+            "rolled_back_from": switch.from_environment,
+            "rolled_back_to": switch.to_environment,
+            "contract_id": contract_id
+        })),
+    ))
+}
+
 /// Create a publisher
 pub async fn create_publisher(
     State(state): State<AppState>,
@@ -331,6 +743,133 @@ pub async fn get_publisher_contracts(
     Ok(Json(contracts))
 }
 
+/// How long a computed publisher dashboard is served from cache.
+const PUBLISHER_ANALYTICS_TTL: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Aggregate dashboard stats across all of a publisher's contracts
+pub async fn get_publisher_analytics(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<PublisherAnalyticsResponse>> {
+    let publisher_uuid = Uuid::parse_str(&id).map_err(|_| {
+        ApiError::bad_request(
+            "InvalidPublisherId",
+            format!("Invalid publisher ID format: {}", id),
+        )
+    })?;
+
+    let cache_scope = format!("publisher:{}", publisher_uuid);
+    if let (Some(cached), true) = state.cache.get(&cache_scope, "analytics").await {
+        if let Ok(response) = serde_json::from_str::<PublisherAnalyticsResponse>(&cached) {
+            return Ok(Json(response));
+        }
+    }
+    let started = std::time::Instant::now();
+
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM publishers WHERE id = $1)")
+        .bind(publisher_uuid)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("check publisher exists", err))?;
+    if !exists {
+        return Err(ApiError::not_found(
+            "PublisherNotFound",
+            format!("No publisher found with ID: {}", id),
+        ));
+    }
+
+    let (total_contracts, verified_contracts): (i64, i64) = sqlx::query_as(
+        "SELECT COUNT(*), COUNT(*) FILTER (WHERE is_verified)
+           FROM contracts
+          WHERE publisher_id = $1",
+    )
+    .bind(publisher_uuid)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("count publisher contracts", err))?;
+
+    let (total_downloads, total_invocations): (i64, i64) = sqlx::query_as(
+        "SELECT COUNT(*) FILTER (WHERE i.interaction_type = 'download'),
+                COUNT(*) FILTER (WHERE i.interaction_type = 'invoke')
+           FROM contract_interactions i
+           JOIN contracts c ON c.id = i.contract_id
+          WHERE c.publisher_id = $1",
+    )
+    .bind(publisher_uuid)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("count publisher interactions", err))?;
+
+    let trust_score_distribution: TrustScoreDistribution = sqlx::query_as(
+        "SELECT COUNT(*) FILTER (WHERE h.total_score >= 80) AS healthy,
+                COUNT(*) FILTER (WHERE h.total_score >= 50 AND h.total_score < 80) AS warning,
+                COUNT(*) FILTER (WHERE h.total_score < 50) AS critical,
+                COUNT(*) FILTER (WHERE h.contract_id IS NULL) AS unscored
+           FROM contracts c
+           LEFT JOIN contract_health h ON h.contract_id = c.id
+          WHERE c.publisher_id = $1",
+    )
+    .bind(publisher_uuid)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("publisher trust score distribution", err))?;
+
+    let average_trust_score: Option<f64> = sqlx::query_scalar(
+        "SELECT AVG(h.total_score)::float8
+           FROM contract_health h
+           JOIN contracts c ON c.id = h.contract_id
+          WHERE c.publisher_id = $1",
+    )
+    .bind(publisher_uuid)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("publisher average trust score", err))?;
+
+    let recent_events: Vec<AnalyticsEvent> = sqlx::query_as(
+        "SELECT e.*
+           FROM analytics_events e
+           JOIN contracts c ON c.id = e.contract_id
+          WHERE c.publisher_id = $1
+          ORDER BY e.created_at DESC
+          LIMIT 20",
+    )
+    .bind(publisher_uuid)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("publisher recent events", err))?;
+
+    let verification_coverage = if total_contracts > 0 {
+        verified_contracts as f64 * 100.0 / total_contracts as f64
+    } else {
+        0.0
+    };
+
+    let response = PublisherAnalyticsResponse {
+        publisher_id: publisher_uuid,
+        total_contracts,
+        verified_contracts,
+        verification_coverage,
+        total_downloads,
+        total_invocations,
+        average_trust_score,
+        trust_score_distribution,
+        recent_events,
+        generated_at: chrono::Utc::now(),
+    };
+
+    if !state.cache.config().enabled {
+        // Baseline for the improvement factor in /api/cache/stats
+        state.cache.record_uncached_latency(started.elapsed());
+    } else if let Ok(serialized) = serde_json::to_string(&response) {
+        state
+            .cache
+            .put(&cache_scope, "analytics", serialized, Some(PUBLISHER_ANALYTICS_TTL))
+            .await;
+    }
+
+    Ok(Json(response))
+}
+
 
 /// Get contract health
 pub async fn get_contract_health(
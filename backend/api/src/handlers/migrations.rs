@@ -1,10 +1,9 @@
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{Path, State},
     Json,
 };
 use shared::models::{
-    CreateMigrationRequest, Migration, MigrationStatus, PaginatedResponse,
+    CreateMigrationRequest, Migration, PaginatedResponse,
     UpdateMigrationStatusRequest,
 };
 use uuid::Uuid;
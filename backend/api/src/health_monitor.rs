@@ -1,6 +1,8 @@
 use anyhow::Result;
 use chrono::Utc;
-use shared::{Contract, ContractHealth, ContractStats, HealthStatus};
+use shared::{
+    Contract, ContractHealth, ContractStats, DeprecatedDependency, HealthStatus, VerificationTier,
+};
 use sqlx::PgPool;
 use tokio::time;
 use tracing::{error, info};
@@ -34,27 +36,75 @@ async fn perform_health_checks(pool: &PgPool) -> Result<()> {
     info!("Found {} contracts to check", contracts.len());
 
     for contract in contracts {
-        // 2. Fetch stats (last activity)
-        let stats: Option<ContractStats> = sqlx::query_as("SELECT * FROM contract_stats WHERE contract_id = $1")
-            .bind(contract.id)
-            .fetch_optional(pool)
-            .await?;
-
-        // 3. Fetch verification status (if not in contract struct, though it is)
-        // contract.is_verified is available
-
-        // 4. Calculate health score
-        let health = calculate_health(&contract, stats.as_ref());
-
-        // 5. Update database
-        upsert_contract_health(pool, &health).await?;
+        check_contract(pool, &contract).await?;
     }
 
     info!("Health checks completed");
     Ok(())
 }
 
-fn calculate_health(contract: &Contract, stats: Option<&ContractStats>) -> ContractHealth {
+/// Recompute the health (trust) score of a single contract immediately,
+/// e.g. after a new audit report has been ingested.
+pub async fn refresh_contract_health(pool: &PgPool, contract_id: uuid::Uuid) -> Result<()> {
+    let contract: Contract = sqlx::query_as("SELECT * FROM contracts WHERE id = $1")
+        .bind(contract_id)
+        .fetch_one(pool)
+        .await?;
+
+    check_contract(pool, &contract).await
+}
+
+async fn check_contract(pool: &PgPool, contract: &Contract) -> Result<()> {
+    // 1. Fetch stats (last activity)
+    let stats: Option<ContractStats> = sqlx::query_as("SELECT * FROM contract_stats WHERE contract_id = $1")
+        .bind(contract.id)
+        .fetch_optional(pool)
+        .await?;
+
+    // 2. Fetch the latest security audit, if any
+    let latest_audit: Option<(chrono::DateTime<Utc>, f64)> = sqlx::query_as(
+        "SELECT audit_date, overall_score FROM security_audits
+          WHERE contract_id = $1 ORDER BY audit_date DESC LIMIT 1",
+    )
+    .bind(contract.id)
+    .fetch_optional(pool)
+    .await?;
+
+    // 3. Fetch deprecated direct dependencies
+    let deprecated = deprecated_dependencies(pool, contract.id).await?;
+
+    // 4. Calculate health score
+    let health = calculate_health(contract, stats.as_ref(), latest_audit, deprecated);
+
+    // 5. Update database
+    upsert_contract_health(pool, &health).await
+}
+
+/// Direct dependencies of a contract that have been deprecated.
+pub async fn deprecated_dependencies(
+    pool: &PgPool,
+    contract_id: uuid::Uuid,
+) -> Result<Vec<DeprecatedDependency>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT d.dependency_name, dep.contract_id, dep.reason, dep.replacement_contract_id,
+                r.name AS replacement_name, dep.created_at AS deprecated_at
+           FROM contract_dependencies d
+           JOIN contract_deprecations dep ON dep.contract_id = d.dependency_contract_id
+           LEFT JOIN contracts r ON r.id = dep.replacement_contract_id
+          WHERE d.contract_id = $1
+          ORDER BY d.dependency_name",
+    )
+    .bind(contract_id)
+    .fetch_all(pool)
+    .await
+}
+
+fn calculate_health(
+    contract: &Contract,
+    stats: Option<&ContractStats>,
+    latest_audit: Option<(chrono::DateTime<Utc>, f64)>,
+    deprecated_dependencies: Vec<DeprecatedDependency>,
+) -> ContractHealth {
     let mut score = 100;
     
     // Penalize for not being verified
@@ -77,11 +127,21 @@ fn calculate_health(contract: &Contract, stats: Option<&ContractStats>) -> Contr
         score -= 20;
     }
 
-    // Placeholder for audit check (not implemented yet)
-    // score -= 10; 
+    // Verified contracts earn the rest of their score by tier: only an
+    // audited (L3) contract can reach 100
+    if contract.is_verified {
+        score -= match contract.verification_tier {
+            VerificationTier::Unverified | VerificationTier::Source => 10,
+            VerificationTier::Build => 5,
+            VerificationTier::Audit => 0,
+        };
+    }
+
+    // Penalize for relying on deprecated contracts (capped)
+    score -= (deprecated_dependencies.len() as i32 * 10).min(20);
 
     // Ensure score is within 0-100
-    score = score.max(0).min(100);
+    score = score.clamp(0, 100);
 
     let mut recommendations = Vec::new();
 
@@ -96,6 +156,11 @@ fn calculate_health(contract: &Contract, stats: Option<&ContractStats>) -> Contr
 
     if !contract.is_verified {
         recommendations.push("Verify the contract source code to improve trust and health score.".to_string());
+    } else if contract.verification_tier < VerificationTier::Audit {
+        recommendations.push(format!(
+            "Contract is at verification tier {}. Attest a reproducible build or file an audit report to raise it.",
+            contract.verification_tier.label()
+        ));
     }
 
     if days_since_activity > 90 {
@@ -104,6 +169,20 @@ fn calculate_health(contract: &Contract, stats: Option<&ContractStats>) -> Contr
         recommendations.push("Contract has been inactive for over 30 days.".to_string());
     }
 
+    for dep in &deprecated_dependencies {
+        let replacement = dep
+            .replacement_name
+            .as_deref()
+            .map(|name| format!(" Migrate to {}.", name))
+            .unwrap_or_default();
+        recommendations.push(format!(
+            "Dependency {} is deprecated: {}.{}",
+            dep.dependency_name,
+            dep.reason.trim_end_matches('.'),
+            replacement
+        ));
+    }
+
     if recommendations.is_empty() {
         recommendations.push("Contract is healthy and active. Keep it up!".to_string());
     }
@@ -112,10 +191,14 @@ fn calculate_health(contract: &Contract, stats: Option<&ContractStats>) -> Contr
         contract_id: contract.id,
         status,
         last_activity,
-        security_score: score / 2, // Placeholder logic
-        audit_date: None,
+        // Audited contracts report their checklist score; others fall back to half the total
+        security_score: latest_audit
+            .map(|(_, audit_score)| audit_score.round() as i32)
+            .unwrap_or(score / 2),
+        audit_date: latest_audit.map(|(date, _)| date),
         total_score: score,
         recommendations,
+        deprecated_dependencies,
         updated_at: Utc::now(),
     }
 }
@@ -123,8 +206,8 @@ fn calculate_health(contract: &Contract, stats: Option<&ContractStats>) -> Contr
 async fn upsert_contract_health(pool: &PgPool, health: &ContractHealth) -> Result<()> {
     sqlx::query(
         r#"
-        INSERT INTO contract_health (contract_id, status, last_activity, security_score, audit_date, total_score, recommendations, updated_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        INSERT INTO contract_health (contract_id, status, last_activity, security_score, audit_date, total_score, recommendations, deprecated_dependencies, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
         ON CONFLICT (contract_id) 
         DO UPDATE SET 
             status = EXCLUDED.status,
@@ -133,16 +216,18 @@ async fn upsert_contract_health(pool: &PgPool, health: &ContractHealth) -> Resul
             audit_date = EXCLUDED.audit_date,
             total_score = EXCLUDED.total_score,
             recommendations = EXCLUDED.recommendations,
+            deprecated_dependencies = EXCLUDED.deprecated_dependencies,
             updated_at = EXCLUDED.updated_at
         "#
     )
     .bind(health.contract_id)
-    .bind(&health.status)
+    .bind(health.status)
     .bind(health.last_activity)
     .bind(health.security_score)
     .bind(health.audit_date)
     .bind(health.total_score)
     .bind(&health.recommendations)
+    .bind(sqlx::types::Json(&health.deprecated_dependencies))
     .bind(health.updated_at)
     .execute(pool)
     .await?;
@@ -0,0 +1,186 @@
+// api/src/impersonation.rs
+//
+// Enforcement and audit trail for admin impersonation tokens (issued by
+// impersonation_handlers.rs).
+//
+// Every request carrying an impersonation token is checked against its
+// `impersonation_sessions` row (revoked or expired sessions are rejected),
+// refused if it would write through a read-only session or to another
+// publisher's contract, and recorded in `impersonation_audit_log` with both
+// the admin and the publisher.  Handlers that write to `contract_audit_log`
+// tag their entries with the session as well.
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use uuid::Uuid;
+
+use crate::{
+    auth::{optional_impersonation, ImpersonationClaims},
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    state::AppState,
+};
+
+/// Methods a read-only impersonation session may use.
+fn is_read_only_method(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+async fn ensure_session_active(state: &AppState, claims: &ImpersonationClaims) -> ApiResult<()> {
+    let active: bool = sqlx::query_scalar(
+        "SELECT EXISTS(
+             SELECT 1 FROM impersonation_sessions
+              WHERE id = $1 AND revoked_at IS NULL AND expires_at > NOW())",
+    )
+    .bind(claims.sid)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("check impersonation session", err))?;
+
+    if !active {
+        return Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "ImpersonationEnded",
+            "This impersonation session has been revoked or has expired",
+        ));
+    }
+    Ok(())
+}
+
+/// Registry contract a request path is scoped to (`/api/contracts/:id/...`).
+fn contract_in_path(path: &str) -> Option<Uuid> {
+    path.strip_prefix("/api/contracts/")?
+        .split('/')
+        .next()?
+        .parse()
+        .ok()
+}
+
+async fn ensure_contract_in_scope(
+    state: &AppState,
+    claims: &ImpersonationClaims,
+    contract_id: Uuid,
+) -> ApiResult<()> {
+    let publisher_id: Option<Uuid> =
+        sqlx::query_scalar("SELECT publisher_id FROM contracts WHERE id = $1")
+            .bind(contract_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("get contract publisher", err))?;
+
+    if publisher_id.is_some_and(|id| id != claims.sub) {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "NotAuthorizedPublisher",
+            "This impersonation session is scoped to another publisher",
+        ));
+    }
+    Ok(())
+}
+
+/// Whether an impersonated request may proceed.
+async fn authorize(
+    state: &AppState,
+    claims: &ImpersonationClaims,
+    method: &Method,
+    path: &str,
+) -> ApiResult<()> {
+    ensure_session_active(state, claims).await?;
+    if is_read_only_method(method) {
+        return Ok(());
+    }
+    if claims.read_only {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "ImpersonationReadOnly",
+            "This impersonation session is read-only",
+        ));
+    }
+    // Checked here so every contract write path is covered, whether or not
+    // its handler authenticates the owner itself
+    if let Some(contract_id) = contract_in_path(path) {
+        ensure_contract_in_scope(state, claims, contract_id).await?;
+    }
+    Ok(())
+}
+
+pub async fn impersonation_middleware(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let claims = match optional_impersonation(&state.auth, request.headers()) {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return next.run(request).await,
+        Err(err) => return err.into_response(),
+    };
+
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+
+    let response = match authorize(&state, &claims, &method, &path).await {
+        Ok(()) => next.run(request).await,
+        Err(err) => err.into_response(),
+    };
+
+    tracing::info!(
+        session_id = %claims.sid,
+        admin = %claims.act,
+        publisher_id = %claims.sub,
+        status = response.status().as_u16(),
+        "impersonated {} {}",
+        method,
+        path
+    );
+
+    if let Err(err) = sqlx::query(
+        "INSERT INTO impersonation_audit_log
+            (session_id, admin_name, publisher_id, method, path, status_code)
+         VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(claims.sid)
+    .bind(&claims.act)
+    .bind(claims.sub)
+    .bind(method.as_str())
+    .bind(&path)
+    .bind(i32::from(response.status().as_u16()))
+    .execute(&state.db)
+    .await
+    {
+        tracing::error!(session_id = %claims.sid, "failed to record impersonated request: {}", err);
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_only_sessions_allow_only_safe_methods() {
+        assert!(is_read_only_method(&Method::GET));
+        assert!(is_read_only_method(&Method::HEAD));
+        assert!(!is_read_only_method(&Method::POST));
+        assert!(!is_read_only_method(&Method::PUT));
+        assert!(!is_read_only_method(&Method::DELETE));
+    }
+
+    #[test]
+    fn finds_the_contract_a_path_is_scoped_to() {
+        let id = Uuid::new_v4();
+        assert_eq!(contract_in_path(&format!("/api/contracts/{}", id)), Some(id));
+        assert_eq!(
+            contract_in_path(&format!("/api/contracts/{}/budget", id)),
+            Some(id)
+        );
+        assert_eq!(contract_in_path("/api/contracts"), None);
+        assert_eq!(contract_in_path("/api/contracts/search"), None);
+        assert_eq!(contract_in_path(&format!("/api/publishers/{}", id)), None);
+    }
+}
@@ -0,0 +1,200 @@
+// api/src/impersonation_handlers.rs
+//
+// Admin impersonation of publishers, for support staff reproducing issues.
+//
+// Routes (registered in impersonation_routes.rs):
+//   POST   /api/admin/impersonations           – admin: start a session, returns a token
+//   GET    /api/admin/impersonations           – admin: active sessions
+//   DELETE /api/admin/impersonations/:id       – admin: revoke a session
+//   GET    /api/admin/impersonations/:id/audit – admin: requests made in a session
+//
+// Tokens are HS256 JWTs signed with the session secret (see auth.rs) and are
+// read-only unless `read_only: false` is requested.  Only named admins
+// (`admin.identities`) may start a session; the admin recorded is the
+// signed-in identity.  Enforcement and the per-request audit trail live in
+// impersonation.rs.
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    http::HeaderMap,
+    Json,
+};
+use chrono::{Duration, Utc};
+use shared::{
+    ImpersonationAuditEntry, ImpersonationSession, ImpersonationTokenResponse,
+    StartImpersonationRequest,
+};
+use uuid::Uuid;
+
+use crate::{
+    auth::{
+        require_admin, session_secret, sign_jwt, ImpersonationClaims, MAX_IMPERSONATION_MINUTES,
+    },
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    state::AppState,
+};
+
+const DEFAULT_IMPERSONATION_MINUTES: i64 = 15;
+
+fn map_json_rejection(err: JsonRejection) -> ApiError {
+    ApiError::bad_request(
+        "InvalidRequest",
+        format!("Invalid JSON payload: {}", err.body_text()),
+    )
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/admin/impersonations
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn start_impersonation(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    payload: Result<Json<StartImpersonationRequest>, JsonRejection>,
+) -> ApiResult<Json<ImpersonationTokenResponse>> {
+    let admin_name = require_admin(&state.auth, &headers)?.require_named()?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let secret = session_secret(&state.auth)?;
+
+    let reason = req.reason.trim();
+    if reason.is_empty() {
+        return Err(ApiError::bad_request(
+            "InvalidImpersonation",
+            "a reason is required",
+        ));
+    }
+    let ttl = req.ttl_minutes.unwrap_or(DEFAULT_IMPERSONATION_MINUTES);
+    if !(1..=MAX_IMPERSONATION_MINUTES).contains(&ttl) {
+        return Err(ApiError::bad_request(
+            "InvalidImpersonation",
+            format!(
+                "ttl_minutes must be between 1 and {}",
+                MAX_IMPERSONATION_MINUTES
+            ),
+        ));
+    }
+
+    let publisher_exists: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM publishers WHERE id = $1)")
+            .bind(req.publisher_id)
+            .fetch_one(&state.db)
+            .await
+            .map_err(|err| db_internal_error("check publisher exists", err))?;
+    if !publisher_exists {
+        return Err(ApiError::not_found(
+            "PublisherNotFound",
+            format!("No publisher found with ID: {}", req.publisher_id),
+        ));
+    }
+
+    let now = Utc::now();
+    let session: ImpersonationSession = sqlx::query_as(
+        "INSERT INTO impersonation_sessions (publisher_id, admin_name, reason, read_only, expires_at)
+         VALUES ($1, $2, $3, $4, $5)
+         RETURNING *",
+    )
+    .bind(req.publisher_id)
+    .bind(&admin_name)
+    .bind(reason)
+    .bind(req.read_only.unwrap_or(true))
+    .bind(now + Duration::minutes(ttl))
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("start impersonation", err))?;
+
+    let token = sign_jwt(
+        secret,
+        &ImpersonationClaims {
+            sid: session.id,
+            sub: session.publisher_id,
+            act: session.admin_name.clone(),
+            read_only: session.read_only,
+            iat: now.timestamp(),
+            exp: session.expires_at.timestamp(),
+        },
+    );
+
+    tracing::warn!(
+        session_id = %session.id,
+        admin = %session.admin_name,
+        publisher_id = %session.publisher_id,
+        read_only = session.read_only,
+        reason = %session.reason,
+        "impersonation session started"
+    );
+
+    Ok(Json(ImpersonationTokenResponse { session, token }))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/admin/impersonations
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn list_active_impersonations(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Vec<ImpersonationSession>>> {
+    require_admin(&state.auth, &headers)?;
+
+    let sessions: Vec<ImpersonationSession> = sqlx::query_as(
+        "SELECT * FROM impersonation_sessions
+          WHERE revoked_at IS NULL AND expires_at > NOW()
+          ORDER BY created_at DESC",
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list impersonation sessions", err))?;
+
+    Ok(Json(sessions))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// DELETE /api/admin/impersonations/:id
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn revoke_impersonation(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> ApiResult<Json<ImpersonationSession>> {
+    require_admin(&state.auth, &headers)?;
+
+    let session: ImpersonationSession = sqlx::query_as(
+        "UPDATE impersonation_sessions SET revoked_at = COALESCE(revoked_at, NOW())
+          WHERE id = $1
+          RETURNING *",
+    )
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("revoke impersonation", err))?
+    .ok_or_else(|| {
+        ApiError::not_found(
+            "ImpersonationNotFound",
+            format!("No impersonation session found with ID: {}", id),
+        )
+    })?;
+
+    tracing::info!(session_id = %id, "impersonation session revoked");
+
+    Ok(Json(session))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/admin/impersonations/:id/audit
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn get_impersonation_audit(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Vec<ImpersonationAuditEntry>>> {
+    require_admin(&state.auth, &headers)?;
+
+    let entries: Vec<ImpersonationAuditEntry> = sqlx::query_as(
+        "SELECT * FROM impersonation_audit_log WHERE session_id = $1 ORDER BY created_at",
+    )
+    .bind(id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list impersonation audit log", err))?;
+
+    Ok(Json(entries))
+}
@@ -0,0 +1,26 @@
+// api/src/impersonation_routes.rs
+// Admin impersonation routes.
+
+use axum::{
+    routing::{delete, get},
+    Router,
+};
+
+use crate::{impersonation_handlers, state::AppState};
+
+pub fn impersonation_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/admin/impersonations",
+            get(impersonation_handlers::list_active_impersonations)
+                .post(impersonation_handlers::start_impersonation),
+        )
+        .route(
+            "/api/admin/impersonations/:id",
+            delete(impersonation_handlers::revoke_impersonation),
+        )
+        .route(
+            "/api/admin/impersonations/:id/audit",
+            get(impersonation_handlers::get_impersonation_audit),
+        )
+}
@@ -0,0 +1,271 @@
+// api/src/key_challenge.rs
+//
+// Proof that a caller controls a Stellar account key.
+//
+// The registry hands out a short-lived challenge (an HS256 JWT naming the
+// address) and the caller signs its UTF-8 bytes with the account's Ed25519
+// key.  Signatures over the raw challenge and SEP-53 signed messages (what
+// wallets such as Freighter produce) are both accepted.  Every challenge
+// nonce is redeemed once, through `consume_nonce`.
+
+use axum::http::StatusCode;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use ring::signature::{UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    auth::{decode_jwt, session_secret, sign_jwt, AuthKeys},
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+};
+
+/// How long a challenge can be signed and redeemed.
+pub const CHALLENGE_TTL_MINUTES: i64 = 5;
+
+/// Version byte of an `G...` account id (ed25519 public key) strkey.
+const ACCOUNT_ID_VERSION: u8 = 6 << 3;
+
+/// Prefix of SEP-53 signed messages.
+const SEP53_PREFIX: &[u8] = b"Stellar Signed Message:\n";
+
+/// Claims of a key challenge.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KeyChallenge {
+    /// Stellar address the caller claims to control
+    pub address: String,
+    /// Session identity the challenge was issued to, if any; linking only
+    /// accepts challenges issued to the linking session
+    pub identity: Option<Uuid>,
+    pub nonce: Uuid,
+    pub exp: i64,
+}
+
+/// Decode a `G...` Stellar address into its Ed25519 public key.
+pub fn decode_account_id(address: &str) -> Option<[u8; 32]> {
+    let raw = base32_decode(address)?;
+    if raw.len() != 35 || raw[0] != ACCOUNT_ID_VERSION {
+        return None;
+    }
+    let checksum = u16::from_le_bytes([raw[33], raw[34]]);
+    if crc16_xmodem(&raw[..33]) != checksum {
+        return None;
+    }
+    raw[1..33].try_into().ok()
+}
+
+fn base32_decode(input: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(input.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for c in input.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'2'..=b'7' => c - b'2' + 26,
+            _ => return None,
+        };
+        buffer = (buffer << 5) | u32::from(value);
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    // Leftover bits must be zero padding
+    (buffer == 0).then_some(out)
+}
+
+fn crc16_xmodem(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for byte in data {
+        crc ^= u16::from(*byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// Decode a signature given as hex or base64.
+fn decode_signature(signature: &str) -> Option<Vec<u8>> {
+    let signature = signature.trim();
+    if signature.len() == 128 {
+        if let Ok(bytes) = hex::decode(signature) {
+            return Some(bytes);
+        }
+    }
+    STANDARD.decode(signature).ok()
+}
+
+/// Whether `signature` is `address`'s signature over `message`, either
+/// directly or as a SEP-53 signed message.
+pub fn verify_signature(address: &str, message: &str, signature: &str) -> bool {
+    let (Some(public_key), Some(signature)) =
+        (decode_account_id(address), decode_signature(signature))
+    else {
+        return false;
+    };
+    let key = UnparsedPublicKey::new(&ED25519, public_key);
+    if key.verify(message.as_bytes(), &signature).is_ok() {
+        return true;
+    }
+
+    let mut sep53 = Sha256::new();
+    sep53.update(SEP53_PREFIX);
+    sep53.update(message.as_bytes());
+    key.verify(&sep53.finalize(), &signature).is_ok()
+}
+
+/// Issue a challenge for `address`, bound to the calling session if any.
+pub fn issue_challenge(
+    keys: &AuthKeys,
+    address: &str,
+    identity: Option<Uuid>,
+) -> ApiResult<(String, DateTime<Utc>)> {
+    if decode_account_id(address).is_none() {
+        return Err(ApiError::bad_request(
+            "InvalidAddress",
+            format!("`{}` is not a Stellar account address", address),
+        ));
+    }
+    let secret = session_secret(keys)?;
+    let expires_at = Utc::now() + Duration::minutes(CHALLENGE_TTL_MINUTES);
+    let challenge = KeyChallenge {
+        address: address.to_string(),
+        identity,
+        nonce: Uuid::new_v4(),
+        exp: expires_at.timestamp(),
+    };
+    Ok((sign_jwt(secret, &challenge), expires_at))
+}
+
+/// Check a signed challenge and redeem its nonce, returning its claims.
+pub async fn redeem_challenge(
+    pool: &PgPool,
+    keys: &AuthKeys,
+    challenge: &str,
+    signature: &str,
+) -> ApiResult<KeyChallenge> {
+    let secret = session_secret(keys)?;
+    let claims: KeyChallenge = decode_jwt(secret, challenge, Utc::now().timestamp())
+        .map_err(|reason| ApiError::bad_request("InvalidChallenge", reason))?;
+
+    if !verify_signature(&claims.address, challenge, signature) {
+        return Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "InvalidSignature",
+            format!("signature does not match the key of {}", claims.address),
+        ));
+    }
+    consume_nonce(pool, claims.nonce, claims.exp).await?;
+    Ok(claims)
+}
+
+/// Record a one-time nonce, failing if it was already used.  Expired nonces
+/// are pruned along the way.
+pub async fn consume_nonce(pool: &PgPool, nonce: Uuid, exp: i64) -> ApiResult<()> {
+    let expires_at = Utc
+        .timestamp_opt(exp, 0)
+        .single()
+        .unwrap_or_else(Utc::now);
+
+    sqlx::query("DELETE FROM used_auth_nonces WHERE expires_at < NOW()")
+        .execute(pool)
+        .await
+        .map_err(|err| db_internal_error("prune auth nonces", err))?;
+
+    let inserted = sqlx::query(
+        "INSERT INTO used_auth_nonces (nonce, expires_at) VALUES ($1, $2)
+         ON CONFLICT (nonce) DO NOTHING",
+    )
+    .bind(nonce)
+    .bind(expires_at)
+    .execute(pool)
+    .await
+    .map_err(|err| db_internal_error("record auth nonce", err))?
+    .rows_affected();
+
+    if inserted == 0 {
+        return Err(ApiError::bad_request(
+            "NonceReused",
+            "this challenge or login state has already been used",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    fn base32_encode(data: &[u8]) -> String {
+        const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+        let mut out = String::new();
+        let (mut buffer, mut bits) = (0u32, 0u32);
+        for byte in data {
+            buffer = (buffer << 8) | u32::from(*byte);
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                out.push(ALPHABET[((buffer >> bits) & 31) as usize] as char);
+            }
+            buffer &= (1 << bits) - 1;
+        }
+        if bits > 0 {
+            out.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+        }
+        out
+    }
+
+    /// Key pair and `G...` address for tests.
+    pub(crate) fn test_account(seed: u8) -> (Ed25519KeyPair, String) {
+        let pair = Ed25519KeyPair::from_seed_unchecked(&[seed; 32]).unwrap();
+        let mut raw = vec![ACCOUNT_ID_VERSION];
+        raw.extend_from_slice(pair.public_key().as_ref());
+        let checksum = crc16_xmodem(&raw);
+        raw.extend_from_slice(&checksum.to_le_bytes());
+        (pair, base32_encode(&raw))
+    }
+
+    #[test]
+    fn decodes_account_ids_and_rejects_bad_checksums() {
+        let (pair, address) = test_account(7);
+        assert!(address.starts_with('G'));
+        assert_eq!(address.len(), 56);
+        assert_eq!(
+            decode_account_id(&address).map(|k| k.to_vec()),
+            Some(pair.public_key().as_ref().to_vec())
+        );
+
+        let mut tampered = address.clone().into_bytes();
+        tampered[10] = if tampered[10] == b'A' { b'B' } else { b'A' };
+        assert!(decode_account_id(std::str::from_utf8(&tampered).unwrap()).is_none());
+        assert!(decode_account_id("not-an-address").is_none());
+    }
+
+    #[test]
+    fn accepts_raw_and_sep53_signatures_of_the_right_key() {
+        let (pair, address) = test_account(1);
+        let (_, other) = test_account(2);
+        let message = "challenge.token.value";
+
+        let raw = STANDARD.encode(pair.sign(message.as_bytes()));
+        assert!(verify_signature(&address, message, &raw));
+        assert!(!verify_signature(&other, message, &raw));
+        assert!(!verify_signature(&address, "another message", &raw));
+
+        let mut sep53 = Sha256::new();
+        sep53.update(SEP53_PREFIX);
+        sep53.update(message.as_bytes());
+        let signed = hex::encode(pair.sign(&sep53.finalize()));
+        assert!(verify_signature(&address, message, &signed));
+    }
+}
@@ -1,28 +1,79 @@
 mod aggregation;
 mod analytics;
+mod artifact_handlers;
+mod artifact_routes;
+mod attestation_handlers;
+mod attestation_routes;
+mod auth;
+mod auth_handlers;
+mod auth_providers;
+mod auth_routes;
+mod audit_findings;
 mod audit_handlers;
+mod audit_report_handlers;
+mod audit_report_routes;
 mod audit_routes;
-mod benchmark_engine;
-mod benchmark_handlers;
-mod benchmark_routes;
+mod budget_handlers;
+mod budget_monitor;
+mod budget_routes;
 mod cache;
-mod cache_benchmark;
 mod checklist;
+mod compliance_handlers;
+mod compliance_routes;
 mod contract_history_handlers;
 mod contract_history_routes;
+mod dependency_handlers;
+mod dependency_routes;
+mod deployment_approval_handlers;
+mod deployment_approval_routes;
+mod deprecation_handlers;
+mod deprecation_routes;
 mod detector;
 mod error;
+mod event_schema;
+mod event_schema_handlers;
+mod event_schema_routes;
 mod handlers;
+mod impersonation;
+mod impersonation_handlers;
+mod impersonation_routes;
+mod key_challenge;
+mod metadata_handlers;
+mod metadata_routes;
+mod metadata_schema;
+mod models;
+mod multisig_handlers;
+mod multisig_routes;
+mod name_reservation;
+mod name_reservation_handlers;
+mod name_reservation_routes;
+mod object_storage;
+mod query_builder;
+mod quota;
+mod quota_handlers;
+mod quota_routes;
 mod rate_limit;
 mod routes;
 mod scoring;
+mod search_handlers;
+mod search_routes;
 mod state;
+mod status_aggregator;
+mod status_handlers;
+mod status_routes;
+mod verification_tier_handlers;
+mod verification_tier_routes;
+mod wasm_analysis;
+mod wasm_feature_handlers;
+mod wasm_feature_routes;
+mod webhook;
 mod health_monitor;
 
 use anyhow::Result;
 use axum::http::{header, HeaderValue, Method};
 use axum::{middleware, Router};
 use dotenv::dotenv;
+use registry_config::{ApiConfig, ConfigArgs};
 use sqlx::postgres::PgPoolOptions;
 use std::net::SocketAddr;
 use tower_http::cors::CorsLayer;
@@ -45,12 +96,38 @@ async fn main() -> Result<()> {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
-    // Database connection
-    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-
+    // Load configuration (defaults < --config file < env < --set); every
+    // problem is reported before anything starts
+    let (config_args, rest) =
+        ConfigArgs::extract(std::env::args().skip(1)).map_err(anyhow::Error::msg)?;
+    if let Some(arg) = rest.first() {
+        anyhow::bail!("unexpected argument: {}", arg);
+    }
+    let config: ApiConfig = registry_config::load(&config_args)?;
+    if config_args.print_config {
+        print!("{}", registry_config::render(&config));
+        return Ok(());
+    }
+
+    // Database connection.  Fuzzy search uses pg_trgm's `<%` operator, which
+    // reads its cut-off from the session.
     let pool = PgPoolOptions::new()
-        .max_connections(5)
-        .connect(&database_url)
+        .max_connections(config.database.max_connections)
+        .after_connect(|conn, _meta| {
+            Box::pin(async move {
+                sqlx::Executor::execute(
+                    conn,
+                    format!(
+                        "SET pg_trgm.word_similarity_threshold = {}",
+                        search_handlers::FUZZY_MATCH_THRESHOLD
+                    )
+                    .as_str(),
+                )
+                .await?;
+                Ok(())
+            })
+        })
+        .connect(config.database.url())
         .await?;
 
     // Run migrations
@@ -64,15 +141,22 @@ async fn main() -> Result<()> {
     aggregation::spawn_aggregation_task(pool.clone());
 
     // Create app state
-    let state = AppState::new(pool);
-    let rate_limit_state = RateLimitState::from_env();
+    let state = AppState::new(pool, &config);
+    let rate_limit_state = RateLimitState::from_settings(&config.rate_limit);
 
     let cors = CorsLayer::new()
         .allow_origin([
             HeaderValue::from_static("http://localhost:3000"),
             HeaderValue::from_static("https://soroban-registry.vercel.app"),
         ])
-        .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+            Method::OPTIONS,
+        ])
         .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION]);
 
     // Build router
@@ -81,13 +165,32 @@ async fn main() -> Result<()> {
         .merge(routes::publisher_routes())
         .merge(routes::health_routes())
         .merge(routes::migration_routes())
-        .merge(routes::canary_routes())
-        .merge(routes::ab_test_routes())
-        .merge(routes::performance_routes())
+        .merge(contract_history_routes::contract_history_routes())
         .merge(multisig_routes::multisig_routes())
-        .merge(audit_routes::audit_routes())
-        .merge(benchmark_routes::benchmark_routes())
+        .merge(audit_routes::security_audit_routes())
+        .merge(audit_report_routes::audit_report_routes())
+        .merge(artifact_routes::artifact_routes())
+        .merge(metadata_routes::metadata_routes())
+        .merge(verification_tier_routes::verification_tier_routes())
+        .merge(deployment_approval_routes::deployment_approval_routes())
+        .merge(budget_routes::budget_routes())
+        .merge(event_schema_routes::event_schema_routes())
+        .merge(search_routes::search_routes())
+        .merge(compliance_routes::compliance_routes())
+        .merge(auth_routes::auth_routes())
+        .merge(dependency_routes::dependency_routes())
+        .merge(status_routes::status_routes())
+        .merge(quota_routes::quota_routes())
+        .merge(deprecation_routes::deprecation_routes())
+        .merge(wasm_feature_routes::wasm_feature_routes())
+        .merge(impersonation_routes::impersonation_routes())
+        .merge(name_reservation_routes::name_reservation_routes())
+        .merge(attestation_routes::attestation_routes())
         .fallback(handlers::route_not_found)
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            impersonation::impersonation_middleware,
+        ))
         .layer(middleware::from_fn(request_logger))
         .layer(middleware::from_fn_with_state(
             rate_limit_state,
@@ -97,11 +200,16 @@ async fn main() -> Result<()> {
         .layer(cors)
         .with_state(state.clone());
 
-    // Spawn health monitor task
+    // Spawn health monitor, budget monitor and status aggregator tasks
+    tokio::spawn(budget_monitor::run_budget_monitor(
+        state.clone(),
+        std::time::Duration::from_secs(config.budgets.check_interval_seconds),
+    ));
+    tokio::spawn(status_aggregator::run_status_aggregator(state.clone()));
     tokio::spawn(health_monitor::run_health_monitor(state));
 
     // Start server
-    let addr = SocketAddr::from(([0, 0, 0, 0], 3001));
+    let addr: SocketAddr = config.server.bind.parse()?;
     tracing::info!("API server listening on {}", addr);
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
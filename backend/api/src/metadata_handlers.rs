@@ -0,0 +1,183 @@
+// api/src/metadata_handlers.rs
+//
+// Category metadata schemas and contract metadata updates.
+//
+// Routes (registered in metadata_routes.rs):
+//   GET   /api/categories/:category/metadata-schema  – current schema for a category
+//   PUT   /api/categories/:category/metadata-schema  – named admin: create/replace schema
+//   PATCH /api/contracts/:id/metadata                – owner: replace a contract's metadata
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    http::HeaderMap,
+    Json,
+};
+use shared::{
+    AuditActionType, CategoryMetadataSchema, Contract, UpdateContractMetadataRequest,
+    UpsertMetadataSchemaRequest,
+};
+use uuid::Uuid;
+
+use crate::{
+    auth::{require_admin, require_contract_owner},
+    contract_history_handlers::log_contract_change,
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    metadata_schema::{compile_schema, validate_metadata},
+    state::AppState,
+};
+
+fn map_json_rejection(err: JsonRejection) -> ApiError {
+    ApiError::bad_request(
+        "InvalidRequest",
+        format!("Invalid JSON payload: {}", err.body_text()),
+    )
+}
+
+/// Validate contract metadata against the schema registered for `category`.
+///
+/// Contracts without a category, or in a category without a schema, only
+/// need their metadata to be a JSON object.
+pub async fn validate_contract_metadata(
+    state: &AppState,
+    category: Option<&str>,
+    metadata: &serde_json::Value,
+) -> ApiResult<()> {
+    if !metadata.is_object() {
+        return Err(ApiError::bad_request(
+            "InvalidMetadata",
+            "metadata must be a JSON object",
+        ));
+    }
+
+    let Some(category) = category else {
+        return Ok(());
+    };
+
+    let schema: Option<serde_json::Value> =
+        sqlx::query_scalar("SELECT schema FROM category_metadata_schemas WHERE category = $1")
+            .bind(category)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("load category metadata schema", err))?;
+
+    match schema {
+        Some(schema) => validate_metadata(&schema, metadata).map_err(|errors| {
+            ApiError::bad_request(
+                "InvalidMetadata",
+                format!(
+                    "metadata does not match the '{}' category schema: {}",
+                    category,
+                    errors.join("; ")
+                ),
+            )
+        }),
+        None => Ok(()),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/categories/:category/metadata-schema
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn get_category_schema(
+    State(state): State<AppState>,
+    Path(category): Path<String>,
+) -> ApiResult<Json<CategoryMetadataSchema>> {
+    sqlx::query_as("SELECT * FROM category_metadata_schemas WHERE category = $1")
+        .bind(&category)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("get category metadata schema", err))?
+        .map(Json)
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "SchemaNotFound",
+                format!("No metadata schema registered for category: {}", category),
+            )
+        })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// PUT /api/categories/:category/metadata-schema
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn put_category_schema(
+    State(state): State<AppState>,
+    Path(category): Path<String>,
+    headers: HeaderMap,
+    payload: Result<Json<UpsertMetadataSchemaRequest>, JsonRejection>,
+) -> ApiResult<Json<CategoryMetadataSchema>> {
+    let admin = require_admin(&state.auth, &headers)?.require_named()?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+
+    compile_schema(&req.schema).map_err(|msg| ApiError::bad_request("InvalidSchema", msg))?;
+
+    let saved: CategoryMetadataSchema = sqlx::query_as(
+        "INSERT INTO category_metadata_schemas (category, schema, updated_by)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (category) DO UPDATE
+            SET schema = EXCLUDED.schema,
+                updated_by = EXCLUDED.updated_by
+         RETURNING *",
+    )
+    .bind(&category)
+    .bind(&req.schema)
+    .bind(&admin)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("upsert category metadata schema", err))?;
+
+    tracing::info!(category = %category, updated_by = %admin, "category metadata schema updated");
+    Ok(Json(saved))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// PATCH /api/contracts/:id/metadata
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn update_contract_metadata(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    payload: Result<Json<UpdateContractMetadataRequest>, JsonRejection>,
+) -> ApiResult<Json<Contract>> {
+    let owner = require_contract_owner(&state, &headers, id).await?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+
+    let (category, old_metadata): (Option<String>, Option<serde_json::Value>) =
+        sqlx::query_as("SELECT category, metadata FROM contracts WHERE id = $1")
+            .bind(id)
+            .fetch_one(&state.db)
+            .await
+            .map_err(|err| match err {
+                sqlx::Error::RowNotFound => ApiError::not_found(
+                    "ContractNotFound",
+                    format!("No contract found with ID: {}", id),
+                ),
+                _ => db_internal_error("get contract category", err),
+            })?;
+
+    validate_contract_metadata(&state, category.as_deref(), &req.metadata).await?;
+
+    let contract: Contract =
+        sqlx::query_as("UPDATE contracts SET metadata = $2 WHERE id = $1 RETURNING *")
+            .bind(id)
+            .bind(&req.metadata)
+            .fetch_one(&state.db)
+            .await
+            .map_err(|err| db_internal_error("update contract metadata", err))?;
+
+    if let Err(err) = log_contract_change(
+        &state.db,
+        id,
+        AuditActionType::MetadataUpdated,
+        old_metadata,
+        Some(req.metadata),
+        &owner.address,
+        owner.actor.impersonation(),
+    )
+    .await
+    {
+        tracing::error!(contract_id = %id, "failed to record metadata update in the audit log: {}", err);
+    }
+
+    Ok(Json(contract))
+}
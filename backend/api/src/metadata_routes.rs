@@ -0,0 +1,22 @@
+// api/src/metadata_routes.rs
+// Category metadata schema and contract metadata routes.
+
+use axum::{
+    routing::{get, patch},
+    Router,
+};
+
+use crate::{metadata_handlers, state::AppState};
+
+pub fn metadata_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/categories/:category/metadata-schema",
+            get(metadata_handlers::get_category_schema)
+                .put(metadata_handlers::put_category_schema),
+        )
+        .route(
+            "/api/contracts/:id/metadata",
+            patch(metadata_handlers::update_contract_metadata),
+        )
+}
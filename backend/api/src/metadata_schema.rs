@@ -0,0 +1,84 @@
+// api/src/metadata_schema.rs
+//
+// Per-category JSON Schema validation for contract `metadata`.
+//
+// Admins register one schema per category (e.g. `token` requires
+// `decimals` and `symbol`).  Contracts in that category have their metadata
+// validated against it on publish and on every metadata update.  Categories
+// without a schema accept any JSON object.
+
+use jsonschema::JSONSchema;
+use serde_json::Value;
+
+/// Compile a schema, rejecting anything that is not a valid JSON Schema
+/// describing an object.
+pub fn compile_schema(schema: &Value) -> Result<JSONSchema, String> {
+    if schema.get("type").and_then(Value::as_str) != Some("object") {
+        return Err("metadata schema must have \"type\": \"object\"".to_string());
+    }
+    JSONSchema::compile(schema).map_err(|err| format!("invalid JSON Schema: {}", err))
+}
+
+/// Validate `metadata` against `schema`.
+///
+/// Returns every violation as `"<json pointer>: <message>"` so clients can
+/// fix all fields in one round-trip.
+pub fn validate_metadata(schema: &Value, metadata: &Value) -> Result<(), Vec<String>> {
+    if !metadata.is_object() {
+        return Err(vec!["metadata must be a JSON object".to_string()]);
+    }
+
+    let compiled = compile_schema(schema).map_err(|err| vec![err])?;
+    let result = compiled.validate(metadata);
+    match result {
+        Ok(()) => Ok(()),
+        Err(errors) => Err(errors
+            .map(|err| {
+                let path = err.instance_path.to_string();
+                if path.is_empty() {
+                    err.to_string()
+                } else {
+                    format!("{}: {}", path, err)
+                }
+            })
+            .collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn token_schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["decimals", "symbol"],
+            "properties": {
+                "decimals": { "type": "integer", "minimum": 0, "maximum": 18 },
+                "symbol": { "type": "string", "maxLength": 12 }
+            }
+        })
+    }
+
+    #[test]
+    fn accepts_conforming_metadata() {
+        let metadata = json!({ "decimals": 7, "symbol": "USDC" });
+        assert!(validate_metadata(&token_schema(), &metadata).is_ok());
+    }
+
+    #[test]
+    fn reports_every_violation() {
+        let metadata = json!({ "decimals": 42 });
+        let errors = validate_metadata(&token_schema(), &metadata).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().any(|e| e.starts_with("/decimals")));
+        assert!(errors.iter().any(|e| e.contains("symbol")));
+    }
+
+    #[test]
+    fn rejects_non_object_schemas_and_metadata() {
+        assert!(compile_schema(&json!({ "type": "string" })).is_err());
+        assert!(validate_metadata(&token_schema(), &json!([1, 2])).is_err());
+    }
+}
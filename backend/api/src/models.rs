@@ -77,35 +77,30 @@ pub enum DetectionMethod {
 /// A static checklist item definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChecklistItem {
-    pub id: &'static str,
+    pub id: String,
     pub category: CheckCategory,
-    pub title: &'static str,
-    pub description: &'static str,
+    pub title: String,
+    pub description: String,
     pub severity: Severity,
     pub detection: DetectionMethod,
-    pub remediation: &'static str,
-    pub references: Vec<&'static str>,
+    pub remediation: String,
+    pub references: Vec<String>,
 }
 
 // ─────────────────────────────────────────────────────────
 // Runtime / database types
 // ─────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, sqlx::Type)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, sqlx::Type)]
 #[sqlx(type_name = "text", rename_all = "snake_case")]
 pub enum CheckStatus {
     Passed,
     Failed,
     NotApplicable,
+    #[default]
     Pending,
 }
 
-impl Default for CheckStatus {
-    fn default() -> Self {
-        CheckStatus::Pending
-    }
-}
-
 /// One row in `audit_checks` — per-check status for an audit
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct AuditCheckRow {
@@ -166,15 +161,15 @@ pub struct AuditResponse {
 #[derive(Debug, Serialize)]
 pub struct CheckWithStatus {
     // ── static metadata ──
-    pub id: &'static str,
+    pub id: String,
     pub category: String,
-    pub title: &'static str,
-    pub description: &'static str,
+    pub title: String,
+    pub description: String,
     pub severity: String,
-    pub detection_type: &'static str,
+    pub detection_type: String,
     pub auto_patterns: Vec<String>,
-    pub remediation: &'static str,
-    pub references: Vec<&'static str>,
+    pub remediation: String,
+    pub references: Vec<String>,
     // ── live audit status ──
     pub status: CheckStatus,
     pub notes: Option<String>,
@@ -18,6 +18,7 @@ use uuid::Uuid;
 use crate::{
     error::{ApiError, ApiResult},
     handlers::db_internal_error,
+    query_builder::{ListQuery, ProposalColumn},
     state::AppState,
 };
 
@@ -435,54 +436,25 @@ pub async fn list_proposals(
     let page = params.page.unwrap_or(1).max(1);
     let offset = (page - 1) * limit;
 
-    // Dynamic query builder (safe — values are bound, not interpolated)
-    let mut where_clauses: Vec<String> = Vec::new();
-    let mut arg_idx = 1usize;
-
-    if let Some(ref s) = params.status {
-        where_clauses.push(format!("status = ${}", arg_idx));
-        arg_idx += 1;
+    let mut list = ListQuery::<ProposalColumn>::new();
+    if let Some(ref status) = params.status {
+        list.eq(ProposalColumn::Status, status.clone());
     }
-    if params.policy_id.is_some() {
-        where_clauses.push(format!("policy_id = ${}", arg_idx));
-        arg_idx += 1;
+    if let Some(policy_id) = params.policy_id {
+        list.eq(ProposalColumn::PolicyId, policy_id);
     }
-    let _ = arg_idx; // suppress unused warning
-
-    let where_sql = if where_clauses.is_empty() {
-        String::new()
-    } else {
-        format!("WHERE {}", where_clauses.join(" AND "))
-    };
-
-    let count_sql = format!("SELECT COUNT(*) FROM deploy_proposals {}", where_sql);
-    let list_sql = format!(
-        "SELECT * FROM deploy_proposals {} ORDER BY created_at DESC LIMIT {} OFFSET {}",
-        where_sql, limit, offset
-    );
+    list.order_by_desc(ProposalColumn::CreatedAt);
 
-    // Build and execute count query
-    let mut count_q = sqlx::query_scalar::<_, i64>(&count_sql);
-    if let Some(ref s) = params.status {
-        count_q = count_q.bind(s.clone());
-    }
-    if let Some(pid) = params.policy_id {
-        count_q = count_q.bind(pid);
-    }
-    let total: i64 = count_q
+    let total: i64 = list
+        .count()
+        .build_query_scalar()
         .fetch_one(&state.db)
         .await
         .map_err(|err| db_internal_error("count proposals", err))?;
 
-    // Build and execute list query
-    let mut list_q = sqlx::query_as::<_, DeployProposal>(&list_sql);
-    if let Some(ref s) = params.status {
-        list_q = list_q.bind(s.clone());
-    }
-    if let Some(pid) = params.policy_id {
-        list_q = list_q.bind(pid);
-    }
-    let proposals: Vec<DeployProposal> = list_q
+    let proposals: Vec<DeployProposal> = list
+        .select(limit, offset)
+        .build_query_as()
         .fetch_all(&state.db)
         .await
         .map_err(|err| db_internal_error("list proposals", err))?;
@@ -0,0 +1,194 @@
+// api/src/name_reservation.rs
+//
+// Publish-time name checks (see shared::naming for the comparison rules).
+//
+// A name covered by another publisher's reservation is refused.  A name that
+// is confusable with a reservation or with one of the most used contracts is
+// accepted, but the new contract is queued in `name_flags` for moderation
+// (name_reservation_handlers.rs).
+
+use axum::http::StatusCode;
+use shared::{
+    naming::{confusable, in_namespace, skeleton},
+    Contract, NameMatch, NameReservation,
+};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+};
+
+/// How many of the most used contracts new names are compared against.
+const POPULAR_CONTRACTS: i64 = 500;
+
+/// Reservations held by publishers other than `publisher_id`.
+async fn foreign_reservations(
+    conn: &mut PgConnection,
+    publisher_id: Option<Uuid>,
+) -> ApiResult<Vec<NameReservation>> {
+    sqlx::query_as(
+        "SELECT * FROM name_reservations WHERE publisher_id IS DISTINCT FROM $1 ORDER BY created_at",
+    )
+    .bind(publisher_id)
+    .fetch_all(conn)
+    .await
+    .map_err(|err| db_internal_error("list name reservations", err))
+}
+
+fn covers(reservation: &NameReservation, name: &str) -> bool {
+    reservation.skeleton == skeleton(name)
+        || (reservation.namespace && in_namespace(name, &reservation.name))
+}
+
+/// Reservation of another publisher covering `name`, if any.
+pub async fn reservation_conflict(
+    conn: &mut PgConnection,
+    publisher_id: Option<Uuid>,
+    name: &str,
+) -> ApiResult<Option<NameReservation>> {
+    Ok(foreign_reservations(conn, publisher_id)
+        .await?
+        .into_iter()
+        .find(|r| covers(r, name)))
+}
+
+/// Reservations and popular contracts of other publishers that `name` could
+/// be mistaken for.
+pub async fn similar_names(
+    conn: &mut PgConnection,
+    publisher_id: Option<Uuid>,
+    name: &str,
+) -> ApiResult<Vec<NameMatch>> {
+    let mut matches: Vec<NameMatch> = foreign_reservations(&mut *conn, publisher_id)
+        .await?
+        .into_iter()
+        .filter_map(|r| {
+            Some(NameMatch {
+                confusion: confusable(name, &r.name)?,
+                name: r.name,
+                contract_id: None,
+                reservation_id: Some(r.id),
+            })
+        })
+        .collect();
+
+    let popular: Vec<(Uuid, String)> = sqlx::query_as(
+        "SELECT c.id, c.name FROM contracts c
+           LEFT JOIN (SELECT contract_id, COUNT(*) AS n
+                        FROM contract_interactions GROUP BY contract_id) i
+                  ON i.contract_id = c.id
+          WHERE c.publisher_id IS DISTINCT FROM $1
+          ORDER BY c.is_verified DESC, COALESCE(i.n, 0) DESC, c.created_at
+          LIMIT $2",
+    )
+    .bind(publisher_id)
+    .bind(POPULAR_CONTRACTS)
+    .fetch_all(conn)
+    .await
+    .map_err(|err| db_internal_error("list popular contract names", err))?;
+
+    for (id, existing) in popular {
+        let Some(confusion) = confusable(name, &existing) else {
+            continue;
+        };
+        if matches
+            .iter()
+            .any(|m| m.name.eq_ignore_ascii_case(&existing))
+        {
+            continue;
+        }
+        matches.push(NameMatch {
+            name: existing,
+            contract_id: Some(id),
+            reservation_id: None,
+            confusion,
+        });
+    }
+
+    Ok(matches)
+}
+
+/// Refuse reserved names; return the look-alikes a publish should be
+/// flagged for.  Runs on the publishing transaction.
+pub async fn check_publish(
+    conn: &mut PgConnection,
+    publisher_id: Uuid,
+    name: &str,
+) -> ApiResult<Vec<NameMatch>> {
+    if let Some(reservation) = reservation_conflict(&mut *conn, Some(publisher_id), name).await? {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "NameReserved",
+            format!(
+                "The name '{}' is reserved by another publisher ('{}')",
+                name, reservation.name
+            ),
+        ));
+    }
+    similar_names(conn, Some(publisher_id), name).await
+}
+
+/// Queue a freshly published contract for moderation.
+pub async fn flag(pool: &PgPool, contract: &Contract, matches: &[NameMatch]) -> ApiResult<()> {
+    let matches_json = serde_json::to_value(matches).unwrap_or_default();
+    sqlx::query(
+        "INSERT INTO name_flags (contract_id, publisher_id, name, matches) VALUES ($1, $2, $3, $4)",
+    )
+    .bind(contract.id)
+    .bind(contract.publisher_id)
+    .bind(&contract.name)
+    .bind(&matches_json)
+    .execute(pool)
+    .await
+    .map_err(|err| db_internal_error("flag contract name", err))?;
+
+    tracing::warn!(
+        contract_id = %contract.id,
+        publisher_id = %contract.publisher_id,
+        name = %contract.name,
+        similar_to = ?matches.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(),
+        "contract name flagged for moderation"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn reservation(name: &str, namespace: bool) -> NameReservation {
+        NameReservation {
+            id: Uuid::new_v4(),
+            publisher_id: Uuid::new_v4(),
+            name: name.to_string(),
+            skeleton: skeleton(name),
+            namespace,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn reservations_cover_look_alikes_and_namespaces() {
+        let name = reservation("soroswap", false);
+        assert!(covers(&name, "SoroSwap"));
+        assert!(covers(&name, "sor0-swap"));
+        assert!(!covers(&name, "soroswap-router"));
+
+        let namespace = reservation("soroswap", true);
+        assert!(covers(&namespace, "soroswap-router"));
+        assert!(!covers(&namespace, "soroswapper"));
+    }
+
+    #[test]
+    fn reservations_cover_upper_case_spellings() {
+        let name = reservation("stellarindex", false);
+        assert!(covers(&name, "STELLARINDEX"));
+        assert!(covers(&name, "StellarIndex"));
+
+        let namespace = reservation("lumenindex", true);
+        assert!(covers(&namespace, "LUMENINDEX-router"));
+    }
+}
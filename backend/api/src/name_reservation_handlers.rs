@@ -0,0 +1,372 @@
+// api/src/name_reservation_handlers.rs
+//
+// Contract name reservations and squatting moderation.
+//
+// Routes (registered in name_reservation_routes.rs):
+//   GET    /api/names/check                  – would a name be refused or flagged?
+//   GET    /api/names/reservations           – reservations, optionally per publisher
+//   POST   /api/names/reservations           – verified publisher (session): reserve a name/namespace
+//   DELETE /api/names/reservations/:id       – owner (session): release a reservation
+//   GET    /api/admin/name-flags             – admin: flagged names, pending by default
+//   POST   /api/admin/name-flags/:id/review  – admin: dismiss or confirm a flag
+//
+// Publishers count as verified once one of their contracts is.  Publish-time
+// enforcement lives in name_reservation.rs.
+
+use axum::{
+    extract::{
+        rejection::{JsonRejection, QueryRejection},
+        Path, Query, State,
+    },
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+use shared::{
+    naming::{in_namespace, skeleton, Confusion},
+    NameCheckResponse, NameFlag, NameFlagStatus, NameReservation, ReserveNameRequest,
+    ReviewNameFlagRequest,
+};
+use uuid::Uuid;
+
+use crate::{
+    auth::{require_admin, require_publisher},
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    name_reservation::{reservation_conflict, similar_names},
+    search_handlers::escape_like,
+    state::AppState,
+};
+
+/// Reservations one publisher may hold at a time.
+const MAX_RESERVATIONS_PER_PUBLISHER: i64 = 20;
+const MAX_NAME_LENGTH: usize = 255;
+
+fn map_json_rejection(err: JsonRejection) -> ApiError {
+    ApiError::bad_request(
+        "InvalidRequest",
+        format!("Invalid JSON payload: {}", err.body_text()),
+    )
+}
+
+fn map_query_rejection(err: QueryRejection) -> ApiError {
+    ApiError::bad_request(
+        "InvalidQuery",
+        format!("Invalid query parameters: {}", err.body_text()),
+    )
+}
+
+fn validate_name(name: &str) -> ApiResult<&str> {
+    let name = name.trim();
+    if name.is_empty() || name.len() > MAX_NAME_LENGTH || skeleton(name).is_empty() {
+        return Err(ApiError::bad_request(
+            "InvalidName",
+            format!(
+                "name must be 1-{} characters and not only separators",
+                MAX_NAME_LENGTH
+            ),
+        ));
+    }
+    Ok(name)
+}
+
+async fn publisher_id_by_address(state: &AppState, address: &str) -> ApiResult<Option<Uuid>> {
+    sqlx::query_scalar("SELECT id FROM publishers WHERE stellar_address = $1")
+        .bind(address)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("get publisher", err))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/names/check?name=...&publisher_address=...
+// ─────────────────────────────────────────────────────────────────────────────
+#[derive(Debug, Deserialize)]
+pub struct CheckNameQuery {
+    pub name: String,
+    /// Ignore the publisher's own reservations and contracts
+    pub publisher_address: Option<String>,
+}
+
+pub async fn check_name(
+    State(state): State<AppState>,
+    params: Result<Query<CheckNameQuery>, QueryRejection>,
+) -> ApiResult<Json<NameCheckResponse>> {
+    let Query(params) = params.map_err(map_query_rejection)?;
+    let name = validate_name(&params.name)?;
+
+    let publisher_id = match &params.publisher_address {
+        Some(address) => publisher_id_by_address(&state, address).await?,
+        None => None,
+    };
+
+    let mut conn = state
+        .db
+        .acquire()
+        .await
+        .map_err(|err| db_internal_error("acquire connection", err))?;
+    let reserved_by = reservation_conflict(&mut conn, publisher_id, name).await?;
+    let similar = similar_names(&mut conn, publisher_id, name).await?;
+
+    Ok(Json(NameCheckResponse {
+        name: name.to_string(),
+        skeleton: skeleton(name),
+        reserved_by,
+        similar,
+    }))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/names/reservations?publisher_id=...
+// ─────────────────────────────────────────────────────────────────────────────
+#[derive(Debug, Deserialize)]
+pub struct ListReservationsQuery {
+    pub publisher_id: Option<Uuid>,
+}
+
+pub async fn list_reservations(
+    State(state): State<AppState>,
+    params: Result<Query<ListReservationsQuery>, QueryRejection>,
+) -> ApiResult<Json<Vec<NameReservation>>> {
+    let Query(params) = params.map_err(map_query_rejection)?;
+
+    let reservations: Vec<NameReservation> = sqlx::query_as(
+        "SELECT * FROM name_reservations
+          WHERE $1::uuid IS NULL OR publisher_id = $1
+          ORDER BY name",
+    )
+    .bind(params.publisher_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list name reservations", err))?;
+
+    Ok(Json(reservations))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/names/reservations
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn reserve_name(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    payload: Result<Json<ReserveNameRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<NameReservation>)> {
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let name = validate_name(&req.name)?;
+    let namespace = req.namespace.unwrap_or(false);
+
+    let publisher_id = publisher_id_by_address(&state, &req.publisher_address)
+        .await?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "PublisherNotFound",
+                format!("No publisher found with address: {}", req.publisher_address),
+            )
+        })?;
+    require_publisher(&state, &headers, publisher_id).await?;
+
+    let (verified, held): (bool, i64) = sqlx::query_as(
+        "SELECT EXISTS(SELECT 1 FROM contracts WHERE publisher_id = $1 AND is_verified),
+                (SELECT COUNT(*) FROM name_reservations WHERE publisher_id = $1)",
+    )
+    .bind(publisher_id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("check publisher verification", err))?;
+    if !verified {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "PublisherNotVerified",
+            "Only publishers with a verified contract can reserve names",
+        ));
+    }
+    if held >= MAX_RESERVATIONS_PER_PUBLISHER {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "ReservationLimitReached",
+            format!(
+                "Publishers may hold at most {} reservations",
+                MAX_RESERVATIONS_PER_PUBLISHER
+            ),
+        ));
+    }
+
+    let mut conn = state
+        .db
+        .acquire()
+        .await
+        .map_err(|err| db_internal_error("acquire connection", err))?;
+    if let Some(reservation) = reservation_conflict(&mut conn, Some(publisher_id), name).await? {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "NameReserved",
+            format!(
+                "'{}' is already reserved by another publisher",
+                reservation.name
+            ),
+        ));
+    }
+
+    // Names (or, for a namespace, names under it) already published by others
+    let prefix = format!("{}%", escape_like(&name.to_lowercase()));
+    let taken: Vec<String> = sqlx::query_scalar(
+        "SELECT name FROM contracts
+          WHERE publisher_id <> $1 AND (lower(name) = lower($2) OR ($3 AND lower(name) LIKE $4))",
+    )
+    .bind(publisher_id)
+    .bind(name)
+    .bind(namespace)
+    .bind(&prefix)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|err| db_internal_error("check published names", err))?;
+    let similar = similar_names(&mut conn, Some(publisher_id), name).await?;
+    let in_use = taken
+        .iter()
+        .find(|existing| existing.eq_ignore_ascii_case(name) || in_namespace(existing, name))
+        .cloned()
+        .or_else(|| {
+            similar
+                .iter()
+                .find(|m| m.contract_id.is_some() && m.confusion != Confusion::EditDistance)
+                .map(|m| m.name.clone())
+        });
+    if let Some(existing) = in_use {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "NameInUse",
+            format!(
+                "'{}' is already used by another publisher's contract",
+                existing
+            ),
+        ));
+    }
+
+    let reservation: NameReservation = sqlx::query_as(
+        "INSERT INTO name_reservations (publisher_id, name, skeleton, namespace)
+         VALUES ($1, $2, $3, $4)
+         RETURNING *",
+    )
+    .bind(publisher_id)
+    .bind(name)
+    .bind(skeleton(name))
+    .bind(namespace)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| match err {
+        sqlx::Error::Database(ref db) if db.is_unique_violation() => ApiError::new(
+            StatusCode::CONFLICT,
+            "NameReserved",
+            format!("'{}' is already reserved", name),
+        ),
+        _ => db_internal_error("reserve name", err),
+    })?;
+
+    tracing::info!(
+        publisher_id = %publisher_id,
+        name = %reservation.name,
+        namespace,
+        "name reserved"
+    );
+
+    Ok((StatusCode::CREATED, Json(reservation)))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// DELETE /api/names/reservations/:id
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn release_reservation(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    let publisher_id: Uuid =
+        sqlx::query_scalar("SELECT publisher_id FROM name_reservations WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("get name reservation owner", err))?
+            .ok_or_else(|| {
+                ApiError::not_found(
+                    "ReservationNotFound",
+                    format!("No name reservation found with ID: {}", id),
+                )
+            })?;
+    require_publisher(&state, &headers, publisher_id).await?;
+
+    sqlx::query("DELETE FROM name_reservations WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|err| db_internal_error("release name reservation", err))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/admin/name-flags?status=pending
+// ─────────────────────────────────────────────────────────────────────────────
+#[derive(Debug, Deserialize)]
+pub struct ListNameFlagsQuery {
+    /// Defaults to `pending`
+    pub status: Option<NameFlagStatus>,
+}
+
+pub async fn list_name_flags(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    params: Result<Query<ListNameFlagsQuery>, QueryRejection>,
+) -> ApiResult<Json<Vec<NameFlag>>> {
+    require_admin(&state.auth, &headers)?;
+    let Query(params) = params.map_err(map_query_rejection)?;
+
+    let flags: Vec<NameFlag> =
+        sqlx::query_as("SELECT * FROM name_flags WHERE status = $1 ORDER BY created_at")
+            .bind(params.status.unwrap_or(NameFlagStatus::Pending))
+            .fetch_all(&state.db)
+            .await
+            .map_err(|err| db_internal_error("list name flags", err))?;
+
+    Ok(Json(flags))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// POST /api/admin/name-flags/:id/review
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn review_name_flag(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    payload: Result<Json<ReviewNameFlagRequest>, JsonRejection>,
+) -> ApiResult<Json<NameFlag>> {
+    require_admin(&state.auth, &headers)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    if req.status == NameFlagStatus::Pending {
+        return Err(ApiError::bad_request(
+            "InvalidReview",
+            "status must be 'dismissed' or 'confirmed'",
+        ));
+    }
+
+    let flag: NameFlag = sqlx::query_as(
+        "UPDATE name_flags SET status = $2, review_note = $3, reviewed_at = NOW()
+          WHERE id = $1
+          RETURNING *",
+    )
+    .bind(id)
+    .bind(req.status)
+    .bind(&req.note)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("review name flag", err))?
+    .ok_or_else(|| {
+        ApiError::not_found(
+            "NameFlagNotFound",
+            format!("No name flag found with ID: {}", id),
+        )
+    })?;
+
+    tracing::info!(flag_id = %id, status = ?flag.status, "name flag reviewed");
+
+    Ok(Json(flag))
+}
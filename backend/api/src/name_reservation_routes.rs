@@ -0,0 +1,34 @@
+// api/src/name_reservation_routes.rs
+// Name reservation and squatting moderation routes.
+
+use axum::{
+    routing::{delete, get, post},
+    Router,
+};
+
+use crate::{name_reservation_handlers, state::AppState};
+
+pub fn name_reservation_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/names/check",
+            get(name_reservation_handlers::check_name),
+        )
+        .route(
+            "/api/names/reservations",
+            get(name_reservation_handlers::list_reservations)
+                .post(name_reservation_handlers::reserve_name),
+        )
+        .route(
+            "/api/names/reservations/:id",
+            delete(name_reservation_handlers::release_reservation),
+        )
+        .route(
+            "/api/admin/name-flags",
+            get(name_reservation_handlers::list_name_flags),
+        )
+        .route(
+            "/api/admin/name-flags/:id/review",
+            post(name_reservation_handlers::review_name_flag),
+        )
+}
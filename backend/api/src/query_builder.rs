@@ -0,0 +1,259 @@
+// api/src/query_builder.rs
+//
+// Typed filters for list endpoints.
+//
+// Column names come from per-table enums (see `columns!`), so a typo is a
+// compile error, and every filter value is pushed as a bind parameter — user
+// input never ends up in the SQL text.  The same filters drive both the
+// paginated SELECT and its COUNT(*).
+//
+//   let mut list = ListQuery::<ContractColumn>::new();
+//   list.eq(ContractColumn::Category, category.clone());
+//   list.order_by_desc(ContractColumn::CreatedAt);
+//   let rows = list.select(limit, offset).build_query_as::<Contract>().fetch_all(&db).await?;
+
+use std::marker::PhantomData;
+
+use sqlx::{Encode, Postgres, QueryBuilder, Type};
+
+/// A column of one table.
+pub trait Column: Copy + Send + Sync + 'static {
+    const TABLE: &'static str;
+    fn name(self) -> &'static str;
+}
+
+/// Declare a column enum for a table.
+macro_rules! columns {
+    ($(#[$meta:meta])* $vis:vis enum $name:ident in $table:literal {
+        $($variant:ident => $column:literal),+ $(,)?
+    }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        $vis enum $name {
+            $($variant),+
+        }
+
+        impl $crate::query_builder::Column for $name {
+            const TABLE: &'static str = $table;
+
+            fn name(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $column),+
+                }
+            }
+        }
+    };
+}
+
+columns! {
+    /// Filterable columns of `contracts`
+    pub enum ContractColumn in "contracts" {
+        Name => "name",
+        Description => "description",
+        Network => "network",
+        IsVerified => "is_verified",
+        Category => "category",
+        Tags => "tags",
+        VerificationTier => "verification_tier",
+        Metadata => "metadata",
+        CreatedAt => "created_at",
+    }
+}
+
+columns! {
+    /// Filterable columns of `deploy_proposals`
+    pub enum ProposalColumn in "deploy_proposals" {
+        Status => "status",
+        PolicyId => "policy_id",
+        CreatedAt => "created_at",
+    }
+}
+
+/// Appends one SQL fragment (and its binds) to a query.
+type Fragment = Box<dyn Fn(&mut QueryBuilder<'static, Postgres>) + Send + Sync>;
+
+/// Values that can be bound as query parameters.
+pub trait BindValue:
+    for<'q> Encode<'q, Postgres> + Type<Postgres> + Clone + Send + Sync + 'static
+{
+}
+impl<T> BindValue for T where
+    T: for<'q> Encode<'q, Postgres> + Type<Postgres> + Clone + Send + Sync + 'static
+{
+}
+
+pub struct ListQuery<C: Column> {
+    filters: Vec<Fragment>,
+    order: Vec<Fragment>,
+    _table: PhantomData<C>,
+}
+
+impl<C: Column> Default for ListQuery<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Column> ListQuery<C> {
+    pub fn new() -> Self {
+        Self {
+            filters: Vec::new(),
+            order: Vec::new(),
+            _table: PhantomData,
+        }
+    }
+
+    fn filter(&mut self, fragment: Fragment) -> &mut Self {
+        self.filters.push(fragment);
+        self
+    }
+
+    fn compare<T: BindValue>(&mut self, column: C, op: &'static str, value: T) -> &mut Self {
+        self.filter(Box::new(move |qb| {
+            qb.push(column.name()).push(op).push_bind(value.clone());
+        }))
+    }
+
+    /// `column = value`
+    pub fn eq<T: BindValue>(&mut self, column: C, value: T) -> &mut Self {
+        self.compare(column, " = ", value)
+    }
+
+    /// `column >= value`
+    pub fn gte<T: BindValue>(&mut self, column: C, value: T) -> &mut Self {
+        self.compare(column, " >= ", value)
+    }
+
+    /// `column @> value` (JSONB / array containment)
+    pub fn contains<T: BindValue>(&mut self, column: C, value: T) -> &mut Self {
+        self.compare(column, " @> ", value)
+    }
+
+    /// Case-insensitive substring match on any of `columns`, or a trigram
+    /// word match (`<%`, see `FUZZY_MATCH_THRESHOLD`) against `similar_to`.
+    /// `pattern` is a LIKE pattern the caller has already escaped.
+    pub fn search(
+        &mut self,
+        columns: &'static [C],
+        pattern: String,
+        similar_to: C,
+        term: String,
+    ) -> &mut Self {
+        self.filter(Box::new(move |qb| {
+            qb.push("(");
+            for column in columns {
+                qb.push(column.name())
+                    .push(" ILIKE ")
+                    .push_bind(pattern.clone())
+                    .push(" OR ");
+            }
+            qb.push_bind(term.clone())
+                .push(" <% lower(")
+                .push(similar_to.name())
+                .push("))");
+        }))
+    }
+
+    pub fn order_by_desc(&mut self, column: C) -> &mut Self {
+        self.order.push(Box::new(move |qb| {
+            qb.push(column.name()).push(" DESC");
+        }));
+        self
+    }
+
+    /// Closest trigram matches of `term` against `column` first.
+    pub fn order_by_similarity(&mut self, column: C, term: String) -> &mut Self {
+        self.order.push(Box::new(move |qb| {
+            qb.push("word_similarity(")
+                .push_bind(term.clone())
+                .push(", ")
+                .push(column.name())
+                .push(") DESC");
+        }));
+        self
+    }
+
+    fn push_where(&self, qb: &mut QueryBuilder<'static, Postgres>) {
+        for (i, filter) in self.filters.iter().enumerate() {
+            qb.push(if i == 0 { " WHERE " } else { " AND " });
+            filter(qb);
+        }
+    }
+
+    /// `SELECT COUNT(*)` over the filtered rows.
+    pub fn count(&self) -> QueryBuilder<'static, Postgres> {
+        let mut qb = QueryBuilder::new(format!("SELECT COUNT(*) FROM {}", C::TABLE));
+        self.push_where(&mut qb);
+        qb
+    }
+
+    /// One page of filtered, ordered rows.
+    pub fn select(&self, limit: i64, offset: i64) -> QueryBuilder<'static, Postgres> {
+        let mut qb = QueryBuilder::new(format!("SELECT * FROM {}", C::TABLE));
+        self.push_where(&mut qb);
+        for (i, order) in self.order.iter().enumerate() {
+            qb.push(if i == 0 { " ORDER BY " } else { ", " });
+            order(&mut qb);
+        }
+        qb.push(" LIMIT ").push_bind(limit);
+        qb.push(" OFFSET ").push_bind(offset);
+        qb
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binds_every_filter_value() {
+        let mut list = ListQuery::<ContractColumn>::new();
+        list.eq(
+            ContractColumn::Category,
+            "defi'; DROP TABLE contracts; --".to_string(),
+        )
+        .eq(ContractColumn::IsVerified, true)
+        .contains(
+            ContractColumn::Metadata,
+            serde_json::json!({"symbol": "USDC"}),
+        )
+        .order_by_desc(ContractColumn::CreatedAt);
+
+        assert_eq!(
+            list.select(20, 40).sql(),
+            "SELECT * FROM contracts WHERE category = $1 AND is_verified = $2 AND metadata @> $3 \
+             ORDER BY created_at DESC LIMIT $4 OFFSET $5"
+        );
+        assert_eq!(
+            list.count().sql(),
+            "SELECT COUNT(*) FROM contracts WHERE category = $1 AND is_verified = $2 AND metadata @> $3"
+        );
+    }
+
+    #[test]
+    fn search_and_similarity_ordering() {
+        const TEXT: &[ContractColumn] = &[ContractColumn::Name, ContractColumn::Description];
+        let mut list = ListQuery::<ContractColumn>::new();
+        list.search(
+            TEXT,
+            "%tok%".into(),
+            ContractColumn::Name,
+            "tok".into(),
+        )
+        .order_by_similarity(ContractColumn::Name, "tok".into())
+        .order_by_desc(ContractColumn::CreatedAt);
+
+        assert_eq!(
+            list.select(10, 0).sql(),
+            "SELECT * FROM contracts WHERE (name ILIKE $1 OR description ILIKE $2 OR \
+             $3 <% lower(name)) ORDER BY word_similarity($4, name) DESC, \
+             created_at DESC LIMIT $5 OFFSET $6"
+        );
+    }
+
+    #[test]
+    fn no_filters_means_no_where_clause() {
+        let list = ListQuery::<ProposalColumn>::new();
+        assert_eq!(list.count().sql(), "SELECT COUNT(*) FROM deploy_proposals");
+    }
+}
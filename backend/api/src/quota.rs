@@ -0,0 +1,165 @@
+// api/src/quota.rs
+//
+// Per-publisher publishing quotas (contracts, versions per day, wasm storage).
+// Limits come from the publisher's tier, overridden per publisher by admins
+// (see quota_handlers.rs).
+
+use axum::http::StatusCode;
+use shared::{QuotaLimits, QuotaUsage};
+use sqlx::{PgExecutor, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+};
+
+/// Tier of publishers without a `publisher_quotas` row.
+pub const DEFAULT_QUOTA_TIER: &str = "free";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaKind {
+    Contracts,
+    VersionsPerDay,
+    WasmStorageBytes,
+}
+
+/// Limits in force for a publisher.
+pub async fn effective_limits(
+    db: impl PgExecutor<'_>,
+    publisher_id: Uuid,
+) -> ApiResult<QuotaLimits> {
+    sqlx::query_as(
+        "SELECT t.name AS tier,
+                COALESCE(q.max_contracts, t.max_contracts)                   AS max_contracts,
+                COALESCE(q.max_versions_per_day, t.max_versions_per_day)     AS max_versions_per_day,
+                COALESCE(q.max_wasm_storage_bytes, t.max_wasm_storage_bytes) AS max_wasm_storage_bytes
+           FROM quota_tiers t
+           LEFT JOIN publisher_quotas q ON q.publisher_id = $1
+          WHERE t.name = COALESCE(q.tier, $2)",
+    )
+    .bind(publisher_id)
+    .bind(DEFAULT_QUOTA_TIER)
+    .fetch_optional(db)
+    .await
+    .map_err(|err| db_internal_error("get publisher quota", err))?
+    .ok_or_else(|| ApiError::internal("Publisher quota tier is not configured"))
+}
+
+/// What a publisher currently counts against its quota.
+pub async fn usage(db: impl PgExecutor<'_>, publisher_id: Uuid) -> ApiResult<QuotaUsage> {
+    sqlx::query_as(
+        "SELECT
+            (SELECT COUNT(*) FROM contracts WHERE publisher_id = $1) AS contracts,
+            (SELECT COUNT(*) FROM contracts
+              WHERE publisher_id = $1 AND created_at >= date_trunc('day', NOW() AT TIME ZONE 'UTC') AT TIME ZONE 'UTC')
+          + (SELECT COUNT(*) FROM contract_versions v JOIN contracts c ON c.id = v.contract_id
+              WHERE c.publisher_id = $1 AND v.created_at >= date_trunc('day', NOW() AT TIME ZONE 'UTC') AT TIME ZONE 'UTC')
+                AS versions_today,
+            (SELECT COALESCE(SUM(size_bytes), 0)::BIGINT FROM (
+                SELECT DISTINCT ON (a.sha256) a.size_bytes
+                  FROM artifact_uploads a JOIN contracts c ON c.id = a.contract_id
+                 WHERE c.publisher_id = $1 AND a.kind = 'wasm'
+                   AND (a.status = 'completed'
+                        OR (a.status = 'pending' AND a.upload_expires_at > NOW()))
+            ) wasm) AS wasm_storage_bytes",
+    )
+    .bind(publisher_id)
+    .fetch_one(db)
+    .await
+    .map_err(|err| db_internal_error("get publisher quota usage", err))
+}
+
+/// Reject the request if adding `additional` of `kind` would exceed the
+/// publisher's quota.
+///
+/// Locks the publisher row until `tx` ends, so concurrent requests for one
+/// publisher are checked one after another.  Callers must insert what counts
+/// against the quota in the same transaction.
+pub async fn enforce(
+    tx: &mut Transaction<'_, Postgres>,
+    publisher_id: Uuid,
+    requested: &[(QuotaKind, i64)],
+) -> ApiResult<()> {
+    sqlx::query("SELECT 1 FROM publishers WHERE id = $1 FOR UPDATE")
+        .bind(publisher_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(|err| db_internal_error("lock publisher quota", err))?;
+
+    let limits = effective_limits(&mut **tx, publisher_id).await?;
+    let usage = usage(&mut **tx, publisher_id).await?;
+
+    for &(kind, additional) in requested {
+        check_quota(kind, &limits, &usage, additional)
+            .map_err(|msg| ApiError::new(StatusCode::FORBIDDEN, "QuotaExceeded", msg))?;
+    }
+    Ok(())
+}
+
+/// Error message naming current usage and the upgrade path when `additional`
+/// does not fit.
+pub fn check_quota(
+    kind: QuotaKind,
+    limits: &QuotaLimits,
+    usage: &QuotaUsage,
+    additional: i64,
+) -> Result<(), String> {
+    let (used, limit, what) = match kind {
+        QuotaKind::Contracts => (usage.contracts, i64::from(limits.max_contracts), "contracts"),
+        QuotaKind::VersionsPerDay => (
+            usage.versions_today,
+            i64::from(limits.max_versions_per_day),
+            "versions published today",
+        ),
+        QuotaKind::WasmStorageBytes => (
+            usage.wasm_storage_bytes,
+            limits.max_wasm_storage_bytes,
+            "bytes of wasm storage",
+        ),
+    };
+
+    if used + additional <= limit {
+        return Ok(());
+    }
+    Err(format!(
+        "Publisher quota exceeded: {} of {} {} used on the '{}' tier, this request needs {} more. \
+         Ask a registry administrator to raise the quota or move the publisher to a higher tier.",
+        used, limit, what, limits.tier, additional
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> QuotaLimits {
+        QuotaLimits {
+            tier: "free".into(),
+            max_contracts: 25,
+            max_versions_per_day: 20,
+            max_wasm_storage_bytes: 1_000,
+        }
+    }
+
+    #[test]
+    fn allows_usage_up_to_the_limit() {
+        let usage = QuotaUsage { contracts: 24, versions_today: 0, wasm_storage_bytes: 400 };
+
+        assert!(check_quota(QuotaKind::Contracts, &limits(), &usage, 1).is_ok());
+        assert!(check_quota(QuotaKind::WasmStorageBytes, &limits(), &usage, 600).is_ok());
+    }
+
+    #[test]
+    fn reports_usage_and_tier_when_exceeded() {
+        let usage = QuotaUsage { contracts: 25, versions_today: 20, wasm_storage_bytes: 900 };
+
+        let err = check_quota(QuotaKind::Contracts, &limits(), &usage, 1).unwrap_err();
+        assert!(err.contains("25 of 25 contracts"));
+        assert!(err.contains("'free' tier"));
+
+        let err = check_quota(QuotaKind::WasmStorageBytes, &limits(), &usage, 200).unwrap_err();
+        assert!(err.contains("900 of 1000 bytes"));
+        assert!(err.contains("200 more"));
+    }
+}
@@ -0,0 +1,204 @@
+// api/src/quota_handlers.rs
+//
+// Publisher quota tiers and per-publisher adjustments.
+//
+// Routes (registered in quota_routes.rs):
+//   GET /api/quota-tiers             – list tiers
+//   PUT /api/quota-tiers/:name       – admin: create/update a tier
+//   GET /api/publishers/:id/quota    – effective limits and current usage
+//   PUT /api/publishers/:id/quota    – admin: set tier and limit overrides
+//
+// Limits are enforced in quota.rs on publish and wasm upload.
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    http::HeaderMap,
+    Json,
+};
+use shared::{PublisherQuotaStatus, QuotaTier, SetPublisherQuotaRequest, UpsertQuotaTierRequest};
+use uuid::Uuid;
+
+use crate::{
+    auth::require_admin,
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    quota::{effective_limits, usage},
+    state::AppState,
+};
+
+fn map_json_rejection(err: JsonRejection) -> ApiError {
+    ApiError::bad_request(
+        "InvalidRequest",
+        format!("Invalid JSON payload: {}", err.body_text()),
+    )
+}
+
+fn require_positive(limits: &[Option<i64>]) -> ApiResult<()> {
+    if limits.iter().flatten().any(|limit| *limit <= 0) {
+        return Err(ApiError::bad_request(
+            "InvalidQuota",
+            "Quota limits must be positive",
+        ));
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/quota-tiers
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn list_quota_tiers(State(state): State<AppState>) -> ApiResult<Json<Vec<QuotaTier>>> {
+    let tiers: Vec<QuotaTier> =
+        sqlx::query_as("SELECT * FROM quota_tiers ORDER BY max_contracts, name")
+            .fetch_all(&state.db)
+            .await
+            .map_err(|err| db_internal_error("list quota tiers", err))?;
+
+    Ok(Json(tiers))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// PUT /api/quota-tiers/:name
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn upsert_quota_tier(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    payload: Result<Json<UpsertQuotaTierRequest>, JsonRejection>,
+) -> ApiResult<Json<QuotaTier>> {
+    require_admin(&state.auth, &headers)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+
+    let name = name.trim().to_ascii_lowercase();
+    if name.is_empty() || name.len() > 50 {
+        return Err(ApiError::bad_request(
+            "InvalidTierName",
+            "Tier names must be 1-50 characters",
+        ));
+    }
+    require_positive(&[
+        Some(i64::from(req.max_contracts)),
+        Some(i64::from(req.max_versions_per_day)),
+        Some(req.max_wasm_storage_bytes),
+    ])?;
+
+    let tier: QuotaTier = sqlx::query_as(
+        "INSERT INTO quota_tiers (name, max_contracts, max_versions_per_day, max_wasm_storage_bytes)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (name) DO UPDATE SET
+             max_contracts          = EXCLUDED.max_contracts,
+             max_versions_per_day   = EXCLUDED.max_versions_per_day,
+             max_wasm_storage_bytes = EXCLUDED.max_wasm_storage_bytes,
+             updated_at             = NOW()
+         RETURNING *",
+    )
+    .bind(&name)
+    .bind(req.max_contracts)
+    .bind(req.max_versions_per_day)
+    .bind(req.max_wasm_storage_bytes)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("upsert quota tier", err))?;
+
+    tracing::info!(tier = %tier.name, "quota tier updated");
+
+    Ok(Json(tier))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/publishers/:id/quota
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn get_publisher_quota(
+    State(state): State<AppState>,
+    Path(publisher_id): Path<Uuid>,
+) -> ApiResult<Json<PublisherQuotaStatus>> {
+    quota_status(&state, publisher_id).await.map(Json)
+}
+
+async fn quota_status(state: &AppState, publisher_id: Uuid) -> ApiResult<PublisherQuotaStatus> {
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM publishers WHERE id = $1)")
+        .bind(publisher_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("check publisher exists", err))?;
+    if !exists {
+        return Err(ApiError::not_found(
+            "PublisherNotFound",
+            format!("No publisher found with ID: {}", publisher_id),
+        ));
+    }
+
+    let note: Option<String> =
+        sqlx::query_scalar("SELECT note FROM publisher_quotas WHERE publisher_id = $1")
+            .bind(publisher_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("get publisher quota note", err))?
+            .flatten();
+
+    Ok(PublisherQuotaStatus {
+        publisher_id,
+        limits: effective_limits(&state.db, publisher_id).await?,
+        usage: usage(&state.db, publisher_id).await?,
+        note,
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// PUT /api/publishers/:id/quota
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn set_publisher_quota(
+    State(state): State<AppState>,
+    Path(publisher_id): Path<Uuid>,
+    headers: HeaderMap,
+    payload: Result<Json<SetPublisherQuotaRequest>, JsonRejection>,
+) -> ApiResult<Json<PublisherQuotaStatus>> {
+    require_admin(&state.auth, &headers)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+
+    require_positive(&[
+        req.max_contracts.map(i64::from),
+        req.max_versions_per_day.map(i64::from),
+        req.max_wasm_storage_bytes,
+    ])?;
+
+    let tier = req.tier.trim().to_ascii_lowercase();
+    let tier_exists: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM quota_tiers WHERE name = $1)")
+            .bind(&tier)
+            .fetch_one(&state.db)
+            .await
+            .map_err(|err| db_internal_error("check quota tier exists", err))?;
+    if !tier_exists {
+        return Err(ApiError::bad_request(
+            "UnknownQuotaTier",
+            format!("No quota tier named '{}'", tier),
+        ));
+    }
+
+    sqlx::query(
+        "INSERT INTO publisher_quotas
+            (publisher_id, tier, max_contracts, max_versions_per_day, max_wasm_storage_bytes, note)
+         SELECT id, $2, $3, $4, $5, $6 FROM publishers WHERE id = $1
+         ON CONFLICT (publisher_id) DO UPDATE SET
+             tier                   = EXCLUDED.tier,
+             max_contracts          = EXCLUDED.max_contracts,
+             max_versions_per_day   = EXCLUDED.max_versions_per_day,
+             max_wasm_storage_bytes = EXCLUDED.max_wasm_storage_bytes,
+             note                   = EXCLUDED.note,
+             updated_at             = NOW()",
+    )
+    .bind(publisher_id)
+    .bind(&tier)
+    .bind(req.max_contracts)
+    .bind(req.max_versions_per_day)
+    .bind(req.max_wasm_storage_bytes)
+    .bind(&req.note)
+    .execute(&state.db)
+    .await
+    .map_err(|err| db_internal_error("set publisher quota", err))?;
+
+    tracing::info!(publisher_id = %publisher_id, tier = %tier, "publisher quota adjusted");
+
+    // Also reports 404 if the publisher does not exist
+    quota_status(&state, publisher_id).await.map(Json)
+}
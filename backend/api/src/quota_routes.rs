@@ -0,0 +1,22 @@
+// api/src/quota_routes.rs
+// Publisher quota routes.
+
+use axum::{
+    routing::{get, put},
+    Router,
+};
+
+use crate::{quota_handlers, state::AppState};
+
+pub fn quota_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/quota-tiers", get(quota_handlers::list_quota_tiers))
+        .route(
+            "/api/quota-tiers/:name",
+            put(quota_handlers::upsert_quota_tier),
+        )
+        .route(
+            "/api/publishers/:id/quota",
+            get(quota_handlers::get_publisher_quota).put(quota_handlers::set_publisher_quota),
+        )
+}
@@ -17,13 +17,9 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
+use registry_config::RateLimitSettings;
 use serde_json::json;
 
-const DEFAULT_READ_LIMIT_PER_MINUTE: u32 = 100;
-const DEFAULT_WRITE_LIMIT_PER_MINUTE: u32 = 20;
-const DEFAULT_AUTH_LIMIT_PER_MINUTE: u32 = 1_000;
-const DEFAULT_HEALTH_LIMIT_PER_MINUTE: u32 = 10_000;
-const DEFAULT_WINDOW_SECONDS: u64 = 60;
 const ENDPOINT_LIMIT_ENV_PREFIX: &str = "RATE_LIMIT_ENDPOINT_";
 
 const HEADER_RATE_LIMIT_LIMIT: HeaderName = HeaderName::from_static("x-ratelimit-limit");
@@ -37,8 +33,8 @@ pub struct RateLimitState {
 }
 
 impl RateLimitState {
-    pub fn from_env() -> Self {
-        Self::new(RateLimitConfig::from_env())
+    pub fn from_settings(settings: &RateLimitSettings) -> Self {
+        Self::new(RateLimitConfig::from_settings(settings))
     }
 
     fn new(config: RateLimitConfig) -> Self {
@@ -131,18 +127,12 @@ struct RateLimitConfig {
 }
 
 impl RateLimitConfig {
-    fn from_env() -> Self {
-        let read_limit = env_u32("RATE_LIMIT_READ_PER_MINUTE", DEFAULT_READ_LIMIT_PER_MINUTE);
-        let write_limit = env_u32(
-            "RATE_LIMIT_WRITE_PER_MINUTE",
-            DEFAULT_WRITE_LIMIT_PER_MINUTE,
-        );
-        let auth_limit = env_u32("RATE_LIMIT_AUTH_PER_MINUTE", DEFAULT_AUTH_LIMIT_PER_MINUTE);
-        let health_limit = env_u32(
-            "RATE_LIMIT_HEALTH_PER_MINUTE",
-            DEFAULT_HEALTH_LIMIT_PER_MINUTE,
-        );
-        let window_seconds = env_u64("RATE_LIMIT_WINDOW_SECONDS", DEFAULT_WINDOW_SECONDS).max(1);
+    fn from_settings(settings: &RateLimitSettings) -> Self {
+        let read_limit = settings.read_per_minute;
+        let write_limit = settings.write_per_minute;
+        let auth_limit = settings.auth_per_minute;
+        let health_limit = settings.health_per_minute;
+        let window_seconds = settings.window_seconds.max(1);
 
         let mut endpoint_limits = HashMap::new();
         for (key, value) in env::vars() {
@@ -187,7 +177,7 @@ impl RateLimitConfig {
         Self {
             read_limit,
             write_limit,
-            auth_limit: DEFAULT_AUTH_LIMIT_PER_MINUTE,
+            auth_limit: RateLimitSettings::default().auth_per_minute,
             health_limit,
             window,
             endpoint_limits: HashMap::new(),
@@ -326,32 +316,6 @@ fn endpoint_key(method: &Method, path: &str) -> String {
     }
 }
 
-fn env_u32(key: &str, default: u32) -> u32 {
-    match env::var(key) {
-        Ok(raw) => match raw.parse::<u32>() {
-            Ok(value) if value > 0 => value,
-            _ => {
-                tracing::warn!("Invalid value for {key} (`{raw}`), using default {default}");
-                default
-            }
-        },
-        Err(_) => default,
-    }
-}
-
-fn env_u64(key: &str, default: u64) -> u64 {
-    match env::var(key) {
-        Ok(raw) => match raw.parse::<u64>() {
-            Ok(value) if value > 0 => value,
-            _ => {
-                tracing::warn!("Invalid value for {key} (`{raw}`), using default {default}");
-                default
-            }
-        },
-        Err(_) => default,
-    }
-}
-
 fn ceil_duration_to_seconds(duration: Duration) -> u64 {
     let secs = duration.as_secs();
     if duration.subsec_nanos() > 0 {
@@ -3,7 +3,7 @@
 
 use std::collections::HashMap;
 use crate::checklist::all_checks;
-use shared::models::{AuditCheckRow, CategoryScore, CheckStatus, ChecklistItem, DetectionMethod, Severity};
+use crate::models::{AuditCheckRow, CategoryScore, CheckStatus, ChecklistItem, DetectionMethod, Severity};
 
 pub fn severity_weight(sev: &Severity) -> f64 {
     match sev {
@@ -117,6 +117,7 @@ pub fn score_badge(score: f64) -> &'static str {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn build_markdown_report(
     contract_name: &str,
     contract_id: &str,
@@ -129,8 +130,6 @@ pub fn build_markdown_report(
     failures_only: bool,
 ) -> String {
     let all = all_checks();
-    // index by id as &str for lookup
-    let meta: HashMap<&str, &ChecklistItem> = all.iter().map(|c| (c.id.as_str(), c)).collect();
     let status_map: HashMap<&str, &AuditCheckRow> =
         check_rows.iter().map(|r| (r.check_id.as_str(), r)).collect();
 
@@ -0,0 +1,125 @@
+// api/src/search_handlers.rs
+//
+// Search-as-you-type suggestions.
+//
+// Routes (registered in search_routes.rs):
+//   GET /api/contracts/autocomplete?q=tok&limit=10
+//
+// Prefix matches on contract and tag names rank first; pg_trgm word
+// similarity fills in fuzzy matches so small typos still produce
+// suggestions.  Both are served by the trigram indexes from the
+// search_autocomplete migration.
+
+use axum::{
+    extract::{rejection::QueryRejection, Query, State},
+    Json,
+};
+use shared::{AutocompleteParams, AutocompleteSuggestion};
+
+use crate::{
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    state::AppState,
+};
+
+/// Minimum pg_trgm word similarity for a fuzzy match.  Low enough that a
+/// single-character typo in a short word (`tokne` → `token`) still matches.
+/// Set as `pg_trgm.word_similarity_threshold` on every pooled connection, so
+/// queries filter with `<%` and the trigram indexes on `lower(name)` apply.
+pub(crate) const FUZZY_MATCH_THRESHOLD: f32 = 0.4;
+
+const MAX_QUERY_LEN: usize = 64;
+
+fn map_query_rejection(err: QueryRejection) -> ApiError {
+    ApiError::bad_request(
+        "InvalidQuery",
+        format!("Invalid query parameters: {}", err.body_text()),
+    )
+}
+
+/// Escape `%`, `_` and `\` so user input is matched literally by LIKE.
+pub(crate) fn escape_like(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Trim and lowercase the autocomplete term, rejecting empty or oversized input.
+fn normalize_term(q: &str) -> ApiResult<String> {
+    let term = q.trim().to_lowercase();
+    if term.is_empty() {
+        return Err(ApiError::bad_request("InvalidQuery", "q must not be empty"));
+    }
+    if term.chars().count() > MAX_QUERY_LEN {
+        return Err(ApiError::bad_request(
+            "InvalidQuery",
+            format!("q must be at most {} characters", MAX_QUERY_LEN),
+        ));
+    }
+    Ok(term)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/contracts/autocomplete
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn autocomplete(
+    State(state): State<AppState>,
+    params: Result<Query<AutocompleteParams>, QueryRejection>,
+) -> ApiResult<Json<Vec<AutocompleteSuggestion>>> {
+    let Query(params) = params.map_err(map_query_rejection)?;
+    let term = normalize_term(&params.q)?;
+    let limit = params.limit.unwrap_or(10).clamp(1, 25);
+
+    let suggestions: Vec<AutocompleteSuggestion> = sqlx::query_as(
+        r#"SELECT kind, value, contract_id, score FROM (
+               SELECT 'contract' AS kind, name AS value, id AS contract_id,
+                      CASE WHEN lower(name) LIKE $2 ESCAPE '\' THEN 1.0
+                           ELSE word_similarity($1, lower(name)) END::REAL AS score,
+                      0 AS popularity
+                 FROM contracts
+                WHERE lower(name) LIKE $2 ESCAPE '\'
+                   OR $1 <% lower(name)
+               UNION ALL
+               SELECT 'tag', name, NULL,
+                      CASE WHEN lower(name) LIKE $2 ESCAPE '\' THEN 1.0
+                           ELSE word_similarity($1, lower(name)) END::REAL,
+                      usage_count
+                 FROM tags
+                WHERE lower(name) LIKE $2 ESCAPE '\'
+                   OR $1 <% lower(name)
+           ) suggestions
+           ORDER BY score DESC, popularity DESC, length(value), value
+           LIMIT $3"#,
+    )
+    .bind(&term)
+    .bind(format!("{}%", escape_like(&term)))
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("autocomplete", err))?;
+
+    Ok(Json(suggestions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_like_wildcards() {
+        assert_eq!(escape_like("50%_off\\"), "50\\%\\_off\\\\");
+        assert_eq!(escape_like("token"), "token");
+    }
+
+    #[test]
+    fn normalizes_autocomplete_term() {
+        assert_eq!(normalize_term("  ToK ").unwrap(), "tok");
+        assert!(normalize_term("   ").is_err());
+        assert!(normalize_term(&"a".repeat(MAX_QUERY_LEN + 1)).is_err());
+    }
+}
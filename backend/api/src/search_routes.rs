@@ -0,0 +1,13 @@
+// api/src/search_routes.rs
+// Search-as-you-type routes.
+
+use axum::{routing::get, Router};
+
+use crate::{search_handlers, state::AppState};
+
+pub fn search_routes() -> Router<AppState> {
+    Router::new().route(
+        "/api/contracts/autocomplete",
+        get(search_handlers::autocomplete),
+    )
+}
@@ -1,7 +1,11 @@
 use std::time::Instant;
 use std::sync::Arc;
 use sqlx::PgPool;
+use crate::auth::AuthKeys;
+use crate::auth_providers::{configured_providers, AuthProvider};
 use crate::cache::{CacheLayer, CacheConfig};
+use crate::object_storage::{ObjectStorage, ObjectStorageConfig};
+use registry_config::ApiConfig;
 
 /// Application state shared across handlers
 #[derive(Clone)]
@@ -9,15 +13,23 @@ pub struct AppState {
     pub db: PgPool,
     pub started_at: Instant,
     pub cache: Arc<CacheLayer>,
+    /// S3-compatible artifact storage; `None` when not configured
+    pub storage: Option<Arc<ObjectStorage>>,
+    pub auth: Arc<AuthKeys>,
+    /// External login providers enabled in the config
+    pub auth_providers: Arc<[Arc<dyn AuthProvider>]>,
 }
 
 impl AppState {
-    pub fn new(db: PgPool) -> Self {
-        let config = CacheConfig::from_env();
+    pub fn new(db: PgPool, config: &ApiConfig) -> Self {
         Self {
             db,
             started_at: Instant::now(),
-            cache: Arc::new(CacheLayer::new(config)),
+            cache: Arc::new(CacheLayer::new(CacheConfig::from_settings(&config.cache))),
+            storage: ObjectStorageConfig::from_settings(&config.storage)
+                .map(|c| Arc::new(ObjectStorage::new(c))),
+            auth: Arc::new(AuthKeys::from_config(config)),
+            auth_providers: configured_providers(&config.login).into(),
         }
     }
 }
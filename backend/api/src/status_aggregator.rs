@@ -0,0 +1,145 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::PgPool;
+use tokio::time;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::state::AppState;
+
+/// Seconds between status snapshots.
+pub const SNAPSHOT_INTERVAL_SECS: i64 = 60;
+
+/// Snapshots older than this are pruned; also the longest uptime window.
+const RETENTION_DAYS: i64 = 30;
+
+/// Main loop for the status aggregator background task.
+///
+/// Every minute, records a snapshot of registry health for the public status
+/// page. Uptime is the share of expected snapshots that were actually written,
+/// so periods where the API (or its database) was down count against it.
+pub async fn run_status_aggregator(state: AppState) {
+    info!("Starting status aggregator background task");
+
+    let mut interval = time::interval(time::Duration::from_secs(SNAPSHOT_INTERVAL_SECS as u64));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = record_snapshot(&state.db).await {
+            error!("Error recording status snapshot: {}", e);
+        }
+    }
+}
+
+async fn record_snapshot(pool: &PgPool) -> Result<()> {
+    let started = std::time::Instant::now();
+    sqlx::query("SELECT 1").execute(pool).await?;
+    let db_latency_ms = started.elapsed().as_millis().min(i32::MAX as u128) as i32;
+
+    let now = Utc::now();
+    let (count_24h, count_7d, count_30d, first_taken_at): (i64, i64, i64, Option<DateTime<Utc>>) =
+        sqlx::query_as(
+            "SELECT COUNT(*) FILTER (WHERE taken_at > $1 - INTERVAL '24 hours'),
+                    COUNT(*) FILTER (WHERE taken_at > $1 - INTERVAL '7 days'),
+                    COUNT(*) FILTER (WHERE taken_at > $1 - INTERVAL '30 days'),
+                    MIN(taken_at)
+               FROM status_snapshots",
+        )
+        .bind(now)
+        .fetch_one(pool)
+        .await?;
+    // Tracking starts with the first snapshot ever written
+    let first_taken_at = first_taken_at.unwrap_or(now);
+    let uptime = |count: i64, window: Duration| {
+        // +1 for the snapshot being written now
+        uptime_percent(count + 1, covered_secs(now, window, first_taken_at), SNAPSHOT_INTERVAL_SECS)
+    };
+
+    let (queue_depth, avg_turnaround): (i64, Option<f64>) = sqlx::query_as(
+        "SELECT COUNT(*) FILTER (WHERE status = 'pending'),
+                (AVG(EXTRACT(EPOCH FROM verified_at - created_at))
+                    FILTER (WHERE verified_at > NOW() - INTERVAL '7 days'))::DOUBLE PRECISION
+           FROM verifications",
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let mut tx = pool.begin().await?;
+
+    let snapshot_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO status_snapshots
+            (taken_at, db_latency_ms, uptime_24h, uptime_7d, uptime_30d,
+             verification_queue_depth, avg_verification_turnaround_secs)
+         VALUES ($1, $2, $3, $4, $5, $6, $7)
+         RETURNING id",
+    )
+    .bind(now)
+    .bind(db_latency_ms)
+    .bind(uptime(count_24h, Duration::hours(24)))
+    .bind(uptime(count_7d, Duration::days(7)))
+    .bind(uptime(count_30d, Duration::days(RETENTION_DAYS)))
+    .bind(queue_depth)
+    .bind(avg_turnaround)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // Indexing lag: age of the newest indexed interaction on each network
+    sqlx::query(
+        "INSERT INTO status_snapshot_networks (snapshot_id, network, last_indexed_at, lag_seconds)
+         SELECT $1, n.network, MAX(ci.created_at),
+                EXTRACT(EPOCH FROM $2 - MAX(ci.created_at))::BIGINT
+           FROM unnest(enum_range(NULL::network_type)) AS n(network)
+           LEFT JOIN contracts c ON c.network = n.network
+           LEFT JOIN contract_interactions ci ON ci.contract_id = c.id
+          GROUP BY n.network",
+    )
+    .bind(snapshot_id)
+    .bind(now)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query("DELETE FROM status_snapshots WHERE taken_at < $1")
+        .bind(now - Duration::days(RETENTION_DAYS))
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Seconds of `window` (ending at `now`) during which snapshots were expected.
+fn covered_secs(now: DateTime<Utc>, window: Duration, first_taken_at: DateTime<Utc>) -> i64 {
+    let start = (now - window).max(first_taken_at);
+    (now - start).num_seconds().max(0)
+}
+
+/// Percentage of expected snapshots present over `covered_secs`, capped at 100.
+pub fn uptime_percent(samples: i64, covered_secs: i64, interval_secs: i64) -> f64 {
+    let expected = covered_secs / interval_secs + 1;
+    (samples as f64 * 100.0 / expected as f64).min(100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uptime_counts_missing_snapshots_as_downtime() {
+        // One hour at one snapshot a minute expects 61 samples
+        assert_eq!(uptime_percent(61, 3600, 60), 100.0);
+        assert!((uptime_percent(55, 3600, 60) - 90.16).abs() < 0.01);
+        // Tick jitter can produce an extra sample
+        assert_eq!(uptime_percent(62, 3600, 60), 100.0);
+    }
+
+    #[test]
+    fn uptime_window_starts_at_first_snapshot() {
+        let now = Utc::now();
+        let first = now - Duration::hours(2);
+
+        assert_eq!(covered_secs(now, Duration::hours(24), first), 7200);
+        assert_eq!(covered_secs(now, Duration::hours(1), first), 3600);
+        assert_eq!(uptime_percent(1, covered_secs(now, Duration::days(7), now), 60), 100.0);
+    }
+}
@@ -0,0 +1,64 @@
+// api/src/status_handlers.rs
+//
+// Public status page data.
+//
+// Routes (registered in status_routes.rs):
+//   GET /api/status  – latest snapshot: uptime, indexing lag, verification queue
+//
+// Snapshots are written by status_aggregator.rs.
+
+use axum::{extract::State, http::StatusCode, Json};
+use chrono::Utc;
+use shared::{NetworkIndexingLag, StatusResponse, StatusSnapshot};
+
+use crate::{
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    state::AppState,
+    status_aggregator::SNAPSHOT_INTERVAL_SECS,
+};
+
+/// Snapshots older than this many intervals mark the status as stale.
+const STALE_AFTER_INTERVALS: i64 = 5;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/status
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn get_status(State(state): State<AppState>) -> ApiResult<Json<StatusResponse>> {
+    let snapshot: StatusSnapshot =
+        sqlx::query_as("SELECT * FROM status_snapshots ORDER BY taken_at DESC LIMIT 1")
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("fetch status snapshot", err))?
+            .ok_or_else(|| {
+                ApiError::new(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "StatusUnavailable",
+                    "No status snapshot has been recorded yet",
+                )
+            })?;
+
+    let indexing: Vec<NetworkIndexingLag> = sqlx::query_as(
+        "SELECT network, last_indexed_at, lag_seconds
+           FROM status_snapshot_networks
+          WHERE snapshot_id = $1
+          ORDER BY network",
+    )
+    .bind(snapshot.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch indexing lag", err))?;
+
+    let age = (Utc::now() - snapshot.taken_at).num_seconds();
+    let status = if age > SNAPSHOT_INTERVAL_SECS * STALE_AFTER_INTERVALS {
+        "stale"
+    } else {
+        "operational"
+    };
+
+    Ok(Json(StatusResponse {
+        status: status.to_string(),
+        snapshot,
+        indexing,
+    }))
+}
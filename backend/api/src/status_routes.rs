@@ -0,0 +1,10 @@
+// api/src/status_routes.rs
+// Public status page routes.
+
+use axum::{routing::get, Router};
+
+use crate::{state::AppState, status_handlers};
+
+pub fn status_routes() -> Router<AppState> {
+    Router::new().route("/api/status", get(status_handlers::get_status))
+}
@@ -0,0 +1,248 @@
+// api/src/verification_tier_handlers.rs
+//
+// Graduated verification tiers per contract version.
+//
+// Routes (registered in verification_tier_routes.rs):
+//   PUT /api/contracts/:id/versions/:version/verification-tier – named admin: attest a tier
+//
+// The tier of the most recent version is mirrored onto `contracts` so search
+// can filter on it, and `is_verified` follows it.  L3 (audit) can only be
+// attested once a registered auditor has filed a report on that version
+// (audit_report_handlers.rs) in which every critical and high check passed or
+// was ruled out; a bare security audit, which anyone can create, does not
+// count.
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    http::HeaderMap,
+    Json,
+};
+use shared::{ContractVersion, SetVerificationTierRequest, VerificationTier};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::{
+    auth::require_admin,
+    checklist::all_checks,
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    models::{AuditCheckRow, CheckStatus, Severity},
+    state::AppState,
+};
+
+fn map_json_rejection(err: JsonRejection) -> ApiError {
+    ApiError::bad_request(
+        "InvalidRequest",
+        format!("Invalid JSON payload: {}", err.body_text()),
+    )
+}
+
+/// Most severe checklist level an audit report may leave failed or
+/// unassessed and still support the audit tier.
+const MAX_OPEN_SEVERITY: Severity = Severity::Medium;
+
+/// Whether the checks of an ingested audit report support the audit tier:
+/// every check above `MAX_OPEN_SEVERITY` must have passed or been ruled not
+/// applicable.
+fn audit_passes(checks: &[AuditCheckRow]) -> bool {
+    all_checks()
+        .iter()
+        .filter(|item| item.severity > MAX_OPEN_SEVERITY)
+        .all(|item| {
+            checks.iter().any(|row| {
+                row.check_id == item.id.as_str()
+                    && matches!(row.status, CheckStatus::Passed | CheckStatus::NotApplicable)
+            })
+        })
+}
+
+/// Checks of the most recent auditor's report on a contract version, if
+/// there is one.
+async fn reported_audit_checks(
+    conn: &mut PgConnection,
+    contract_version_id: Uuid,
+) -> ApiResult<Option<Vec<AuditCheckRow>>> {
+    let audit_id: Option<Uuid> = sqlx::query_scalar(
+        "SELECT audit_id FROM audit_reports WHERE contract_version_id = $1
+          ORDER BY created_at DESC LIMIT 1",
+    )
+    .bind(contract_version_id)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|err| db_internal_error("get latest audit report", err))?;
+    let Some(audit_id) = audit_id else {
+        return Ok(None);
+    };
+
+    let checks = sqlx::query_as("SELECT * FROM audit_checks WHERE audit_id = $1")
+        .bind(audit_id)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|err| db_internal_error("load audit checks", err))?;
+    Ok(Some(checks))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// PUT /api/contracts/:id/versions/:version/verification-tier
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn set_verification_tier(
+    State(state): State<AppState>,
+    Path((id, version)): Path<(Uuid, String)>,
+    headers: HeaderMap,
+    payload: Result<Json<SetVerificationTierRequest>, JsonRejection>,
+) -> ApiResult<Json<ContractVersion>> {
+    let attested_by = require_admin(&state.auth, &headers)?.require_named()?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin transaction", err))?;
+
+    let version_id: Uuid = sqlx::query_scalar(
+        "SELECT id FROM contract_versions WHERE contract_id = $1 AND version = $2 FOR UPDATE",
+    )
+    .bind(id)
+    .bind(&version)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("get contract version", err))?
+    .ok_or_else(|| {
+        ApiError::not_found(
+            "VersionNotFound",
+            format!("No version {} found for contract {}", version, id),
+        )
+    })?;
+
+    if req.tier == VerificationTier::Audit {
+        match reported_audit_checks(&mut tx, version_id).await? {
+            None => {
+                return Err(ApiError::bad_request(
+                    "AuditRequired",
+                    "The audit tier requires an auditor's report on file for this version",
+                ))
+            }
+            Some(checks) if !audit_passes(&checks) => {
+                return Err(ApiError::bad_request(
+                    "AuditNotPassed",
+                    "The latest audit report leaves critical or high severity checks failed or unassessed",
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+
+    let updated: ContractVersion = sqlx::query_as(
+        "UPDATE contract_versions SET verification_tier = $2 WHERE id = $1 RETURNING *",
+    )
+    .bind(version_id)
+    .bind(req.tier)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("update version verification tier", err))?;
+
+    sqlx::query(
+        "INSERT INTO verification_tier_attestations (contract_version_id, tier, attested_by, evidence_url)
+         VALUES ($1, $2, $3, $4)",
+    )
+    .bind(updated.id)
+    .bind(req.tier)
+    .bind(&attested_by)
+    .bind(&req.evidence_url)
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("record tier attestation", err))?;
+
+    // Mirror the latest version's tier onto the contract
+    sqlx::query(
+        "UPDATE contracts c
+            SET verification_tier = v.verification_tier,
+                is_verified = v.verification_tier > 'unverified'
+           FROM (SELECT verification_tier FROM contract_versions
+                  WHERE contract_id = $1
+                  ORDER BY created_at DESC LIMIT 1) v
+          WHERE c.id = $1",
+    )
+    .bind(id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("sync contract verification tier", err))?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit verification tier", err))?;
+
+    tracing::info!(
+        contract_id = %id,
+        version = %version,
+        tier = %req.tier,
+        attested_by = %attested_by,
+        "verification tier attested"
+    );
+
+    Ok(Json(updated))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn check(check_id: &str, status: CheckStatus) -> AuditCheckRow {
+        AuditCheckRow {
+            id: Uuid::new_v4(),
+            audit_id: Uuid::nil(),
+            check_id: check_id.to_string(),
+            status,
+            notes: None,
+            auto_detected: false,
+            evidence: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// A report that assessed every check, with `overrides` applied.
+    fn report(overrides: &[(&str, CheckStatus)]) -> Vec<AuditCheckRow> {
+        all_checks()
+            .iter()
+            .map(|item| {
+                let status = overrides
+                    .iter()
+                    .find(|(id, _)| *id == item.id.as_str())
+                    .map_or(CheckStatus::Passed, |(_, status)| status.clone());
+                check(item.id.as_str(), status)
+            })
+            .collect()
+    }
+
+    fn id_with_severity(severity: Severity) -> String {
+        all_checks()
+            .into_iter()
+            .find(|item| item.severity == severity)
+            .expect("checklist has an item of every severity")
+            .id
+            .to_string()
+    }
+
+    #[test]
+    fn open_critical_or_high_checks_fail_the_audit() {
+        let critical = id_with_severity(Severity::Critical);
+        let high = id_with_severity(Severity::High);
+        let medium = id_with_severity(Severity::Medium);
+
+        assert!(audit_passes(&report(&[])));
+        assert!(audit_passes(&report(&[(&medium, CheckStatus::Failed)])));
+        assert!(audit_passes(&report(&[(&high, CheckStatus::NotApplicable)])));
+        assert!(!audit_passes(&report(&[(&critical, CheckStatus::Failed)])));
+        assert!(!audit_passes(&report(&[(&high, CheckStatus::Failed)])));
+        assert!(!audit_passes(&report(&[(&high, CheckStatus::Pending)])));
+    }
+
+    #[test]
+    fn a_single_passed_finding_is_not_an_audit() {
+        let critical = id_with_severity(Severity::Critical);
+        assert!(!audit_passes(&[check(&critical, CheckStatus::Passed)]));
+        assert!(!audit_passes(&[]));
+    }
+}
@@ -0,0 +1,13 @@
+// api/src/verification_tier_routes.rs
+// Verification tier attestation routes.
+
+use axum::{routing::put, Router};
+
+use crate::{state::AppState, verification_tier_handlers};
+
+pub fn verification_tier_routes() -> Router<AppState> {
+    Router::new().route(
+        "/api/contracts/:id/versions/:version/verification-tier",
+        put(verification_tier_handlers::set_verification_tier),
+    )
+}
@@ -0,0 +1,82 @@
+// api/src/wasm_analysis.rs
+//
+// Runs `shared::wasm_features` over completed wasm uploads and records the
+// result per wasm hash.  The minimum protocol is mirrored onto every
+// contract version using that wasm, and onto contracts whose latest version
+// it is, so search results can carry it without a join.
+
+use anyhow::{Context, Result};
+use shared::wasm_features::{self, WasmFeatures};
+use sqlx::PgPool;
+
+use crate::object_storage::ObjectStorage;
+
+/// Download, analyse and store the features of a completed wasm upload.
+pub async fn analyze_upload(
+    pool: &PgPool,
+    storage: &ObjectStorage,
+    object_key: &str,
+    sha256: &str,
+) -> Result<WasmFeatures> {
+    let wasm = storage
+        .get_object(object_key)
+        .await?
+        .context("wasm object disappeared from storage")?;
+    let features = wasm_features::analyze(&wasm)?;
+    record(pool, sha256, &features).await?;
+    Ok(features)
+}
+
+async fn record(pool: &PgPool, sha256: &str, features: &WasmFeatures) -> Result<()> {
+    let min_protocol = features.min_protocol_version.map(|v| v as i32);
+    let host_functions: Vec<String> = features
+        .host_functions
+        .iter()
+        .map(|f| format!("{}.{}", f.module, f.name))
+        .collect();
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        "INSERT INTO wasm_features (wasm_sha256, min_protocol_version, pre_release, host_functions, features)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (wasm_sha256) DO UPDATE SET
+             min_protocol_version = EXCLUDED.min_protocol_version,
+             pre_release          = EXCLUDED.pre_release,
+             host_functions       = EXCLUDED.host_functions,
+             features             = EXCLUDED.features,
+             analyzed_at          = NOW()",
+    )
+    .bind(sha256)
+    .bind(min_protocol)
+    .bind(features.pre_release.map(|v| v as i32))
+    .bind(&host_functions)
+    .bind(&features.features)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "UPDATE contract_versions SET min_protocol_version = $2 WHERE lower(wasm_hash) = $1",
+    )
+    .bind(sha256)
+    .bind(min_protocol)
+    .execute(&mut *tx)
+    .await?;
+
+    // Mirror onto contracts whose latest version uses this wasm
+    sqlx::query(
+        "UPDATE contracts c
+            SET min_protocol_version = $2
+          WHERE lower(c.wasm_hash) = $1
+             OR lower((SELECT wasm_hash FROM contract_versions
+                        WHERE contract_id = c.id
+                        ORDER BY created_at DESC LIMIT 1)) = $1",
+    )
+    .bind(sha256)
+    .bind(min_protocol)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
@@ -0,0 +1,134 @@
+// api/src/wasm_feature_handlers.rs
+//
+// Wasm feature detection results and per-network protocol support.
+//
+// Routes (registered in wasm_feature_routes.rs):
+//   GET /api/contracts/:id/versions/:version/features – host functions and minimum protocol
+//   GET /api/networks/protocols                      – protocol each network runs
+//   PUT /api/networks/:network/protocol              – admin: record a network upgrade
+//
+// Features are detected when a wasm upload completes (see wasm_analysis.rs).
+// Clients compare a contract's `min_protocol_version` with its network's
+// protocol to warn about contracts the network cannot run yet.
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    http::HeaderMap,
+    Json,
+};
+use shared::{Network, NetworkProtocol, SetNetworkProtocolRequest, WasmFeatureReport};
+use uuid::Uuid;
+
+use crate::{
+    auth::require_admin,
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    state::AppState,
+};
+
+fn map_json_rejection(err: JsonRejection) -> ApiError {
+    ApiError::bad_request(
+        "InvalidRequest",
+        format!("Invalid JSON payload: {}", err.body_text()),
+    )
+}
+
+fn parse_network(network: &str) -> ApiResult<Network> {
+    match network.to_ascii_lowercase().as_str() {
+        "mainnet" => Ok(Network::Mainnet),
+        "testnet" => Ok(Network::Testnet),
+        "futurenet" => Ok(Network::Futurenet),
+        _ => Err(ApiError::bad_request(
+            "InvalidNetwork",
+            format!("Unknown network: {}", network),
+        )),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/contracts/:id/versions/:version/features
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn get_version_features(
+    State(state): State<AppState>,
+    Path((id, version)): Path<(Uuid, String)>,
+) -> ApiResult<Json<WasmFeatureReport>> {
+    let report: Option<WasmFeatureReport> = sqlx::query_as(
+        "SELECT f.*
+           FROM contract_versions v
+           JOIN wasm_features f ON f.wasm_sha256 = lower(v.wasm_hash)
+          WHERE v.contract_id = $1 AND v.version = $2",
+    )
+    .bind(id)
+    .bind(&version)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("get wasm features", err))?;
+
+    report.map(Json).ok_or_else(|| {
+        ApiError::not_found(
+            "FeaturesNotFound",
+            format!(
+                "No wasm analysis recorded for version {} of contract {}",
+                version, id
+            ),
+        )
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GET /api/networks/protocols
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn list_network_protocols(
+    State(state): State<AppState>,
+) -> ApiResult<Json<Vec<NetworkProtocol>>> {
+    let protocols: Vec<NetworkProtocol> =
+        sqlx::query_as("SELECT * FROM network_protocols ORDER BY network")
+            .fetch_all(&state.db)
+            .await
+            .map_err(|err| db_internal_error("list network protocols", err))?;
+
+    Ok(Json(protocols))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// PUT /api/networks/:network/protocol
+// ─────────────────────────────────────────────────────────────────────────────
+pub async fn set_network_protocol(
+    State(state): State<AppState>,
+    Path(network): Path<String>,
+    headers: HeaderMap,
+    payload: Result<Json<SetNetworkProtocolRequest>, JsonRejection>,
+) -> ApiResult<Json<NetworkProtocol>> {
+    require_admin(&state.auth, &headers)?;
+    let Json(req) = payload.map_err(map_json_rejection)?;
+    let network = parse_network(&network)?;
+
+    if req.protocol_version <= 0 {
+        return Err(ApiError::bad_request(
+            "InvalidProtocolVersion",
+            "Protocol versions must be positive",
+        ));
+    }
+
+    let protocol: NetworkProtocol = sqlx::query_as(
+        "INSERT INTO network_protocols (network, protocol_version)
+         VALUES ($1, $2)
+         ON CONFLICT (network) DO UPDATE SET
+             protocol_version = EXCLUDED.protocol_version,
+             updated_at       = NOW()
+         RETURNING *",
+    )
+    .bind(&network)
+    .bind(req.protocol_version)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("set network protocol", err))?;
+
+    tracing::info!(
+        network = ?protocol.network,
+        protocol_version = protocol.protocol_version,
+        "network protocol updated"
+    );
+
+    Ok(Json(protocol))
+}
@@ -0,0 +1,25 @@
+// api/src/wasm_feature_routes.rs
+// Wasm feature detection and network protocol routes.
+
+use axum::{
+    routing::{get, put},
+    Router,
+};
+
+use crate::{state::AppState, wasm_feature_handlers};
+
+pub fn wasm_feature_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/contracts/:id/versions/:version/features",
+            get(wasm_feature_handlers::get_version_features),
+        )
+        .route(
+            "/api/networks/protocols",
+            get(wasm_feature_handlers::list_network_protocols),
+        )
+        .route(
+            "/api/networks/:network/protocol",
+            put(wasm_feature_handlers::set_network_protocol),
+        )
+}
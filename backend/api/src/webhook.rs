@@ -0,0 +1,152 @@
+// api/src/webhook.rs
+//
+// Guard for webhook URLs supplied by publishers.
+//
+// Webhooks must be https and resolve only to public addresses, so a
+// registered URL cannot reach the registry's own network.  The check runs
+// when the URL is saved and again before every delivery; deliveries connect
+// to the address that was checked (no second lookup) and do not follow
+// redirects.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+
+use reqwest::Url;
+
+/// Parse a webhook URL, requiring https and a host name or address.
+pub fn parse_webhook_url(raw: &str) -> Result<Url, String> {
+    let url = Url::parse(raw.trim()).map_err(|err| format!("not a valid URL: {}", err))?;
+    if url.scheme() != "https" {
+        return Err("webhook URLs must use https".to_string());
+    }
+    if url.host_str().is_none() {
+        return Err("webhook URL has no host".to_string());
+    }
+    if !url.username().is_empty() || url.password().is_some() {
+        return Err("webhook URLs may not carry credentials".to_string());
+    }
+    Ok(url)
+}
+
+/// Resolve the URL's host, failing unless every address is public.
+pub async fn resolve_public(url: &Url) -> Result<SocketAddr, String> {
+    let host = url.host_str().ok_or("webhook URL has no host")?;
+    let port = url.port_or_known_default().unwrap_or(443);
+    // Bracketed IPv6 literals resolve as the bare address
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|err| format!("cannot resolve {}: {}", host, err))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!("{} does not resolve", host));
+    }
+    if let Some(addr) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+        return Err(format!(
+            "{} resolves to non-public address {}",
+            host,
+            addr.ip()
+        ));
+    }
+    Ok(addrs[0])
+}
+
+/// Check a webhook URL end to end, returning it with the address to use.
+pub async fn check_webhook_url(raw: &str) -> Result<(Url, SocketAddr), String> {
+    let url = parse_webhook_url(raw)?;
+    let addr = resolve_public(&url).await?;
+    Ok((url, addr))
+}
+
+/// A client that connects `url`'s host only to `addr` and never redirects.
+pub fn pinned_client(url: &Url, addr: SocketAddr) -> reqwest::Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .redirect(reqwest::redirect::Policy::none());
+    if let Some(host) = url.domain() {
+        builder = builder.resolve(host, addr);
+    }
+    builder.build()
+}
+
+/// Whether an address is publicly routable.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_public_ipv4(mapped),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        // 0.0.0.0/8, carrier-grade NAT 100.64.0.0/10, benchmarking 198.18.0.0/15,
+        // reserved 240.0.0.0/4
+        || a == 0
+        || (a == 100 && (64..128).contains(&b))
+        || (a == 198 && (18..20).contains(&b))
+        || a >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        // unique local fc00::/7, link-local fe80::/10, documentation 2001:db8::/32
+        || (first & 0xfe00) == 0xfc00
+        || (first & 0xffc0) == 0xfe80
+        || (first == 0x2001 && ip.segments()[1] == 0x0db8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_only_https_urls_without_credentials() {
+        assert!(parse_webhook_url("https://hooks.example.com/budget").is_ok());
+        assert!(parse_webhook_url("http://hooks.example.com/budget").is_err());
+        assert!(parse_webhook_url("ftp://hooks.example.com").is_err());
+        assert!(parse_webhook_url("https://user:pw@hooks.example.com").is_err());
+        assert!(parse_webhook_url("not a url").is_err());
+    }
+
+    #[test]
+    fn rejects_internal_addresses() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+        assert!(is_public_ip("93.184.216.34".parse().unwrap()));
+        assert!(is_public_ip("2606:4700::1111".parse().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn literal_internal_hosts_are_refused() {
+        assert!(check_webhook_url("https://127.0.0.1/hook").await.is_err());
+        assert!(check_webhook_url("https://[::1]/hook").await.is_err());
+        assert!(check_webhook_url("https://localhost/hook").await.is_err());
+    }
+}
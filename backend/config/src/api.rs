@@ -0,0 +1,400 @@
+//! Settings of the `api` service.
+
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+
+use crate::{check_url, DatabaseSettings, Secret, ServiceConfig};
+
+/// Longest lifetime the storage backend accepts for a pre-signed URL.
+const MAX_PRESIGN_EXPIRY_SECONDS: u64 = 7 * 24 * 3600;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApiConfig {
+    pub server: ServerSettings,
+    pub database: DatabaseSettings,
+    pub admin: AdminSettings,
+    pub sessions: SessionSettings,
+    pub login: LoginSettings,
+    pub cache: CacheSettings,
+    pub storage: StorageSettings,
+    pub rate_limit: RateLimitSettings,
+    pub budgets: BudgetSettings,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerSettings {
+    pub bind: String,
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            bind: "0.0.0.0:3001".to_string(),
+        }
+    }
+}
+
+/// Admin endpoints are disabled unless a token is set or admin identities
+/// are listed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AdminSettings {
+    pub api_token: Option<Secret>,
+    /// Login identities (`provider:subject`, e.g. `github:583231` for a
+    /// GitHub user id or `stellar:G...` for a Stellar key) whose sessions act
+    /// as named admins; actions needing an accountable admin, such as
+    /// impersonation, require one.  The subject is the provider's stable
+    /// account id, not the username, which can change hands
+    pub identities: Vec<String>,
+}
+
+/// External login is disabled unless a signing secret is set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SessionSettings {
+    pub jwt_secret: Option<Secret>,
+}
+
+/// External login providers for the web UI; each is offered only when its
+/// `client_id` is set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoginSettings {
+    pub github: GitHubLoginSettings,
+    pub oidc: OidcLoginSettings,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GitHubLoginSettings {
+    pub client_id: Option<String>,
+    pub client_secret: Option<Secret>,
+    pub redirect_uri: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OidcLoginSettings {
+    /// Name used in routes, e.g. `okta` in `/api/auth/okta/login`
+    pub provider_name: String,
+    pub client_id: Option<String>,
+    pub client_secret: Option<Secret>,
+    pub redirect_uri: Option<String>,
+    pub authorization_endpoint: Option<String>,
+    pub token_endpoint: Option<String>,
+    pub userinfo_endpoint: Option<String>,
+    pub scopes: String,
+    /// Userinfo claim listing the user's groups
+    pub org_claim: String,
+}
+
+impl Default for OidcLoginSettings {
+    fn default() -> Self {
+        Self {
+            provider_name: "oidc".to_string(),
+            client_id: None,
+            client_secret: None,
+            redirect_uri: None,
+            authorization_endpoint: None,
+            token_endpoint: None,
+            userinfo_endpoint: None,
+            scopes: "openid profile email".to_string(),
+            org_claim: "groups".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheSettings {
+    pub enabled: bool,
+    /// `lru` or `lfu`
+    pub policy: String,
+    pub ttl_seconds: u64,
+    pub max_capacity: u64,
+}
+
+impl Default for CacheSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            policy: "lfu".to_string(),
+            ttl_seconds: 60,
+            max_capacity: 10_000,
+        }
+    }
+}
+
+/// S3-compatible artifact storage; disabled unless `bucket` is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageSettings {
+    pub bucket: Option<String>,
+    pub region: String,
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<Secret>,
+    /// Custom endpoint (MinIO, R2, localstack)
+    pub endpoint: Option<String>,
+    pub presign_expiry_seconds: u64,
+}
+
+impl Default for StorageSettings {
+    fn default() -> Self {
+        Self {
+            bucket: None,
+            region: "us-east-1".to_string(),
+            access_key_id: None,
+            secret_access_key: None,
+            endpoint: None,
+            presign_expiry_seconds: 300,
+        }
+    }
+}
+
+/// Per-client request limits.  Endpoint-specific limits are still read from
+/// `RATE_LIMIT_ENDPOINT_<METHOD>_<PATH>` variables.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitSettings {
+    pub read_per_minute: u32,
+    pub write_per_minute: u32,
+    pub auth_per_minute: u32,
+    pub health_per_minute: u32,
+    pub window_seconds: u64,
+}
+
+impl Default for RateLimitSettings {
+    fn default() -> Self {
+        Self {
+            read_per_minute: 100,
+            write_per_minute: 20,
+            auth_per_minute: 1_000,
+            health_per_minute: 10_000,
+            window_seconds: 60,
+        }
+    }
+}
+
+/// How often indexed usage is compared against contract budgets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BudgetSettings {
+    pub check_interval_seconds: u64,
+}
+
+impl Default for BudgetSettings {
+    fn default() -> Self {
+        Self {
+            check_interval_seconds: 300,
+        }
+    }
+}
+
+impl ServiceConfig for ApiConfig {
+    const ENV: &'static [(&'static str, &'static str)] = &[
+        ("API_BIND", "server.bind"),
+        ("DATABASE_URL", "database.url"),
+        ("DATABASE_MAX_CONNECTIONS", "database.max_connections"),
+        ("ADMIN_API_TOKEN", "admin.api_token"),
+        ("ADMIN_IDENTITIES", "admin.identities"),
+        ("SESSION_JWT_SECRET", "sessions.jwt_secret"),
+        ("GITHUB_CLIENT_ID", "login.github.client_id"),
+        ("GITHUB_CLIENT_SECRET", "login.github.client_secret"),
+        ("GITHUB_REDIRECT_URI", "login.github.redirect_uri"),
+        ("OIDC_PROVIDER_NAME", "login.oidc.provider_name"),
+        ("OIDC_CLIENT_ID", "login.oidc.client_id"),
+        ("OIDC_CLIENT_SECRET", "login.oidc.client_secret"),
+        ("OIDC_REDIRECT_URI", "login.oidc.redirect_uri"),
+        (
+            "OIDC_AUTHORIZATION_ENDPOINT",
+            "login.oidc.authorization_endpoint",
+        ),
+        ("OIDC_TOKEN_ENDPOINT", "login.oidc.token_endpoint"),
+        ("OIDC_USERINFO_ENDPOINT", "login.oidc.userinfo_endpoint"),
+        ("OIDC_SCOPES", "login.oidc.scopes"),
+        ("OIDC_ORG_CLAIM", "login.oidc.org_claim"),
+        ("CACHE_ENABLED", "cache.enabled"),
+        ("CACHE_POLICY", "cache.policy"),
+        ("CACHE_TTL_SECONDS", "cache.ttl_seconds"),
+        ("CACHE_MAX_CAPACITY", "cache.max_capacity"),
+        ("S3_BUCKET", "storage.bucket"),
+        ("S3_REGION", "storage.region"),
+        ("S3_ACCESS_KEY_ID", "storage.access_key_id"),
+        ("S3_SECRET_ACCESS_KEY", "storage.secret_access_key"),
+        ("S3_ENDPOINT", "storage.endpoint"),
+        (
+            "S3_PRESIGN_EXPIRY_SECONDS",
+            "storage.presign_expiry_seconds",
+        ),
+        ("RATE_LIMIT_READ_PER_MINUTE", "rate_limit.read_per_minute"),
+        ("RATE_LIMIT_WRITE_PER_MINUTE", "rate_limit.write_per_minute"),
+        ("RATE_LIMIT_AUTH_PER_MINUTE", "rate_limit.auth_per_minute"),
+        (
+            "RATE_LIMIT_HEALTH_PER_MINUTE",
+            "rate_limit.health_per_minute",
+        ),
+        ("RATE_LIMIT_WINDOW_SECONDS", "rate_limit.window_seconds"),
+        (
+            "BUDGET_CHECK_INTERVAL_SECONDS",
+            "budgets.check_interval_seconds",
+        ),
+    ];
+
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        if self.server.bind.parse::<SocketAddr>().is_err() {
+            errors.push(format!(
+                "server.bind: `{}` is not a socket address",
+                self.server.bind
+            ));
+        }
+        self.database.validate(&mut errors);
+
+        if let Some(secret) = &self.sessions.jwt_secret {
+            if secret.expose().len() < 32 {
+                errors.push("sessions.jwt_secret must be at least 32 bytes".to_string());
+            }
+        }
+
+        self.login.validate(&mut errors, self.sessions.jwt_secret.is_some());
+
+        for identity in &self.admin.identities {
+            let valid = identity
+                .split_once(':')
+                .is_some_and(|(provider, subject)| !provider.is_empty() && !subject.is_empty());
+            if !valid {
+                errors.push(format!(
+                    "admin.identities: `{}` is not of the form provider:subject",
+                    identity
+                ));
+            }
+        }
+        if !self.admin.identities.is_empty() && self.sessions.jwt_secret.is_none() {
+            errors.push("sessions.jwt_secret is required for admin.identities".to_string());
+        }
+
+        if !matches!(self.cache.policy.to_lowercase().as_str(), "lru" | "lfu") {
+            errors.push(format!(
+                "cache.policy: expected `lru` or `lfu`, got `{}`",
+                self.cache.policy
+            ));
+        }
+        if self.cache.ttl_seconds == 0 {
+            errors.push("cache.ttl_seconds must be at least 1".to_string());
+        }
+        if self.cache.max_capacity == 0 {
+            errors.push("cache.max_capacity must be at least 1".to_string());
+        }
+
+        let storage = &self.storage;
+        if storage.bucket.is_some() {
+            if storage.access_key_id.is_none() {
+                errors.push(
+                    "storage.access_key_id is required when storage.bucket is set".to_string(),
+                );
+            }
+            if storage.secret_access_key.is_none() {
+                errors.push(
+                    "storage.secret_access_key is required when storage.bucket is set".to_string(),
+                );
+            }
+        }
+        if let Some(endpoint) = &storage.endpoint {
+            check_url(
+                &mut errors,
+                "storage.endpoint",
+                endpoint,
+                &["http", "https"],
+            );
+        }
+        if !(1..=MAX_PRESIGN_EXPIRY_SECONDS).contains(&storage.presign_expiry_seconds) {
+            errors.push(format!(
+                "storage.presign_expiry_seconds must be between 1 and {}",
+                MAX_PRESIGN_EXPIRY_SECONDS
+            ));
+        }
+
+        let limits = &self.rate_limit;
+        for (key, value) in [
+            ("read_per_minute", limits.read_per_minute),
+            ("write_per_minute", limits.write_per_minute),
+            ("auth_per_minute", limits.auth_per_minute),
+            ("health_per_minute", limits.health_per_minute),
+        ] {
+            if value == 0 {
+                errors.push(format!("rate_limit.{} must be at least 1", key));
+            }
+        }
+        if limits.window_seconds == 0 {
+            errors.push("rate_limit.window_seconds must be at least 1".to_string());
+        }
+        if self.budgets.check_interval_seconds == 0 {
+            errors.push("budgets.check_interval_seconds must be at least 1".to_string());
+        }
+
+        errors
+    }
+}
+
+impl LoginSettings {
+    fn validate(&self, errors: &mut Vec<String>, sessions_enabled: bool) {
+        let github = &self.github;
+        check_complete(
+            errors,
+            "login.github",
+            &[
+                ("client_id", github.client_id.is_some()),
+                ("client_secret", github.client_secret.is_some()),
+                ("redirect_uri", github.redirect_uri.is_some()),
+            ],
+        );
+
+        let oidc = &self.oidc;
+        check_complete(
+            errors,
+            "login.oidc",
+            &[
+                ("client_id", oidc.client_id.is_some()),
+                ("client_secret", oidc.client_secret.is_some()),
+                ("redirect_uri", oidc.redirect_uri.is_some()),
+                ("authorization_endpoint", oidc.authorization_endpoint.is_some()),
+                ("token_endpoint", oidc.token_endpoint.is_some()),
+                ("userinfo_endpoint", oidc.userinfo_endpoint.is_some()),
+            ],
+        );
+        if matches!(oidc.provider_name.as_str(), "" | "github" | "stellar") {
+            errors.push(format!(
+                "login.oidc.provider_name: `{}` is not a usable provider name",
+                oidc.provider_name
+            ));
+        }
+
+        for (key, value) in [
+            ("login.github.redirect_uri", &github.redirect_uri),
+            ("login.oidc.redirect_uri", &oidc.redirect_uri),
+            ("login.oidc.authorization_endpoint", &oidc.authorization_endpoint),
+            ("login.oidc.token_endpoint", &oidc.token_endpoint),
+            ("login.oidc.userinfo_endpoint", &oidc.userinfo_endpoint),
+        ] {
+            if let Some(url) = value {
+                check_url(errors, key, url, &["http", "https"]);
+            }
+        }
+
+        if (github.client_id.is_some() || oidc.client_id.is_some()) && !sessions_enabled {
+            errors.push("login providers need sessions.jwt_secret to be set".to_string());
+        }
+    }
+}
+
+/// A provider is configured with all of its settings or none of them.
+fn check_complete(errors: &mut Vec<String>, section: &str, settings: &[(&str, bool)]) {
+    if settings.iter().any(|(_, set)| *set) {
+        for (key, _) in settings.iter().filter(|(_, set)| !set) {
+            errors.push(format!("{}.{} is required to enable {}", section, key, section));
+        }
+    }
+}
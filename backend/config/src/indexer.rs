@@ -0,0 +1,70 @@
+//! Settings of the `indexer` service.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{check_url, DatabaseSettings, ServiceConfig};
+
+const NETWORKS: &[&str] = &["mainnet", "testnet", "futurenet"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IndexerConfig {
+    pub database: DatabaseSettings,
+    pub stellar: StellarSettings,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StellarSettings {
+    pub rpc_url: String,
+    /// `mainnet`, `testnet` or `futurenet`
+    pub network: String,
+    pub poll_interval_seconds: u64,
+}
+
+impl Default for StellarSettings {
+    fn default() -> Self {
+        Self {
+            rpc_url: "https://soroban-testnet.stellar.org".to_string(),
+            network: "testnet".to_string(),
+            poll_interval_seconds: 10,
+        }
+    }
+}
+
+impl ServiceConfig for IndexerConfig {
+    const ENV: &'static [(&'static str, &'static str)] = &[
+        ("DATABASE_URL", "database.url"),
+        ("DATABASE_MAX_CONNECTIONS", "database.max_connections"),
+        ("STELLAR_RPC_URL", "stellar.rpc_url"),
+        ("STELLAR_NETWORK", "stellar.network"),
+        (
+            "INDEXER_POLL_INTERVAL_SECONDS",
+            "stellar.poll_interval_seconds",
+        ),
+    ];
+
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        self.database.validate(&mut errors);
+
+        check_url(
+            &mut errors,
+            "stellar.rpc_url",
+            &self.stellar.rpc_url,
+            &["http", "https"],
+        );
+        if !NETWORKS.contains(&self.stellar.network.as_str()) {
+            errors.push(format!(
+                "stellar.network: expected one of {}, got `{}`",
+                NETWORKS.join(", "),
+                self.stellar.network
+            ));
+        }
+        if self.stellar.poll_interval_seconds == 0 {
+            errors.push("stellar.poll_interval_seconds must be at least 1".to_string());
+        }
+
+        errors
+    }
+}
@@ -0,0 +1,499 @@
+//! Layered, validated configuration for the registry's backend services.
+//!
+//! Each service describes its settings as a serde struct implementing
+//! [`ServiceConfig`].  Values are layered, later sources winning:
+//!
+//! 1. the struct's `Default`
+//! 2. a TOML file (`--config <path>` or `REGISTRY_CONFIG`)
+//! 3. environment variables, under the names the services always used
+//!    (`DATABASE_URL`, `S3_BUCKET`, ...)
+//! 4. `--set section.key=value` on the command line
+//!
+//! Validation reports every problem at once rather than failing on the
+//! first, and `--print-config` shows the effective configuration with
+//! secrets redacted.
+
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::path::PathBuf;
+use toml::{Table, Value};
+
+mod api;
+mod indexer;
+mod seeder;
+
+pub use api::*;
+pub use indexer::*;
+pub use seeder::*;
+
+/// Environment variable naming the config file when `--config` is absent.
+pub const CONFIG_FILE_ENV: &str = "REGISTRY_CONFIG";
+
+const REDACTED: &str = "[redacted]";
+
+/// A secret value; never printed or serialized in clear.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl Serialize for Secret {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(REDACTED)
+    }
+}
+
+impl<'de> Deserialize<'de> for Secret {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Secret)
+    }
+}
+
+/// Postgres connection, shared by every service.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseSettings {
+    pub url: Option<Secret>,
+    pub max_connections: u32,
+}
+
+impl Default for DatabaseSettings {
+    fn default() -> Self {
+        Self {
+            url: None,
+            max_connections: 5,
+        }
+    }
+}
+
+impl DatabaseSettings {
+    /// The connection URL; empty only if validation was skipped.
+    pub fn url(&self) -> &str {
+        self.url.as_ref().map(Secret::expose).unwrap_or_default()
+    }
+
+    pub(crate) fn validate(&self, errors: &mut Vec<String>) {
+        match &self.url {
+            Some(url) => check_url(
+                errors,
+                "database.url",
+                url.expose(),
+                &["postgres", "postgresql"],
+            ),
+            None => errors.push("database.url is required (DATABASE_URL)".to_string()),
+        }
+        if self.max_connections == 0 {
+            errors.push("database.max_connections must be at least 1".to_string());
+        }
+    }
+}
+
+/// Settings of one service.
+pub trait ServiceConfig: Default + Serialize + DeserializeOwned {
+    /// Environment variables read by the service and the setting each sets.
+    const ENV: &'static [(&'static str, &'static str)];
+
+    /// Every problem with the configuration; empty when it is usable.
+    fn validate(&self) -> Vec<String>;
+}
+
+/// Config sources given on the command line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigArgs {
+    pub file: Option<PathBuf>,
+    /// `section.key=value` overrides, applied last
+    pub overrides: Vec<(String, String)>,
+    pub print_config: bool,
+}
+
+impl ConfigArgs {
+    /// Take `--config`, `--set` and `--print-config` out of `args`, returning
+    /// the remaining arguments for the service's own parser.
+    pub fn extract(args: impl IntoIterator<Item = String>) -> Result<(Self, Vec<String>), String> {
+        let mut config = Self::default();
+        let mut rest = Vec::new();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg.clone(), None),
+            };
+            match flag.as_str() {
+                "--config" => {
+                    let path = inline
+                        .or_else(|| args.next())
+                        .ok_or("--config needs a path")?;
+                    config.file = Some(PathBuf::from(path));
+                }
+                "--set" => {
+                    let assignment = inline
+                        .or_else(|| args.next())
+                        .ok_or("--set needs key=value")?;
+                    let (key, value) = assignment
+                        .split_once('=')
+                        .ok_or_else(|| format!("--set {}: expected key=value", assignment))?;
+                    config
+                        .overrides
+                        .push((key.trim().to_string(), value.to_string()));
+                }
+                "--print-config" => config.print_config = true,
+                _ => rest.push(arg),
+            }
+        }
+        Ok((config, rest))
+    }
+}
+
+/// Configuration problems, all reported together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigErrors(pub Vec<String>);
+
+impl fmt::Display for ConfigErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "invalid configuration ({} problem(s)):", self.0.len())?;
+        for error in &self.0 {
+            writeln!(f, "  - {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigErrors {}
+
+/// Load and validate a service's configuration from the process environment.
+pub fn load<C: ServiceConfig>(args: &ConfigArgs) -> Result<C, ConfigErrors> {
+    load_from(args, |key| std::env::var(key).ok())
+}
+
+/// Load and validate with an explicit environment lookup.
+pub fn load_from<C: ServiceConfig>(
+    args: &ConfigArgs,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<C, ConfigErrors> {
+    let mut errors = Vec::new();
+
+    // Defaults come from `#[serde(default)]`; the serialised default is only
+    // used to type environment values, as secrets serialise redacted.
+    let defaults = match Value::try_from(C::default()) {
+        Ok(Value::Table(table)) => table,
+        _ => Table::new(),
+    };
+    let mut merged = Table::new();
+
+    let file = args.file.clone().or_else(|| {
+        env(CONFIG_FILE_ENV)
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+    });
+    if let Some(path) = file {
+        match std::fs::read_to_string(&path) {
+            Ok(content) => match content.parse::<Table>() {
+                Ok(table) => merge(&mut merged, table),
+                Err(err) => errors.push(format!("{}: {}", path.display(), err)),
+            },
+            Err(err) => errors.push(format!("{}: {}", path.display(), err)),
+        }
+    }
+
+    for (var, path) in C::ENV {
+        if let Some(raw) = env(var).filter(|v| !v.is_empty()) {
+            set(&mut merged, &defaults, path, &raw, var, &mut errors);
+        }
+    }
+
+    for (path, raw) in &args.overrides {
+        set(
+            &mut merged,
+            &defaults,
+            path,
+            raw,
+            &format!("--set {}", path),
+            &mut errors,
+        );
+    }
+
+    let config = match C::deserialize(Value::Table(merged)) {
+        Ok(config) => Some(config),
+        Err(err) => {
+            errors.push(err.to_string().trim().to_string());
+            None
+        }
+    };
+    if let Some(config) = &config {
+        errors.extend(config.validate());
+    }
+
+    match config {
+        Some(config) if errors.is_empty() => Ok(config),
+        _ => Err(ConfigErrors(errors)),
+    }
+}
+
+/// The effective configuration as TOML, secrets redacted.
+pub fn render<C: Serialize>(config: &C) -> String {
+    toml::to_string_pretty(config).unwrap_or_else(|err| format!("# unprintable: {}", err))
+}
+
+fn merge(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(overlay)) => merge(base, overlay),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Set `path` from a string, typed after the default at that path.
+fn set(
+    table: &mut Table,
+    defaults: &Table,
+    path: &str,
+    raw: &str,
+    source: &str,
+    errors: &mut Vec<String>,
+) {
+    let value = match lookup(defaults, path) {
+        Some(Value::Integer(_)) => match raw.trim().parse() {
+            Ok(n) => Value::Integer(n),
+            Err(_) => {
+                return errors.push(format!("{}: expected an integer, got `{}`", source, raw))
+            }
+        },
+        Some(Value::Boolean(_)) => match raw.trim().to_ascii_lowercase().as_str() {
+            "true" | "1" | "yes" => Value::Boolean(true),
+            "false" | "0" | "no" => Value::Boolean(false),
+            _ => return errors.push(format!("{}: expected true or false, got `{}`", source, raw)),
+        },
+        Some(Value::Float(_)) => match raw.trim().parse() {
+            Ok(n) => Value::Float(n),
+            Err(_) => return errors.push(format!("{}: expected a number, got `{}`", source, raw)),
+        },
+        // Lists are comma-separated
+        Some(Value::Array(_)) => Value::Array(
+            raw.split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| Value::String(item.to_string()))
+                .collect(),
+        ),
+        _ => Value::String(raw.to_string()),
+    };
+
+    let mut keys: Vec<&str> = path.split('.').collect();
+    let Some(last) = keys.pop() else { return };
+    let mut current = table;
+    for key in keys {
+        let entry = current
+            .entry(key.to_string())
+            .or_insert_with(|| Value::Table(Table::new()));
+        let Value::Table(next) = entry else {
+            return errors.push(format!("{}: `{}` is not a section", source, key));
+        };
+        current = next;
+    }
+    current.insert(last.to_string(), value);
+}
+
+fn lookup<'a>(table: &'a Table, path: &str) -> Option<&'a Value> {
+    let mut keys = path.split('.');
+    let mut value = table.get(keys.next()?)?;
+    for key in keys {
+        value = value.as_table()?.get(key)?;
+    }
+    Some(value)
+}
+
+/// Push `error` unless `value` is a URL with one of `schemes`.
+pub(crate) fn check_url(errors: &mut Vec<String>, key: &str, value: &str, schemes: &[&str]) {
+    let ok = value
+        .split_once("://")
+        .is_some_and(|(scheme, rest)| schemes.contains(&scheme) && !rest.is_empty());
+    if !ok {
+        errors.push(format!(
+            "{}: `{}` is not a {} URL",
+            key,
+            value,
+            schemes.join("/")
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| vars.get(key).cloned()
+    }
+
+    #[test]
+    fn later_sources_win() {
+        let dir = std::env::temp_dir().join(format!("registry-config-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("api.toml");
+        std::fs::write(
+            &file,
+            "[server]\nbind = \"127.0.0.1:4000\"\n[database]\nmax_connections = 20\n",
+        )
+        .unwrap();
+
+        let args = ConfigArgs {
+            file: Some(file),
+            overrides: vec![("database.max_connections".into(), "30".into())],
+            print_config: false,
+        };
+        let config: ApiConfig = load_from(
+            &args,
+            env(&[
+                ("DATABASE_URL", "postgres://localhost/registry"),
+                ("DATABASE_MAX_CONNECTIONS", "10"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(config.server.bind, "127.0.0.1:4000");
+        assert_eq!(config.database.max_connections, 30);
+        assert_eq!(
+            config.database.url.as_ref().map(Secret::expose),
+            Some("postgres://localhost/registry")
+        );
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn reports_every_problem_at_once() {
+        let errors = load_from::<ApiConfig>(
+            &ConfigArgs::default(),
+            env(&[
+                ("CACHE_TTL_SECONDS", "soon"),
+                ("SESSION_JWT_SECRET", "short"),
+                ("S3_BUCKET", "artifacts"),
+            ]),
+        )
+        .unwrap_err();
+
+        let all = errors.0.join("\n");
+        assert!(all.contains("CACHE_TTL_SECONDS"));
+        assert!(all.contains("database.url"));
+        assert!(all.contains("sessions.jwt_secret"));
+        assert!(all.contains("storage.access_key_id"));
+        assert!(all.contains("storage.secret_access_key"));
+        assert_eq!(errors.0.len(), 5, "{}", all);
+    }
+
+    #[test]
+    fn partial_sections_keep_the_other_defaults() {
+        let args = ConfigArgs {
+            file: None,
+            overrides: vec![("database.max_connections".into(), "2".into())],
+            print_config: false,
+        };
+        let config: SeederConfig = load_from(&args, env(&[])).unwrap();
+
+        assert_eq!(config.database.max_connections, 2);
+        assert_eq!(
+            config.database.url(),
+            "postgresql://localhost/soroban_registry"
+        );
+    }
+
+    #[test]
+    fn half_configured_login_provider_is_reported() {
+        let errors = load_from::<ApiConfig>(
+            &ConfigArgs::default(),
+            env(&[
+                ("DATABASE_URL", "postgres://localhost/registry"),
+                ("SESSION_JWT_SECRET", "0123456789abcdef0123456789abcdef"),
+                ("GITHUB_CLIENT_ID", "Iv1.abc"),
+            ]),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            errors.0,
+            vec![
+                "login.github.client_secret is required to enable login.github",
+                "login.github.redirect_uri is required to enable login.github",
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_are_read_comma_separated() {
+        let config: ApiConfig = load_from(
+            &ConfigArgs::default(),
+            env(&[
+                ("DATABASE_URL", "postgres://localhost/registry"),
+                ("SESSION_JWT_SECRET", "0123456789abcdef0123456789abcdef"),
+                ("ADMIN_IDENTITIES", "github:583231, stellar:GABC"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(config.admin.identities, vec!["github:583231", "stellar:GABC"]);
+    }
+
+    #[test]
+    fn printed_config_redacts_secrets() {
+        let config: ApiConfig = load_from(
+            &ConfigArgs::default(),
+            env(&[
+                ("DATABASE_URL", "postgres://user:hunter2@db/registry"),
+                ("ADMIN_API_TOKEN", "admin-token"),
+            ]),
+        )
+        .unwrap();
+
+        let printed = render(&config);
+        assert!(!printed.contains("hunter2"));
+        assert!(!printed.contains("admin-token"));
+        assert!(printed.contains(REDACTED));
+        assert!(format!("{:?}", config).contains(REDACTED));
+    }
+
+    #[test]
+    fn extracts_config_flags_and_leaves_the_rest() {
+        let args = [
+            "--count",
+            "10",
+            "--set=cache.enabled=false",
+            "--config",
+            "a.toml",
+            "--print-config",
+        ]
+        .map(String::from);
+        let (config, rest) = ConfigArgs::extract(args).unwrap();
+
+        assert_eq!(rest, vec!["--count", "10"]);
+        assert_eq!(config.file, Some(PathBuf::from("a.toml")));
+        assert_eq!(
+            config.overrides,
+            vec![("cache.enabled".into(), "false".into())]
+        );
+        assert!(config.print_config);
+    }
+}
@@ -0,0 +1,53 @@
+//! Settings of the `seeder` tool.
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::{DatabaseSettings, Secret, ServiceConfig};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SeederConfig {
+    #[serde(deserialize_with = "database_over_default")]
+    pub database: DatabaseSettings,
+}
+
+impl Default for SeederConfig {
+    fn default() -> Self {
+        Self {
+            database: DatabaseSettings {
+                url: Some(Secret::new("postgresql://localhost/soroban_registry")),
+                ..DatabaseSettings::default()
+            },
+        }
+    }
+}
+
+/// A `[database]` table only replaces the keys it sets, so setting
+/// `max_connections` alone keeps the seeder's default URL.
+fn database_over_default<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<DatabaseSettings, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct PartialDatabase {
+        url: Option<Secret>,
+        max_connections: Option<u32>,
+    }
+
+    let partial = PartialDatabase::deserialize(deserializer)?;
+    let default = SeederConfig::default().database;
+    Ok(DatabaseSettings {
+        url: partial.url.or(default.url),
+        max_connections: partial.max_connections.unwrap_or(default.max_connections),
+    })
+}
+
+impl ServiceConfig for SeederConfig {
+    const ENV: &'static [(&'static str, &'static str)] = &[("DATABASE_URL", "database.url")];
+
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        self.database.validate(&mut errors);
+        errors
+    }
+}
@@ -1,11 +1,40 @@
 // Blockchain indexer for monitoring Stellar network
-// This will be implemented in future iterations
+//
+// Polls Soroban RPC every `stellar.poll_interval_seconds` and records the
+// costs of invocations of registered contracts (usage.rs).  `--once` runs a
+// single pass and exits.
+
+mod rpc;
+mod usage;
+
+use std::time::Duration;
 
 use anyhow::Result;
+use registry_config::{ConfigArgs, IndexerConfig};
+use sqlx::postgres::PgPoolOptions;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+use crate::rpc::RpcClient;
+
 #[tokio::main]
 async fn main() -> Result<()> {
+    dotenv::dotenv().ok();
+
+    let (config_args, rest) =
+        ConfigArgs::extract(std::env::args().skip(1)).map_err(anyhow::Error::msg)?;
+    let mut once = false;
+    for arg in rest {
+        match arg.as_str() {
+            "--once" => once = true,
+            _ => anyhow::bail!("unexpected argument: {}", arg),
+        }
+    }
+    let config: IndexerConfig = registry_config::load(&config_args)?;
+    if config_args.print_config {
+        print!("{}", registry_config::render(&config));
+        return Ok(());
+    }
+
     // Initialize tracing
     tracing_subscriber::registry()
         .with(
@@ -15,15 +44,30 @@ async fn main() -> Result<()> {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
-    tracing::info!("Indexer service starting...");
-    tracing::info!("This service will monitor Stellar network for contract deployments");
-    tracing::info!("Implementation coming soon!");
+    tracing::info!(
+        network = %config.stellar.network,
+        rpc_url = %config.stellar.rpc_url,
+        poll_interval_seconds = config.stellar.poll_interval_seconds,
+        "Indexer service starting..."
+    );
 
-    // TODO: Implement indexer logic
-    // - Connect to Stellar RPC
-    // - Monitor for new contract deployments
-    // - Extract contract metadata
-    // - Store in database
+    let pool = PgPoolOptions::new()
+        .max_connections(config.database.max_connections)
+        .connect(config.database.url())
+        .await?;
+    let rpc = RpcClient::new(&config.stellar.rpc_url);
+    let network = config.stellar.network.as_str();
 
-    Ok(())
+    loop {
+        match usage::ingest(&pool, &rpc, network).await {
+            Ok(recorded) => tracing::info!(recorded, "invocation usage ingested"),
+            // A single pass reports its failure; the service retries next poll
+            Err(err) if once => return Err(err),
+            Err(err) => tracing::error!(error = %err, "invocation usage ingestion failed"),
+        }
+        if once {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_secs(config.stellar.poll_interval_seconds)).await;
+    }
 }
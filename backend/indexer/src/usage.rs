@@ -0,0 +1,216 @@
+//! Invocation cost ingestion.
+//!
+//! Pages through `getTransactions` (JSON-encoded XDR) from where the last
+//! pass stopped and records, for every successful invocation of a registered
+//! contract, its CPU instruction budget and the fee charged as
+//! `cpu_instructions` / `invocation_fee` rows in `performance_metrics`.  The
+//! API's budget monitor compares these against contract budgets.
+//!
+//! Rows carry the transaction hash, so a page that is read twice is recorded
+//! once.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+use crate::rpc::RpcClient;
+
+/// Transactions requested per `getTransactions` page.
+const PAGE_SIZE: usize = 200;
+
+/// Costs of one successful contract invocation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvocationUsage {
+    pub tx_hash: String,
+    pub contract_id: String,
+    pub function_name: Option<String>,
+    /// Instruction budget of the transaction, which caps what it consumed
+    pub cpu_instructions: i64,
+    /// Total fee charged, in stroops
+    pub fee_charged: i64,
+    pub at: DateTime<Utc>,
+}
+
+/// 64-bit XDR integers are encoded as strings in JSON; smaller ones as numbers.
+fn as_i64(value: &Value) -> Option<i64> {
+    value
+        .as_i64()
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+}
+
+/// Usage of a transaction that successfully invoked a contract, if it did.
+pub fn parse_transaction(tx: &Value) -> Option<InvocationUsage> {
+    if tx["status"] != "SUCCESS" {
+        return None;
+    }
+
+    let envelope = &tx["envelopeJson"];
+    let body = if envelope["tx_fee_bump"].is_object() {
+        &envelope["tx_fee_bump"]["tx"]["inner_tx"]["tx"]["tx"]
+    } else {
+        &envelope["tx"]["tx"]
+    };
+    let invoke = body["operations"].as_array()?.iter().find_map(|op| {
+        op["body"]["invoke_host_function"]["host_function"].get("invoke_contract")
+    })?;
+
+    Some(InvocationUsage {
+        tx_hash: tx["txHash"].as_str()?.to_string(),
+        contract_id: invoke["contract_address"].as_str()?.to_string(),
+        function_name: invoke["function_name"].as_str().map(str::to_string),
+        cpu_instructions: as_i64(&body["ext"]["v1"]["resources"]["instructions"])?,
+        fee_charged: as_i64(&tx["resultJson"]["fee_charged"])?,
+        at: DateTime::from_timestamp(as_i64(&tx["createdAt"])?, 0)?,
+    })
+}
+
+async fn load_cursor(pool: &PgPool, name: &str) -> Result<Option<String>> {
+    Ok(
+        sqlx::query_scalar("SELECT cursor FROM indexer_cursors WHERE name = $1")
+            .bind(name)
+            .fetch_optional(pool)
+            .await?,
+    )
+}
+
+async fn save_cursor(pool: &PgPool, name: &str, cursor: &str) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO indexer_cursors (name, cursor) VALUES ($1, $2)
+         ON CONFLICT (name) DO UPDATE SET cursor = EXCLUDED.cursor, updated_at = NOW()",
+    )
+    .bind(name)
+    .bind(cursor)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Record an invocation against the registered contract it called.  Returns
+/// whether the contract is registered on `network`.
+async fn record(pool: &PgPool, network: &str, usage: &InvocationUsage) -> Result<bool> {
+    let recorded = sqlx::query(
+        "INSERT INTO performance_metrics
+             (contract_id, metric_type, function_name, value, timestamp, metadata)
+         SELECT c.id, m.kind::metric_type, $3, m.value, $4, jsonb_build_object('tx_hash', $5::TEXT)
+           FROM contracts c,
+                (VALUES ('cpu_instructions', $6::BIGINT),
+                        ('invocation_fee', $7::BIGINT)) AS m(kind, value)
+          WHERE c.contract_id = $1 AND c.network = $2::network_type
+         ON CONFLICT (contract_id, metric_type, (metadata ->> 'tx_hash'))
+            WHERE metadata ->> 'tx_hash' IS NOT NULL
+         DO NOTHING",
+    )
+    .bind(&usage.contract_id)
+    .bind(network)
+    .bind(&usage.function_name)
+    .bind(usage.at)
+    .bind(&usage.tx_hash)
+    .bind(usage.cpu_instructions)
+    .bind(usage.fee_charged)
+    .execute(pool)
+    .await?;
+    Ok(recorded.rows_affected() > 0)
+}
+
+/// One ingestion pass: read every transaction since the last pass.  Returns
+/// how many invocations of registered contracts were recorded.
+pub async fn ingest(pool: &PgPool, rpc: &RpcClient, network: &str) -> Result<usize> {
+    let cursor_name = format!("invocations:{}", network);
+    let mut cursor = load_cursor(pool, &cursor_name).await?;
+    let mut recorded = 0;
+
+    loop {
+        let params = match &cursor {
+            Some(cursor) => json!({
+                "pagination": { "cursor": cursor, "limit": PAGE_SIZE },
+                "xdrFormat": "json",
+            }),
+            // First pass: start at the tip rather than replaying retention
+            None => {
+                let latest = rpc.call("getLatestLedger", json!({})).await?;
+                json!({
+                    "startLedger": latest["sequence"],
+                    "pagination": { "limit": PAGE_SIZE },
+                    "xdrFormat": "json",
+                })
+            }
+        };
+        let page = rpc.call("getTransactions", params).await?;
+        let transactions = page["transactions"].as_array().cloned().unwrap_or_default();
+
+        for usage in transactions.iter().filter_map(parse_transaction) {
+            if record(pool, network, &usage).await? {
+                recorded += 1;
+            }
+        }
+
+        let next = page["cursor"].as_str().map(str::to_string);
+        if let Some(next) = &next {
+            save_cursor(pool, &cursor_name, next).await?;
+        }
+        // A short page is the tip; an unchanged cursor means no progress
+        if transactions.len() < PAGE_SIZE || next.is_none() || next == cursor {
+            break;
+        }
+        cursor = next;
+    }
+
+    Ok(recorded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACT: &str = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC";
+
+    fn invocation(status: &str) -> Value {
+        json!({
+            "status": status,
+            "txHash": "ab".repeat(32),
+            "ledger": 1000,
+            "createdAt": 1_719_792_000,
+            "envelopeJson": { "tx": { "tx": {
+                "operations": [{ "body": { "invoke_host_function": {
+                    "host_function": { "invoke_contract": {
+                        "contract_address": CONTRACT,
+                        "function_name": "transfer",
+                        "args": [],
+                    }},
+                    "auth": [],
+                }}}],
+                "ext": { "v1": { "resources": { "instructions": 2_500_000 }, "resource_fee": "90000" } },
+            }, "signatures": [] }},
+            "resultJson": { "fee_charged": "100123", "result": { "tx_success": [] } },
+        })
+    }
+
+    #[test]
+    fn parses_successful_invocations() {
+        let usage = parse_transaction(&invocation("SUCCESS")).unwrap();
+        assert_eq!(usage.contract_id, CONTRACT);
+        assert_eq!(usage.function_name.as_deref(), Some("transfer"));
+        assert_eq!(usage.cpu_instructions, 2_500_000);
+        assert_eq!(usage.fee_charged, 100_123);
+        assert_eq!(usage.at.to_rfc3339(), "2024-07-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn skips_failed_and_non_contract_transactions() {
+        assert_eq!(parse_transaction(&invocation("FAILED")), None);
+
+        let mut payment = invocation("SUCCESS");
+        payment["envelopeJson"]["tx"]["tx"]["operations"] =
+            json!([{ "body": { "payment": { "amount": "10" } } }]);
+        assert_eq!(parse_transaction(&payment), None);
+    }
+
+    #[test]
+    fn unwraps_fee_bumps() {
+        let inner = invocation("SUCCESS");
+        let mut bumped = inner.clone();
+        bumped["envelopeJson"] = json!({ "tx_fee_bump": { "tx": { "inner_tx": { "tx": inner["envelopeJson"]["tx"] } } } });
+        assert_eq!(parse_transaction(&bumped), parse_transaction(&inner));
+    }
+}
@@ -1,5 +1,5 @@
 use anyhow::{Context, Result};
-use rand::{Rng, RngCore, SeedableRng};
+use rand::Rng;
 use rand::rngs::StdRng;
 use shared::{Contract, Network, Publisher};
 use sqlx::PgPool;
@@ -106,7 +106,7 @@ pub async fn create_contracts(
 
     for i in 0..count {
         let publisher = &publishers[i % publishers.len()];
-        let network = networks[i % networks.len()];
+        let network = networks[i % networks.len()].clone();
 
         let name = if let Some(data) = custom_data {
             if let Some(names) = data.get("contract_names").and_then(|v| v.as_array()) {
@@ -3,6 +3,7 @@ mod data;
 use anyhow::{Context, Result};
 use clap::Parser;
 use colored::Colorize;
+use registry_config::{ConfigArgs, SeederConfig};
 use sqlx::postgres::PgPoolOptions;
 use std::collections::HashMap;
 use std::fs;
@@ -21,13 +22,27 @@ struct Args {
     #[arg(long)]
     data_file: Option<String>,
 
-    #[arg(long, default_value = "postgresql://localhost/soroban_registry")]
-    database_url: String,
+    /// Overrides `database.url` from the config file or `DATABASE_URL`
+    #[arg(long)]
+    database_url: Option<String>,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let args = Args::parse();
+    // --config, --set and --print-config are shared with the other services
+    let (mut config_args, rest) =
+        ConfigArgs::extract(std::env::args()).map_err(anyhow::Error::msg)?;
+    let args = Args::parse_from(rest);
+    if let Some(url) = &args.database_url {
+        config_args
+            .overrides
+            .push(("database.url".to_string(), url.clone()));
+    }
+    let config: SeederConfig = registry_config::load(&config_args)?;
+    if config_args.print_config {
+        print!("{}", registry_config::render(&config));
+        return Ok(());
+    }
 
     println!("{}", "=".repeat(80).cyan());
     println!("{}", "Soroban Registry Database Seeder".bold().cyan());
@@ -35,8 +50,8 @@ async fn main() -> Result<()> {
     println!();
 
     let pool = PgPoolOptions::new()
-        .max_connections(5)
-        .connect(&args.database_url)
+        .max_connections(config.database.max_connections)
+        .connect(config.database.url())
         .await
         .context("Failed to connect to database")?;
 
@@ -69,10 +84,10 @@ async fn main() -> Result<()> {
     println!("{} Created {} contracts", "✓".green(), contracts.len());
 
     let versions = data::create_versions(&pool, &contracts, &mut rng).await?;
-    println!("{} Created {} contract versions", "✓".green(), versions.len());
+    println!("{} Created {} contract versions", "✓".green(), versions);
 
     let verifications = data::create_verifications(&pool, &contracts, &mut rng).await?;
-    println!("{} Created {} verifications", "✓".green(), verifications.len());
+    println!("{} Created {} verifications", "✓".green(), verifications);
 
     let elapsed = start_time.elapsed();
     println!();
@@ -0,0 +1,50 @@
+//! Signed verification attestations.
+//!
+//! After a contract's source is verified, the verifier signs a statement of
+//! what it checked with the registry's Ed25519 key.  The signed bytes are the
+//! UTF-8 message built by [`message`], one `key:value` pair per line, so
+//! third parties can rebuild it from the attestation fields and check the
+//! signature with any Ed25519 implementation.
+
+use chrono::{DateTime, Utc};
+
+/// Signature scheme of attestations.
+pub const ALGORITHM: &str = "ed25519";
+
+/// First line of every attestation message; bumped if the format changes.
+pub const MESSAGE_VERSION: &str = "soroban-registry-attestation/v1";
+
+/// The exact bytes (as UTF-8) an attestation signature covers.
+pub fn message(
+    contract_id: &str,
+    network: &str,
+    wasm_hash: &str,
+    source_commit: Option<&str>,
+    signed_at: DateTime<Utc>,
+) -> String {
+    format!(
+        "{}\ncontract_id:{}\nnetwork:{}\nwasm_hash:{}\nsource_commit:{}\ntimestamp:{}",
+        MESSAGE_VERSION,
+        contract_id,
+        network,
+        wasm_hash.to_ascii_lowercase(),
+        source_commit.unwrap_or_default(),
+        signed_at.timestamp()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_lists_every_signed_field() {
+        let signed_at = DateTime::from_timestamp(1_718_000_000, 0).unwrap();
+        assert_eq!(
+            message("CABC", "testnet", "AB12", Some("deadbeef"), signed_at),
+            "soroban-registry-attestation/v1\ncontract_id:CABC\nnetwork:testnet\n\
+             wasm_hash:ab12\nsource_commit:deadbeef\ntimestamp:1718000000"
+        );
+        assert!(message("CABC", "testnet", "ab12", None, signed_at).contains("\nsource_commit:\n"));
+    }
+}
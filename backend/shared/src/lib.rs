@@ -1,9 +1,14 @@
 pub mod abi;
+pub mod attestation;
 pub mod error;
 pub mod models;
+pub mod naming;
+pub mod resolver;
 pub mod semver;
+pub mod wasm_features;
 
 pub use abi::*;
 pub use error::*;
 pub use models::*;
+pub use resolver::*;
 pub use semver::*;
@@ -21,6 +21,15 @@ pub struct Contract {
     pub is_verified: bool,
     pub category: Option<String>,
     pub tags: Vec<String>,
+    /// Category-specific metadata, validated against the category's schema
+    #[serde(default)]
+    pub metadata: serde_json::Value,
+    /// Verification tier of the latest version
+    #[serde(default)]
+    pub verification_tier: VerificationTier,
+    /// Minimum Soroban protocol of the latest version's wasm, once analysed
+    #[serde(default)]
+    pub min_protocol_version: Option<i32>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -28,6 +37,7 @@ pub struct Contract {
 /// Network where the contract is deployed
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "network_type", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum Network {
     Mainnet,
     Testnet,
@@ -44,7 +54,99 @@ pub struct ContractVersion {
     pub source_url: Option<String>,
     pub commit_hash: Option<String>,
     pub release_notes: Option<String>,
+    #[serde(default)]
+    pub verification_tier: VerificationTier,
+    /// Minimum Soroban protocol required by this version's wasm, once analysed
+    #[serde(default)]
+    pub min_protocol_version: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A security patch as it relates to one contract (see GET /api/contracts/:id/patches)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ContractPatch {
+    pub id: Uuid,
+    pub target_version: String,
+    pub severity: String,
+    pub new_wasm_hash: String,
+    pub description: Option<String>,
     pub created_at: DateTime<Utc>,
+    /// When the patch was applied to this contract, if it was
+    pub applied_at: Option<DateTime<Utc>>,
+    /// Version of this contract whose wasm is the patched wasm, if any
+    pub fixed_in_version: Option<String>,
+}
+
+/// Graduated verification levels, ordered from weakest to strongest.
+///
+/// Postgres compares enum values in declaration order, so
+/// `verification_tier >= 'build'` selects L2 and L3.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, sqlx::Type)]
+#[sqlx(type_name = "verification_tier", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum VerificationTier {
+    #[default]
+    Unverified,
+    /// L1 — submitted source matched the on-chain wasm
+    Source,
+    /// L2 — reproducible build attested
+    Build,
+    /// L3 — passing report from a registered auditor on file
+    Audit,
+}
+
+impl VerificationTier {
+    /// Numeric level shown in badges (L0–L3).
+    pub fn level(&self) -> u8 {
+        match self {
+            Self::Unverified => 0,
+            Self::Source => 1,
+            Self::Build => 2,
+            Self::Audit => 3,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Unverified => "Unverified",
+            Self::Source => "L1 Source matched",
+            Self::Build => "L2 Reproducible build",
+            Self::Audit => "L3 Audited",
+        }
+    }
+}
+
+impl std::fmt::Display for VerificationTier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Unverified => "unverified",
+            Self::Source => "source",
+            Self::Build => "build",
+            Self::Audit => "audit",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl std::str::FromStr for VerificationTier {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "unverified" | "l0" => Ok(Self::Unverified),
+            "source" | "l1" => Ok(Self::Source),
+            "build" | "l2" => Ok(Self::Build),
+            "audit" | "l3" => Ok(Self::Audit),
+            _ => Err(format!("Unknown verification tier: {}", s)),
+        }
+    }
+}
+
+/// Request body for PUT /api/contracts/:id/versions/:version/verification-tier
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetVerificationTierRequest {
+    pub tier: VerificationTier,
+    pub evidence_url: Option<String>,
 }
 
 /// Verification status and details
@@ -80,6 +182,8 @@ pub struct Publisher {
     pub github_url: Option<String>,
     pub website: Option<String>,
     pub created_at: DateTime<Utc>,
+    /// Set once the publisher's personal data has been erased
+    pub erased_at: Option<DateTime<Utc>>,
 }
 
 /// Contract interaction statistics
@@ -92,6 +196,32 @@ pub struct ContractStats {
     pub last_interaction: Option<DateTime<Utc>>,
 }
 
+/// Health (trust score) band of a contract
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Healthy,
+    Warning,
+    Critical,
+}
+
+/// One row in `contract_health`, recomputed by the health monitor
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ContractHealth {
+    pub contract_id: Uuid,
+    pub status: HealthStatus,
+    pub last_activity: DateTime<Utc>,
+    pub security_score: i32,
+    pub audit_date: Option<DateTime<Utc>>,
+    pub total_score: i32,
+    pub recommendations: Vec<String>,
+    /// Direct dependencies that have been deprecated
+    #[sqlx(json)]
+    pub deprecated_dependencies: Vec<DeprecatedDependency>,
+    pub updated_at: DateTime<Utc>,
+}
+
 /// Request to publish a new contract
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PublishRequest {
@@ -103,6 +233,9 @@ pub struct PublishRequest {
     pub tags: Vec<String>,
     pub source_url: Option<String>,
     pub publisher_address: String,
+    /// Category-specific metadata (validated against the category schema)
+    #[serde(default)]
+    pub metadata: Option<serde_json::Value>,
     // Dependencies (new field)
     #[serde(default)]
     pub dependencies: Vec<DependencyDeclaration>,
@@ -153,11 +286,41 @@ pub struct ContractSearchParams {
     pub verified_only: Option<bool>,
     pub category: Option<String>,
     pub tags: Option<Vec<String>>,
+    /// Only return contracts at or above this verification tier
+    pub min_tier: Option<VerificationTier>,
+    /// JSON object matched against contract metadata by containment,
+    /// e.g. `{"symbol":"USDC"}`
+    pub metadata: Option<String>,
     pub page: Option<i64>,
     #[serde(alias = "page_size")]
     pub limit: Option<i64>,
 }
 
+/// JSON Schema registered by an admin for a contract category
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CategoryMetadataSchema {
+    pub category: String,
+    pub schema: serde_json::Value,
+    pub updated_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request body for PUT /api/categories/:category/metadata-schema
+///
+/// The change is attributed to the signed-in admin identity, never a field
+/// of the body.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpsertMetadataSchemaRequest {
+    pub schema: serde_json::Value,
+}
+
+/// Request body for PATCH /api/contracts/:id/metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateContractMetadataRequest {
+    pub metadata: serde_json::Value,
+}
+
 /// Paginated response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaginatedResponse<T> {
@@ -214,6 +377,13 @@ pub struct CreateMigrationRequest {
     pub wasm_hash: String,
 }
 
+/// Request to update a migration's status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateMigrationStatusRequest {
+    pub status: MigrationStatus,
+    pub log_output: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "deployment_environment", rename_all = "lowercase")]
 pub enum DeploymentEnvironment {
@@ -577,6 +747,113 @@ pub struct CreateAlertConfigRequest {
     pub severity: Option<AlertSeverity>,
 }
 
+// ────────────────────────────────────────────────────────────────────────────
+// Multi-signature deployment models
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Lifecycle of a deployment proposal
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq)]
+#[sqlx(type_name = "proposal_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ProposalStatus {
+    Pending,
+    Approved,
+    Executed,
+    Expired,
+    Rejected,
+}
+
+impl std::fmt::Display for ProposalStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::Executed => "executed",
+            Self::Expired => "expired",
+            Self::Rejected => "rejected",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Who may sign deployment proposals and how many signatures approve one
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MultisigPolicy {
+    pub id: Uuid,
+    pub name: String,
+    pub threshold: i32,
+    pub signer_addresses: Vec<String>,
+    pub expiry_seconds: i32,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request body for POST /api/multisig/policies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatePolicyRequest {
+    pub name: String,
+    pub threshold: i32,
+    pub signer_addresses: Vec<String>,
+    pub expiry_seconds: Option<i32>,
+    pub created_by: String,
+}
+
+/// A deployment waiting for signatures under a multisig policy
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DeployProposal {
+    pub id: Uuid,
+    pub contract_name: String,
+    pub contract_id: String,
+    pub wasm_hash: String,
+    pub network: Network,
+    pub description: Option<String>,
+    pub policy_id: Uuid,
+    pub status: ProposalStatus,
+    pub expires_at: DateTime<Utc>,
+    pub executed_at: Option<DateTime<Utc>>,
+    pub proposer: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request body for POST /api/contracts/deploy-proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateProposalRequest {
+    pub contract_name: String,
+    pub contract_id: String,
+    pub wasm_hash: String,
+    pub network: Network,
+    pub description: Option<String>,
+    pub policy_id: Uuid,
+    pub proposer: String,
+}
+
+/// One signer's approval of a proposal
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ProposalSignature {
+    pub id: Uuid,
+    pub proposal_id: Uuid,
+    pub signer_address: String,
+    pub signature_data: Option<String>,
+    pub signed_at: DateTime<Utc>,
+}
+
+/// Request body for POST /api/contracts/:id/sign
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignProposalRequest {
+    pub signer_address: String,
+    pub signature_data: Option<String>,
+}
+
+/// A proposal with its policy and the signatures collected so far
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalWithSignatures {
+    pub proposal: DeployProposal,
+    pub policy: MultisigPolicy,
+    pub signatures: Vec<ProposalSignature>,
+    pub signatures_needed: i32,
+}
+
 // ────────────────────────────────────────────────────────────────────────────
 // Analytics models
 // ────────────────────────────────────────────────────────────────────────────
@@ -675,6 +952,35 @@ pub struct TimelineEntry {
     pub count: i64,
 }
 
+/// Response for GET /api/publishers/:id/analytics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublisherAnalyticsResponse {
+    pub publisher_id: Uuid,
+    pub total_contracts: i64,
+    pub verified_contracts: i64,
+    /// Share of the publisher's contracts that are verified, 0.0–100.0
+    pub verification_coverage: f64,
+    pub total_downloads: i64,
+    pub total_invocations: i64,
+    pub average_trust_score: Option<f64>,
+    pub trust_score_distribution: TrustScoreDistribution,
+    pub recent_events: Vec<AnalyticsEvent>,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Number of contracts in each trust-score band (same bands as contract health)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, FromRow)]
+pub struct TrustScoreDistribution {
+    /// total_score 80–100
+    pub healthy: i64,
+    /// total_score 50–79
+    pub warning: i64,
+    /// total_score below 50
+    pub critical: i64,
+    /// No health record computed yet
+    pub unscored: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeployGreenRequest {
     pub contract_id: String,
@@ -685,6 +991,9 @@ pub struct DeployGreenRequest {
 pub struct SwitchDeploymentRequest {
     pub contract_id: String,
     pub force: Option<bool>,
+    /// Stellar address of whoever asks for the switch; required when the
+    /// contract has a deployment approval policy
+    pub requested_by: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -708,6 +1017,7 @@ pub enum AuditActionType {
     PublisherChanged,
     VersionCreated,
     Rollback,
+    SettingsChanged,
 }
 
 impl std::fmt::Display for AuditActionType {
@@ -719,6 +1029,7 @@ impl std::fmt::Display for AuditActionType {
             Self::PublisherChanged   => "publisher_changed",
             Self::VersionCreated     => "version_created",
             Self::Rollback           => "rollback",
+            Self::SettingsChanged    => "settings_changed",
         };
         write!(f, "{}", s)
     }
@@ -734,6 +1045,9 @@ pub struct ContractAuditLog {
     pub new_value:   Option<serde_json::Value>,
     pub changed_by:  String,
     pub timestamp:   DateTime<Utc>,
+    /// Admin impersonation session the change was made through, if any
+    pub impersonation_session_id: Option<Uuid>,
+    pub impersonated_by: Option<String>,
 }
 
 /// Full contract state captured at each audited change in `contract_snapshots`.
@@ -772,8 +1086,9 @@ pub struct VersionDiff {
 /// Request body for POST /api/contracts/:id/rollback/:snapshot_id
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RollbackRequest {
-    /// Stellar address (or admin service ID) authorising the rollback
-    pub changed_by: String,
+    /// Recorded for rollbacks made with the static admin token; otherwise
+    /// the authenticated owner or named admin is recorded
+    pub changed_by: Option<String>,
 }
 
 /// Paginated response for audit log
@@ -784,3 +1099,1016 @@ pub struct AuditLogPage {
     pub page:        i64,
     pub total_pages: i64,
 }
+
+// ════════════════════════════════════════════════════════════════════════════
+// Artifact storage types
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Kind of large artifact stored in object storage.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "artifact_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    Wasm,
+    SourceBundle,
+    /// PDF audit report uploaded by a registered auditor
+    AuditReport,
+}
+
+impl std::fmt::Display for ArtifactKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Wasm => write!(f, "wasm"),
+            Self::SourceBundle => write!(f, "source_bundle"),
+            Self::AuditReport => write!(f, "audit_report"),
+        }
+    }
+}
+
+/// Lifecycle of a direct-to-bucket upload.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "artifact_upload_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ArtifactUploadStatus {
+    Pending,
+    Completed,
+    Failed,
+}
+
+/// One row in `artifact_uploads`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ArtifactUpload {
+    pub id: Uuid,
+    pub contract_id: Uuid,
+    pub kind: ArtifactKind,
+    pub object_key: String,
+    pub sha256: String,
+    pub size_bytes: i64,
+    pub status: ArtifactUploadStatus,
+    pub error_message: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Publisher that started the upload (wasm and source bundles)
+    pub publisher_id: Option<Uuid>,
+    /// Auditor that started the upload (audit reports)
+    pub auditor_id: Option<Uuid>,
+    /// When the pre-signed PUT expires; a pending wasm upload counts
+    /// against the storage quota until then
+    pub upload_expires_at: Option<DateTime<Utc>>,
+}
+
+/// Request body for POST /api/artifacts/uploads
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateArtifactUploadRequest {
+    pub contract_id: Uuid,
+    pub kind: ArtifactKind,
+    /// Hex-encoded SHA-256 of the artifact the client is about to upload
+    pub sha256: String,
+    pub size_bytes: i64,
+}
+
+/// A pre-signed URL handed to the client instead of proxying bytes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresignedUrl {
+    pub method: String,
+    pub url: String,
+    /// Headers that must be sent verbatim with the request
+    pub headers: std::collections::BTreeMap<String, String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Response for POST /api/artifacts/uploads
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactUploadResponse {
+    pub upload: ArtifactUpload,
+    pub upload_url: PresignedUrl,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Audit report intake types
+// ════════════════════════════════════════════════════════════════════════════
+
+/// A registered third-party auditor allowed to upload reports.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Auditor {
+    pub id: Uuid,
+    pub name: String,
+    pub stellar_address: Option<String>,
+    pub website: Option<String>,
+    /// SHA-256 of the auditor's API key; the key itself is never stored
+    #[serde(skip_serializing)]
+    pub api_key_hash: String,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request body for POST /api/auditors
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterAuditorRequest {
+    pub name: String,
+    pub stellar_address: Option<String>,
+    pub website: Option<String>,
+}
+
+/// Response for POST /api/auditors — the API key is only shown once
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterAuditorResponse {
+    pub auditor: Auditor,
+    pub api_key: String,
+}
+
+/// One row in `audit_reports`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuditReport {
+    pub id: Uuid,
+    pub contract_id: Uuid,
+    pub audit_id: Uuid,
+    pub auditor_id: Uuid,
+    /// Contract version the report covers
+    pub contract_version_id: Option<Uuid>,
+    /// Completed `audit_report` artifact upload holding the PDF
+    pub artifact_upload_id: Uuid,
+    pub findings: serde_json::Value,
+    pub schema_version: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request body for POST /api/contracts/:id/audit-reports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitAuditReportRequest {
+    /// Version of the contract that was audited
+    pub version: String,
+    pub artifact_upload_id: Uuid,
+    /// Structured findings conforming to GET /api/audit-reports/schema
+    pub findings: serde_json::Value,
+}
+
+/// Response for POST /api/contracts/:id/audit-reports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditReportResponse {
+    pub report: AuditReport,
+    pub overall_score: f64,
+    /// Number of checklist items populated from the findings
+    pub mapped_checks: usize,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Deployment approval types
+// ════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "deployment_action", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum DeploymentAction {
+    Switch,
+    Rollback,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "switch_request_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum SwitchRequestStatus {
+    Pending,
+    Executed,
+    Rejected,
+    Expired,
+    Failed,
+}
+
+/// One row in `deployment_approval_policies`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DeploymentApprovalPolicy {
+    pub contract_id: Uuid,
+    pub approvers: Option<Vec<String>>,
+    pub required_approvals: Option<i32>,
+    /// Reuse an existing multisig policy's signers and threshold
+    pub multisig_policy_id: Option<Uuid>,
+    pub expiry_seconds: i32,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request body for PUT /api/contracts/:id/deployment-approval-policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetApprovalPolicyRequest {
+    pub approvers: Option<Vec<String>>,
+    pub required_approvals: Option<i32>,
+    pub multisig_policy_id: Option<Uuid>,
+    pub expiry_seconds: Option<i32>,
+}
+
+/// One row in `deployment_switch_requests`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DeploymentSwitchRequest {
+    pub id: Uuid,
+    pub contract_id: Uuid,
+    pub action: DeploymentAction,
+    pub force: bool,
+    pub requested_by: String,
+    pub approvers: Vec<String>,
+    pub required_approvals: i32,
+    pub status: SwitchRequestStatus,
+    pub error_message: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One row in `deployment_switch_decisions`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SwitchDecision {
+    pub id: Uuid,
+    pub request_id: Uuid,
+    pub approver: String,
+    pub approved: bool,
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request body for POST /api/deployments/switch-requests/:id/{approve,reject}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecideSwitchRequest {
+    /// Approver address to act as; defaults to the session's only proven
+    /// address among the request's approvers
+    pub approver: Option<String>,
+    pub comment: Option<String>,
+}
+
+/// A queued switch together with the decisions recorded so far.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwitchRequestWithDecisions {
+    #[serde(flatten)]
+    pub request: DeploymentSwitchRequest,
+    pub decisions: Vec<SwitchDecision>,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Resource budget types
+// ════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "budget_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetKind {
+    /// Max CPU instructions of a single invocation
+    CpuInstructions,
+    /// Max fee (stroops) of a single invocation
+    FeePerInvocation,
+    /// Max total fees (stroops) per UTC day
+    DailyFees,
+}
+
+/// One row in `contract_budgets`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ContractBudget {
+    pub contract_id: Uuid,
+    pub max_cpu_instructions: Option<i64>,
+    pub max_fee_per_invocation: Option<i64>,
+    pub max_daily_fees: Option<i64>,
+    /// Receives a POST for every new budget alert; left out of public
+    /// responses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+    pub updated_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request body for PUT /api/contracts/:id/budget
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetBudgetRequest {
+    pub max_cpu_instructions: Option<i64>,
+    pub max_fee_per_invocation: Option<i64>,
+    pub max_daily_fees: Option<i64>,
+    pub webhook_url: Option<String>,
+}
+
+/// One row in `budget_alerts`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BudgetAlert {
+    pub id: Uuid,
+    pub contract_id: Uuid,
+    pub kind: BudgetKind,
+    pub limit_value: i64,
+    pub observed_value: i64,
+    /// UTC day the alert covers
+    pub window_start: chrono::NaiveDate,
+    /// When the webhook accepted the alert
+    pub notified_at: Option<DateTime<Utc>>,
+    pub delivery_attempts: i32,
+    pub last_delivery_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Usage observed for a contract during the current UTC day.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, FromRow)]
+pub struct BudgetUsage {
+    pub max_cpu_instructions: Option<i64>,
+    pub max_fee_per_invocation: Option<i64>,
+    pub total_fees: Option<i64>,
+    pub invocations: i64,
+}
+
+/// A budget limit exceeded by observed usage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BudgetBreach {
+    pub kind: BudgetKind,
+    pub limit: i64,
+    pub observed: i64,
+}
+
+/// Response for GET /api/contracts/:id/budget/status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetStatusResponse {
+    /// Without its `webhook_url`
+    pub budget: ContractBudget,
+    pub webhook_configured: bool,
+    pub usage_today: BudgetUsage,
+    pub breaches: Vec<BudgetBreach>,
+    pub recent_alerts: Vec<BudgetAlert>,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Contract event schema types
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Where an event field is carried: in the topic list or in the data payload.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventFieldLocation {
+    Topic,
+    Data,
+}
+
+/// One named, typed field of a contract event.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EventFieldSchema {
+    pub name: String,
+    /// Soroban type, e.g. `address`, `i128`, `vec<symbol>` or a UDT name from the ABI
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub location: EventFieldLocation,
+}
+
+/// Layout of a single event: constant prefix topics followed by named fields.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EventSchemaDefinition {
+    pub event_name: String,
+    /// Constant leading topics (usually the event name as a symbol)
+    #[serde(default)]
+    pub prefix_topics: Vec<String>,
+    pub fields: Vec<EventFieldSchema>,
+}
+
+/// One row in `contract_event_schemas`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ContractEventSchema {
+    pub id: Uuid,
+    pub contract_id: Uuid,
+    pub version: String,
+    pub event_name: String,
+    pub prefix_topics: Vec<String>,
+    pub fields: serde_json::Value,
+    pub registered_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request body for PUT /api/contracts/:id/versions/:version/event-schemas
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterEventSchemasRequest {
+    pub events: Vec<EventSchemaDefinition>,
+}
+
+/// A raw event as emitted on chain, with positional topics and data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RawContractEvent {
+    pub topics: Vec<serde_json::Value>,
+    pub data: serde_json::Value,
+    #[serde(default)]
+    pub ledger: Option<i64>,
+    #[serde(default)]
+    pub tx_hash: Option<String>,
+}
+
+/// Request body for POST /api/contracts/:id/events/decode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodeEventsRequest {
+    /// Contract version whose schemas apply; defaults to the latest version
+    pub version: Option<String>,
+    pub events: Vec<RawContractEvent>,
+}
+
+/// A decoded field value with its declared type.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TypedEventValue {
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub value: serde_json::Value,
+}
+
+/// An event decoded through its registered schema.
+///
+/// Events that match no schema come back with `event_name: None` and their
+/// positional `topics`/`data` untouched.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodedContractEvent {
+    pub event_name: Option<String>,
+    pub fields: std::collections::BTreeMap<String, TypedEventValue>,
+    pub topics: Vec<serde_json::Value>,
+    pub data: serde_json::Value,
+    pub ledger: Option<i64>,
+    pub tx_hash: Option<String>,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Search autocomplete types
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Query parameters for GET /api/contracts/autocomplete
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutocompleteParams {
+    pub q: String,
+    /// Max suggestions to return (default 10, max 25)
+    pub limit: Option<i64>,
+}
+
+/// A search-as-you-type suggestion.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AutocompleteSuggestion {
+    /// `contract` or `tag`
+    pub kind: String,
+    pub value: String,
+    /// Set for `contract` suggestions
+    pub contract_id: Option<Uuid>,
+    /// 1.0 for prefix matches, trigram word similarity otherwise
+    pub score: f32,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Publisher data export / erasure types
+// ════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "erasure_request_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ErasureRequestStatus {
+    Pending,
+    Completed,
+    Rejected,
+}
+
+/// One row in `publisher_erasure_requests`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PublisherErasureRequest {
+    pub id: Uuid,
+    pub publisher_id: Uuid,
+    /// Publisher address, replaced by its pseudonym once erased
+    pub requested_by: String,
+    pub reason: Option<String>,
+    pub status: ErasureRequestStatus,
+    pub reviewed_by: Option<String>,
+    pub review_note: Option<String>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request body for POST /api/publishers/:id/erasure-requests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateErasureRequest {
+    pub reason: Option<String>,
+}
+
+/// Request body for POST /api/erasure-requests/:id/{approve,reject}
+///
+/// The reviewer is the signed-in admin identity, never a field of the body.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewErasureRequest {
+    pub note: Option<String>,
+}
+
+/// One row in `publisher_erasure_audit`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ErasureAuditEntry {
+    pub id: Uuid,
+    pub request_id: Uuid,
+    /// `requested`, `approved`, `rejected` or `erased`
+    pub action: String,
+    pub actor: String,
+    pub detail: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An erasure request with its audit trail.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErasureRequestWithAudit {
+    #[serde(flatten)]
+    pub request: PublisherErasureRequest,
+    pub audit: Vec<ErasureAuditEntry>,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// External login (OIDC / GitHub) types
+// ════════════════════════════════════════════════════════════════════════════
+
+/// One row in `external_identities`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ExternalIdentityRecord {
+    pub id: Uuid,
+    /// Provider name, e.g. `github`
+    pub provider: String,
+    /// Stable provider-side user id
+    pub subject: String,
+    pub username: Option<String>,
+    pub email: Option<String>,
+    /// Organisations / groups reported by the provider at last login
+    pub orgs: Vec<String>,
+    /// Publisher this identity is directly linked to
+    pub publisher_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub last_login_at: DateTime<Utc>,
+}
+
+/// Login option listed by GET /api/auth/providers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthProviderInfo {
+    pub name: String,
+    /// `github` or `oidc`
+    pub kind: String,
+    pub login_url: String,
+}
+
+/// Session returned after external login and by GET /api/auth/session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthSessionResponse {
+    /// Only set right after login
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    pub identity: ExternalIdentityRecord,
+    /// Publishers this identity may publish for (direct link or org grant)
+    pub publishers: Vec<Uuid>,
+}
+
+/// Response for GET /api/auth/whoami
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WhoAmIResponse {
+    /// `admin`, `auditor`, `session` or `impersonation`
+    pub kind: String,
+    /// Auditor name, session username or impersonating admin
+    pub name: Option<String>,
+    /// Publishers a session may publish for
+    #[serde(default)]
+    pub publishers: Vec<Uuid>,
+}
+
+/// Response for POST /api/auth/token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredentialTokenResponse {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+    /// `admin` or `auditor`
+    pub kind: String,
+    /// Auditor name
+    pub name: Option<String>,
+}
+
+/// Request body for POST /api/auth/challenge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyChallengeRequest {
+    /// Stellar address whose key will sign the challenge
+    pub address: String,
+}
+
+/// Response for POST /api/auth/challenge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyChallengeResponse {
+    /// Sign the UTF-8 bytes of this string with the address's key
+    pub challenge: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A signed key challenge; request body for POST /api/auth/stellar and
+/// POST /api/auth/link
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyProof {
+    pub challenge: String,
+    /// Ed25519 signature, hex or base64
+    pub signature: String,
+}
+
+/// One row in `publisher_org_grants`: members of `org` on `provider` may
+/// publish on behalf of the publisher.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PublisherOrgGrant {
+    pub publisher_id: Uuid,
+    pub provider: String,
+    pub org: String,
+    /// External identity that added the grant; `None` once it is erased
+    pub granted_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request body for POST /api/publishers/:id/org-grants
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrantOrgRequest {
+    pub provider: String,
+    pub org: String,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Public status page types
+// ════════════════════════════════════════════════════════════════════════════
+
+/// One row in `status_snapshots`, written by the API's status aggregator.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct StatusSnapshot {
+    pub id: Uuid,
+    pub taken_at: DateTime<Utc>,
+    pub db_latency_ms: i32,
+    /// Percentages (0–100) of expected snapshots actually recorded
+    pub uptime_24h: f64,
+    pub uptime_7d: f64,
+    pub uptime_30d: f64,
+    /// Verifications still pending
+    pub verification_queue_depth: i64,
+    /// Mean submission → verified time over the last 7 days
+    pub avg_verification_turnaround_secs: Option<f64>,
+}
+
+/// Indexing lag of one network at snapshot time
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct NetworkIndexingLag {
+    pub network: Network,
+    pub last_indexed_at: Option<DateTime<Utc>>,
+    pub lag_seconds: Option<i64>,
+}
+
+/// Response for GET /api/status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusResponse {
+    /// `operational`, or `stale` when the aggregator has stopped reporting
+    pub status: String,
+    pub snapshot: StatusSnapshot,
+    pub indexing: Vec<NetworkIndexingLag>,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Publisher quota types
+// ════════════════════════════════════════════════════════════════════════════
+
+/// One row in `quota_tiers`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct QuotaTier {
+    pub name: String,
+    pub max_contracts: i32,
+    pub max_versions_per_day: i32,
+    pub max_wasm_storage_bytes: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request body for PUT /api/quota-tiers/:name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpsertQuotaTierRequest {
+    pub max_contracts: i32,
+    pub max_versions_per_day: i32,
+    pub max_wasm_storage_bytes: i64,
+}
+
+/// Request body for PUT /api/publishers/:id/quota.
+///
+/// Limits left unset fall back to the tier's values.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetPublisherQuotaRequest {
+    pub tier: String,
+    pub max_contracts: Option<i32>,
+    pub max_versions_per_day: Option<i32>,
+    pub max_wasm_storage_bytes: Option<i64>,
+    pub note: Option<String>,
+}
+
+/// Limits in force for a publisher: its tier plus any overrides
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct QuotaLimits {
+    pub tier: String,
+    pub max_contracts: i32,
+    pub max_versions_per_day: i32,
+    pub max_wasm_storage_bytes: i64,
+}
+
+/// Current consumption counted against a publisher's quota
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct QuotaUsage {
+    pub contracts: i64,
+    /// New contracts and contract versions since UTC midnight
+    pub versions_today: i64,
+    /// Distinct wasm artifacts uploaded (pending or completed)
+    pub wasm_storage_bytes: i64,
+}
+
+/// Response for GET /api/publishers/:id/quota
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublisherQuotaStatus {
+    pub publisher_id: Uuid,
+    pub limits: QuotaLimits,
+    pub usage: QuotaUsage,
+    /// Admin note recorded with the publisher's quota adjustment
+    pub note: Option<String>,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Contract deprecation types
+// ════════════════════════════════════════════════════════════════════════════
+
+/// One row in `contract_deprecations`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ContractDeprecation {
+    pub contract_id: Uuid,
+    pub reason: String,
+    pub replacement_contract_id: Option<Uuid>,
+    pub deprecated_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request body for PUT /api/contracts/:id/deprecation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeprecateContractRequest {
+    pub reason: String,
+    pub replacement_contract_id: Option<Uuid>,
+}
+
+/// Response for PUT /api/contracts/:id/deprecation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeprecationResponse {
+    pub deprecation: ContractDeprecation,
+    /// Dependents a new deprecation notice was recorded for.  Notices are
+    /// not pushed anywhere; dependents read them from the notices endpoint.
+    pub notified_dependents: u64,
+}
+
+/// A deprecated dependency of a contract
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DeprecatedDependency {
+    pub dependency_name: String,
+    pub contract_id: Uuid,
+    pub reason: String,
+    pub replacement_contract_id: Option<Uuid>,
+    pub replacement_name: Option<String>,
+    pub deprecated_at: DateTime<Utc>,
+}
+
+/// Notice recorded for a dependent when one of its dependencies is deprecated
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DeprecationNotice {
+    pub id: Uuid,
+    pub dependent_contract_id: Uuid,
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub dependency: DeprecatedDependency,
+    pub created_at: DateTime<Utc>,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Wasm feature detection types
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Analysis of an uploaded wasm, keyed by its SHA-256 (see `wasm_features`).
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WasmFeatureReport {
+    pub wasm_sha256: String,
+    pub min_protocol_version: Option<i32>,
+    pub pre_release: Option<i32>,
+    /// Imported host functions as `module.name`
+    pub host_functions: Vec<String>,
+    pub features: Vec<String>,
+    pub analyzed_at: DateTime<Utc>,
+}
+
+/// Highest Soroban protocol a network currently runs.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct NetworkProtocol {
+    pub network: Network,
+    pub protocol_version: i32,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request body for PUT /api/networks/:network/protocol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetNetworkProtocolRequest {
+    pub protocol_version: i32,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Admin impersonation types
+// ════════════════════════════════════════════════════════════════════════════
+
+/// One row in `impersonation_sessions`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ImpersonationSession {
+    pub id: Uuid,
+    pub publisher_id: Uuid,
+    pub admin_name: String,
+    pub reason: String,
+    pub read_only: bool,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// Request body for POST /api/admin/impersonations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartImpersonationRequest {
+    pub publisher_id: Uuid,
+    pub reason: String,
+    /// Defaults to true; writes need an explicit `false`
+    pub read_only: Option<bool>,
+    /// Token lifetime, default 15 minutes
+    pub ttl_minutes: Option<i64>,
+}
+
+/// Response for POST /api/admin/impersonations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImpersonationTokenResponse {
+    pub session: ImpersonationSession,
+    pub token: String,
+}
+
+/// One request made with an impersonation token
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ImpersonationAuditEntry {
+    pub id: Uuid,
+    pub session_id: Uuid,
+    pub admin_name: String,
+    pub publisher_id: Uuid,
+    pub method: String,
+    pub path: String,
+    pub status_code: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Name reservation and squatting types
+// ════════════════════════════════════════════════════════════════════════════
+
+/// One row in `name_reservations`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct NameReservation {
+    pub id: Uuid,
+    pub publisher_id: Uuid,
+    pub name: String,
+    /// `naming::skeleton(name)`
+    pub skeleton: String,
+    /// Also reserves `<name>-*`, `<name>_*`, `<name>.*` and `<name>/*`
+    pub namespace: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request body for POST /api/names/reservations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReserveNameRequest {
+    /// Publisher to reserve for; the caller must be signed in as it
+    pub publisher_address: String,
+    pub name: String,
+    pub namespace: Option<bool>,
+}
+
+/// An existing name a candidate could be mistaken for.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NameMatch {
+    pub name: String,
+    pub contract_id: Option<Uuid>,
+    pub reservation_id: Option<Uuid>,
+    pub confusion: crate::naming::Confusion,
+}
+
+/// Response for GET /api/names/check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NameCheckResponse {
+    pub name: String,
+    pub skeleton: String,
+    /// Reservation of another publisher covering the name, if any
+    pub reserved_by: Option<NameReservation>,
+    /// Publishing would be flagged for moderation because of these
+    pub similar: Vec<NameMatch>,
+}
+
+/// Moderation state of a name flag
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum NameFlagStatus {
+    Pending,
+    Dismissed,
+    Confirmed,
+}
+
+/// One row in `name_flags`: a published name that looks like squatting.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct NameFlag {
+    pub id: Uuid,
+    pub contract_id: Uuid,
+    pub publisher_id: Uuid,
+    pub name: String,
+    /// `Vec<NameMatch>`
+    pub matches: serde_json::Value,
+    pub status: NameFlagStatus,
+    pub review_note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+}
+
+/// Request body for POST /api/admin/name-flags/:id/review
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewNameFlagRequest {
+    /// `dismissed` or `confirmed`
+    pub status: NameFlagStatus,
+    pub note: Option<String>,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Verification job types
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Lifecycle of a verification job
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum VerificationJobStatus {
+    Queued,
+    Building,
+    Comparing,
+    /// The pipeline ran to the end; the outcome is in the report
+    Succeeded,
+    /// The pipeline could not run
+    Failed,
+    Cancelled,
+}
+
+impl VerificationJobStatus {
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Succeeded | Self::Failed | Self::Cancelled)
+    }
+}
+
+/// One row in `verification_jobs`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct VerificationJob {
+    pub id: Uuid,
+    pub contract_id: Uuid,
+    pub status: VerificationJobStatus,
+    /// Inline source; not echoed back
+    #[serde(skip_serializing, default)]
+    pub source_code: Option<String>,
+    /// Provenance of git sources
+    pub source_repository: Option<String>,
+    pub source_commit: Option<String>,
+    pub source_path: Option<String>,
+    pub compiler_version: String,
+    pub build_params: serde_json::Value,
+    pub deployed_wasm_hash: String,
+    /// Deployed module, when supplied; used to explain mismatches
+    #[serde(skip_serializing, default)]
+    pub deployed_wasm: Option<Vec<u8>>,
+    /// `verifier::VerificationReport`, once the job succeeded
+    pub report: Option<serde_json::Value>,
+    pub error_message: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Verification attestation types
+// ════════════════════════════════════════════════════════════════════════════
+
+/// One row in `verification_attestations`: the registry's signed statement
+/// that a contract's source was verified (see `attestation::message`).
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct VerificationAttestation {
+    pub id: Uuid,
+    pub contract_id: Uuid,
+    pub job_id: Option<Uuid>,
+    /// On-chain contract address
+    pub onchain_contract_id: String,
+    pub network: String,
+    pub wasm_hash: String,
+    pub source_commit: Option<String>,
+    pub signed_at: DateTime<Utc>,
+    /// Hex Ed25519 public key of the signer
+    pub public_key: String,
+    /// Hex Ed25519 signature over the attestation message
+    pub signature: String,
+}
+
+/// Response for GET /api/contracts/:id/attestation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttestationResponse {
+    #[serde(flatten)]
+    pub attestation: VerificationAttestation,
+    /// Always `ed25519`
+    pub algorithm: String,
+    /// The exact signed message
+    pub message: String,
+}
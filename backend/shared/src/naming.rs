@@ -0,0 +1,187 @@
+//! Contract name normalisation and confusable-name detection.
+//!
+//! Names are compared by their *skeleton*: lowercased, with look-alike
+//! characters (Cyrillic/Greek letters, `0`/`o`, `rn`/`m`, ...) folded to one
+//! ASCII form and separators dropped, so `Sоroswap` (Cyrillic `о`),
+//! `soro-swap` and `SOROSWAP` all share the skeleton `soroswap`.  Names whose
+//! skeletons are a small edit distance apart are reported as similar.
+
+use serde::{Deserialize, Serialize};
+
+/// Why two names were judged confusable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Confusion {
+    /// Same name apart from case
+    Identical,
+    /// Same skeleton: differs only by look-alike characters or separators
+    Homoglyph,
+    /// Skeletons within the edit-distance threshold
+    EditDistance,
+}
+
+impl std::fmt::Display for Confusion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Identical => write!(f, "identical"),
+            Self::Homoglyph => write!(f, "homoglyph"),
+            Self::EditDistance => write!(f, "edit_distance"),
+        }
+    }
+}
+
+fn fold_char(c: char) -> Option<char> {
+    // Lowercase first so both cases of a letter land on the same fold
+    let c = c.to_lowercase().next().unwrap_or(c);
+    let folded = match c {
+        // Separators carry no weight
+        '-' | '_' | '.' | ' ' | '/' => return None,
+        // Upper-case I reads as l in most UI fonts, so i and l are one letter
+        'i' | 'l' | '1' | '|' | 'ı' | 'ℓ' | 'і' | 'ι' => 'l',
+        '0' | 'о' | 'ο' => 'o',
+        '3' | 'е' | 'ε' => 'e',
+        '5' | '$' | 'ѕ' => 's',
+        '@' | 'а' | 'α' => 'a',
+        'р' | 'ρ' => 'p',
+        'с' | 'ϲ' => 'c',
+        'у' | 'γ' => 'y',
+        'х' | 'χ' => 'x',
+        'ј' => 'j',
+        'к' | 'κ' => 'k',
+        'ν' => 'v',
+        'м' | 'μ' => 'm',
+        'т' | 'τ' => 't',
+        'в' | 'β' => 'b',
+        'н' | 'η' => 'h',
+        c => c,
+    };
+    Some(folded)
+}
+
+/// Canonical form used to compare names.
+pub fn skeleton(name: &str) -> String {
+    let folded: String = name.trim().chars().filter_map(fold_char).collect();
+    // Multi-letter look-alikes, after single characters are folded
+    folded.replace("rn", "m").replace("vv", "w")
+}
+
+/// Levenshtein distance between two strings, by character.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// Largest edit distance still considered confusable for a skeleton length.
+///
+/// Short names are too dense to compare loosely: `dex` and `dai` are not
+/// squatting on each other.
+fn max_distance(len: usize) -> usize {
+    match len {
+        0..=4 => 0,
+        5..=9 => 1,
+        _ => 2,
+    }
+}
+
+/// Whether `candidate` could be mistaken for `existing`.
+pub fn confusable(candidate: &str, existing: &str) -> Option<Confusion> {
+    if candidate.trim().to_lowercase() == existing.trim().to_lowercase() {
+        return Some(Confusion::Identical);
+    }
+
+    let a = skeleton(candidate);
+    let b = skeleton(existing);
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+    if a == b {
+        return Some(Confusion::Homoglyph);
+    }
+
+    let limit = max_distance(a.chars().count().min(b.chars().count()));
+    (limit > 0 && edit_distance(&a, &b) <= limit).then_some(Confusion::EditDistance)
+}
+
+/// Whether `name` falls under a reserved `namespace`, e.g. `soroswap-router`
+/// under `soroswap`.
+pub fn in_namespace(name: &str, namespace: &str) -> bool {
+    // Fold look-alikes but keep separators, which mark the namespace boundary
+    let fold = |s: &str| -> String {
+        s.trim()
+            .chars()
+            .map(|c| fold_char(c).unwrap_or(c))
+            .collect()
+    };
+    let name = fold(name);
+    let namespace = fold(namespace);
+    match name.strip_prefix(&namespace) {
+        Some("") => true,
+        Some(rest) => rest.starts_with(['-', '_', '.', '/']),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skeleton_folds_homoglyphs_and_separators() {
+        // Cyrillic о and а
+        assert_eq!(skeleton("Sоroswаp"), "soroswap");
+        assert_eq!(skeleton("soro-swap"), "soroswap");
+        assert_eq!(skeleton("S0R0SWAP"), "soroswap");
+        assert_eq!(skeleton("Iumen"), skeleton("lumen"));
+        assert_eq!(skeleton("STELLARINDEX"), skeleton("stellarindex"));
+        assert_eq!(skeleton("іndex"), skeleton("index"));
+        assert_eq!(skeleton("rnoney"), skeleton("money"));
+    }
+
+    #[test]
+    fn detects_confusable_names() {
+        assert_eq!(
+            confusable("Soroswap", "soroswap"),
+            Some(Confusion::Identical)
+        );
+        assert_eq!(
+            confusable("sor0swap", "soroswap"),
+            Some(Confusion::Homoglyph)
+        );
+        assert_eq!(
+            confusable("sorosvap", "soroswap"),
+            Some(Confusion::EditDistance)
+        );
+        assert_eq!(
+            confusable("blend-capital", "blendcapitol"),
+            Some(Confusion::EditDistance)
+        );
+        assert_eq!(confusable("router", "rotator"), None);
+        assert_eq!(confusable("dex", "dai"), None);
+        assert_eq!(confusable("oracle", "aquarius"), None);
+    }
+
+    #[test]
+    fn matches_names_under_a_namespace() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert!(in_namespace("Soroswap-Router", "soroswap"));
+        assert!(in_namespace("soroswap", "soroswap"));
+        assert!(in_namespace("S0roswap.factory", "soroswap"));
+        assert!(!in_namespace("soroswapper", "soroswap"));
+        assert!(!in_namespace("swap", "soroswap"));
+    }
+}
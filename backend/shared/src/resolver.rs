@@ -0,0 +1,200 @@
+//! Dependency resolution with human-readable conflict explanations.
+//!
+//! Registry dependencies are declared per contract, so resolution is flat:
+//! every package gets the highest published version satisfying all
+//! constraints placed on it.  When none exists, the constraints are reduced
+//! to a minimal conflicting set ("A requires B ^1.2.0, C requires B ^2.0.0,
+//! no version of B satisfies both").
+
+use crate::semver::{SemVer, VersionConstraint};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, Clone)]
+pub struct Requirement {
+    /// Who declares the dependency (contract name)
+    pub requester: String,
+    pub package: String,
+    pub constraint: VersionConstraint,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConflictRequirement {
+    pub requester: String,
+    pub constraint: String,
+}
+
+/// A minimal set of constraints on `package` that no published version meets.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConflictTrace {
+    pub package: String,
+    pub requirements: Vec<ConflictRequirement>,
+    pub available: Vec<String>,
+    pub explanation: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Resolution {
+    pub resolved: BTreeMap<String, String>,
+    pub conflicts: Vec<ConflictTrace>,
+}
+
+impl Resolution {
+    pub fn is_ok(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+fn best_match<'a>(reqs: &[&Requirement], versions: &'a [SemVer]) -> Option<&'a SemVer> {
+    versions
+        .iter()
+        .filter(|v| reqs.iter().all(|r| r.constraint.matches(v)))
+        .max()
+}
+
+/// Drop constraints one at a time while the rest still conflict, leaving a
+/// set where every constraint is needed for the conflict.  Later
+/// constraints are dropped first so the trace favours the ones declared first.
+fn minimal_conflict<'a>(reqs: &[&'a Requirement], versions: &[SemVer]) -> Vec<&'a Requirement> {
+    let mut core: Vec<&Requirement> = reqs.to_vec();
+    for i in (0..core.len()).rev() {
+        let mut without = core.clone();
+        without.remove(i);
+        if !without.is_empty() && best_match(&without, versions).is_none() {
+            core = without;
+        }
+    }
+    core
+}
+
+fn explain(package: &str, core: &[&Requirement], available: &[String]) -> String {
+    let clauses: Vec<String> = core
+        .iter()
+        .map(|r| format!("{} requires {} {}", r.requester, package, r.constraint))
+        .collect();
+
+    match clauses.as_slice() {
+        [only] if available.is_empty() => {
+            format!("{}, but no version of {} is published", only, package)
+        }
+        [only] => format!(
+            "{}, but no published version of {} satisfies it (available: {})",
+            only,
+            package,
+            available.join(", ")
+        ),
+        [_, _] => format!(
+            "{}, no version of {} satisfies both",
+            clauses.join(", "),
+            package
+        ),
+        _ => format!(
+            "{}, no version of {} satisfies all of them",
+            clauses.join(", "),
+            package
+        ),
+    }
+}
+
+/// Resolve every package constrained in `requirements` against the versions
+/// published for it in `available`.
+pub fn resolve(requirements: &[Requirement], available: &HashMap<String, Vec<SemVer>>) -> Resolution {
+    let mut by_package: BTreeMap<&str, Vec<&Requirement>> = BTreeMap::new();
+    for req in requirements {
+        by_package.entry(req.package.as_str()).or_default().push(req);
+    }
+
+    let mut resolution = Resolution::default();
+    for (package, reqs) in by_package {
+        let versions = available.get(package).map(Vec::as_slice).unwrap_or_default();
+
+        match best_match(&reqs, versions) {
+            Some(version) => {
+                resolution.resolved.insert(package.to_string(), version.to_string());
+            }
+            None => {
+                let core = minimal_conflict(&reqs, versions);
+                let mut sorted = versions.to_vec();
+                sorted.sort();
+                let available: Vec<String> = sorted.iter().map(SemVer::to_string).collect();
+                resolution.conflicts.push(ConflictTrace {
+                    package: package.to_string(),
+                    explanation: explain(package, &core, &available),
+                    requirements: core
+                        .iter()
+                        .map(|r| ConflictRequirement {
+                            requester: r.requester.clone(),
+                            constraint: r.constraint.to_string(),
+                        })
+                        .collect(),
+                    available,
+                });
+            }
+        }
+    }
+    resolution
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn req(requester: &str, package: &str, constraint: &str) -> Requirement {
+        Requirement {
+            requester: requester.into(),
+            package: package.into(),
+            constraint: VersionConstraint::parse(constraint).unwrap(),
+        }
+    }
+
+    fn versions(list: &[&str]) -> Vec<SemVer> {
+        list.iter().map(|v| SemVer::parse(v).unwrap()).collect()
+    }
+
+    #[test]
+    fn picks_highest_version_satisfying_all_constraints() {
+        let available = HashMap::from([("token".to_string(), versions(&["1.2.0", "1.4.1", "2.0.0"]))]);
+        let resolution = resolve(
+            &[req("amm", "token", "^1.2.0"), req("vault", "token", "~1.4.0")],
+            &available,
+        );
+        assert!(resolution.is_ok());
+        assert_eq!(resolution.resolved["token"], "1.4.1");
+    }
+
+    #[test]
+    fn explains_minimal_pairwise_conflict() {
+        let available = HashMap::from([("B".to_string(), versions(&["1.2.0", "1.3.0", "2.0.0"]))]);
+        let resolution = resolve(
+            &[
+                req("A", "B", "^1.2.0"),
+                req("D", "B", "^1.0.0"),
+                req("C", "B", "^2.0.0"),
+            ],
+            &available,
+        );
+
+        let conflict = &resolution.conflicts[0];
+        assert_eq!(conflict.requirements.len(), 2);
+        assert_eq!(
+            conflict.explanation,
+            "A requires B ^1.2.0, C requires B ^2.0.0, no version of B satisfies both"
+        );
+    }
+
+    #[test]
+    fn explains_unsatisfiable_single_constraint() {
+        let available = HashMap::from([("B".to_string(), versions(&["2.0.0", "1.0.0"]))]);
+        let resolution = resolve(&[req("A", "B", "^3.0.0")], &available);
+        assert_eq!(
+            resolution.conflicts[0].explanation,
+            "A requires B ^3.0.0, but no published version of B satisfies it (available: 1.0.0, 2.0.0)"
+        );
+
+        let resolution = resolve(&[req("A", "missing", "1.0.0")], &HashMap::new());
+        assert_eq!(
+            resolution.conflicts[0].explanation,
+            "A requires missing 1.0.0, but no version of missing is published"
+        );
+    }
+}
@@ -93,3 +93,13 @@ impl VersionConstraint {
         }
     }
 }
+
+impl std::fmt::Display for VersionConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VersionConstraint::Exact(v) => write!(f, "{}", v),
+            VersionConstraint::Caret(v) => write!(f, "^{}", v),
+            VersionConstraint::Tilde(v) => write!(f, "~{}", v),
+        }
+    }
+}
@@ -0,0 +1,280 @@
+//! Static analysis of Soroban contract wasm.
+//!
+//! Reads the import section (host functions the contract calls) and the
+//! `contractenvmetav0` custom section, where the SDK records the environment
+//! interface version — and so the minimum protocol — the contract was built
+//! against.  Only section framing is decoded; function bodies are skipped.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+const WASM_MAGIC: &[u8; 4] = b"\0asm";
+const WASM_VERSION: u32 = 1;
+
+const CUSTOM_SECTION: u8 = 0;
+const IMPORT_SECTION: u8 = 2;
+
+/// Custom section holding `SCEnvMetaEntry` XDR.
+pub const ENV_META_SECTION: &str = "contractenvmetav0";
+const SC_ENV_META_KIND_INTERFACE_VERSION: u32 = 0;
+
+/// A host function import, e.g. `l._` (module `l`, name `_`).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct HostFunction {
+    pub module: String,
+    pub name: String,
+}
+
+/// What a contract needs from the network it runs on.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WasmFeatures {
+    /// Protocol of the environment interface the contract was built against
+    pub min_protocol_version: Option<u32>,
+    /// Non-zero when built against a pre-release environment
+    pub pre_release: Option<u32>,
+    pub host_functions: Vec<HostFunction>,
+    /// Host function groups used, e.g. `ledger`, `crypto`
+    pub features: Vec<String>,
+}
+
+/// Host function group for a Soroban env import module.
+fn host_module_feature(module: &str) -> Option<&'static str> {
+    Some(match module {
+        "x" => "context",
+        "i" => "int",
+        "m" => "map",
+        "v" => "vec",
+        "l" => "ledger",
+        "d" => "call",
+        "b" => "buf",
+        "c" => "crypto",
+        "a" => "address",
+        "p" => "prng",
+        "t" => "test",
+        _ => return None,
+    })
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .context("unexpected end of wasm")?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn byte(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn leb_u32(&mut self) -> Result<u32> {
+        let mut result: u32 = 0;
+        for shift in (0..35).step_by(7) {
+            let byte = self.byte()?;
+            result |= u32::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+        }
+        bail!("malformed LEB128 integer")
+    }
+
+    fn name(&mut self) -> Result<&'a str> {
+        let len = self.leb_u32()? as usize;
+        std::str::from_utf8(self.take(len)?).context("name is not valid UTF-8")
+    }
+
+    fn limits(&mut self) -> Result<()> {
+        let flags = self.byte()?;
+        self.leb_u32()?;
+        if flags & 0x01 != 0 {
+            self.leb_u32()?;
+        }
+        Ok(())
+    }
+}
+
+/// Analyse a contract's wasm.
+pub fn analyze(wasm: &[u8]) -> Result<WasmFeatures> {
+    let mut reader = Reader::new(wasm);
+    if reader.take(4).ok() != Some(WASM_MAGIC.as_slice()) {
+        bail!("not a wasm module");
+    }
+    let version = u32::from_le_bytes(reader.take(4)?.try_into()?);
+    if version != WASM_VERSION {
+        bail!("unsupported wasm version {}", version);
+    }
+
+    let mut features = WasmFeatures::default();
+    while !reader.is_empty() {
+        let id = reader.byte()?;
+        let size = reader.leb_u32()? as usize;
+        let mut section = Reader::new(reader.take(size)?);
+
+        match id {
+            IMPORT_SECTION => read_imports(&mut section, &mut features)?,
+            CUSTOM_SECTION if section.name()? == ENV_META_SECTION => {
+                read_env_meta(&section.bytes[section.pos..], &mut features)?;
+            }
+            _ => {}
+        }
+    }
+
+    features.host_functions.sort();
+    features.features = features
+        .host_functions
+        .iter()
+        .filter_map(|f| host_module_feature(&f.module))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    Ok(features)
+}
+
+fn read_imports(section: &mut Reader, features: &mut WasmFeatures) -> Result<()> {
+    for _ in 0..section.leb_u32()? {
+        let module = section.name()?;
+        let name = section.name()?;
+        match section.byte()? {
+            // func: type index
+            0x00 => {
+                section.leb_u32()?;
+                features.host_functions.push(HostFunction {
+                    module: module.to_string(),
+                    name: name.to_string(),
+                });
+            }
+            // table: reftype + limits
+            0x01 => {
+                section.byte()?;
+                section.limits()?;
+            }
+            // memory: limits
+            0x02 => section.limits()?,
+            // global: valtype + mutability
+            0x03 => {
+                section.take(2)?;
+            }
+            kind => bail!("unknown import kind {:#x}", kind),
+        }
+    }
+    Ok(())
+}
+
+/// `SCEnvMetaEntry` stream: a kind discriminant followed by the interface
+/// version, whose high 32 bits are the protocol and low 32 bits the
+/// pre-release number.
+fn read_env_meta(data: &[u8], features: &mut WasmFeatures) -> Result<()> {
+    let mut reader = Reader::new(data);
+    while !reader.is_empty() {
+        let kind = u32::from_be_bytes(reader.take(4)?.try_into()?);
+        if kind != SC_ENV_META_KIND_INTERFACE_VERSION {
+            bail!("unknown {} entry kind {}", ENV_META_SECTION, kind);
+        }
+        let protocol = u32::from_be_bytes(reader.take(4)?.try_into()?);
+        let pre_release = u32::from_be_bytes(reader.take(4)?.try_into()?);
+
+        features.min_protocol_version = features.min_protocol_version.max(Some(protocol));
+        features.pre_release = Some(pre_release);
+    }
+    Ok(())
+}
+
+/// Warning for a contract that needs a newer protocol than the network runs.
+pub fn protocol_warning(
+    min_protocol_version: Option<i32>,
+    network_protocol: i32,
+) -> Option<String> {
+    let required = min_protocol_version?;
+    (required > network_protocol).then(|| {
+        format!(
+            "requires protocol {} but the network supports protocol {}",
+            required, network_protocol
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(s: &str) -> Vec<u8> {
+        let mut out = vec![s.len() as u8];
+        out.extend_from_slice(s.as_bytes());
+        out
+    }
+
+    fn section(id: u8, payload: Vec<u8>) -> Vec<u8> {
+        let mut out = vec![id, payload.len() as u8];
+        out.extend(payload);
+        out
+    }
+
+    fn module(protocol: u32, imports: &[(&str, &str)]) -> Vec<u8> {
+        let mut wasm = b"\0asm".to_vec();
+        wasm.extend_from_slice(&1u32.to_le_bytes());
+
+        let mut meta = name(ENV_META_SECTION);
+        meta.extend_from_slice(&0u32.to_be_bytes());
+        meta.extend_from_slice(&protocol.to_be_bytes());
+        meta.extend_from_slice(&0u32.to_be_bytes());
+        wasm.extend(section(CUSTOM_SECTION, meta));
+
+        let mut payload = vec![imports.len() as u8 + 1];
+        for (module, field) in imports {
+            payload.extend(name(module));
+            payload.extend(name(field));
+            payload.extend([0x00, 0x00]);
+        }
+        // A memory import is skipped, not reported as a host function
+        payload.extend(name("env"));
+        payload.extend(name("memory"));
+        payload.extend([0x02, 0x01, 0x01, 0x10]);
+        wasm.extend(section(IMPORT_SECTION, payload));
+        wasm
+    }
+
+    #[test]
+    fn reads_protocol_and_host_functions() {
+        let features = analyze(&module(22, &[("l", "_"), ("c", "0"), ("l", "1")])).unwrap();
+
+        assert_eq!(features.min_protocol_version, Some(22));
+        assert_eq!(features.pre_release, Some(0));
+        assert_eq!(features.host_functions.len(), 3);
+        assert_eq!(features.features, vec!["crypto", "ledger"]);
+    }
+
+    #[test]
+    fn rejects_non_wasm_and_truncated_input() {
+        assert!(analyze(b"not wasm").is_err());
+
+        let wasm = module(21, &[("x", "0")]);
+        assert!(analyze(&wasm[..wasm.len() - 3]).is_err());
+    }
+
+    #[test]
+    fn warns_only_when_network_is_behind() {
+        assert!(protocol_warning(Some(23), 22).is_some());
+        assert!(protocol_warning(Some(22), 22).is_none());
+        assert!(protocol_warning(None, 22).is_none());
+    }
+}
@@ -0,0 +1,152 @@
+// verifier/src/attestation.rs
+//
+// Signing of verification attestations (see shared::attestation for the
+// signed message).  The signing key is a 32-byte Ed25519 seed, hex-encoded
+// in `VERIFIER_SIGNING_KEY`; its public key is published with every
+// attestation.
+
+use chrono::{DateTime, Utc};
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
+use shared::{attestation, RegistryError, VerificationAttestation};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::VerificationReport;
+
+pub struct AttestationSigner {
+    key_pair: Ed25519KeyPair,
+}
+
+impl std::fmt::Debug for AttestationSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AttestationSigner")
+            .field("public_key", &self.public_key_hex())
+            .finish()
+    }
+}
+
+impl AttestationSigner {
+    /// Signer from a hex-encoded 32-byte Ed25519 seed.
+    pub fn from_seed_hex(seed: &str) -> Result<Self, RegistryError> {
+        let seed = hex::decode(seed.trim())
+            .ok()
+            .filter(|seed| seed.len() == 32)
+            .ok_or_else(|| {
+                RegistryError::InvalidInput("signing key must be a 32-byte hex seed".to_string())
+            })?;
+        let key_pair = Ed25519KeyPair::from_seed_unchecked(&seed)
+            .map_err(|_| RegistryError::InvalidInput("invalid Ed25519 seed".to_string()))?;
+        Ok(Self { key_pair })
+    }
+
+    /// Signer from `VERIFIER_SIGNING_KEY`, if set.
+    pub fn from_env() -> Result<Option<Self>, RegistryError> {
+        match std::env::var("VERIFIER_SIGNING_KEY") {
+            Ok(seed) if !seed.trim().is_empty() => Self::from_seed_hex(&seed).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(self.key_pair.public_key().as_ref())
+    }
+
+    /// Hex signature over `message`.
+    pub fn sign(&self, message: &str) -> String {
+        hex::encode(self.key_pair.sign(message.as_bytes()).as_ref())
+    }
+
+    /// Sign and store an attestation for a verified contract.
+    pub async fn attest(
+        &self,
+        pool: &PgPool,
+        contract_id: Uuid,
+        job_id: Option<Uuid>,
+        report: &VerificationReport,
+    ) -> Result<VerificationAttestation, RegistryError> {
+        let (onchain_contract_id, network): (String, String) =
+            sqlx::query_as("SELECT contract_id, network::text FROM contracts WHERE id = $1")
+                .bind(contract_id)
+                .fetch_optional(pool)
+                .await?
+                .ok_or_else(|| RegistryError::NotFound(format!("contract {}", contract_id)))?;
+
+        // Whole seconds, so the stored time matches the signed unix time
+        let signed_at = DateTime::<Utc>::from_timestamp(Utc::now().timestamp(), 0)
+            .ok_or_else(|| RegistryError::Internal("clock out of range".to_string()))?;
+        let source_commit = report.source.as_ref().map(|p| p.commit.as_str());
+        let message = attestation::message(
+            &onchain_contract_id,
+            &network,
+            &report.deployed_wasm_hash,
+            source_commit,
+            signed_at,
+        );
+
+        let attestation: VerificationAttestation = sqlx::query_as(
+            "INSERT INTO verification_attestations
+                 (contract_id, job_id, onchain_contract_id, network, wasm_hash,
+                  source_commit, signed_at, public_key, signature)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+             RETURNING *",
+        )
+        .bind(contract_id)
+        .bind(job_id)
+        .bind(&onchain_contract_id)
+        .bind(&network)
+        .bind(report.deployed_wasm_hash.to_ascii_lowercase())
+        .bind(source_commit)
+        .bind(signed_at)
+        .bind(self.public_key_hex())
+        .bind(self.sign(&message))
+        .fetch_one(pool)
+        .await?;
+
+        tracing::info!(
+            contract_id = %contract_id,
+            attestation_id = %attestation.id,
+            "verification attested"
+        );
+        Ok(attestation)
+    }
+}
+
+/// Check a hex signature over `message` against a hex public key.
+pub fn verify_signature(public_key_hex: &str, message: &str, signature_hex: &str) -> bool {
+    let (Ok(public_key), Ok(signature)) = (hex::decode(public_key_hex), hex::decode(signature_hex))
+    else {
+        return false;
+    };
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(message.as_bytes(), &signature)
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signatures_verify_against_the_published_key() {
+        let signer = AttestationSigner::from_seed_hex(&"07".repeat(32)).unwrap();
+        let signed_at = DateTime::from_timestamp(1_718_000_000, 0).unwrap();
+        let message = attestation::message("CABC", "testnet", "ab12", Some("deadbeef"), signed_at);
+        let signature = signer.sign(&message);
+
+        assert_eq!(signer.public_key_hex().len(), 64);
+        assert!(verify_signature(
+            &signer.public_key_hex(),
+            &message,
+            &signature
+        ));
+
+        let tampered = attestation::message("CABC", "mainnet", "ab12", Some("deadbeef"), signed_at);
+        assert!(!verify_signature(
+            &signer.public_key_hex(),
+            &tampered,
+            &signature
+        ));
+
+        assert!(AttestationSigner::from_seed_hex("abcd").is_err());
+    }
+}
@@ -0,0 +1,416 @@
+// verifier/src/jobs.rs
+//
+// Verification job queue.  Builds take minutes, so the API only queues a job
+// (`submit_verification`) and clients poll `get_job_status`.  Workers started
+// with `start_workers` claim queued jobs from Postgres with
+// `FOR UPDATE SKIP LOCKED`, so any number of worker processes can share the
+// queue.  A running job's worker refreshes its `heartbeat_at` while it
+// builds; a job whose worker died mid-build is picked up again once its
+// heartbeat is older than `STALE_AFTER`.
+//
+// The toolchain that reproduced a publisher's contract is remembered in
+// `publisher_toolchains`; requests with an unknown compiler version from the
+// same publisher try it before the rest of the matrix.  With a signing key,
+// every verified job also gets a signed attestation (attestation.rs).
+
+use std::{sync::Arc, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use shared::{RegistryError, VerificationJob, VerificationJobStatus};
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::{
+    attestation::AttestationSigner, check_deployed_wasm, check_request, verify_candidates,
+    BuildSandbox, GitSource, Toolchain, ToolchainMatrix, VerificationOutcome, VerificationReport,
+    VerificationSource,
+};
+
+/// How long an idle worker waits before looking for work again.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How often a running job checks whether it was cancelled and refreshes
+/// its heartbeat.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A building or comparing job whose heartbeat is older than this is assumed
+/// orphaned; several missed heartbeats, however many toolchains of the
+/// matrix the job ends up building with.
+const STALE_AFTER: Duration = Duration::from_secs(2 * 60);
+
+/// A verification request to queue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewVerificationJob {
+    pub contract_id: Uuid,
+    /// `"source": "inline"` with `source_code`, or `"source": "git"` with
+    /// `repository`, `commit` and `path`
+    #[serde(flatten)]
+    pub source: VerificationSource,
+    pub compiler_version: String,
+    /// Must pin `soroban_sdk`; may carry `cargo_lock`
+    #[serde(default)]
+    pub build_params: Value,
+    pub deployed_wasm_hash: String,
+    /// The deployed module, if the caller has it; lets mismatches be
+    /// explained section by section
+    #[serde(default, skip_serializing)]
+    pub deployed_wasm: Option<Vec<u8>>,
+}
+
+/// Queue a verification.  The metadata is checked up front so malformed
+/// requests are refused instead of failing later in a worker.
+pub async fn submit_verification(
+    pool: &PgPool,
+    job: NewVerificationJob,
+) -> Result<VerificationJob, RegistryError> {
+    let (deployed_wasm_hash, _) = check_request(
+        &job.compiler_version,
+        &job.build_params,
+        &job.deployed_wasm_hash,
+        &ToolchainMatrix::default(),
+    )?;
+    check_deployed_wasm(job.deployed_wasm.as_deref(), &deployed_wasm_hash)?;
+    job.source.validate()?;
+    let (source_code, git) = match &job.source {
+        VerificationSource::Inline { source_code } => (Some(source_code), None),
+        VerificationSource::Git(git) => (None, Some(git)),
+    };
+
+    let job: VerificationJob = sqlx::query_as(
+        "INSERT INTO verification_jobs
+             (contract_id, source_code, source_repository, source_commit, source_path,
+              compiler_version, build_params, deployed_wasm_hash, deployed_wasm)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+         RETURNING *",
+    )
+    .bind(job.contract_id)
+    .bind(source_code)
+    .bind(git.map(|g| &g.repository))
+    .bind(git.map(|g| g.commit.to_ascii_lowercase()))
+    .bind(git.map(|g| &g.path))
+    .bind(&job.compiler_version)
+    .bind(&job.build_params)
+    .bind(&deployed_wasm_hash)
+    .bind(&job.deployed_wasm)
+    .fetch_one(pool)
+    .await?;
+
+    tracing::info!(job_id = %job.id, contract_id = %job.contract_id, "verification queued");
+    Ok(job)
+}
+
+/// Current state of a job, with its report once it succeeded.
+pub async fn get_job_status(pool: &PgPool, job_id: Uuid) -> Result<VerificationJob, RegistryError> {
+    sqlx::query_as("SELECT * FROM verification_jobs WHERE id = $1")
+        .bind(job_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| RegistryError::NotFound(format!("verification job {}", job_id)))
+}
+
+/// Cancel a job that has not finished.  A running build is stopped by its
+/// worker within `CANCEL_POLL_INTERVAL`.
+pub async fn cancel_job(pool: &PgPool, job_id: Uuid) -> Result<VerificationJob, RegistryError> {
+    let cancelled: Option<VerificationJob> = sqlx::query_as(
+        "UPDATE verification_jobs
+            SET status = 'cancelled', finished_at = NOW()
+          WHERE id = $1 AND status IN ('queued', 'building', 'comparing')
+          RETURNING *",
+    )
+    .bind(job_id)
+    .fetch_optional(pool)
+    .await?;
+
+    match cancelled {
+        Some(job) => {
+            tracing::info!(job_id = %job.id, "verification cancelled");
+            Ok(job)
+        }
+        None => {
+            let job = get_job_status(pool, job_id).await?;
+            Err(RegistryError::InvalidInput(format!(
+                "verification job {} already finished ({:?})",
+                job.id, job.status
+            )))
+        }
+    }
+}
+
+/// What workers build with.
+struct Worker {
+    sandbox: Arc<dyn BuildSandbox>,
+    matrix: ToolchainMatrix,
+    signer: Option<AttestationSigner>,
+}
+
+/// Spawn `concurrency` workers on the current runtime.  Each builds one
+/// contract at a time in `sandbox`, trying `matrix` for requests with an
+/// unknown compiler version, and attesting verified jobs with `signer`.
+pub fn start_workers(
+    pool: PgPool,
+    concurrency: usize,
+    sandbox: Arc<dyn BuildSandbox>,
+    matrix: ToolchainMatrix,
+    signer: Option<AttestationSigner>,
+) -> Vec<JoinHandle<()>> {
+    tracing::info!(
+        concurrency,
+        sandbox = sandbox.name(),
+        matrix = matrix.toolchains().len(),
+        attesting = signer.is_some(),
+        "starting verification workers"
+    );
+    let context = Arc::new(Worker {
+        sandbox,
+        matrix,
+        signer,
+    });
+    (0..concurrency.max(1))
+        .map(|worker| tokio::spawn(work(pool.clone(), worker, context.clone())))
+        .collect()
+}
+
+async fn work(pool: PgPool, worker: usize, context: Arc<Worker>) {
+    loop {
+        match claim_next(&pool).await {
+            Ok(Some(job)) => run_job(&pool, worker, &context, job).await,
+            Ok(None) => tokio::time::sleep(POLL_INTERVAL).await,
+            Err(err) => {
+                tracing::error!(worker, error = %err, "failed to claim verification job");
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    }
+}
+
+async fn claim_next(pool: &PgPool) -> Result<Option<VerificationJob>, RegistryError> {
+    Ok(sqlx::query_as(
+        "UPDATE verification_jobs
+            SET status = 'building', started_at = NOW(), heartbeat_at = NOW()
+          WHERE id = (
+                SELECT id FROM verification_jobs
+                 WHERE status = 'queued'
+                    OR (status IN ('building', 'comparing')
+                        AND COALESCE(heartbeat_at, started_at)
+                            < NOW() - make_interval(secs => $1))
+                 ORDER BY created_at
+                 FOR UPDATE SKIP LOCKED
+                 LIMIT 1)
+          RETURNING *",
+    )
+    .bind(STALE_AFTER.as_secs_f64())
+    .fetch_optional(pool)
+    .await?)
+}
+
+async fn run_job(pool: &PgPool, worker: usize, context: &Worker, job: VerificationJob) {
+    tracing::info!(worker, job_id = %job.id, contract_id = %job.contract_id, "verification started");
+
+    // Dropping the pipeline on cancellation stops the build
+    let result = tokio::select! {
+        result = execute(pool, context, &job) => result,
+        _ = wait_for_cancel(pool, job.id) => {
+            tracing::info!(worker, job_id = %job.id, "verification stopped after cancel");
+            return;
+        }
+    };
+
+    let finished = match result {
+        Ok(report) => {
+            tracing::info!(worker, job_id = %job.id, status = ?report.status, "verification finished");
+            let report = serde_json::to_value(&report).unwrap_or_default();
+            finish(
+                pool,
+                job.id,
+                VerificationJobStatus::Succeeded,
+                Some(report),
+                None,
+            )
+            .await
+        }
+        Err(err) => {
+            tracing::warn!(worker, job_id = %job.id, error = %err, "verification failed");
+            finish(
+                pool,
+                job.id,
+                VerificationJobStatus::Failed,
+                None,
+                Some(err.to_string()),
+            )
+            .await
+        }
+    };
+    if let Err(err) = finished {
+        tracing::error!(worker, job_id = %job.id, error = %err, "failed to record verification result");
+    }
+}
+
+async fn execute(
+    pool: &PgPool,
+    context: &Worker,
+    job: &VerificationJob,
+) -> Result<VerificationReport, RegistryError> {
+    let (deployed_wasm_hash, mut candidates) = check_request(
+        &job.compiler_version,
+        &job.build_params,
+        &job.deployed_wasm_hash,
+        &context.matrix,
+    )?;
+    if candidates.len() > 1 {
+        let pinned_sdk = job.build_params["soroban_sdk"].as_str();
+        let known = known_good_toolchain(pool, job.contract_id)
+            .await?
+            .filter(|known| pinned_sdk.is_none_or(|sdk| sdk == known.soroban_sdk));
+        if let Some(known) = known {
+            candidates.retain(|toolchain| *toolchain != known);
+            candidates.insert(0, known);
+        }
+    }
+
+    let report = verify_candidates(
+        &source_of(job)?,
+        &candidates,
+        job.build_params["cargo_lock"].as_str(),
+        &deployed_wasm_hash,
+        job.deployed_wasm.as_deref(),
+        context.sandbox.as_ref(),
+        |status| set_status(pool, job.id, status),
+    )
+    .await?;
+
+    if report.status == VerificationOutcome::Verified {
+        remember_toolchain(pool, job.contract_id, &report.toolchain).await?;
+        if let Some(signer) = &context.signer {
+            // The verification stands even if it cannot be attested
+            if let Err(err) = signer
+                .attest(pool, job.contract_id, Some(job.id), &report)
+                .await
+            {
+                tracing::error!(job_id = %job.id, error = %err, "failed to attest verification");
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Toolchain that last reproduced a contract of the same publisher.
+async fn known_good_toolchain(
+    pool: &PgPool,
+    contract_id: Uuid,
+) -> Result<Option<Toolchain>, RegistryError> {
+    let row: Option<(String, String)> = sqlx::query_as(
+        "SELECT pt.rustc, pt.soroban_sdk
+           FROM publisher_toolchains pt
+           JOIN contracts c ON c.publisher_id = pt.publisher_id
+          WHERE c.id = $1",
+    )
+    .bind(contract_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(|(rustc, soroban_sdk)| Toolchain { rustc, soroban_sdk }))
+}
+
+async fn remember_toolchain(
+    pool: &PgPool,
+    contract_id: Uuid,
+    toolchain: &Toolchain,
+) -> Result<(), RegistryError> {
+    sqlx::query(
+        "INSERT INTO publisher_toolchains (publisher_id, rustc, soroban_sdk, contract_id)
+         SELECT publisher_id, $2, $3, id FROM contracts WHERE id = $1
+         ON CONFLICT (publisher_id) DO UPDATE
+            SET rustc = EXCLUDED.rustc,
+                soroban_sdk = EXCLUDED.soroban_sdk,
+                contract_id = EXCLUDED.contract_id,
+                updated_at = NOW()",
+    )
+    .bind(contract_id)
+    .bind(&toolchain.rustc)
+    .bind(&toolchain.soroban_sdk)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+fn source_of(job: &VerificationJob) -> Result<VerificationSource, RegistryError> {
+    match (&job.source_code, &job.source_repository, &job.source_commit) {
+        (Some(source_code), _, _) => Ok(VerificationSource::Inline {
+            source_code: source_code.clone(),
+        }),
+        (None, Some(repository), Some(commit)) => Ok(VerificationSource::Git(GitSource {
+            repository: repository.clone(),
+            commit: commit.clone(),
+            path: job.source_path.clone().unwrap_or_default(),
+        })),
+        _ => Err(RegistryError::Internal(format!(
+            "verification job {} has no source",
+            job.id
+        ))),
+    }
+}
+
+/// Resolves once the job has been cancelled (or deleted).  Until then it
+/// keeps the job's heartbeat fresh so no other worker reclaims it.
+async fn wait_for_cancel(pool: &PgPool, job_id: Uuid) {
+    loop {
+        tokio::time::sleep(CANCEL_POLL_INTERVAL).await;
+        let status: Result<Option<VerificationJobStatus>, sqlx::Error> = sqlx::query_scalar(
+            "UPDATE verification_jobs
+                SET heartbeat_at = CASE WHEN status IN ('building', 'comparing')
+                                        THEN NOW() ELSE heartbeat_at END
+              WHERE id = $1
+              RETURNING status",
+        )
+        .bind(job_id)
+        .fetch_optional(pool)
+        .await;
+        match status {
+            Ok(None) | Ok(Some(VerificationJobStatus::Cancelled)) => return,
+            Ok(Some(_)) => {}
+            Err(err) => {
+                tracing::warn!(job_id = %job_id, error = %err, "failed to poll verification job")
+            }
+        }
+    }
+}
+
+// Cancelled jobs keep their status: a cancel that races the worker wins.
+// Called before every toolchain attempt, so it also refreshes the heartbeat.
+async fn set_status(
+    pool: &PgPool,
+    job_id: Uuid,
+    status: VerificationJobStatus,
+) -> Result<(), RegistryError> {
+    sqlx::query(
+        "UPDATE verification_jobs SET status = $2, heartbeat_at = NOW()
+          WHERE id = $1 AND status <> 'cancelled'",
+    )
+    .bind(job_id)
+    .bind(status)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+async fn finish(
+    pool: &PgPool,
+    job_id: Uuid,
+    status: VerificationJobStatus,
+    report: Option<Value>,
+    error_message: Option<String>,
+) -> Result<(), RegistryError> {
+    sqlx::query(
+        "UPDATE verification_jobs
+            SET status = $2, report = $3, error_message = $4, finished_at = NOW()
+          WHERE id = $1 AND status <> 'cancelled'",
+    )
+    .bind(job_id)
+    .bind(status)
+    .bind(report)
+    .bind(error_message)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
@@ -1,50 +1,339 @@
 // Contract verification engine
-// Compiles source code and compares with on-chain bytecode
+// Rebuilds submitted source with its pinned toolchain (or a matrix of
+// candidates when the toolchain is unknown) and compares the result with
+// on-chain bytecode; successful verifications can be attested with a
+// signature (attestation.rs)
 
-use anyhow::Result;
-use shared::RegistryError;
+pub mod attestation;
+pub mod jobs;
+pub mod sandbox;
+pub mod source;
+pub mod toolchain;
+pub mod wasm;
+pub mod workspace;
 
-/// Verify that source code matches deployed contract bytecode
+use std::{future::Future, time::Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use shared::{RegistryError, VerificationJobStatus};
+
+use sandbox::BuildOutput;
+pub use sandbox::{BuildSandbox, ContainerSandbox, HostSandbox};
+pub use source::{GitSource, Provenance, VerificationSource};
+pub use toolchain::{Toolchain, ToolchainMatrix};
+use workspace::BuildWorkspace;
+
+/// Lines of build output kept in a report.
+const LOG_EXCERPT_LINES: usize = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationOutcome {
+    /// Rebuilt bytecode matches the deployed hash
+    Verified,
+    /// Source built, but to different bytecode
+    Mismatch,
+    /// Source did not build with the pinned toolchain
+    BuildFailed,
+}
+
+/// One toolchain tried for a verification.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolchainAttempt {
+    pub toolchain: Toolchain,
+    pub status: VerificationOutcome,
+}
+
+/// Everything a verification run found out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationReport {
+    pub status: VerificationOutcome,
+    pub deployed_wasm_hash: String,
+    /// SHA-256 of the rebuilt module as produced by cargo
+    pub built_wasm_hash: Option<String>,
+    /// SHA-256 of the rebuilt module without build-environment sections
+    pub stripped_wasm_hash: Option<String>,
+    pub toolchain: Toolchain,
+    /// Whether dependencies came from a Cargo.lock
+    pub locked: bool,
+    /// Repository, commit and path for git sources
+    pub source: Option<Provenance>,
+    /// Every toolchain built with, in order; the last one is `toolchain`
+    #[serde(default)]
+    pub attempts: Vec<ToolchainAttempt>,
+    /// Which sections differ, on a mismatch with the deployed module at hand
+    #[serde(default)]
+    pub diff: Option<wasm::WasmDiff>,
+    /// Tail of the cargo output
+    pub build_log_excerpt: String,
+    pub duration_ms: u64,
+}
+
+fn log_excerpt(log: &str) -> String {
+    let lines: Vec<&str> = log.lines().collect();
+    lines[lines.len().saturating_sub(LOG_EXCERPT_LINES)..].join("\n")
+}
+
+fn validate_hash(hash: &str) -> Result<String, RegistryError> {
+    if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(RegistryError::InvalidInput(format!(
+            "deployed_wasm_hash must be a hex SHA-256, got '{}'",
+            hash
+        )));
+    }
+    Ok(hash.to_ascii_lowercase())
+}
+
+/// Compare a build with the deployed hash.  Either the raw or the stripped
+/// module may match: the deployed module may itself have been stripped.
+fn assess(
+    deployed_wasm_hash: &str,
+    wasm: &[u8],
+) -> Result<(VerificationOutcome, String, String), RegistryError> {
+    let built = wasm::sha256_hex(wasm);
+    let stripped = wasm::sha256_hex(&wasm::strip_non_deterministic(wasm)?);
+    let status = if built == deployed_wasm_hash || stripped == deployed_wasm_hash {
+        VerificationOutcome::Verified
+    } else {
+        VerificationOutcome::Mismatch
+    };
+    Ok((status, built, stripped))
+}
+
+/// Check a request's metadata before anything is built: the normalised
+/// deployed hash and the pinned toolchain.
+pub(crate) fn check_request(
+    compiler_version: &str,
+    build_params: &Value,
+    deployed_wasm_hash: &str,
+    matrix: &ToolchainMatrix,
+) -> Result<(String, Vec<Toolchain>), RegistryError> {
+    let deployed_wasm_hash = validate_hash(deployed_wasm_hash)?;
+    let candidates = Toolchain::candidates(compiler_version, build_params, matrix)?;
+    Ok((deployed_wasm_hash, candidates))
+}
+
+/// Deployed module supplied with a request, checked against its hash.
+pub(crate) fn check_deployed_wasm(
+    deployed_wasm: Option<&[u8]>,
+    deployed_wasm_hash: &str,
+) -> Result<(), RegistryError> {
+    match deployed_wasm {
+        Some(wasm) if !wasm::sha256_hex(wasm).eq_ignore_ascii_case(deployed_wasm_hash) => {
+            Err(RegistryError::InvalidInput(
+                "deployed_wasm does not match deployed_wasm_hash".to_string(),
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Turn a finished build into a report.  Mismatches are explained section
+/// by section when the deployed module is available.
+pub(crate) fn build_report(
+    deployed_wasm_hash: String,
+    deployed_wasm: Option<&[u8]>,
+    workspace: &BuildWorkspace,
+    output: BuildOutput,
+    started: Instant,
+) -> Result<VerificationReport, RegistryError> {
+    let (status, built_wasm_hash, stripped_wasm_hash, diff) = match output.wasm {
+        Some(wasm) => {
+            let (status, built, stripped) = assess(&deployed_wasm_hash, &wasm)?;
+            let diff = match deployed_wasm {
+                Some(deployed) if status == VerificationOutcome::Mismatch => {
+                    Some(wasm::compare(&wasm, deployed)?)
+                }
+                _ => None,
+            };
+            (status, Some(built), Some(stripped), diff)
+        }
+        None => (VerificationOutcome::BuildFailed, None, None, None),
+    };
+
+    Ok(VerificationReport {
+        status,
+        deployed_wasm_hash,
+        built_wasm_hash,
+        stripped_wasm_hash,
+        toolchain: workspace.toolchain().clone(),
+        locked: workspace.locked(),
+        source: workspace.provenance().cloned(),
+        attempts: Vec::new(),
+        diff,
+        build_log_excerpt: log_excerpt(&output.log),
+        duration_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+/// Build with each candidate toolchain in turn until one reproduces the
+/// deployed hash.  `on_stage` is told when a build starts (after the first)
+/// and when its output is compared.  The report is the verified attempt's,
+/// or the last one's.
+pub(crate) async fn verify_candidates<F, Fut>(
+    source: &VerificationSource,
+    candidates: &[Toolchain],
+    cargo_lock: Option<&str>,
+    deployed_wasm_hash: &str,
+    deployed_wasm: Option<&[u8]>,
+    sandbox: &dyn BuildSandbox,
+    mut on_stage: F,
+) -> Result<VerificationReport, RegistryError>
+where
+    F: FnMut(VerificationJobStatus) -> Fut,
+    Fut: Future<Output = Result<(), RegistryError>>,
+{
+    let started = Instant::now();
+    let mut attempts = Vec::new();
+    let mut last = None;
+
+    for (i, toolchain) in candidates.iter().enumerate() {
+        if i > 0 {
+            on_stage(VerificationJobStatus::Building).await?;
+        }
+        tracing::info!(rustc = %toolchain.rustc, soroban_sdk = %toolchain.soroban_sdk, "building");
+        let (workspace, output) = compile_contract(source, toolchain, cargo_lock, sandbox).await?;
+        on_stage(VerificationJobStatus::Comparing).await?;
+
+        let report = build_report(
+            deployed_wasm_hash.to_string(),
+            deployed_wasm,
+            &workspace,
+            output,
+            started,
+        )?;
+        attempts.push(ToolchainAttempt {
+            toolchain: toolchain.clone(),
+            status: report.status,
+        });
+        let verified = report.status == VerificationOutcome::Verified;
+        last = Some(report);
+        if verified {
+            break;
+        }
+    }
+
+    let mut report =
+        last.ok_or_else(|| RegistryError::Internal("no toolchain to build with".to_string()))?;
+    report.attempts = attempts;
+    Ok(report)
+}
+
+/// Verify that source code matches deployed contract bytecode.
+///
+/// `compiler_version` and `build_params` come from the verification request;
+/// `build_params` must pin `soroban_sdk` and may carry the project's
+/// `cargo_lock` for inline source.  An unknown `compiler_version` tries each
+/// toolchain in `matrix`.  With the deployed module at hand, a mismatch is
+/// explained section by section.  Builds take minutes: the API goes through the job
+/// queue in [`jobs`] rather than calling this directly.
 pub async fn verify_contract(
-    _source_code: &str,
+    source: &VerificationSource,
+    compiler_version: &str,
+    build_params: &Value,
     deployed_wasm_hash: &str,
-) -> Result<bool, RegistryError> {
-    // TODO: Implement verification logic
-    // 1. Compile source code using soroban-sdk
-    // 2. Generate WASM bytecode
-    // 3. Hash the bytecode
-    // 4. Compare with deployed_wasm_hash
+    deployed_wasm: Option<&[u8]>,
+    sandbox: &dyn BuildSandbox,
+    matrix: &ToolchainMatrix,
+) -> Result<VerificationReport, RegistryError> {
+    let (deployed_wasm_hash, candidates) =
+        check_request(compiler_version, build_params, deployed_wasm_hash, matrix)?;
+    check_deployed_wasm(deployed_wasm, &deployed_wasm_hash)?;
+    source.validate()?;
 
     tracing::info!(
-        "Verification requested for contract with hash: {}",
-        deployed_wasm_hash
+        deployed_wasm_hash = %deployed_wasm_hash,
+        candidates = candidates.len(),
+        sandbox = sandbox.name(),
+        "verification started"
     );
-    tracing::warn!("Verification engine not yet implemented");
 
-    Ok(false)
-}
+    let report = verify_candidates(
+        source,
+        &candidates,
+        build_params["cargo_lock"].as_str(),
+        &deployed_wasm_hash,
+        deployed_wasm,
+        sandbox,
+        |_| async { Ok(()) },
+    )
+    .await?;
 
-/// Compile Rust source code to WASM
-pub async fn compile_contract(_source_code: &str) -> Result<Vec<u8>, RegistryError> {
-    // TODO: Implement compilation
-    // - Set up temporary build environment
-    // - Write source to temp directory
-    // - Run cargo build with soroban target
-    // - Return compiled WASM bytes
+    tracing::info!(status = ?report.status, duration_ms = report.duration_ms, "verification finished");
+    Ok(report)
+}
 
-    Err(RegistryError::Internal(
-        "Compilation not yet implemented".to_string(),
-    ))
+/// Compile a contract to WASM with the given toolchain.  The workspace is
+/// returned alongside the output; it is removed when dropped.
+pub async fn compile_contract(
+    source: &VerificationSource,
+    toolchain: &Toolchain,
+    cargo_lock: Option<&str>,
+    sandbox: &dyn BuildSandbox,
+) -> Result<(BuildWorkspace, BuildOutput), RegistryError> {
+    let workspace = BuildWorkspace::from_source(source, toolchain, cargo_lock).await?;
+    sandbox.prepare(toolchain).await?;
+    let output = sandbox.build(&workspace).await?;
+    Ok((workspace, output))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::wasm::tests::{custom_section, module, TYPE_SECTION};
+    use serde_json::json;
 
     #[tokio::test]
     async fn test_verify_contract() {
-        // Placeholder test
-        let result = verify_contract("", "test_hash").await;
-        assert!(result.is_ok());
+        // Requests without a pinned toolchain are rejected before building
+        let hash = "ab".repeat(32);
+        let source = VerificationSource::Inline {
+            source_code: "#![no_std]".to_string(),
+        };
+        let result = verify_contract(
+            &source,
+            "stable",
+            &json!({}),
+            &hash,
+            None,
+            &HostSandbox,
+            &ToolchainMatrix::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(RegistryError::InvalidInput(_))));
+
+        let result = verify_contract(
+            &source,
+            "1.79.0",
+            &json!({ "soroban_sdk": "21.7.7" }),
+            "test_hash",
+            None,
+            &HostSandbox,
+            &ToolchainMatrix::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(RegistryError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn matches_raw_or_stripped_hash() {
+        let built = module(&[TYPE_SECTION.to_vec(), custom_section("producers", b"rustc")]);
+        let stripped = module(&[TYPE_SECTION.to_vec()]);
+
+        let (status, _, _) = assess(&wasm::sha256_hex(&built), &built).unwrap();
+        assert_eq!(status, VerificationOutcome::Verified);
+        let (status, _, _) = assess(&wasm::sha256_hex(&stripped), &built).unwrap();
+        assert_eq!(status, VerificationOutcome::Verified);
+        let (status, _, _) = assess(&"00".repeat(32), &built).unwrap();
+        assert_eq!(status, VerificationOutcome::Mismatch);
+    }
+
+    #[test]
+    fn keeps_the_tail_of_the_build_log() {
+        let log: String = (0..100).map(|i| format!("line {}\n", i)).collect();
+        let excerpt = log_excerpt(&log);
+        assert_eq!(excerpt.lines().count(), LOG_EXCERPT_LINES);
+        assert!(excerpt.ends_with("line 99"));
     }
 }
@@ -0,0 +1,463 @@
+// verifier/src/sandbox.rs
+//
+// Where contract builds run.  Submitted source is untrusted: build scripts
+// and proc macros execute arbitrary code at compile time.  `ContainerSandbox`
+// is the production backend; `HostSandbox` runs cargo directly and is only
+// meant for development against trusted source.
+
+use std::{
+    path::Path,
+    process::{Output, Stdio},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use shared::RegistryError;
+use tokio::{io::AsyncWriteExt, process::Command};
+use uuid::Uuid;
+
+use crate::{
+    toolchain::{Toolchain, WASM_TARGET},
+    workspace::{io_error, BuildWorkspace},
+};
+
+/// Result of a build, successful or not.
+#[derive(Debug)]
+pub struct BuildOutput {
+    /// Compiled module; `None` when the build failed
+    pub wasm: Option<Vec<u8>>,
+    /// Combined cargo output
+    pub log: String,
+}
+
+#[async_trait]
+pub trait BuildSandbox: Send + Sync {
+    /// `host`, `docker` or `podman`.
+    fn name(&self) -> &str;
+
+    /// Make `toolchain` available before builds that need it.
+    async fn prepare(&self, toolchain: &Toolchain) -> Result<(), RegistryError>;
+
+    /// Run the release build.  A failing build is not an error: its log is
+    /// part of the verification report.
+    async fn build(&self, workspace: &BuildWorkspace) -> Result<BuildOutput, RegistryError>;
+}
+
+/// Longest a single contract build may run.
+pub const BUILD_TIMEOUT: Duration = Duration::from_secs(600);
+
+fn combined_log(output: &Output) -> String {
+    let mut log = String::from_utf8_lossy(&output.stdout).into_owned();
+    log.push_str(&String::from_utf8_lossy(&output.stderr));
+    log
+}
+
+fn timed_out(timeout: Duration) -> BuildOutput {
+    BuildOutput {
+        wasm: None,
+        log: format!("build timed out after {}s", timeout.as_secs()),
+    }
+}
+
+async fn read_artifact(
+    output: &Output,
+    workspace: &BuildWorkspace,
+) -> Result<Option<Vec<u8>>, RegistryError> {
+    if !output.status.success() {
+        return Ok(None);
+    }
+    tokio::fs::read(workspace.artifact_path())
+        .await
+        .map(Some)
+        .map_err(|err| io_error("read build artifact", err))
+}
+
+// ─── Host ───────────────────────────────────────────────────────────────────
+
+/// Environment variables passed through to cargo; everything else is dropped.
+const INHERITED_ENV: &[&str] = &["PATH", "HOME", "RUSTUP_HOME", "CARGO_HOME"];
+
+/// `program` on the verifier host with a cleared environment, so host
+/// configuration cannot leak into builds.
+pub(crate) fn host_command(program: &str) -> Command {
+    let mut command = Command::new(program);
+    command
+        .env_clear()
+        .env("CARGO_TERM_COLOR", "never")
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+    for key in INHERITED_ENV {
+        if let Some(value) = std::env::var_os(key) {
+            command.env(key, value);
+        }
+    }
+    command
+}
+
+/// Runs cargo on the verifier host with a cleared environment.  Provides no
+/// isolation: use only for trusted source.
+#[derive(Debug, Default)]
+pub struct HostSandbox;
+
+#[async_trait]
+impl BuildSandbox for HostSandbox {
+    fn name(&self) -> &str {
+        "host"
+    }
+
+    async fn prepare(&self, toolchain: &Toolchain) -> Result<(), RegistryError> {
+        toolchain.ensure_installed().await
+    }
+
+    async fn build(&self, workspace: &BuildWorkspace) -> Result<BuildOutput, RegistryError> {
+        let mut command = host_command("cargo");
+        command
+            .arg(format!("+{}", workspace.toolchain().rustc))
+            .args(["build", "--release", "--target", WASM_TARGET])
+            .args(["--package", workspace.package()])
+            .current_dir(workspace.root())
+            .env("CARGO_TARGET_DIR", workspace.target_dir());
+        if workspace.locked() {
+            command.arg("--locked");
+        }
+
+        let output = match tokio::time::timeout(BUILD_TIMEOUT, command.output()).await {
+            Ok(output) => output.map_err(|err| io_error("run cargo", err))?,
+            Err(_) => return Ok(timed_out(BUILD_TIMEOUT)),
+        };
+        Ok(BuildOutput {
+            wasm: read_artifact(&output, workspace).await?,
+            log: combined_log(&output),
+        })
+    }
+}
+
+// ─── Container ──────────────────────────────────────────────────────────────
+
+/// Cargo source replacement pointing at the dependencies vendored before the
+/// offline build.  Passed with `--config` so a repository's own
+/// `.cargo/config.toml` is left alone.
+const VENDOR_CONFIG: &[&str] = &[
+    "source.crates-io.replace-with=\"vendored-sources\"",
+    "source.vendored-sources.directory=\"vendor\"",
+];
+
+/// Longest dependency vendoring may run.
+const VENDOR_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Builds in a Docker or podman container.
+///
+/// Dependencies are first vendored into the workspace by `cargo vendor`,
+/// which downloads crates without compiling anything.  The build itself then
+/// runs offline: no network, read-only root filesystem and source mount, all
+/// capabilities dropped, the workspace owner's uid, and CPU, memory, process
+/// and time limits.  Images are derived from the official `rust` image per
+/// rustc release with the wasm target added.
+#[derive(Debug, Clone)]
+pub struct ContainerSandbox {
+    /// `docker` or `podman`
+    pub runtime: String,
+    /// Base image, `{rustc}` is replaced by the rustc release
+    pub base_image: String,
+    pub cpus: String,
+    pub memory: String,
+    pub pids_limit: u32,
+    pub timeout: Duration,
+}
+
+impl Default for ContainerSandbox {
+    fn default() -> Self {
+        Self {
+            runtime: "docker".to_string(),
+            base_image: "rust:{rustc}-slim".to_string(),
+            cpus: "2".to_string(),
+            memory: "4g".to_string(),
+            pids_limit: 512,
+            timeout: BUILD_TIMEOUT,
+        }
+    }
+}
+
+/// Removes a container when dropped, so a timed-out or cancelled build does
+/// not outlive its future.
+struct ContainerGuard<'a> {
+    runtime: &'a str,
+    name: String,
+}
+
+impl Drop for ContainerGuard<'_> {
+    fn drop(&mut self) {
+        let _ = std::process::Command::new(self.runtime)
+            .args(["rm", "--force", &self.name])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+    }
+}
+
+#[cfg(unix)]
+fn owner(path: &Path) -> Result<String, RegistryError> {
+    use std::os::unix::fs::MetadataExt;
+    let metadata = std::fs::metadata(path).map_err(|err| io_error("stat workspace", err))?;
+    Ok(format!("{}:{}", metadata.uid(), metadata.gid()))
+}
+
+#[cfg(not(unix))]
+fn owner(_path: &Path) -> Result<String, RegistryError> {
+    Err(RegistryError::Internal(
+        "container builds need a unix host".to_string(),
+    ))
+}
+
+/// Which container step to run.
+enum Step {
+    /// `cargo vendor`, with network, source writable
+    Vendor,
+    /// Offline build, source read-only, artifacts in the workspace target dir
+    Build,
+}
+
+impl ContainerSandbox {
+    pub fn new(runtime: impl Into<String>) -> Self {
+        Self {
+            runtime: runtime.into(),
+            ..Self::default()
+        }
+    }
+
+    fn image(toolchain: &Toolchain) -> String {
+        format!("soroban-verify:{}", toolchain.rustc)
+    }
+
+    fn dockerfile(&self, toolchain: &Toolchain) -> String {
+        format!(
+            "FROM {}\nRUN rustup target add {}\n",
+            self.base_image.replace("{rustc}", &toolchain.rustc),
+            WASM_TARGET
+        )
+    }
+
+    /// `run` arguments for one step, up to and including the image.
+    fn run_args(
+        &self,
+        name: &str,
+        user: &str,
+        workspace: &BuildWorkspace,
+        step: &Step,
+    ) -> Vec<String> {
+        let source = workspace.source_dir();
+        let source = source.display();
+        let workdir = Path::new("/src").join(workspace.relative_root());
+        let mut args: Vec<String> = vec![
+            "run".into(),
+            "--rm".into(),
+            "--name".into(),
+            name.into(),
+            "--user".into(),
+            user.into(),
+            "--read-only".into(),
+            "--tmpfs".into(),
+            "/tmp:rw,exec,size=2g".into(),
+            "--cap-drop".into(),
+            "ALL".into(),
+            "--security-opt".into(),
+            "no-new-privileges".into(),
+            "--pids-limit".into(),
+            self.pids_limit.to_string(),
+            "--cpus".into(),
+            self.cpus.clone(),
+            "--memory".into(),
+            self.memory.clone(),
+            "--memory-swap".into(),
+            self.memory.clone(),
+            "--env".into(),
+            "HOME=/tmp".into(),
+            "--env".into(),
+            "CARGO_HOME=/tmp/cargo".into(),
+            "--env".into(),
+            "CARGO_TERM_COLOR=never".into(),
+            // Overrides any rust-toolchain file in the source
+            "--env".into(),
+            format!("RUSTUP_TOOLCHAIN={}", workspace.toolchain().rustc),
+            "--workdir".into(),
+            workdir.display().to_string(),
+        ];
+        match step {
+            Step::Vendor => args.extend(["--volume".into(), format!("{}:/src", source)]),
+            Step::Build => args.extend([
+                "--network".into(),
+                "none".into(),
+                "--volume".into(),
+                format!("{}:/src:ro", source),
+                "--volume".into(),
+                format!("{}:/out", workspace.target_dir().display()),
+                "--env".into(),
+                "CARGO_TARGET_DIR=/out".into(),
+            ]),
+        }
+        args.push(Self::image(workspace.toolchain()));
+        args
+    }
+
+    /// Run one step; `None` when it timed out.
+    async fn run_step(
+        &self,
+        workspace: &BuildWorkspace,
+        step: Step,
+        cargo_args: &[&str],
+        timeout: Duration,
+    ) -> Result<Option<Output>, RegistryError> {
+        let guard = ContainerGuard {
+            runtime: &self.runtime,
+            name: format!("soroban-verify-{}", Uuid::new_v4()),
+        };
+        let user = owner(&workspace.source_dir())?;
+        let mut command = Command::new(&self.runtime);
+        command
+            .args(self.run_args(&guard.name, &user, workspace, &step))
+            .arg("cargo")
+            .args(cargo_args)
+            .stdin(Stdio::null())
+            .kill_on_drop(true);
+
+        let output = match tokio::time::timeout(timeout, command.output()).await {
+            Ok(output) => {
+                Some(output.map_err(|err| io_error(&format!("run {}", self.runtime), err))?)
+            }
+            Err(_) => None,
+        };
+        drop(guard);
+        Ok(output)
+    }
+}
+
+#[async_trait]
+impl BuildSandbox for ContainerSandbox {
+    fn name(&self) -> &str {
+        &self.runtime
+    }
+
+    /// Build the toolchain image unless it already exists.
+    async fn prepare(&self, toolchain: &Toolchain) -> Result<(), RegistryError> {
+        let image = Self::image(toolchain);
+        let present = Command::new(&self.runtime)
+            .args(["image", "inspect", &image])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await
+            .map_err(|err| io_error(&format!("run {}", self.runtime), err))?;
+        if present.success() {
+            return Ok(());
+        }
+
+        tracing::info!(image = %image, "building verifier toolchain image");
+        let mut child = Command::new(&self.runtime)
+            .args(["build", "--tag", &image, "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| io_error(&format!("run {}", self.runtime), err))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(self.dockerfile(toolchain).as_bytes())
+                .await
+                .map_err(|err| io_error("write Dockerfile", err))?;
+        }
+        let output = child
+            .wait_with_output()
+            .await
+            .map_err(|err| io_error(&format!("run {}", self.runtime), err))?;
+        if !output.status.success() {
+            return Err(RegistryError::Internal(format!(
+                "failed to build image {}: {}",
+                image,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(())
+    }
+
+    async fn build(&self, workspace: &BuildWorkspace) -> Result<BuildOutput, RegistryError> {
+        let mut vendor_args = vec!["vendor", "--versioned-dirs"];
+        if workspace.locked() {
+            vendor_args.push("--locked");
+        }
+        vendor_args.push("vendor");
+        let Some(vendored) = self
+            .run_step(workspace, Step::Vendor, &vendor_args, VENDOR_TIMEOUT)
+            .await?
+        else {
+            return Ok(timed_out(VENDOR_TIMEOUT));
+        };
+        let mut log = combined_log(&vendored);
+        if !vendored.status.success() {
+            return Ok(BuildOutput { wasm: None, log });
+        }
+
+        std::fs::create_dir_all(workspace.target_dir())
+            .map_err(|err| io_error("create target/", err))?;
+        let mut build_args = vec![
+            "build",
+            "--release",
+            "--target",
+            WASM_TARGET,
+            "--package",
+            workspace.package(),
+            "--offline",
+            "--locked",
+        ];
+        for config in VENDOR_CONFIG {
+            build_args.extend(["--config", config]);
+        }
+        let Some(built) = self
+            .run_step(workspace, Step::Build, &build_args, self.timeout)
+            .await?
+        else {
+            return Ok(timed_out(self.timeout));
+        };
+
+        log.push_str(&combined_log(&built));
+        Ok(BuildOutput {
+            wasm: read_artifact(&built, workspace).await?,
+            log,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn container_build_is_offline_and_read_only() {
+        let toolchain = Toolchain {
+            rustc: "1.79.0".to_string(),
+            soroban_sdk: "21.7.7".to_string(),
+        };
+        let workspace = BuildWorkspace::prepare("", &toolchain, None).unwrap();
+        let sandbox = ContainerSandbox::new("podman");
+        let source = workspace.source_dir().display().to_string();
+
+        let build = sandbox.run_args("build", "1000:1000", &workspace, &Step::Build);
+        let joined = build.join(" ");
+        assert!(joined.contains("--network none"));
+        assert!(joined.contains(&format!("{}:/src:ro", source)));
+        assert!(joined.contains("--read-only"));
+        assert!(joined.contains("--cap-drop ALL"));
+        assert_eq!(build.last().unwrap(), "soroban-verify:1.79.0");
+
+        let vendor = sandbox
+            .run_args("vendor", "1000:1000", &workspace, &Step::Vendor)
+            .join(" ");
+        assert!(!vendor.contains("--network"));
+        assert!(vendor.contains(&format!("{}:/src ", source)));
+
+        assert_eq!(
+            sandbox.dockerfile(&toolchain),
+            "FROM rust:1.79.0-slim\nRUN rustup target add wasm32-unknown-unknown\n"
+        );
+    }
+}
@@ -0,0 +1,346 @@
+// verifier/src/source.rs
+//
+// Where the source of a verification comes from: inline text submitted with
+// the request, or a git repository pinned to a commit.  Repositories are
+// fetched at exactly that commit and the contract crate is located with
+// `cargo metadata`; the result is recorded as the verification's provenance.
+
+use std::{
+    path::{Component, Path, PathBuf},
+    process::Stdio,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use shared::RegistryError;
+use tokio::process::Command;
+
+use crate::{sandbox::host_command, workspace::io_error};
+
+/// Longest a repository fetch may run.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum VerificationSource {
+    /// A single `lib.rs`, built in a generated crate
+    Inline {
+        source_code: String,
+    },
+    Git(GitSource),
+}
+
+/// A contract crate in a git repository.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitSource {
+    /// `https://` clone URL
+    pub repository: String,
+    /// Full commit SHA
+    pub commit: String,
+    /// Directory of the contract crate (or of a workspace holding exactly
+    /// one contract crate), relative to the repository root
+    #[serde(default)]
+    pub path: String,
+}
+
+/// What a verification was built from, as recorded on its report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    pub repository: String,
+    pub commit: String,
+    pub path: String,
+    /// Cargo package that was built
+    pub package: String,
+}
+
+/// The crate to build inside a checkout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractCrate {
+    pub package: String,
+    /// Library target name; the module is `<lib_name>.wasm`
+    pub lib_name: String,
+    /// Cargo workspace root, relative to the checkout
+    pub workspace_root: PathBuf,
+}
+
+fn invalid(msg: String) -> RegistryError {
+    RegistryError::InvalidInput(msg)
+}
+
+impl VerificationSource {
+    pub fn validate(&self) -> Result<(), RegistryError> {
+        match self {
+            Self::Inline { source_code } if source_code.trim().is_empty() => {
+                Err(invalid("source_code is empty".to_string()))
+            }
+            Self::Inline { .. } => Ok(()),
+            Self::Git(git) => git.validate(),
+        }
+    }
+}
+
+impl GitSource {
+    pub fn validate(&self) -> Result<(), RegistryError> {
+        // Other transports (file://, ssh, ext::) could reach the verifier host
+        if !self.repository.starts_with("https://")
+            || self
+                .repository
+                .chars()
+                .any(|c| c.is_whitespace() || c.is_control())
+        {
+            return Err(invalid(format!(
+                "repository must be an https:// URL, got '{}'",
+                self.repository
+            )));
+        }
+        if !matches!(self.commit.len(), 40 | 64)
+            || !self.commit.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return Err(invalid(format!(
+                "commit must be a full commit SHA, got '{}'",
+                self.commit
+            )));
+        }
+        let escapes = Path::new(&self.path)
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir));
+        if escapes {
+            return Err(invalid(format!(
+                "path must be relative to the repository root, got '{}'",
+                self.path
+            )));
+        }
+        Ok(())
+    }
+
+    async fn git(&self, dir: &Path, args: &[&str]) -> Result<(), RegistryError> {
+        let mut command = Command::new("git");
+        command
+            .arg("-C")
+            .arg(dir)
+            .args([
+                "-c",
+                "protocol.allow=never",
+                "-c",
+                "protocol.https.allow=always",
+            ])
+            .args(args)
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .stdin(Stdio::null())
+            .kill_on_drop(true);
+
+        let output = tokio::time::timeout(FETCH_TIMEOUT, command.output())
+            .await
+            .map_err(|_| {
+                invalid(format!(
+                    "fetching {} timed out after {}s",
+                    self.repository,
+                    FETCH_TIMEOUT.as_secs()
+                ))
+            })?
+            .map_err(|err| io_error("run git", err))?;
+        if !output.status.success() {
+            return Err(invalid(format!(
+                "failed to fetch {} at {}: {}",
+                self.repository,
+                self.commit,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check the pinned commit out into `dir`, without history or submodules.
+    pub async fn checkout(&self, dir: &Path) -> Result<(), RegistryError> {
+        self.validate()?;
+        std::fs::create_dir_all(dir).map_err(|err| io_error("create checkout", err))?;
+        self.git(dir, &["init", "--quiet"]).await?;
+        self.git(
+            dir,
+            &[
+                "fetch",
+                "--quiet",
+                "--depth",
+                "1",
+                "--no-tags",
+                &self.repository,
+                &self.commit,
+            ],
+        )
+        .await?;
+        self.git(dir, &["checkout", "--quiet", "--detach", "FETCH_HEAD"])
+            .await
+    }
+
+    /// Locate the contract crate in a checkout.  `cargo metadata --no-deps`
+    /// reads manifests only; it neither resolves dependencies nor runs code
+    /// from the repository.
+    pub async fn detect_crate(&self, checkout: &Path) -> Result<ContractCrate, RegistryError> {
+        let checkout = checkout
+            .canonicalize()
+            .map_err(|err| io_error("resolve checkout", err))?;
+        let manifest_dir = checkout.join(&self.path);
+        let manifest = manifest_dir.join("Cargo.toml");
+        if !manifest.is_file() {
+            return Err(invalid(format!("no Cargo.toml at '{}'", self.path)));
+        }
+
+        // Run from outside the checkout so its rust-toolchain file is ignored
+        let mut command = host_command("cargo");
+        command
+            .args([
+                "metadata",
+                "--no-deps",
+                "--format-version",
+                "1",
+                "--manifest-path",
+            ])
+            .arg(&manifest)
+            .current_dir(checkout.parent().unwrap_or(&checkout));
+        let output = command
+            .output()
+            .await
+            .map_err(|err| io_error("run cargo metadata", err))?;
+        if !output.status.success() {
+            return Err(invalid(format!(
+                "cargo metadata failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        let metadata: Value = serde_json::from_slice(&output.stdout).map_err(|err| {
+            RegistryError::Internal(format!("unreadable cargo metadata: {}", err))
+        })?;
+
+        select_contract(&metadata, &checkout, &manifest_dir)
+    }
+}
+
+/// Pick the contract crate out of `cargo metadata` output: the package whose
+/// manifest is in `manifest_dir`, or else the only cdylib package in the
+/// workspace rooted there.
+pub fn select_contract(
+    metadata: &Value,
+    checkout: &Path,
+    manifest_dir: &Path,
+) -> Result<ContractCrate, RegistryError> {
+    let workspace_root = Path::new(metadata["workspace_root"].as_str().unwrap_or_default());
+    let workspace_root = workspace_root
+        .strip_prefix(checkout)
+        .map_err(|_| invalid("cargo workspace root lies outside the repository".to_string()))?
+        .to_path_buf();
+
+    let candidates: Vec<(&Value, &Value)> = metadata["packages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|package| {
+            let lib = package["targets"].as_array()?.iter().find(|target| {
+                target["kind"]
+                    .as_array()
+                    .is_some_and(|kinds| kinds.iter().any(|k| k == "cdylib"))
+            })?;
+            Some((package, lib))
+        })
+        .collect();
+
+    let in_dir = |package: &Value| {
+        Path::new(package["manifest_path"].as_str().unwrap_or_default()).parent()
+            == Some(manifest_dir)
+    };
+    let chosen = match candidates.iter().find(|(package, _)| in_dir(package)) {
+        Some(found) => found,
+        None if candidates.len() == 1 => &candidates[0],
+        None if candidates.is_empty() => {
+            return Err(invalid("no cdylib contract crate found".to_string()))
+        }
+        None => {
+            let names: Vec<&str> = candidates
+                .iter()
+                .filter_map(|(package, _)| package["name"].as_str())
+                .collect();
+            return Err(invalid(format!(
+                "several contract crates found ({}); point path at one of them",
+                names.join(", ")
+            )));
+        }
+    };
+
+    let (package, lib) = chosen;
+    Ok(ContractCrate {
+        package: package["name"].as_str().unwrap_or_default().to_string(),
+        lib_name: lib["name"].as_str().unwrap_or_default().replace('-', "_"),
+        workspace_root,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn git(repository: &str, commit: &str, path: &str) -> GitSource {
+        GitSource {
+            repository: repository.to_string(),
+            commit: commit.to_string(),
+            path: path.to_string(),
+        }
+    }
+
+    #[test]
+    fn validates_git_sources() {
+        let sha = "a".repeat(40);
+        assert!(git("https://github.com/org/repo", &sha, "contracts/token")
+            .validate()
+            .is_ok());
+        assert!(git("file:///etc", &sha, "").validate().is_err());
+        assert!(git("https://github.com/org/repo", "main", "")
+            .validate()
+            .is_err());
+        assert!(git("https://github.com/org/repo", &sha, "../x")
+            .validate()
+            .is_err());
+        assert!(git("https://github.com/org/repo", &sha, "/abs")
+            .validate()
+            .is_err());
+
+        let source: VerificationSource = serde_json::from_value(json!({
+            "source": "git",
+            "repository": "https://github.com/org/repo",
+            "commit": sha,
+        }))
+        .unwrap();
+        assert!(
+            matches!(source, VerificationSource::Git(GitSource { ref path, .. }) if path.is_empty())
+        );
+    }
+
+    #[test]
+    fn selects_the_contract_crate() {
+        let package = |name: &str, dir: &str, kind: &str| {
+            json!({
+                "name": name,
+                "manifest_path": format!("/co/{}/Cargo.toml", dir),
+                "targets": [{ "name": name, "kind": [kind] }],
+            })
+        };
+        let metadata = json!({
+            "workspace_root": "/co",
+            "packages": [
+                package("token-contract", "contracts/token", "cdylib"),
+                package("pool", "contracts/pool", "cdylib"),
+                package("helpers", "helpers", "lib"),
+            ],
+        });
+        let checkout = Path::new("/co");
+
+        let token =
+            select_contract(&metadata, checkout, &checkout.join("contracts/token")).unwrap();
+        assert_eq!(token.package, "token-contract");
+        assert_eq!(token.lib_name, "token_contract");
+        assert_eq!(token.workspace_root, PathBuf::new());
+
+        // Ambiguous from the workspace root
+        assert!(select_contract(&metadata, checkout, checkout).is_err());
+    }
+}
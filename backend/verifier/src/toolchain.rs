@@ -0,0 +1,265 @@
+// verifier/src/toolchain.rs
+//
+// The toolchain a contract is rebuilt with.  Both versions are pinned from
+// the metadata submitted with the verification request: rustc from
+// `compiler_version` and soroban-sdk from `build_params.soroban_sdk`.  When
+// the compiler version is unknown, a matrix of candidate toolchains is tried
+// instead, most recent first.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use shared::RegistryError;
+use tokio::process::Command;
+
+/// Target Soroban contracts are built for.
+pub const WASM_TARGET: &str = "wasm32-unknown-unknown";
+
+/// rustc / soroban-sdk pairs tried when a request does not pin rustc.
+const DEFAULT_MATRIX: &[(&str, &str)] = &[
+    ("1.84.0", "22.0.7"),
+    ("1.81.0", "21.7.7"),
+    ("1.79.0", "21.2.0"),
+    ("1.77.0", "20.5.0"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Toolchain {
+    /// rustc release, e.g. `1.79.0`
+    pub rustc: String,
+    /// soroban-sdk release, e.g. `21.7.7`
+    pub soroban_sdk: String,
+}
+
+/// `1.79.0`, `22.0.0-rc.3`: exact releases only, never ranges or channels.
+fn is_exact_version(version: &str) -> bool {
+    let (release, pre) = match version.split_once('-') {
+        Some((release, pre)) => (release, Some(pre)),
+        None => (version, None),
+    };
+    let parts: Vec<&str> = release.split('.').collect();
+    parts.len() == 3
+        && parts
+            .iter()
+            .all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+        && pre.is_none_or(|pre| {
+            !pre.is_empty() && pre.chars().all(|c| c.is_ascii_alphanumeric() || c == '.')
+        })
+}
+
+/// `compiler_version` values meaning the submitter does not know it.
+fn is_unknown(compiler_version: &str) -> bool {
+    let version = compiler_version.trim();
+    version.is_empty()
+        || version.eq_ignore_ascii_case("unknown")
+        || version.eq_ignore_ascii_case("auto")
+}
+
+/// Release part of a version as numbers, for ordering.
+fn release_key(version: &str) -> Vec<u64> {
+    version
+        .split('-')
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+impl Toolchain {
+    /// Toolchains to build a request with: the pinned one, or the matrix
+    /// when `compiler_version` is unknown.  A pinned soroban-sdk still
+    /// applies to every matrix entry.
+    pub fn candidates(
+        compiler_version: &str,
+        build_params: &Value,
+        matrix: &ToolchainMatrix,
+    ) -> Result<Vec<Self>, RegistryError> {
+        if !is_unknown(compiler_version) {
+            return Ok(vec![Self::from_metadata(compiler_version, build_params)?]);
+        }
+
+        let pinned_sdk = build_params["soroban_sdk"].as_str();
+        if let Some(sdk) = pinned_sdk.filter(|sdk| !is_exact_version(sdk)) {
+            return Err(RegistryError::InvalidInput(format!(
+                "build_params.soroban_sdk must name an exact soroban-sdk release, got '{}'",
+                sdk
+            )));
+        }
+
+        let mut candidates: Vec<Self> = Vec::new();
+        for toolchain in matrix.toolchains() {
+            let candidate = Self {
+                rustc: toolchain.rustc.clone(),
+                soroban_sdk: pinned_sdk.unwrap_or(&toolchain.soroban_sdk).to_string(),
+            };
+            if !candidates.contains(&candidate) {
+                candidates.push(candidate);
+            }
+        }
+        if candidates.is_empty() {
+            return Err(RegistryError::InvalidInput(
+                "compiler_version is unknown and no toolchain matrix is configured".to_string(),
+            ));
+        }
+        Ok(candidates)
+    }
+
+    /// Read the pinned toolchain from a verification request.
+    ///
+    /// `compiler_version` may be a bare version or `rustc --version` output
+    /// (`rustc 1.79.0 (129f3b996 2024-06-10)`).
+    pub fn from_metadata(
+        compiler_version: &str,
+        build_params: &Value,
+    ) -> Result<Self, RegistryError> {
+        let rustc = compiler_version
+            .split_whitespace()
+            .find(|word| word.starts_with(|c: char| c.is_ascii_digit()))
+            .unwrap_or_default();
+        if !is_exact_version(rustc) {
+            return Err(RegistryError::InvalidInput(format!(
+                "compiler_version must name an exact rustc release, got '{}'",
+                compiler_version
+            )));
+        }
+
+        let soroban_sdk = build_params["soroban_sdk"].as_str().unwrap_or_default();
+        if !is_exact_version(soroban_sdk) {
+            return Err(RegistryError::InvalidInput(format!(
+                "build_params.soroban_sdk must name an exact soroban-sdk release, got '{}'",
+                soroban_sdk
+            )));
+        }
+
+        Ok(Self {
+            rustc: rustc.to_string(),
+            soroban_sdk: soroban_sdk.to_string(),
+        })
+    }
+
+    /// Install the rustc release and its wasm target through rustup; a no-op
+    /// when already present.
+    pub async fn ensure_installed(&self) -> Result<(), RegistryError> {
+        let output = Command::new("rustup")
+            .args(["toolchain", "install", &self.rustc, "--profile", "minimal"])
+            .args(["--target", WASM_TARGET])
+            .output()
+            .await
+            .map_err(|err| RegistryError::Internal(format!("failed to run rustup: {}", err)))?;
+
+        if !output.status.success() {
+            return Err(RegistryError::Internal(format!(
+                "failed to install rustc {}: {}",
+                self.rustc,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Candidate toolchains for requests with an unknown compiler version,
+/// ordered most recent first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolchainMatrix(Vec<Toolchain>);
+
+impl Default for ToolchainMatrix {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_MATRIX
+                .iter()
+                .map(|(rustc, sdk)| Toolchain {
+                    rustc: rustc.to_string(),
+                    soroban_sdk: sdk.to_string(),
+                })
+                .collect(),
+        )
+    }
+}
+
+impl ToolchainMatrix {
+    pub fn new(mut toolchains: Vec<Toolchain>) -> Self {
+        toolchains.sort_by(|a, b| {
+            (release_key(&b.rustc), release_key(&b.soroban_sdk))
+                .cmp(&(release_key(&a.rustc), release_key(&a.soroban_sdk)))
+        });
+        toolchains.dedup();
+        Self(toolchains)
+    }
+
+    /// Parse `rustc/soroban-sdk` pairs separated by commas, e.g.
+    /// `1.81.0/21.7.7, 1.79.0/21.2.0`.
+    pub fn parse(spec: &str) -> Result<Self, RegistryError> {
+        let toolchains = spec
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let (rustc, sdk) = entry.split_once('/').ok_or_else(|| {
+                    RegistryError::InvalidInput(format!(
+                        "toolchain matrix entry '{}' is not rustc/soroban-sdk",
+                        entry
+                    ))
+                })?;
+                Toolchain::from_metadata(
+                    rustc.trim(),
+                    &serde_json::json!({ "soroban_sdk": sdk.trim() }),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(toolchains))
+    }
+
+    pub fn toolchains(&self) -> &[Toolchain] {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reads_pinned_versions_from_metadata() {
+        let toolchain = Toolchain::from_metadata(
+            "rustc 1.79.0 (129f3b996 2024-06-10)",
+            &json!({ "soroban_sdk": "21.7.7" }),
+        )
+        .unwrap();
+        assert_eq!(toolchain.rustc, "1.79.0");
+        assert_eq!(toolchain.soroban_sdk, "21.7.7");
+
+        assert!(
+            Toolchain::from_metadata("1.79.0", &json!({ "soroban_sdk": "22.0.0-rc.3" })).is_ok()
+        );
+    }
+
+    #[test]
+    fn rejects_unpinned_versions() {
+        assert!(Toolchain::from_metadata("stable", &json!({ "soroban_sdk": "21.7.7" })).is_err());
+        assert!(Toolchain::from_metadata("1.79", &json!({ "soroban_sdk": "21.7.7" })).is_err());
+        assert!(Toolchain::from_metadata("1.79.0", &json!({ "soroban_sdk": "^21" })).is_err());
+        assert!(Toolchain::from_metadata("1.79.0", &json!({})).is_err());
+    }
+
+    #[test]
+    fn falls_back_to_the_matrix_when_rustc_is_unknown() {
+        let matrix = ToolchainMatrix::parse("1.79.0/21.2.0, 1.81.0/21.7.7").unwrap();
+        assert_eq!(matrix.toolchains()[0].rustc, "1.81.0");
+
+        let pinned =
+            Toolchain::candidates("1.79.0", &json!({ "soroban_sdk": "21.7.7" }), &matrix).unwrap();
+        assert_eq!(pinned.len(), 1);
+
+        let candidates = Toolchain::candidates("unknown", &json!({}), &matrix).unwrap();
+        assert_eq!(candidates, matrix.toolchains());
+
+        let candidates =
+            Toolchain::candidates("", &json!({ "soroban_sdk": "21.7.7" }), &matrix).unwrap();
+        assert!(candidates.iter().all(|t| t.soroban_sdk == "21.7.7"));
+
+        assert!(ToolchainMatrix::parse("1.81.0").is_err());
+        assert!(Toolchain::candidates("", &json!({}), &ToolchainMatrix::new(Vec::new())).is_err());
+    }
+}
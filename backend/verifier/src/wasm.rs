@@ -0,0 +1,392 @@
+// verifier/src/wasm.rs
+//
+// Section-level handling of wasm modules.  Rebuilding the same source on a
+// different machine changes a few custom sections (producers, debug info,
+// names) without changing behaviour, so those are stripped before hashing.
+// When a rebuild still does not match, `compare` explains which sections
+// differ.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use shared::RegistryError;
+
+const WASM_HEADER: &[u8; 8] = b"\0asm\x01\0\0\0";
+const CUSTOM_SECTION: u8 = 0;
+
+/// Custom sections whose contents depend on the build environment.
+const NON_DETERMINISTIC_SECTIONS: &[&str] = &[
+    "producers",
+    "name",
+    "target_features",
+    "sourceMappingURL",
+    "build_id",
+];
+
+/// Custom sections written by the soroban-sdk from the contract's interface
+/// and `contractmeta!` entries.
+const CONTRACT_METADATA_SECTIONS: &[&str] =
+    &["contractspecv0", "contractenvmetav0", "contractmetav0"];
+
+/// One section of a wasm module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section<'a> {
+    pub id: u8,
+    /// Name of a custom section
+    pub name: Option<&'a str>,
+    /// The whole section, id and size included
+    pub raw: &'a [u8],
+}
+
+fn invalid(msg: &str) -> RegistryError {
+    RegistryError::InvalidInput(format!("invalid wasm: {}", msg))
+}
+
+fn read_leb_u32(bytes: &[u8], pos: &mut usize) -> Result<u32, RegistryError> {
+    let mut result: u32 = 0;
+    for shift in (0..35).step_by(7) {
+        let byte = *bytes.get(*pos).ok_or_else(|| invalid("truncated LEB128"))?;
+        *pos += 1;
+        result |= u32::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(result);
+        }
+    }
+    Err(invalid("LEB128 value too long"))
+}
+
+/// Split a module into its sections.
+pub fn sections(wasm: &[u8]) -> Result<Vec<Section<'_>>, RegistryError> {
+    if !wasm.starts_with(WASM_HEADER) {
+        return Err(invalid("missing wasm header"));
+    }
+
+    let mut sections = Vec::new();
+    let mut pos = WASM_HEADER.len();
+    while pos < wasm.len() {
+        let start = pos;
+        let id = wasm[pos];
+        pos += 1;
+        let size = read_leb_u32(wasm, &mut pos)? as usize;
+        let body_start = pos;
+        let end = body_start
+            .checked_add(size)
+            .filter(|end| *end <= wasm.len())
+            .ok_or_else(|| invalid("section runs past end of module"))?;
+
+        let name = if id == CUSTOM_SECTION {
+            let len = read_leb_u32(wasm, &mut pos)? as usize;
+            let name = wasm
+                .get(pos..pos + len)
+                .filter(|_| pos + len <= end)
+                .ok_or_else(|| invalid("custom section name runs past section"))?;
+            Some(
+                std::str::from_utf8(name)
+                    .map_err(|_| invalid("custom section name is not UTF-8"))?,
+            )
+        } else {
+            None
+        };
+
+        sections.push(Section {
+            id,
+            name,
+            raw: &wasm[start..end],
+        });
+        pos = end;
+    }
+    Ok(sections)
+}
+
+fn is_non_deterministic(section: &Section<'_>) -> bool {
+    section.name.is_some_and(|name| {
+        NON_DETERMINISTIC_SECTIONS.contains(&name) || name.starts_with(".debug_")
+    })
+}
+
+/// The module without custom sections that depend on the build environment.
+/// Contract metadata sections (`contractspecv0`, `contractenvmetav0`, ...)
+/// are kept: they are part of the contract's interface.
+pub fn strip_non_deterministic(wasm: &[u8]) -> Result<Vec<u8>, RegistryError> {
+    let mut stripped = WASM_HEADER.to_vec();
+    for section in sections(wasm)? {
+        if !is_non_deterministic(&section) {
+            stripped.extend_from_slice(section.raw);
+        }
+    }
+    Ok(stripped)
+}
+
+fn section_label(section: &Section<'_>) -> String {
+    let name = match section.id {
+        CUSTOM_SECTION => return format!("custom '{}'", section.name.unwrap_or_default()),
+        1 => "type",
+        2 => "import",
+        3 => "function",
+        4 => "table",
+        5 => "memory",
+        6 => "global",
+        7 => "export",
+        8 => "start",
+        9 => "element",
+        10 => "code",
+        11 => "data",
+        12 => "datacount",
+        id => return format!("unknown ({})", id),
+    };
+    name.to_string()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SectionChange {
+    /// Only in the rebuilt module
+    Added,
+    /// Only in the deployed module
+    Missing,
+    Changed,
+}
+
+/// A section that differs between the rebuilt and the deployed module.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectionDiff {
+    /// `code`, `data`, `custom 'producers'`, ...
+    pub section: String,
+    pub change: SectionChange,
+    pub built_size: Option<usize>,
+    pub deployed_size: Option<usize>,
+}
+
+/// Section-level comparison of a rebuilt module with the deployed one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WasmDiff {
+    pub sections: Vec<SectionDiff>,
+    /// Likely cause, for publishers debugging a mismatch
+    pub explanation: String,
+}
+
+/// Sections by label; repeated labels (several custom sections of one name)
+/// are concatenated.
+fn labelled(sections: &[Section<'_>]) -> Vec<(String, Vec<u8>)> {
+    let mut labelled: Vec<(String, Vec<u8>)> = Vec::new();
+    for section in sections {
+        let label = section_label(section);
+        match labelled.iter_mut().find(|(l, _)| *l == label) {
+            Some((_, bytes)) => bytes.extend_from_slice(section.raw),
+            None => labelled.push((label, section.raw.to_vec())),
+        }
+    }
+    labelled
+}
+
+fn explain(sections: &[SectionDiff], built: &[Section<'_>], deployed: &[Section<'_>]) -> String {
+    if sections.is_empty() {
+        return "modules are identical".to_string();
+    }
+
+    let custom_names: Vec<&str> = sections
+        .iter()
+        .filter_map(|d| d.section.strip_prefix("custom '")?.strip_suffix('\''))
+        .collect();
+    let only_custom = custom_names.len() == sections.len();
+    let listed = sections
+        .iter()
+        .map(|d| d.section.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if only_custom {
+        let in_either = |name: &str| {
+            built
+                .iter()
+                .chain(deployed)
+                .find(|s| s.name == Some(name))
+                .is_some_and(|s| is_non_deterministic(s))
+        };
+        if custom_names.iter().all(|name| in_either(name)) {
+            return format!(
+                "only {} section{} differ{}, likely toolchain metadata; the code is identical",
+                listed,
+                if sections.len() == 1 { "" } else { "s" },
+                if sections.len() == 1 { "s" } else { "" },
+            );
+        }
+        if custom_names
+            .iter()
+            .all(|name| CONTRACT_METADATA_SECTIONS.contains(name) || in_either(name))
+        {
+            return format!(
+                "only contract metadata differs ({}), likely a different soroban-sdk version or contractmeta entries; the code is identical",
+                listed
+            );
+        }
+        return format!(
+            "only custom sections differ ({}); the code is identical",
+            listed
+        );
+    }
+
+    let differs = |name: &str| sections.iter().any(|d| d.section == name);
+    if ["type", "import", "export"].iter().any(|s| differs(s)) {
+        return format!(
+            "the contract interface differs ({}); the source likely does not match the deployed contract",
+            listed
+        );
+    }
+    if differs("code") && !differs("data") {
+        return format!(
+            "function bodies differ ({}) with the same interface, likely a different compiler version, optimisation settings or dependency versions",
+            listed
+        );
+    }
+    if differs("data") && !differs("code") {
+        return format!(
+            "only static data differs ({}), e.g. embedded strings, constants or panic messages",
+            listed
+        );
+    }
+    format!("code and data differ ({})", listed)
+}
+
+/// Compare a rebuilt module with the deployed one, section by section.
+pub fn compare(built: &[u8], deployed: &[u8]) -> Result<WasmDiff, RegistryError> {
+    let built_sections = sections(built)?;
+    let deployed_sections = sections(deployed)?;
+    let built_labelled = labelled(&built_sections);
+    let deployed_labelled = labelled(&deployed_sections);
+
+    let mut diffs = Vec::new();
+    for (label, bytes) in &built_labelled {
+        let other = deployed_labelled.iter().find(|(l, _)| l == label);
+        let change = match other {
+            None => SectionChange::Added,
+            Some((_, other)) if other != bytes => SectionChange::Changed,
+            Some(_) => continue,
+        };
+        diffs.push(SectionDiff {
+            section: label.clone(),
+            change,
+            built_size: Some(bytes.len()),
+            deployed_size: other.map(|(_, other)| other.len()),
+        });
+    }
+    for (label, bytes) in &deployed_labelled {
+        if !built_labelled.iter().any(|(l, _)| l == label) {
+            diffs.push(SectionDiff {
+                section: label.clone(),
+                change: SectionChange::Missing,
+                built_size: None,
+                deployed_size: Some(bytes.len()),
+            });
+        }
+    }
+
+    let explanation = explain(&diffs, &built_sections, &deployed_sections);
+    Ok(WasmDiff {
+        sections: diffs,
+        explanation,
+    })
+}
+
+/// Lower-case hex SHA-256, as stored for wasm hashes.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn custom_section(name: &str, payload: &[u8]) -> Vec<u8> {
+        let mut body = vec![name.len() as u8];
+        body.extend_from_slice(name.as_bytes());
+        body.extend_from_slice(payload);
+        let mut section = vec![CUSTOM_SECTION, body.len() as u8];
+        section.extend(body);
+        section
+    }
+
+    pub(crate) fn module(sections: &[Vec<u8>]) -> Vec<u8> {
+        let mut wasm = WASM_HEADER.to_vec();
+        for section in sections {
+            wasm.extend_from_slice(section);
+        }
+        wasm
+    }
+
+    // Type section with a single `() -> ()` function type
+    pub(crate) const TYPE_SECTION: [u8; 6] = [1, 4, 1, 0x60, 0, 0];
+
+    #[test]
+    fn splits_modules_into_sections() {
+        let wasm = module(&[TYPE_SECTION.to_vec(), custom_section("producers", b"rustc")]);
+        let sections = sections(&wasm).unwrap();
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].id, 1);
+        assert_eq!(sections[1].name, Some("producers"));
+
+        assert!(super::sections(b"\0asm").is_err());
+        assert!(super::sections(&module(&[vec![1, 10, 0]])).is_err());
+    }
+
+    #[test]
+    fn strips_only_build_environment_sections() {
+        let spec = custom_section("contractspecv0", b"spec");
+        let with_noise = module(&[
+            TYPE_SECTION.to_vec(),
+            spec.clone(),
+            custom_section("producers", b"rustc 1.79.0"),
+            custom_section(".debug_info", b"\x01\x02"),
+        ]);
+        let clean = module(&[TYPE_SECTION.to_vec(), spec]);
+
+        assert_eq!(strip_non_deterministic(&with_noise).unwrap(), clean);
+        assert_eq!(
+            sha256_hex(&strip_non_deterministic(&with_noise).unwrap()),
+            sha256_hex(&clean)
+        );
+    }
+
+    // Code section with one empty function body
+    fn code_section(body: u8) -> Vec<u8> {
+        vec![10, 4, 1, 2, 0, body]
+    }
+
+    #[test]
+    fn explains_which_sections_differ() {
+        let built = module(&[
+            TYPE_SECTION.to_vec(),
+            code_section(0x0b),
+            custom_section("producers", b"rustc 1.81.0"),
+        ]);
+
+        let toolchain_only = module(&[
+            TYPE_SECTION.to_vec(),
+            code_section(0x0b),
+            custom_section("producers", b"rustc 1.79.0"),
+        ]);
+        let diff = compare(&built, &toolchain_only).unwrap();
+        assert_eq!(diff.sections.len(), 1);
+        assert_eq!(diff.sections[0].section, "custom 'producers'");
+        assert_eq!(diff.sections[0].change, SectionChange::Changed);
+        assert!(diff
+            .explanation
+            .starts_with("only custom 'producers' section differs"));
+
+        let code = module(&[TYPE_SECTION.to_vec(), code_section(0x01)]);
+        let diff = compare(&built, &code).unwrap();
+        assert!(diff.sections.iter().any(|d| d.section == "code"));
+        assert!(diff
+            .sections
+            .iter()
+            .any(|d| d.section == "custom 'producers'" && d.change == SectionChange::Added));
+        assert!(diff.explanation.starts_with("function bodies differ"));
+
+        let interface = module(&[vec![1, 5, 1, 0x60, 1, 0x7e, 0], code_section(0x0b)]);
+        assert!(compare(&built, &interface)
+            .unwrap()
+            .explanation
+            .contains("interface differs"));
+
+        assert!(compare(&built, &built).unwrap().sections.is_empty());
+    }
+}
@@ -0,0 +1,229 @@
+// verifier/src/workspace.rs
+//
+// Throwaway cargo workspace a contract is rebuilt in.  The workspace lives in
+// a temp directory holding the source (`source/`: a generated crate for
+// inline source, or a repository checkout) and the build output (`target/`).
+// Inline crates pin soroban-sdk exactly and carry the submitted Cargo.lock
+// when there is one.  Building is up to a sandbox (sandbox.rs).
+
+use std::path::{Path, PathBuf};
+
+use shared::RegistryError;
+use tempfile::TempDir;
+
+use crate::{
+    source::{Provenance, VerificationSource},
+    toolchain::{Toolchain, WASM_TARGET},
+};
+
+const CRATE_NAME: &str = "contract";
+
+/// Release profile generated by `soroban contract init`.
+const RELEASE_PROFILE: &str = r#"[profile.release]
+opt-level = "z"
+overflow-checks = true
+debug = 0
+strip = "symbols"
+debug-assertions = false
+panic = "abort"
+codegen-units = 1
+lto = true
+"#;
+
+pub struct BuildWorkspace {
+    dir: TempDir,
+    /// Cargo workspace root, relative to `source_dir()`
+    root: PathBuf,
+    package: String,
+    lib_name: String,
+    toolchain: Toolchain,
+    locked: bool,
+    provenance: Option<Provenance>,
+}
+
+fn temp_dir() -> Result<TempDir, RegistryError> {
+    tempfile::Builder::new()
+        .prefix("soroban-verify-")
+        .tempdir()
+        .map_err(|err| io_error("create build directory", err))
+}
+
+pub(crate) fn io_error(action: &str, err: std::io::Error) -> RegistryError {
+    RegistryError::Internal(format!("failed to {}: {}", action, err))
+}
+
+fn manifest(toolchain: &Toolchain) -> String {
+    format!(
+        r#"[package]
+name = "{CRATE_NAME}"
+version = "0.0.0"
+edition = "2021"
+publish = false
+
+[lib]
+crate-type = ["cdylib"]
+
+[dependencies]
+soroban-sdk = "={sdk}"
+
+{RELEASE_PROFILE}
+[workspace]
+"#,
+        sdk = toolchain.soroban_sdk,
+    )
+}
+
+fn toolchain_file(toolchain: &Toolchain) -> String {
+    format!(
+        "[toolchain]\nchannel = \"{}\"\ntargets = [\"{}\"]\nprofile = \"minimal\"\n",
+        toolchain.rustc, WASM_TARGET
+    )
+}
+
+impl BuildWorkspace {
+    /// Lay out the source for a build.  Git sources are checked out and
+    /// built with the repository's own Cargo.lock; `cargo_lock` only applies
+    /// to inline source.
+    pub async fn from_source(
+        source: &VerificationSource,
+        toolchain: &Toolchain,
+        cargo_lock: Option<&str>,
+    ) -> Result<Self, RegistryError> {
+        source.validate()?;
+        match source {
+            VerificationSource::Inline { source_code } => {
+                Self::prepare(source_code, toolchain, cargo_lock)
+            }
+            VerificationSource::Git(git) => {
+                let dir = temp_dir()?;
+                let checkout = dir.path().join("source");
+                git.checkout(&checkout).await?;
+                let contract = git.detect_crate(&checkout).await?;
+                let root = contract.workspace_root;
+                let locked = checkout.join(&root).join("Cargo.lock").is_file();
+
+                Ok(Self {
+                    dir,
+                    root,
+                    provenance: Some(Provenance {
+                        repository: git.repository.clone(),
+                        commit: git.commit.to_ascii_lowercase(),
+                        path: git.path.clone(),
+                        package: contract.package.clone(),
+                    }),
+                    package: contract.package,
+                    lib_name: contract.lib_name,
+                    toolchain: toolchain.clone(),
+                    locked,
+                })
+            }
+        }
+    }
+
+    /// Write an inline contract into a generated crate.
+    pub fn prepare(
+        source_code: &str,
+        toolchain: &Toolchain,
+        cargo_lock: Option<&str>,
+    ) -> Result<Self, RegistryError> {
+        let dir = temp_dir()?;
+        let root = dir.path().join("source");
+        std::fs::create_dir_all(root.join("src")).map_err(|err| io_error("create src/", err))?;
+        let files = [
+            ("Cargo.toml", manifest(toolchain)),
+            ("rust-toolchain.toml", toolchain_file(toolchain)),
+            ("src/lib.rs", source_code.to_string()),
+        ];
+        for (path, contents) in files {
+            std::fs::write(root.join(path), contents)
+                .map_err(|err| io_error(&format!("write {}", path), err))?;
+        }
+        if let Some(lock) = cargo_lock {
+            std::fs::write(root.join("Cargo.lock"), lock)
+                .map_err(|err| io_error("write Cargo.lock", err))?;
+        }
+
+        Ok(Self {
+            dir,
+            root: PathBuf::new(),
+            package: CRATE_NAME.to_string(),
+            lib_name: CRATE_NAME.to_string(),
+            toolchain: toolchain.clone(),
+            locked: cargo_lock.is_some(),
+            provenance: None,
+        })
+    }
+
+    /// Whether dependencies are resolved from a Cargo.lock.
+    pub fn locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Everything the build may read: the generated crate or the checkout.
+    pub fn source_dir(&self) -> PathBuf {
+        self.dir.path().join("source")
+    }
+
+    /// Cargo workspace root, relative to `source_dir()`.
+    pub fn relative_root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Cargo workspace root.
+    pub fn root(&self) -> PathBuf {
+        self.source_dir().join(&self.root)
+    }
+
+    pub fn target_dir(&self) -> PathBuf {
+        self.dir.path().join("target")
+    }
+
+    /// Cargo package to build.
+    pub fn package(&self) -> &str {
+        &self.package
+    }
+
+    pub fn toolchain(&self) -> &Toolchain {
+        &self.toolchain
+    }
+
+    /// Repository the source was checked out from.
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_ref()
+    }
+
+    /// Where cargo leaves the module.
+    pub fn artifact_path(&self) -> PathBuf {
+        self.target_dir()
+            .join(WASM_TARGET)
+            .join("release")
+            .join(format!("{}.wasm", self.lib_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_a_pinned_isolated_crate() {
+        let toolchain = Toolchain {
+            rustc: "1.79.0".to_string(),
+            soroban_sdk: "21.7.7".to_string(),
+        };
+        let workspace =
+            BuildWorkspace::prepare("#![no_std]", &toolchain, Some("version = 3\n")).unwrap();
+        let root = workspace.root();
+
+        let manifest = std::fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert!(manifest.contains(r#"soroban-sdk = "=21.7.7""#));
+        assert!(manifest.contains("[workspace]"));
+        let toolchain_file = std::fs::read_to_string(root.join("rust-toolchain.toml")).unwrap();
+        assert!(toolchain_file.contains(r#"channel = "1.79.0""#));
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+            "#![no_std]"
+        );
+        assert!(workspace.locked());
+    }
+}
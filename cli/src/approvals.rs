@@ -0,0 +1,253 @@
+// cli/src/approvals.rs
+// CLI functions for the deployment switch approval workflow (two-person rule)
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde_json::json;
+
+fn status_colored(status: &str) -> colored::ColoredString {
+    match status {
+        "pending" => status.yellow(),
+        "executed" => status.green(),
+        "rejected" | "failed" => status.red(),
+        _ => status.bright_black(),
+    }
+}
+
+async fn error_message(response: reqwest::Response) -> String {
+    let status = response.status();
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    format!(
+        "API error ({}): {}",
+        status,
+        body["message"].as_str().unwrap_or("unknown error")
+    )
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Require approvals for a contract's switches
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub async fn set_policy(
+    api_url: &str,
+    contract_id: &str,
+    approvers: Option<Vec<String>>,
+    required: Option<i32>,
+    multisig_policy_id: Option<&str>,
+) -> Result<()> {
+    let client = crate::credentials::api_client(api_url)?;
+    let url = format!(
+        "{}/api/contracts/{}/deployment-approval-policy",
+        api_url, contract_id
+    );
+
+    let response = client
+        .put(&url)
+        .json(&json!({
+            "approvers": approvers,
+            "required_approvals": required,
+            "multisig_policy_id": multisig_policy_id,
+        }))
+        .send()
+        .await
+        .context("Failed to set approval policy")?;
+
+    if !response.status().is_success() {
+        anyhow::bail!(error_message(response).await);
+    }
+
+    let policy: serde_json::Value = response.json().await?;
+
+    println!("\n{}", "✓ Deployment approval policy saved".green().bold());
+    if let Some(id) = policy["multisig_policy_id"].as_str() {
+        println!("  {}: multisig policy {}", "Approvers".bold(), id.bright_black());
+    } else {
+        let approvers: Vec<&str> = policy["approvers"]
+            .as_array()
+            .map(|a| a.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+        println!(
+            "  {}: {} of {}",
+            "Required".bold(),
+            policy["required_approvals"].as_i64().unwrap_or(0),
+            approvers.len()
+        );
+        for approver in approvers {
+            println!("    • {}", approver.bright_black());
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// List queued switch requests
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub async fn list_requests(
+    api_url: &str,
+    contract_id: Option<&str>,
+    status: Option<&str>,
+    limit: usize,
+) -> Result<()> {
+    let client = crate::credentials::api_client(api_url)?;
+    let mut url = format!("{}/api/deployments/switch-requests?limit={}", api_url, limit);
+    if let Some(id) = contract_id {
+        url.push_str(&format!("&contract_id={}", id));
+    }
+    if let Some(s) = status {
+        url.push_str(&format!("&status={}", s));
+    }
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .context("Failed to list switch requests")?;
+
+    if !response.status().is_success() {
+        anyhow::bail!(error_message(response).await);
+    }
+
+    let items: Vec<serde_json::Value> = response.json().await?;
+
+    println!("\n{}", "Deployment Switch Requests:".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+
+    if items.is_empty() {
+        println!("{}", "No switch requests found.".yellow());
+        return Ok(());
+    }
+
+    for item in &items {
+        println!(
+            "\n{} {} {}",
+            "●".green(),
+            item["action"].as_str().unwrap_or("?").bold(),
+            item["id"].as_str().unwrap_or("").bright_black()
+        );
+        println!(
+            "  Status: {} | Needs: {} | Requested by: {}",
+            status_colored(item["status"].as_str().unwrap_or("?")),
+            item["required_approvals"].as_i64().unwrap_or(0),
+            item["requested_by"].as_str().unwrap_or("?")
+        );
+        println!("  Expires: {}", item["expires_at"].as_str().unwrap_or("?"));
+    }
+
+    println!("\n{}", "=".repeat(80).cyan());
+    println!("Found {} request(s)\n", items.len());
+
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Show one switch request
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn print_request(data: &serde_json::Value) {
+    let decisions = data["decisions"].as_array().cloned().unwrap_or_default();
+    let approvals = decisions.iter().filter(|d| d["approved"] == true).count();
+
+    println!(
+        "  {}: {}",
+        "Action".bold(),
+        data["action"].as_str().unwrap_or("?")
+    );
+    println!(
+        "  {}: {}",
+        "Status".bold(),
+        status_colored(data["status"].as_str().unwrap_or("?"))
+    );
+    println!(
+        "  {}: {}/{}",
+        "Approvals".bold(),
+        approvals,
+        data["required_approvals"].as_i64().unwrap_or(0)
+    );
+    if let Some(err) = data["error_message"].as_str() {
+        println!("  {}: {}", "Error".bold().red(), err);
+    }
+
+    for decision in &decisions {
+        let mark = if decision["approved"] == true {
+            "✓".green()
+        } else {
+            "✗".red()
+        };
+        print!("    {} {}", mark, decision["approver"].as_str().unwrap_or("?"));
+        if let Some(comment) = decision["comment"].as_str() {
+            print!(" — {}", comment.bright_black());
+        }
+        println!();
+    }
+}
+
+pub async fn request_info(api_url: &str, request_id: &str) -> Result<()> {
+    let client = crate::credentials::api_client(api_url)?;
+    let url = format!("{}/api/deployments/switch-requests/{}", api_url, request_id);
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .context("Failed to fetch switch request")?;
+
+    if !response.status().is_success() {
+        anyhow::bail!(error_message(response).await);
+    }
+
+    let data: serde_json::Value = response.json().await?;
+
+    println!("\n{}", "Switch Request:".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    print_request(&data);
+    println!();
+
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Approve / reject a switch request
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub async fn decide(
+    api_url: &str,
+    request_id: &str,
+    approver: Option<&str>,
+    comment: Option<&str>,
+    approve: bool,
+) -> Result<()> {
+    let client = crate::credentials::api_client(api_url)?;
+    let verb = if approve { "approve" } else { "reject" };
+    let url = format!(
+        "{}/api/deployments/switch-requests/{}/{}",
+        api_url, request_id, verb
+    );
+
+    let response = client
+        .post(&url)
+        .json(&json!({ "approver": approver, "comment": comment }))
+        .send()
+        .await
+        .with_context(|| format!("Failed to {} switch request", verb))?;
+
+    if !response.status().is_success() {
+        anyhow::bail!(error_message(response).await);
+    }
+
+    let data: serde_json::Value = response.json().await?;
+
+    let headline = match data["status"].as_str() {
+        Some("executed") => "✓ Approved — switch executed".green().bold(),
+        Some("failed") => "✗ Approved, but the switch failed".red().bold(),
+        Some("rejected") => "✗ Switch request rejected".red().bold(),
+        _ => "✓ Decision recorded — awaiting more approvals".yellow().bold(),
+    };
+    println!("\n{}", headline);
+    print_request(&data);
+    println!();
+
+    Ok(())
+}
@@ -10,13 +10,56 @@ use crate::patch::{PatchManager, Severity};
 use crate::profiler;
 use crate::test_framework;
 
+/// Render a contract's `verification_tier` as a coloured badge.
+fn tier_badge(tier: &str) -> colored::ColoredString {
+    match tier {
+        "audit" => "L3 Audited".green().bold(),
+        "build" => "L2 Reproducible build".green(),
+        "source" => "L1 Source matched".cyan(),
+        _ => "L0 Unverified".yellow(),
+    }
+}
+
+/// Highest protocol `network` runs, per the registry; `None` if unknown.
+async fn network_protocol(
+    client: &reqwest::Client,
+    api_url: &str,
+    network: Network,
+) -> Option<i32> {
+    let protocols: Vec<serde_json::Value> = client
+        .get(format!("{}/api/networks/protocols", api_url))
+        .send()
+        .await
+        .ok()?
+        .json()
+        .await
+        .ok()?;
+
+    protocols
+        .iter()
+        .find(|p| {
+            p["network"]
+                .as_str()
+                .is_some_and(|n| n.eq_ignore_ascii_case(&network.to_string()))
+        })
+        .and_then(|p| p["protocol_version"].as_i64())
+        .map(|v| v as i32)
+}
+
+/// Warning for a contract whose wasm targets a newer protocol than `network_protocol`.
+fn protocol_warning(contract: &serde_json::Value, network_protocol: Option<i32>) -> Option<String> {
+    let required = contract["min_protocol_version"].as_i64().map(|v| v as i32);
+    shared::wasm_features::protocol_warning(required, network_protocol?)
+}
+
 pub async fn search(
     api_url: &str,
     query: &str,
     network: Network,
     verified_only: bool,
+    min_tier: Option<&str>,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::credentials::api_client(api_url)?;
     let mut url = format!(
         "{}/api/contracts?query={}&network={}",
         api_url, query, network
@@ -26,6 +69,10 @@ pub async fn search(
         url.push_str("&verified_only=true");
     }
 
+    if let Some(tier) = min_tier {
+        url.push_str(&format!("&min_tier={}", tier));
+    }
+
     let response = client
         .get(&url)
         .send()
@@ -43,27 +90,35 @@ pub async fn search(
         return Ok(());
     }
 
+    let network_protocol = network_protocol(&client, api_url, network).await;
+
     for contract in items {
         let name = contract["name"].as_str().unwrap_or("Unknown");
         let contract_id = contract["contract_id"].as_str().unwrap_or("");
         let is_verified = contract["is_verified"].as_bool().unwrap_or(false);
         let network = contract["network"].as_str().unwrap_or("");
+        let tier = contract["verification_tier"].as_str().unwrap_or("unverified");
 
         println!("\n{} {}", "●".green(), name.bold());
         println!("  ID: {}", contract_id.bright_black());
         println!(
-            "  Status: {} | Network: {}",
+            "  Status: {} | Tier: {} | Network: {}",
             if is_verified {
                 "✓ Verified".green()
             } else {
                 "○ Unverified".yellow()
             },
+            tier_badge(tier),
             network.bright_blue()
         );
 
         if let Some(desc) = contract["description"].as_str() {
             println!("  {}", desc.bright_black());
         }
+
+        if let Some(warning) = protocol_warning(contract, network_protocol) {
+            println!("  {} {}", "⚠".yellow(), warning.yellow());
+        }
     }
 
     println!("\n{}", "=".repeat(80).cyan());
@@ -73,7 +128,7 @@ pub async fn search(
 }
 
 pub async fn info(api_url: &str, contract_id: &str, network: Network) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::credentials::api_client(api_url)?;
     let url = format!(
         "{}/api/contracts/{}?network={}",
         api_url, contract_id, network
@@ -120,6 +175,21 @@ pub async fn info(api_url: &str, contract_id: &str, network: Network) -> Result<
             "○ No".yellow()
         }
     );
+    println!(
+        "{}: {}",
+        "Verification Tier".bold(),
+        tier_badge(contract["verification_tier"].as_str().unwrap_or("unverified"))
+    );
+
+    if let Some(protocol) = contract["min_protocol_version"].as_i64() {
+        println!("{}: {}", "Min Protocol".bold(), protocol);
+    }
+    if let Some(warning) = protocol_warning(
+        &contract,
+        network_protocol(&client, api_url, network).await,
+    ) {
+        println!("{} {} on {}", "⚠".yellow(), warning.yellow(), network);
+    }
 
     if let Some(desc) = contract["description"].as_str() {
         println!("\n{}: {}", "Description".bold(), desc);
@@ -144,6 +214,7 @@ pub async fn info(api_url: &str, contract_id: &str, network: Network) -> Result<
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn publish(
     api_url: &str,
     contract_id: &str,
@@ -153,10 +224,16 @@ pub async fn publish(
     category: Option<&str>,
     tags: Vec<String>,
     publisher: &str,
+    metadata: Option<&str>,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::credentials::api_client(api_url)?;
     let url = format!("{}/api/contracts", api_url);
 
+    let metadata: Option<serde_json::Value> = metadata
+        .map(serde_json::from_str)
+        .transpose()
+        .context("--metadata must be valid JSON")?;
+
     let payload = json!({
         "contract_id": contract_id,
         "name": name,
@@ -165,6 +242,7 @@ pub async fn publish(
         "category": category,
         "tags": tags,
         "publisher_address": publisher,
+        "metadata": metadata,
     });
 
     println!("\n{}", "Publishing contract...".bold().cyan());
@@ -205,7 +283,7 @@ pub async fn publish(
 }
 
 pub async fn list(api_url: &str, limit: usize, network: Network) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::credentials::api_client(api_url)?;
     let url = format!(
         "{}/api/contracts?page_size={}&network={}",
         api_url, limit, network
@@ -297,7 +375,7 @@ pub async fn migrate(
     }
 
     // 3. Create Migration Record (Pending)
-    let client = reqwest::Client::new();
+    let client = crate::credentials::api_client(api_url)?;
     let create_url = format!("{}/api/migrations", api_url);
 
     let payload = json!({
@@ -407,7 +485,7 @@ pub async fn export(
 ) -> Result<()> {
     println!("\n{}", "Exporting contract...".bold().cyan());
 
-    let client = reqwest::Client::new();
+    let client = crate::credentials::api_client(api_url)?;
     let url = format!("{}/api/contracts/{}", api_url, contract_id);
 
     let (name, network) = match client.get(&url).send().await {
@@ -445,7 +523,6 @@ pub async fn export(
 }
 
 pub async fn import(
-    api_url: &str,
     archive: &str,
     network: Network,
     output_dir: &str,
@@ -496,6 +573,85 @@ pub async fn import(
     Ok(())
 }
 
+pub async fn publisher_stats(api_url: &str, publisher_id: &str) -> Result<()> {
+    let client = crate::credentials::api_client(api_url)?;
+    let url = format!("{}/api/publishers/{}/analytics", api_url, publisher_id);
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .context("Failed to fetch publisher analytics")?;
+
+    if !response.status().is_success() {
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            anyhow::bail!("Publisher not found: {}", publisher_id);
+        }
+        let err = response.text().await?;
+        anyhow::bail!("Failed to fetch publisher analytics: {}", err);
+    }
+
+    let stats: serde_json::Value = response.json().await?;
+
+    println!("\n{}", "Publisher Analytics:".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+
+    let total = stats["total_contracts"].as_i64().unwrap_or(0);
+    let verified = stats["verified_contracts"].as_i64().unwrap_or(0);
+    let coverage = stats["verification_coverage"].as_f64().unwrap_or(0.0);
+
+    println!("\n{}: {}", "Publisher".bold(), publisher_id.bright_black());
+    println!("{}: {}", "Contracts".bold(), total);
+    println!(
+        "{}: {}/{} ({:.1}%)",
+        "Verified".bold(),
+        verified,
+        total,
+        coverage
+    );
+    println!(
+        "{}: {}",
+        "Downloads".bold(),
+        stats["total_downloads"].as_i64().unwrap_or(0)
+    );
+    println!(
+        "{}: {}",
+        "Invocations".bold(),
+        stats["total_invocations"].as_i64().unwrap_or(0)
+    );
+
+    match stats["average_trust_score"].as_f64() {
+        Some(avg) => println!("{}: {:.1}/100", "Avg Trust Score".bold(), avg),
+        None => println!("{}: {}", "Avg Trust Score".bold(), "n/a".bright_black()),
+    }
+
+    let dist = &stats["trust_score_distribution"];
+    println!("\n{}", "Trust Score Distribution:".bold());
+    println!("  {} {}", "Healthy  (80-100):".green(), dist["healthy"].as_i64().unwrap_or(0));
+    println!("  {} {}", "Warning  (50-79): ".yellow(), dist["warning"].as_i64().unwrap_or(0));
+    println!("  {} {}", "Critical (0-49):  ".red(), dist["critical"].as_i64().unwrap_or(0));
+    println!("  {} {}", "Unscored:         ".bright_black(), dist["unscored"].as_i64().unwrap_or(0));
+
+    if let Some(events) = stats["recent_events"].as_array() {
+        if !events.is_empty() {
+            println!("\n{}", "Recent Events:".bold());
+            for event in events.iter().take(10) {
+                println!(
+                    "  {} {} {}",
+                    event["created_at"].as_str().unwrap_or("").bright_black(),
+                    event["event_type"].as_str().unwrap_or("unknown").bright_blue(),
+                    event["contract_id"].as_str().unwrap_or("")
+                );
+            }
+        }
+    }
+
+    println!("\n{}", "=".repeat(80).cyan());
+    println!();
+
+    Ok(())
+}
+
 fn severity_colored(sev: &Severity) -> colored::ColoredString {
     match sev {
         Severity::Critical => "CRITICAL".red().bold(),
@@ -653,7 +809,7 @@ pub async fn profile(
     println!("Functions Profiled: {}", profile_data.functions.len());
 
     let mut sorted_functions: Vec<_> = profile_data.functions.values().collect();
-    sorted_functions.sort_by(|a, b| b.total_time.cmp(&a.total_time));
+    sorted_functions.sort_by_key(|f| std::cmp::Reverse(f.total_time));
 
     println!("\n{}", "Top Functions:".bold());
     for (i, func) in sorted_functions.iter().take(10).enumerate() {
@@ -709,8 +865,11 @@ pub async fn profile(
         }
     }
 
+    Ok(())
+}
+
 pub async fn deps_list(api_url: &str, contract_id: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::credentials::api_client(api_url)?;
     let url = format!("{}/api/contracts/{}/dependencies", api_url, contract_id);
 
     let response = client
@@ -737,7 +896,47 @@ pub async fn deps_list(api_url: &str, contract_id: &str) -> Result<()> {
         return Ok(());
     }
 
-    fn print_tree(nodes: &[serde_json::Value], prefix: &str, is_last: bool) {
+    // Annotate deprecated dependencies with the reason and replacement
+    fn collect_ids(nodes: &[serde_json::Value], ids: &mut std::collections::BTreeSet<String>) {
+        for node in nodes {
+            if let Some(id) = node["contract_id"].as_str().filter(|id| *id != "unknown") {
+                ids.insert(id.to_string());
+            }
+            if let Some(children) = node["dependencies"].as_array() {
+                collect_ids(children, ids);
+            }
+        }
+    }
+    let mut ids = std::collections::BTreeSet::new();
+    collect_ids(tree, &mut ids);
+
+    // One batched lookup for the whole tree
+    let mut deprecations = std::collections::HashMap::new();
+    let ids: Vec<String> = ids.into_iter().collect();
+    for chunk in ids.chunks(200) {
+        let url = format!("{}/api/deprecations", api_url);
+        let response = client
+            .get(&url)
+            .query(&[("contract_ids", chunk.join(","))])
+            .send()
+            .await
+            .context("Failed to fetch deprecations")?;
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to fetch deprecations: {}", response.status());
+        }
+        let found: Vec<serde_json::Value> = response.json().await?;
+        for deprecation in found {
+            if let Some(id) = deprecation["contract_id"].as_str() {
+                deprecations.insert(id.to_string(), deprecation);
+            }
+        }
+    }
+
+    fn print_tree(
+        nodes: &[serde_json::Value],
+        prefix: &str,
+        deprecations: &std::collections::HashMap<String, serde_json::Value>,
+    ) {
         for (i, node) in nodes.iter().enumerate() {
             let name = node["name"].as_str().unwrap_or("Unknown");
             let constraint = node["constraint_to_parent"].as_str().unwrap_or("*");
@@ -755,16 +954,30 @@ pub async fn deps_list(api_url: &str, contract_id: &str) -> Result<()> {
                 if contract_id == "unknown" { "[Unresolved]".red() } else { "".normal() }
             );
 
+            if let Some(deprecation) = deprecations.get(contract_id) {
+                let child_prefix = format!("{}{}", prefix, if is_node_last { "    " } else { "│   " });
+                print!(
+                    "{}{} {}",
+                    child_prefix,
+                    "⚠ deprecated:".yellow().bold(),
+                    deprecation["reason"].as_str().unwrap_or("no reason given")
+                );
+                if let Some(replacement) = deprecation["replacement_contract_id"].as_str() {
+                    print!(" {}", format!("(use {})", replacement).bright_black());
+                }
+                println!();
+            }
+
             if let Some(children) = node["dependencies"].as_array() {
                 if !children.is_empty() {
                      let new_prefix = format!("{}{}", prefix, if is_node_last { "    " } else { "│   " });
-                     print_tree(children, &new_prefix, true);
+                     print_tree(children, &new_prefix, deprecations);
                 }
             }
         }
     }
 
-    print_tree(tree, "", true);
+    print_tree(tree, "", &deprecations);
 
 
     println!("\n{}", "=".repeat(80).cyan());
@@ -773,6 +986,66 @@ pub async fn deps_list(api_url: &str, contract_id: &str) -> Result<()> {
     Ok(())
 }
 
+pub async fn deps_resolve(api_url: &str, contract_id: &str) -> Result<()> {
+    let client = crate::credentials::api_client(api_url)?;
+    let url = format!(
+        "{}/api/contracts/{}/dependencies/resolve",
+        api_url, contract_id
+    );
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .context("Failed to resolve contract dependencies")?;
+
+    if !response.status().is_success() {
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            anyhow::bail!("Contract not found");
+        }
+        anyhow::bail!("Failed to resolve dependencies: {}", response.status());
+    }
+
+    let data: serde_json::Value = response.json().await?;
+
+    println!("\n{}", "Dependency Resolution:".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+
+    if let Some(resolved) = data["resolved"].as_object() {
+        for (name, version) in resolved {
+            println!(
+                "  {} {} {}",
+                "✓".green(),
+                name.bold(),
+                version.as_str().unwrap_or("?")
+            );
+        }
+    }
+
+    let conflicts = data["conflicts"].as_array().cloned().unwrap_or_default();
+    for conflict in &conflicts {
+        println!(
+            "  {} {}: {}",
+            "✗".red(),
+            conflict["package"].as_str().unwrap_or("?").bold(),
+            conflict["explanation"].as_str().unwrap_or("no solution")
+        );
+    }
+
+    let invalid = data["invalid_constraints"].as_array().cloned().unwrap_or_default();
+    for message in &invalid {
+        println!("  {} {}", "!".yellow(), message.as_str().unwrap_or(""));
+    }
+
+    println!("\n{}", "=".repeat(80).cyan());
+    if !conflicts.is_empty() {
+        anyhow::bail!("{} dependency conflict(s)", conflicts.len());
+    }
+    println!("{}\n", "All dependencies resolved.".green().bold());
+
+    Ok(())
+}
+
 
 pub async fn run_tests(
     test_file: &str,
@@ -865,7 +1138,7 @@ pub async fn run_tests(
     }
 
     if let Some(junit_path) = junit_output {
-        test_framework::generate_junit_xml(&[result], Path::new(junit_path))?;
+        test_framework::generate_junit_xml(std::slice::from_ref(&result), Path::new(junit_path))?;
         println!("\n{} JUnit XML report exported to: {}", "✓".green(), junit_path);
     }
 
@@ -1,94 +1,147 @@
-use anyhow::{Context, Result};
-use serde::{Deserialize, Serialize};
-use std::fmt;
-use std::fs;
-use std::path::PathBuf;
-use std::str::FromStr;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum Network {
-    Mainnet,
-    Testnet,
-    Futurenet,
-}
-
-impl fmt::Display for Network {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Network::Mainnet => write!(f, "mainnet"),
-            Network::Testnet => write!(f, "testnet"),
-            Network::Futurenet => write!(f, "futurenet"),
-        }
-    }
-}
-
-impl FromStr for Network {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "mainnet" => Ok(Network::Mainnet),
-            "testnet" => Ok(Network::Testnet),
-            "futurenet" => Ok(Network::Futurenet),
-            _ => anyhow::bail!("Invalid network: {}. Allowed values: mainnet, testnet, futurenet", s),
-        }
-    }
-}
-
-#[derive(Debug, Deserialize, Default)]
-struct ConfigFile {
-    network: Option<String>,
-}
-
-pub fn resolve_network(cli_flag: Option<String>) -> Result<Network> {
-    // 1. CLI Flag
-    if let Some(net_str) = cli_flag {
-        return net_str.parse::<Network>();
-    }
-
-    // 2. Config File
-    if let Some(config_path) = config_file_path() {
-        if config_path.exists() {
-            let content = fs::read_to_string(&config_path)
-                .with_context(|| format!("Failed to read config file at {:?}", config_path))?;
-            
-            let config: ConfigFile = toml::from_str(&content)
-                .with_context(|| "Failed to parse config file")?;
-
-            if let Some(net_str) = config.network {
-                return net_str.parse::<Network>();
-            }
-        }
-    }
-
-    // 3. Default
-    Ok(Network::Testnet)
-}
-
-fn config_file_path() -> Option<PathBuf> {
-    dirs::home_dir().map(|mut p| {
-        p.push(".soroban-registry.toml");
-        p
-    })
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::io::Write;
-    use tempfile::NamedTempFile;
-
-    #[test]
-    fn test_network_parsing() {
-        assert_eq!("mainnet".parse::<Network>().unwrap(), Network::Mainnet);
-        assert_eq!("testnet".parse::<Network>().unwrap(), Network::Testnet);
-        assert_eq!("futurenet".parse::<Network>().unwrap(), Network::Futurenet);
-        assert_eq!("Mainnet".parse::<Network>().unwrap(), Network::Mainnet); // Case insensitive
-        assert!("invalid".parse::<Network>().is_err());
-    }
-
-    // Note: Integration tests involving file system would require mocking or temporary files.
-    // Given the constraints and the environment, we focus on unit tests for parsing here.
-    // `resolve_network` with file interaction is harder to test in isolation without dependency injection or mocking `dirs` / `fs`.
-}
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Futurenet,
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Network::Mainnet => write!(f, "mainnet"),
+            Network::Testnet => write!(f, "testnet"),
+            Network::Futurenet => write!(f, "futurenet"),
+        }
+    }
+}
+
+impl FromStr for Network {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mainnet" => Ok(Network::Mainnet),
+            "testnet" => Ok(Network::Testnet),
+            "futurenet" => Ok(Network::Futurenet),
+            _ => anyhow::bail!("Invalid network: {}. Allowed values: mainnet, testnet, futurenet", s),
+        }
+    }
+}
+
+impl Network {
+    /// Public Soroban RPC endpoint used when no `rpc_url` is configured.
+    pub fn default_rpc_url(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "https://soroban-rpc.mainnet.stellar.gateway.fm",
+            Network::Testnet => "https://soroban-testnet.stellar.org",
+            Network::Futurenet => "https://rpc-futurenet.stellar.org",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct ConfigFile {
+    network: Option<String>,
+    rpc_url: Option<String>,
+}
+
+pub fn resolve_network(cli_flag: Option<String>) -> Result<Network> {
+    // 1. CLI Flag
+    if let Some(net_str) = cli_flag {
+        return net_str.parse::<Network>();
+    }
+
+    // 2. Config File
+    if let Some(config_path) = config_file_path() {
+        if config_path.exists() {
+            let content = fs::read_to_string(&config_path)
+                .with_context(|| format!("Failed to read config file at {:?}", config_path))?;
+            
+            let config: ConfigFile = toml::from_str(&content)
+                .with_context(|| "Failed to parse config file")?;
+
+            if let Some(net_str) = config.network {
+                return net_str.parse::<Network>();
+            }
+        }
+    }
+
+    // 3. Default
+    Ok(Network::Testnet)
+}
+
+/// Resolve the Soroban RPC endpoint: `SOROBAN_RPC_URL`, then `rpc_url` in
+/// the config file, then the network's public default.
+pub fn resolve_rpc_url(network: Network) -> String {
+    if let Ok(url) = std::env::var("SOROBAN_RPC_URL") {
+        if !url.is_empty() {
+            return url;
+        }
+    }
+
+    config_file_path()
+        .filter(|p| p.exists())
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|content| toml::from_str::<ConfigFile>(&content).ok())
+        .and_then(|config| config.rpc_url)
+        .unwrap_or_else(|| network.default_rpc_url().to_string())
+}
+
+/// Parse and validate config file contents, returning the configured network.
+pub fn validate_config(content: &str) -> Result<Option<Network>> {
+    let config: ConfigFile =
+        toml::from_str(content).with_context(|| "Failed to parse config file")?;
+
+    if let Some(url) = &config.rpc_url {
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            anyhow::bail!("rpc_url must be an http(s) URL, got {:?}", url);
+        }
+    }
+
+    config.network.map(|n| n.parse::<Network>()).transpose()
+}
+
+pub fn config_file_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|mut p| {
+        p.push(".soroban-registry.toml");
+        p
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_parsing() {
+        assert_eq!("mainnet".parse::<Network>().unwrap(), Network::Mainnet);
+        assert_eq!("testnet".parse::<Network>().unwrap(), Network::Testnet);
+        assert_eq!("futurenet".parse::<Network>().unwrap(), Network::Futurenet);
+        assert_eq!("Mainnet".parse::<Network>().unwrap(), Network::Mainnet); // Case insensitive
+        assert!("invalid".parse::<Network>().is_err());
+    }
+
+    #[test]
+    fn test_validate_config() {
+        assert_eq!(
+            validate_config("network = \"mainnet\"").unwrap(),
+            Some(Network::Mainnet)
+        );
+        assert_eq!(validate_config("").unwrap(), None);
+        assert!(validate_config("network = \"moonnet\"").is_err());
+        assert!(validate_config("rpc_url = \"localhost:8000\"").is_err());
+        assert!(validate_config("network = ").is_err());
+    }
+
+    // Note: Integration tests involving file system would require mocking or temporary files.
+    // Given the constraints and the environment, we focus on unit tests for parsing here.
+    // `resolve_network` with file interaction is harder to test in isolation without dependency injection or mocking `dirs` / `fs`.
+}
@@ -0,0 +1,265 @@
+// cli/src/credentials.rs
+// `soroban-registry login` / `logout` / `whoami` and the stored API token.
+//
+// Tokens are kept per registry URL in ~/.soroban-registry/credentials.toml
+// (created with mode 0600 on Unix) and attached as a bearer token by
+// `api_client`.  The admin token and auditor API keys are never stored: login
+// exchanges them for a session-length credential token.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct Credential {
+    token: String,
+    /// `admin`, `auditor` or `session`, as reported by the registry
+    kind: String,
+    name: Option<String>,
+    saved_at: String,
+    #[serde(default)]
+    expires_at: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CredentialsFile {
+    #[serde(default)]
+    registries: BTreeMap<String, Credential>,
+}
+
+fn credentials_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".soroban-registry").join("credentials.toml"))
+}
+
+/// Registries are keyed without a trailing slash so both spellings match.
+fn registry_key(api_url: &str) -> String {
+    api_url.trim_end_matches('/').to_string()
+}
+
+fn load() -> Result<CredentialsFile> {
+    let Some(path) = credentials_path() else {
+        return Ok(CredentialsFile::default());
+    };
+    match fs::read_to_string(&path) {
+        Ok(content) => toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display())),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(CredentialsFile::default()),
+        Err(err) => Err(err).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+fn save(file: &CredentialsFile) -> Result<()> {
+    let path = credentials_path().context("Cannot determine home directory")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut out = options
+        .open(&path)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    // `mode` only applies on creation; tighten files written by older versions
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        out.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    out.write_all(toml::to_string_pretty(file)?.as_bytes())
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Stored token for `api_url`, if logged in.
+pub fn stored_token(api_url: &str) -> Option<String> {
+    load()
+        .ok()
+        .and_then(|file| file.registries.get(&registry_key(api_url)).cloned())
+        .map(|c| c.token)
+}
+
+/// HTTP client that sends the stored token (if any) on every request.
+pub fn api_client(api_url: &str) -> Result<reqwest::Client> {
+    let mut headers = HeaderMap::new();
+    if let Some(token) = stored_token(api_url) {
+        let mut value = HeaderValue::from_str(&format!("Bearer {}", token))
+            .context("Stored token contains invalid characters; run `soroban-registry login` again")?;
+        value.set_sensitive(true);
+        headers.insert(AUTHORIZATION, value);
+    }
+    Ok(reqwest::Client::builder().default_headers(headers).build()?)
+}
+
+async fn fetch_whoami(api_url: &str, token: &str) -> Result<serde_json::Value> {
+    let url = format!("{}/api/auth/whoami", registry_key(api_url));
+    let response = reqwest::Client::new()
+        .get(&url)
+        .bearer_auth(token)
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach {}", api_url))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        anyhow::bail!(
+            "Token rejected ({}): {}",
+            status,
+            body["message"].as_str().unwrap_or("unknown error")
+        );
+    }
+    Ok(response.json().await?)
+}
+
+/// Exchange the admin token or an auditor API key for a credential token.
+async fn exchange_token(api_url: &str, token: &str) -> Result<serde_json::Value> {
+    let url = format!("{}/api/auth/token", registry_key(api_url));
+    let response = reqwest::Client::new()
+        .post(&url)
+        .bearer_auth(token)
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach {}", api_url))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        anyhow::bail!(
+            "Could not exchange the token for a session ({}): {}",
+            status,
+            body["message"].as_str().unwrap_or("unknown error")
+        );
+    }
+    Ok(response.json().await?)
+}
+
+fn describe(kind: &str, name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("{} ({})", name, kind),
+        None => kind.to_string(),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// login / logout / whoami
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub async fn login(api_url: &str, token: Option<String>) -> Result<()> {
+    let token = match token {
+        Some(token) => token,
+        None => {
+            print!("Paste an API token (admin token, auditor key or web session token): ");
+            io::stdout().flush()?;
+            let mut line = String::new();
+            io::stdin().lock().read_line(&mut line)?;
+            line
+        }
+    };
+    let token = token.trim().to_string();
+    if token.is_empty() {
+        anyhow::bail!("No token given");
+    }
+
+    let identity = fetch_whoami(api_url, &token).await?;
+    let kind = identity["kind"].as_str().unwrap_or("unknown").to_string();
+    let (token, expires_at) = match kind.as_str() {
+        "admin" | "auditor" => {
+            let exchanged = exchange_token(api_url, &token).await?;
+            let session = exchanged["token"]
+                .as_str()
+                .context("Registry returned no credential token")?
+                .to_string();
+            (session, exchanged["expires_at"].as_str().map(str::to_string))
+        }
+        _ => (token, None),
+    };
+    let credential = Credential {
+        token,
+        kind,
+        name: identity["name"].as_str().map(str::to_string),
+        saved_at: chrono::Utc::now().to_rfc3339(),
+        expires_at,
+    };
+
+    let mut file = load()?;
+    let who = describe(&credential.kind, credential.name.as_deref());
+    let expires_at_note = credential.expires_at.clone();
+    file.registries.insert(registry_key(api_url), credential);
+    save(&file)?;
+
+    println!("\n{} Logged in to {} as {}", "✓".green(), api_url.bold(), who.bold());
+    if let Some(expires_at) = &expires_at_note {
+        println!("  {} {}", "Session expires".bright_black(), expires_at);
+    }
+    println!();
+    Ok(())
+}
+
+pub fn logout(api_url: &str) -> Result<()> {
+    let mut file = load()?;
+    if file.registries.remove(&registry_key(api_url)).is_none() {
+        println!("{}", format!("Not logged in to {}", api_url).yellow());
+        return Ok(());
+    }
+    save(&file)?;
+    println!("{} Logged out of {}", "✓".green(), api_url.bold());
+    Ok(())
+}
+
+pub async fn whoami(api_url: &str) -> Result<()> {
+    let token = stored_token(api_url)
+        .with_context(|| format!("Not logged in to {}; run `soroban-registry login`", api_url))?;
+    let identity = fetch_whoami(api_url, &token).await?;
+
+    println!(
+        "{}",
+        describe(
+            identity["kind"].as_str().unwrap_or("unknown"),
+            identity["name"].as_str()
+        )
+        .bold()
+    );
+    let publishers = identity["publishers"].as_array().cloned().unwrap_or_default();
+    if !publishers.is_empty() {
+        println!("  {}:", "May publish for".bright_black());
+        for id in publishers {
+            println!("    • {}", id.as_str().unwrap_or("?"));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn credentials_round_trip_per_registry() {
+        let mut file = CredentialsFile::default();
+        file.registries.insert(
+            registry_key("http://localhost:3001/"),
+            Credential {
+                token: "aud_abc".into(),
+                kind: "auditor".into(),
+                name: Some("Acme Audits".into()),
+                saved_at: "2024-06-01T00:00:00Z".into(),
+                expires_at: Some("2024-06-01T12:00:00Z".into()),
+            },
+        );
+
+        let parsed: CredentialsFile = toml::from_str(&toml::to_string_pretty(&file).unwrap()).unwrap();
+        let credential = &parsed.registries[&registry_key("http://localhost:3001")];
+        assert_eq!(credential.token, "aud_abc");
+        assert_eq!(describe(&credential.kind, credential.name.as_deref()), "Acme Audits (auditor)");
+    }
+}
@@ -0,0 +1,270 @@
+// cli/src/doctor.rs
+// `soroban-registry doctor` — diagnose the local environment and print fixes.
+
+use anyhow::Result;
+use colored::Colorize;
+use std::fs;
+use std::process::Command;
+use std::time::Duration;
+
+use crate::config::{self, Network};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Ok,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug)]
+struct Check {
+    name: &'static str,
+    status: Status,
+    detail: String,
+    fix: Option<String>,
+}
+
+impl Check {
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self { name, status: Status::Ok, detail: detail.into(), fix: None }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self { name, status: Status::Warn, detail: detail.into(), fix: Some(fix.into()) }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self { name, status: Status::Fail, detail: detail.into(), fix: Some(fix.into()) }
+    }
+}
+
+pub async fn run(api_url: &str, network: Network) -> Result<()> {
+    println!("\n{}", "Soroban Registry Doctor".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()?;
+    let rpc_url = config::resolve_rpc_url(network);
+
+    let checks = vec![
+        check_config_file(),
+        check_history_file(),
+        check_api(&client, api_url).await,
+        check_rpc(&client, &rpc_url).await,
+        check_rustc(),
+        check_wasm_target(),
+        check_soroban_cli(),
+    ];
+
+    for check in &checks {
+        let badge = match check.status {
+            Status::Ok => "✓".green(),
+            Status::Warn => "!".yellow(),
+            Status::Fail => "✗".red(),
+        };
+        println!("\n{} {}: {}", badge, check.name.bold(), check.detail);
+        if let Some(fix) = &check.fix {
+            println!("    {} {}", "fix:".bright_blue(), fix);
+        }
+    }
+
+    let failures = checks.iter().filter(|c| c.status == Status::Fail).count();
+    let warnings = checks.iter().filter(|c| c.status == Status::Warn).count();
+
+    println!("\n{}", "=".repeat(80).cyan());
+    if failures == 0 && warnings == 0 {
+        println!("{}\n", "All checks passed.".green().bold());
+        return Ok(());
+    }
+    println!("{} failure(s), {} warning(s)\n", failures, warnings);
+
+    if failures > 0 {
+        anyhow::bail!("doctor found {} problem(s)", failures);
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Configuration
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn check_config_file() -> Check {
+    const NAME: &str = "Config file";
+    let Some(path) = config::config_file_path() else {
+        return Check::warn(NAME, "cannot determine home directory", "set the HOME environment variable");
+    };
+    if !path.exists() {
+        return Check::ok(NAME, format!("{} not present, using defaults", path.display()));
+    }
+
+    match fs::read_to_string(&path).map_err(anyhow::Error::from).and_then(|c| config::validate_config(&c)) {
+        Ok(Some(network)) => Check::ok(NAME, format!("{} (network = {})", path.display(), network)),
+        Ok(None) => Check::ok(NAME, path.display().to_string()),
+        Err(err) => Check::fail(
+            NAME,
+            format!("{} is invalid: {:#}", path.display(), err),
+            "fix the file or remove it; valid keys are `network` (mainnet | testnet | futurenet) and `rpc_url`",
+        ),
+    }
+}
+
+fn check_history_file() -> Check {
+    const NAME: &str = "History file";
+    let Some(home) = dirs::home_dir() else {
+        return Check::warn(NAME, "cannot determine home directory", "set the HOME environment variable");
+    };
+    let path = home.join(".soroban-registry").join("deployments.ndjson");
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Check::ok(NAME, "no history recorded yet");
+    };
+
+    let corrupt = count_corrupt_lines(&content);
+    if corrupt == 0 {
+        Check::ok(NAME, path.display().to_string())
+    } else {
+        Check::warn(
+            NAME,
+            format!("{} has {} unreadable line(s)", path.display(), corrupt),
+            format!("remove the corrupt lines or delete {}", path.display()),
+        )
+    }
+}
+
+fn count_corrupt_lines(ndjson: &str) -> usize {
+    ndjson
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter(|line| serde_json::from_str::<serde_json::Value>(line).is_err())
+        .count()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Connectivity
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn check_api(client: &reqwest::Client, api_url: &str) -> Check {
+    const NAME: &str = "Registry API";
+    let url = format!("{}/health", api_url.trim_end_matches('/'));
+    match client.get(&url).send().await {
+        Ok(resp) if resp.status().is_success() => Check::ok(NAME, format!("{} reachable", api_url)),
+        Ok(resp) => Check::fail(
+            NAME,
+            format!("{} returned HTTP {}", url, resp.status()),
+            "check that --api-url / SOROBAN_REGISTRY_API_URL points at a registry API",
+        ),
+        Err(err) => Check::fail(
+            NAME,
+            format!("cannot reach {}: {}", api_url, err),
+            "start the API or set --api-url / SOROBAN_REGISTRY_API_URL",
+        ),
+    }
+}
+
+async fn check_rpc(client: &reqwest::Client, rpc_url: &str) -> Check {
+    const NAME: &str = "Soroban RPC";
+    let body = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "getHealth" });
+    let resp = match client.post(rpc_url).json(&body).send().await {
+        Ok(resp) => resp,
+        Err(err) => {
+            return Check::fail(
+                NAME,
+                format!("cannot reach {}: {}", rpc_url, err),
+                "set SOROBAN_RPC_URL or `rpc_url` in ~/.soroban-registry.toml",
+            )
+        }
+    };
+
+    match resp.json::<serde_json::Value>().await {
+        Ok(v) if v["result"]["status"] == "healthy" => Check::ok(NAME, format!("{} healthy", rpc_url)),
+        Ok(v) => Check::warn(
+            NAME,
+            format!("{} reported {}", rpc_url, v.get("result").or(v.get("error")).unwrap_or(&v)),
+            "the RPC node may be syncing; retry later or use another endpoint",
+        ),
+        Err(_) => Check::fail(
+            NAME,
+            format!("{} did not return a JSON-RPC response", rpc_url),
+            "check that the URL points at a Soroban RPC server",
+        ),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Toolchain
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    Command::new(program)
+        .args(args)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+}
+
+fn check_rustc() -> Check {
+    match command_output("rustc", &["--version"]) {
+        Some(version) => Check::ok("rustc", version),
+        None => Check::fail(
+            "rustc",
+            "not found on PATH",
+            "install Rust via https://rustup.rs",
+        ),
+    }
+}
+
+fn has_wasm_target(installed: &str) -> bool {
+    installed
+        .lines()
+        .any(|t| matches!(t.trim(), "wasm32-unknown-unknown" | "wasm32v1-none"))
+}
+
+fn check_wasm_target() -> Check {
+    const NAME: &str = "wasm target";
+    match command_output("rustup", &["target", "list", "--installed"]) {
+        Some(installed) if has_wasm_target(&installed) => Check::ok(NAME, "installed"),
+        Some(_) => Check::fail(
+            NAME,
+            "no wasm target installed",
+            "rustup target add wasm32-unknown-unknown",
+        ),
+        None => Check::warn(
+            NAME,
+            "rustup not found, cannot list targets",
+            "install rustup or make sure your toolchain includes wasm32-unknown-unknown",
+        ),
+    }
+}
+
+fn check_soroban_cli() -> Check {
+    const NAME: &str = "Soroban CLI";
+    command_output("stellar", &["--version"])
+        .or_else(|| command_output("soroban", &["--version"]))
+        .map(|v| Check::ok(NAME, v.lines().next().unwrap_or_default().to_string()))
+        .unwrap_or_else(|| {
+            Check::warn(
+                NAME,
+                "neither `stellar` nor `soroban` found on PATH",
+                "cargo install --locked stellar-cli",
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_wasm_targets() {
+        assert!(has_wasm_target("x86_64-unknown-linux-gnu\nwasm32-unknown-unknown\n"));
+        assert!(has_wasm_target("wasm32v1-none"));
+        assert!(!has_wasm_target("x86_64-unknown-linux-gnu\nwasm32-wasi\n"));
+    }
+
+    #[test]
+    fn counts_corrupt_history_lines() {
+        assert_eq!(count_corrupt_lines("{\"a\":1}\n\n{\"b\":2}\n"), 0);
+        assert_eq!(count_corrupt_lines("{\"a\":1}\n{truncated\n"), 1);
+    }
+}
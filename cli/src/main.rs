@@ -1,12 +1,17 @@
+mod approvals;
 mod commands;
 mod config;
+mod credentials;
+mod doctor;
 mod export;
 mod import;
 mod manifest;
 mod multisig;
 mod patch;
 mod profiler;
+mod release_notes;
 mod test_framework;
+mod vendor;
 mod wizard;
 
 use anyhow::Result;
@@ -46,6 +51,9 @@ pub enum Commands {
         /// Only show verified contracts
         #[arg(long)]
         verified_only: bool,
+        /// Minimum verification tier (source, build, audit)
+        #[arg(long, value_parser = ["source", "build", "audit"])]
+        min_tier: Option<String>,
     },
 
     /// Get detailed information about a contract
@@ -79,6 +87,10 @@ pub enum Commands {
         /// Publisher Stellar address
         #[arg(long)]
         publisher: String,
+
+        /// Category-specific metadata as a JSON object (e.g. '{"symbol":"USDC","decimals":7}')
+        #[arg(long)]
+        metadata: Option<String>,
     },
 
     /// List recent contracts
@@ -145,6 +157,63 @@ pub enum Commands {
     /// Launch the interactive setup wizard
     Wizard {},
 
+    /// Diagnose configuration, connectivity and local toolchain problems
+    Doctor,
+
+    /// Store an API token for this registry; later commands send it automatically
+    Login {
+        /// Token to store (prompted for if omitted)
+        #[arg(long)]
+        token: Option<String>,
+    },
+
+    /// Forget the stored token for this registry
+    Logout,
+
+    /// Show who the stored token authenticates as
+    Whoami,
+
+    /// Download pinned dependency wasm and ABIs and write a lockfile
+    Vendor {
+        /// Dependency manifest
+        #[arg(long, default_value = "deps.toml")]
+        manifest: String,
+
+        /// Output directory
+        #[arg(long, default_value = "vendor")]
+        out: String,
+
+        /// Maximum concurrent downloads
+        #[arg(long, default_value_t = 4)]
+        jobs: usize,
+
+        /// Also generate Rust bindings (needs the stellar or soroban CLI)
+        #[arg(long)]
+        bindings: bool,
+    },
+
+    /// Generate Markdown release notes between two published versions
+    ReleaseNotes {
+        /// Registry contract ID
+        contract_id: String,
+
+        /// Previous release, e.g. v1.2.0
+        #[arg(long)]
+        from: String,
+
+        /// New release, e.g. v1.3.0
+        #[arg(long)]
+        to: String,
+
+        /// Custom template with {{placeholders}} (see release_notes.rs)
+        #[arg(long)]
+        template: Option<String>,
+
+        /// Write to a file instead of stdout
+        #[arg(long)]
+        out: Option<String>,
+    },
+
     /// Show command history
     History {
         /// Filter by search term
@@ -168,6 +237,18 @@ pub enum Commands {
         action: MultisigCommands,
     },
 
+    /// Approval workflow for deployment switches and rollbacks
+    Approvals {
+        #[command(subcommand)]
+        action: ApprovalCommands,
+    },
+
+    /// Publisher account commands
+    Publisher {
+        #[command(subcommand)]
+        action: PublisherCommands,
+    },
+
     /// Profile contract execution performance
     Profile {
         /// Path to contract file
@@ -215,6 +296,12 @@ pub enum Commands {
         #[arg(long, short)]
         verbose: bool,
     },
+
+    /// Manage contract dependencies
+    Deps {
+        #[command(subcommand)]
+        command: DepsCommands,
+    },
 }
 
 /// Sub-commands for the `multisig` group
@@ -276,6 +363,68 @@ pub enum MultisigCommands {
     },
 }
 
+/// Sub-commands for the `approvals` group
+#[derive(Debug, Subcommand)]
+pub enum ApprovalCommands {
+    /// Require K approvals before a contract's deployments can be switched
+    SetPolicy {
+        /// Registry contract UUID
+        contract_id: String,
+        /// Comma-separated approver addresses
+        #[arg(long, conflicts_with = "multisig_policy_id")]
+        approvers: Option<String>,
+        /// Number of approvals required
+        #[arg(long, requires = "approvers")]
+        required: Option<i32>,
+        /// Reuse an existing multisig policy as the approver set
+        #[arg(long)]
+        multisig_policy_id: Option<String>,
+    },
+
+    /// List queued switch requests
+    List {
+        #[arg(long)]
+        contract_id: Option<String>,
+        #[arg(long)]
+        status: Option<String>,
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+
+    /// Show a switch request and its decisions
+    Info { request_id: String },
+
+    /// Approve a pending switch request
+    Approve {
+        request_id: String,
+        /// Approver address to act as, if the session controls several
+        #[arg(long)]
+        approver: Option<String>,
+        #[arg(long)]
+        comment: Option<String>,
+    },
+
+    /// Reject a pending switch request
+    Reject {
+        request_id: String,
+        /// Approver address to act as, if the session controls several
+        #[arg(long)]
+        approver: Option<String>,
+        #[arg(long)]
+        comment: Option<String>,
+    },
+}
+
+/// Sub-commands for the `publisher` group
+#[derive(Debug, Subcommand)]
+pub enum PublisherCommands {
+    /// Show aggregate analytics across all of a publisher's contracts
+    Stats {
+        /// Publisher registry ID (UUID)
+        publisher_id: String,
+    },
+}
+
 /// Sub-commands for the `patch` group
 #[derive(Debug, Subcommand)]
 pub enum PatchCommands {
@@ -300,20 +449,21 @@ pub enum PatchCommands {
         patch_id: String,
     },
 
-    /// Manage contract dependencies
-    Deps {
-        #[command(subcommand)]
-        command: DepsCommands,
-    },
 }
 
-#[derive(Subcommand)]
-enum DepsCommands {
+/// Sub-commands for the `deps` group
+#[derive(Debug, Subcommand)]
+pub enum DepsCommands {
     /// List dependencies for a contract
     List {
         /// Contract ID
         contract_id: String,
     },
+    /// Resolve dependency versions and explain any conflicts
+    Resolve {
+        /// Contract ID
+        contract_id: String,
+    },
 }
 
 #[tokio::main]
@@ -334,20 +484,31 @@ async fn main() -> Result<()> {
     log::debug!("API URL: {}", cli.api_url);
 
     // ── Resolve network ───────────────────────────────────────────────────────
-    let network = config::resolve_network(cli.network)?;
+    // `doctor` has to run with a broken network setting, which it diagnoses
+    let network = match config::resolve_network(cli.network) {
+        Ok(network) => network,
+        Err(err) if matches!(cli.command, Commands::Doctor) => {
+            eprintln!("! {:#}; checking against testnet", err);
+            config::Network::Testnet
+        }
+        Err(err) => return Err(err),
+    };
     log::debug!("Network: {:?}", network);
 
     match cli.command {
-        Commands::Search { query, verified_only } => {
-            log::debug!("Command: search | query={:?} verified_only={}", query, verified_only);
-            commands::search(&cli.api_url, &query, network, verified_only).await?;
+        Commands::Search { query, verified_only, min_tier } => {
+            log::debug!(
+                "Command: search | query={:?} verified_only={} min_tier={:?}",
+                query, verified_only, min_tier
+            );
+            commands::search(&cli.api_url, &query, network, verified_only, min_tier.as_deref()).await?;
         }
         Commands::Info { contract_id } => {
             log::debug!("Command: info | contract_id={}", contract_id);
             commands::info(&cli.api_url, &contract_id, network).await?;
         }
         Commands::Publish {
-            contract_id, name, description, category, tags, publisher,
+            contract_id, name, description, category, tags, publisher, metadata,
         } => {
             let tags_vec = tags
                 .map(|t| t.split(',').map(|s| s.trim().to_string()).collect())
@@ -359,7 +520,7 @@ async fn main() -> Result<()> {
             commands::publish(
                 &cli.api_url, &contract_id, &name,
                 description.as_deref(), network,
-                category.as_deref(), tags_vec, &publisher,
+                category.as_deref(), tags_vec, &publisher, metadata.as_deref(),
             ).await?;
         }
         Commands::List { limit } => {
@@ -379,7 +540,7 @@ async fn main() -> Result<()> {
         }
         Commands::Import { archive, output_dir } => {
             log::debug!("Command: import | archive={} output_dir={}", archive, output_dir);
-            commands::import(&cli.api_url, &archive, network, &output_dir).await?;
+            commands::import(&archive, network, &output_dir).await?;
         }
         Commands::Doc { contract_path, output } => {
             log::debug!("Command: doc | contract_path={} output={}", contract_path, output);
@@ -453,6 +614,80 @@ async fn main() -> Result<()> {
                 multisig::list_proposals(&cli.api_url, status.as_deref(), limit).await?;
             }
         },
+        Commands::Doctor => {
+            log::debug!("Command: doctor");
+            doctor::run(&cli.api_url, network).await?;
+        }
+        Commands::Login { token } => {
+            log::debug!("Command: login | token_given={}", token.is_some());
+            credentials::login(&cli.api_url, token).await?;
+        }
+        Commands::Logout => {
+            log::debug!("Command: logout");
+            credentials::logout(&cli.api_url)?;
+        }
+        Commands::Whoami => {
+            log::debug!("Command: whoami");
+            credentials::whoami(&cli.api_url).await?;
+        }
+        Commands::Vendor { manifest, out, jobs, bindings } => {
+            log::debug!(
+                "Command: vendor | manifest={} out={} jobs={} bindings={}",
+                manifest, out, jobs, bindings
+            );
+            vendor::vendor(&cli.api_url, &manifest, &out, jobs, bindings).await?;
+        }
+        Commands::ReleaseNotes { contract_id, from, to, template, out } => {
+            log::debug!(
+                "Command: release-notes | contract_id={} from={} to={}",
+                contract_id, from, to
+            );
+            release_notes::release_notes(
+                &cli.api_url, &contract_id, &from, &to,
+                template.as_deref(), out.as_deref(),
+            )
+            .await?;
+        }
+        Commands::Approvals { action } => match action {
+            ApprovalCommands::SetPolicy {
+                contract_id, approvers, required, multisig_policy_id,
+            } => {
+                let approvers = approvers
+                    .map(|a| a.split(',').map(|s| s.trim().to_string()).collect());
+                log::debug!(
+                    "Command: approvals set-policy | contract_id={} approvers={:?} required={:?}",
+                    contract_id, approvers, required
+                );
+                approvals::set_policy(
+                    &cli.api_url, &contract_id, approvers, required,
+                    multisig_policy_id.as_deref(),
+                ).await?;
+            }
+            ApprovalCommands::List { contract_id, status, limit } => {
+                log::debug!("Command: approvals list | contract_id={:?} status={:?}", contract_id, status);
+                approvals::list_requests(
+                    &cli.api_url, contract_id.as_deref(), status.as_deref(), limit,
+                ).await?;
+            }
+            ApprovalCommands::Info { request_id } => {
+                log::debug!("Command: approvals info | request_id={}", request_id);
+                approvals::request_info(&cli.api_url, &request_id).await?;
+            }
+            ApprovalCommands::Approve { request_id, approver, comment } => {
+                log::debug!("Command: approvals approve | request_id={} approver={:?}", request_id, approver);
+                approvals::decide(&cli.api_url, &request_id, approver.as_deref(), comment.as_deref(), true).await?;
+            }
+            ApprovalCommands::Reject { request_id, approver, comment } => {
+                log::debug!("Command: approvals reject | request_id={} approver={:?}", request_id, approver);
+                approvals::decide(&cli.api_url, &request_id, approver.as_deref(), comment.as_deref(), false).await?;
+            }
+        },
+        Commands::Publisher { action } => match action {
+            PublisherCommands::Stats { publisher_id } => {
+                log::debug!("Command: publisher stats | publisher_id={}", publisher_id);
+                commands::publisher_stats(&cli.api_url, &publisher_id).await?;
+            }
+        },
         Commands::Profile {
             contract_path,
             method,
@@ -491,6 +726,9 @@ async fn main() -> Result<()> {
             DepsCommands::List { contract_id } => {
                 commands::deps_list(&cli.api_url, &contract_id).await?;
             }
+            DepsCommands::Resolve { contract_id } => {
+                commands::deps_resolve(&cli.api_url, &contract_id).await?;
+            }
         },
     }
 
@@ -17,7 +17,7 @@ pub async fn create_policy(
     expiry_secs: Option<u32>,
     created_by: &str,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::credentials::api_client(api_url)?;
     let url = format!("{}/api/multisig/policies", api_url);
 
     let payload = json!({
@@ -85,6 +85,7 @@ pub async fn create_policy(
 // Create a new deployment proposal
 // ─────────────────────────────────────────────────────────────────────────────
 
+#[allow(clippy::too_many_arguments)]
 pub async fn create_proposal(
     api_url: &str,
     contract_name: &str,
@@ -95,7 +96,7 @@ pub async fn create_proposal(
     proposer: &str,
     description: Option<&str>,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::credentials::api_client(api_url)?;
     let url = format!("{}/api/contracts/deploy-proposal", api_url);
 
     let payload = json!({
@@ -176,7 +177,7 @@ pub async fn sign_proposal(
     signer_address: &str,
     signature_data: Option<&str>,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::credentials::api_client(api_url)?;
     let url = format!("{}/api/contracts/{}/sign", api_url, proposal_id);
 
     let payload = json!({
@@ -235,7 +236,7 @@ pub async fn sign_proposal(
 // ─────────────────────────────────────────────────────────────────────────────
 
 pub async fn execute_proposal(api_url: &str, proposal_id: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::credentials::api_client(api_url)?;
     let url = format!("{}/api/contracts/{}/execute", api_url, proposal_id);
 
     println!("\n{}", "Executing deployment proposal...".bold().cyan());
@@ -281,7 +282,7 @@ pub async fn execute_proposal(api_url: &str, proposal_id: &str) -> Result<()> {
 // ─────────────────────────────────────────────────────────────────────────────
 
 pub async fn proposal_info(api_url: &str, proposal_id: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::credentials::api_client(api_url)?;
     let url = format!("{}/api/contracts/{}/proposal", api_url, proposal_id);
 
     let response = client
@@ -410,7 +411,7 @@ pub async fn list_proposals(
     status_filter: Option<&str>,
     limit: usize,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::credentials::api_client(api_url)?;
     let mut url = format!("{}/api/multisig/proposals?limit={}", api_url, limit);
     if let Some(s) = status_filter {
         url.push_str(&format!("&status={}", s));
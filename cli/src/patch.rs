@@ -77,7 +77,7 @@ impl PatchManager {
         severity: Severity,
         rollout: u8,
     ) -> Result<SecurityPatch> {
-        let client = reqwest::Client::new();
+        let client = crate::credentials::api_client(api_url)?;
         let payload = serde_json::json!({
             "target_version": version,
             "severity": severity,
@@ -102,7 +102,7 @@ impl PatchManager {
         api_url: &str,
         patch_id: &str,
     ) -> Result<(SecurityPatch, Vec<serde_json::Value>)> {
-        let client = reqwest::Client::new();
+        let client = crate::credentials::api_client(api_url)?;
 
         let patch_resp = client
             .get(format!("{}/api/patches/{}", api_url, patch_id))
@@ -137,7 +137,7 @@ impl PatchManager {
         contract_id: &str,
         patch_id: &str,
     ) -> Result<PatchAudit> {
-        let client = reqwest::Client::new();
+        let client = crate::credentials::api_client(api_url)?;
 
         let patch_resp = client
             .get(format!("{}/api/patches/{}", api_url, patch_id))
@@ -0,0 +1,419 @@
+// cli/src/release_notes.rs
+// `soroban-registry release-notes <contract_id> --from v1.2.0 --to v1.3.0`
+//
+// Composes Markdown release notes from the registry: ABI changes between the
+// two versions, changelog entries of every version in (from, to], security
+// patches applied in that window and advisories fixed by the new versions.
+//
+// The default template can be replaced with `--template`; it is plain text
+// with these placeholders:
+//
+//   {{contract}} {{from}} {{to}} {{date}}
+//   {{abi_changes}} {{changelog}} {{patches}} {{advisories}}
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::Deserialize;
+use serde_json::Value;
+use shared::semver::SemVer;
+use std::collections::BTreeMap;
+use std::fs;
+
+pub const DEFAULT_TEMPLATE: &str = "\
+# {{contract}} {{to}}
+
+_Changes since {{from}} · {{date}}_
+
+## Interface changes
+
+{{abi_changes}}
+
+## Changelog
+
+{{changelog}}
+
+## Security patches
+
+{{patches}}
+
+## Resolved advisories
+
+{{advisories}}
+";
+
+#[derive(Debug, Deserialize)]
+struct Version {
+    version: String,
+    release_notes: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Patch {
+    severity: String,
+    target_version: String,
+    description: Option<String>,
+    applied_at: Option<DateTime<Utc>>,
+    fixed_in_version: Option<String>,
+}
+
+/// Parse `v1.2.0` or `1.2.0`.
+fn parse_version(v: &str) -> Option<SemVer> {
+    SemVer::parse(v.strip_prefix('v').unwrap_or(v))
+}
+
+/// Signature of an ABI entry, used to spot changed functions and types.
+fn signature(entry: &Value) -> String {
+    if entry["type"].as_str() != Some("function") {
+        return entry.to_string();
+    }
+    let inputs: Vec<String> = entry["inputs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|i| {
+            format!(
+                "{}: {}",
+                i["name"].as_str().unwrap_or("_"),
+                i["value"]["type"].as_str().unwrap_or("?")
+            )
+        })
+        .collect();
+    let outputs: Vec<&str> = entry["outputs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|o| o["type"].as_str())
+        .collect();
+
+    let mut sig = format!(
+        "{}({})",
+        entry["name"].as_str().unwrap_or("?"),
+        inputs.join(", ")
+    );
+    if !outputs.is_empty() {
+        sig.push_str(&format!(" -> {}", outputs.join(", ")));
+    }
+    sig
+}
+
+fn abi_index(abi: &Value) -> BTreeMap<(String, String), &Value> {
+    abi.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| {
+            let kind = e["type"].as_str()?.to_string();
+            let name = e["name"].as_str()?.to_string();
+            Some(((kind, name), e))
+        })
+        .collect()
+}
+
+/// Markdown bullets for entries added, removed or changed between two ABIs.
+pub fn abi_changes(from: &Value, to: &Value) -> Vec<String> {
+    let old = abi_index(from);
+    let new = abi_index(to);
+    let mut changes = Vec::new();
+
+    for ((kind, name), entry) in &old {
+        match new.get(&(kind.clone(), name.clone())) {
+            None => changes.push(format!("- **Breaking:** removed {} `{}`", kind, name)),
+            Some(updated) if signature(updated) != signature(entry) => {
+                if kind == "function" {
+                    changes.push(format!(
+                        "- **Breaking:** changed `{}` → `{}`",
+                        signature(entry),
+                        signature(updated)
+                    ));
+                } else {
+                    changes.push(format!("- **Breaking:** changed {} `{}`", kind, name));
+                }
+            }
+            Some(_) => {}
+        }
+    }
+    for ((kind, name), entry) in &new {
+        if !old.contains_key(&(kind.clone(), name.clone())) {
+            if kind == "function" {
+                changes.push(format!("- Added `{}`", signature(entry)));
+            } else {
+                changes.push(format!("- Added {} `{}`", kind, name));
+            }
+        }
+    }
+    changes
+}
+
+/// Fill `{{placeholders}}` in `template`; empty sections read "None."
+///
+/// Substitution is a single pass, so placeholder-like text inside a value
+/// (a changelog mentioning `{{to}}`, say) is left as written.  Unknown
+/// placeholders are kept verbatim.
+pub fn render(template: &str, values: &[(&str, String)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+        let key = &after[..end];
+        match values.iter().find(|(name, _)| *name == key) {
+            Some((_, value)) if value.trim().is_empty() => out.push_str("None."),
+            Some((_, value)) => out.push_str(value),
+            None => out.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+async fn get_json<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<T> {
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        anyhow::bail!("{} returned {}", url, response.status());
+    }
+    Ok(response.json().await?)
+}
+
+pub async fn release_notes(
+    api_url: &str,
+    contract_id: &str,
+    from: &str,
+    to: &str,
+    template: Option<&str>,
+    out: Option<&str>,
+) -> Result<()> {
+    let from_ver = parse_version(from).with_context(|| format!("Invalid version: {}", from))?;
+    let to_ver = parse_version(to).with_context(|| format!("Invalid version: {}", to))?;
+    if from_ver >= to_ver {
+        anyhow::bail!("--from ({}) must be older than --to ({})", from, to);
+    }
+
+    let template = match template {
+        Some(path) => {
+            fs::read_to_string(path).with_context(|| format!("Failed to read template {}", path))?
+        }
+        None => DEFAULT_TEMPLATE.to_string(),
+    };
+
+    let client = crate::credentials::api_client(api_url)?;
+    let contract: Value = get_json(
+        &client,
+        &format!("{}/api/contracts/{}", api_url, contract_id),
+    )
+    .await?;
+    let versions: Vec<Version> = get_json(
+        &client,
+        &format!("{}/api/contracts/{}/versions", api_url, contract_id),
+    )
+    .await?;
+
+    // Registry version strings as published, e.g. "1.2.0"
+    let find = |wanted: &SemVer| {
+        versions
+            .iter()
+            .find(|v| parse_version(&v.version).as_ref() == Some(wanted))
+            .with_context(|| format!("Version {} is not published", wanted))
+    };
+    let from_entry = find(&from_ver)?;
+    let to_entry = find(&to_ver)?;
+
+    let abi_url = |v: &str| {
+        format!(
+            "{}/api/contracts/{}/versions/{}/abi",
+            api_url, contract_id, v
+        )
+    };
+    let abi_changes = match (
+        get_json::<Value>(&client, &abi_url(&from_entry.version)).await,
+        get_json::<Value>(&client, &abi_url(&to_entry.version)).await,
+    ) {
+        (Ok(old), Ok(new)) => {
+            let changes = abi_changes(&old, &new);
+            if changes.is_empty() {
+                "No interface changes.".to_string()
+            } else {
+                changes.join("\n")
+            }
+        }
+        _ => "_ABI not available for both versions._".to_string(),
+    };
+
+    let mut in_range: Vec<(SemVer, &Version)> = versions
+        .iter()
+        .filter_map(|v| parse_version(&v.version).map(|parsed| (parsed, v)))
+        .filter(|(parsed, _)| *parsed > from_ver && *parsed <= to_ver)
+        .collect();
+    in_range.sort_by(|a, b| b.0.cmp(&a.0));
+    let changelog = in_range
+        .iter()
+        .filter_map(|(_, v)| {
+            let notes = v.release_notes.as_deref()?.trim();
+            (!notes.is_empty()).then(|| format!("### {}\n\n{}", v.version, notes))
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    let patches: Option<Vec<Patch>> = match get_json(
+        &client,
+        &format!("{}/api/contracts/{}/patches", api_url, contract_id),
+    )
+    .await
+    {
+        Ok(patches) => Some(patches),
+        Err(err) => {
+            // stderr, so notes printed to stdout stay clean
+            eprintln!(
+                "{} {}",
+                "⚠".yellow(),
+                format!("Could not fetch security patches: {:#}", err).yellow()
+            );
+            None
+        }
+    };
+    let describe = |p: &Patch| {
+        format!(
+            "**{}** {}",
+            p.severity.to_uppercase(),
+            p.description.as_deref().unwrap_or(&p.target_version)
+        )
+    };
+    let unavailable = "_Security patches could not be fetched._".to_string();
+    let applied = patches
+        .iter()
+        .flatten()
+        .filter(|p| {
+            p.applied_at
+                .is_some_and(|at| at > from_entry.created_at && at <= to_entry.created_at)
+        })
+        .map(|p| format!("- {}", describe(p)))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let advisories = patches
+        .iter()
+        .flatten()
+        .filter_map(|p| {
+            let fixed = p.fixed_in_version.as_deref()?;
+            in_range
+                .iter()
+                .any(|(_, v)| v.version == fixed)
+                .then(|| format!("- {} (fixed in {})", describe(p), fixed))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let notes = render(
+        &template,
+        &[
+            (
+                "contract",
+                contract["name"].as_str().unwrap_or(contract_id).to_string(),
+            ),
+            ("from", from_entry.version.clone()),
+            ("to", to_entry.version.clone()),
+            ("date", to_entry.created_at.format("%Y-%m-%d").to_string()),
+            ("abi_changes", abi_changes),
+            ("changelog", changelog),
+            (
+                "patches",
+                if patches.is_some() { applied } else { unavailable.clone() },
+            ),
+            (
+                "advisories",
+                if patches.is_some() { advisories } else { unavailable },
+            ),
+        ],
+    );
+
+    match out {
+        Some(path) => {
+            fs::write(path, &notes).with_context(|| format!("Failed to write {}", path))?;
+            println!("{} Release notes written to {}", "✓".green(), path.bold());
+        }
+        None => print!("{}", notes),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn function(name: &str, inputs: &[(&str, &str)], output: Option<&str>) -> Value {
+        json!({
+            "type": "function",
+            "name": name,
+            "inputs": inputs
+                .iter()
+                .map(|(n, t)| json!({ "name": n, "value": { "type": t } }))
+                .collect::<Vec<_>>(),
+            "outputs": output.map(|t| vec![json!({ "type": t })]).unwrap_or_default(),
+        })
+    }
+
+    #[test]
+    fn reports_added_removed_and_changed_entries() {
+        let old = json!([
+            function("transfer", &[("to", "address"), ("amount", "i64")], None),
+            function("burn", &[("amount", "i128")], None),
+            { "type": "struct", "name": "Config", "fields": [] },
+        ]);
+        let new = json!([
+            function("transfer", &[("to", "address"), ("amount", "i128")], None),
+            function("balance", &[("id", "address")], Some("i128")),
+            { "type": "struct", "name": "Config", "fields": [] },
+        ]);
+
+        let changes = abi_changes(&old, &new);
+        assert_eq!(
+            changes,
+            vec![
+                "- **Breaking:** removed function `burn`",
+                "- **Breaking:** changed `transfer(to: address, amount: i64)` → \
+                 `transfer(to: address, amount: i128)`",
+                "- Added `balance(id: address) -> i128`",
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_placeholders_and_empty_sections() {
+        let out = render(
+            "{{contract}} {{to}}: {{patches}}",
+            &[
+                ("contract", "token".into()),
+                ("to", "1.3.0".into()),
+                ("patches", String::new()),
+            ],
+        );
+        assert_eq!(out, "token 1.3.0: None.");
+    }
+
+    #[test]
+    fn substitutes_in_a_single_pass() {
+        let out = render(
+            "{{changelog}} / {{to}} / {{unknown}} / {{open",
+            &[
+                ("changelog", "mentions {{to}}".into()),
+                ("to", "1.3.0".into()),
+            ],
+        );
+        assert_eq!(out, "mentions {{to}} / 1.3.0 / {{unknown}} / {{open");
+    }
+
+    #[test]
+    fn accepts_tag_style_versions() {
+        assert_eq!(parse_version("v1.2.0"), SemVer::parse("1.2.0"));
+        assert!(parse_version("release-1").is_none());
+    }
+}
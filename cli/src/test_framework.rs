@@ -83,14 +83,12 @@ pub struct CoverageMetrics {
 }
 
 pub struct TestRunner {
-    contract_path: String,
     contracts: HashMap<String, ContractInfo>,
     coverage: CoverageTracker,
 }
 
 #[derive(Debug, Clone)]
 struct ContractInfo {
-    name: String,
     methods: Vec<String>,
 }
 
@@ -137,7 +135,6 @@ impl TestRunner {
     pub fn new(contract_path: &str) -> Result<Self> {
         let contracts = Self::discover_contracts(contract_path)?;
         Ok(Self {
-            contract_path: contract_path.to_string(),
             contracts,
             coverage: CoverageTracker::new(),
         })
@@ -154,13 +151,7 @@ impl TestRunner {
                 .and_then(|s| s.to_str())
                 .unwrap_or("contract")
                 .to_string();
-            contracts.insert(
-                name.clone(),
-                ContractInfo {
-                    name: name.clone(),
-                    methods,
-                },
-            );
+            contracts.insert(name, ContractInfo { methods });
         } else if path.is_dir() {
             for entry in fs::read_dir(path)? {
                 let entry = entry?;
@@ -172,13 +163,7 @@ impl TestRunner {
                         .and_then(|s| s.to_str())
                         .unwrap_or("contract")
                         .to_string();
-                    contracts.insert(
-                        name.clone(),
-                        ContractInfo {
-                            name: name.clone(),
-                            methods,
-                        },
-                    );
+                    contracts.insert(name, ContractInfo { methods });
                 }
             }
         }
@@ -331,7 +316,7 @@ impl TestRunner {
         )))
     }
 
-    async fn execute_action(&self, action: &TestAction) -> Result<()> {
+    async fn execute_action(&mut self, action: &TestAction) -> Result<()> {
         match action.action.as_str() {
             "deploy" => {
                 tokio::time::sleep(Duration::from_millis(5)).await;
@@ -387,11 +372,7 @@ impl TestRunner {
                 }
             }
             "state" => {
-                if let Some(ref field) = assertion.field {
-                    Ok(true)
-                } else {
-                    Ok(false)
-                }
+                Ok(assertion.field.is_some())
             }
             "event" => Ok(true),
             _ => Err(anyhow::anyhow!("Unknown assertion type: {}", assertion.r#type)),
@@ -445,7 +426,6 @@ pub fn generate_junit_xml(results: &[TestResult], output_path: &Path) -> Result<
     ));
 
     for result in results {
-        let status = if result.passed { "pass" } else { "fail" };
         xml.push_str(&format!(
             "    <testcase name=\"{}\" classname=\"contract-test\" time=\"{:.3}\">\n",
             result.scenario,
@@ -0,0 +1,385 @@
+// cli/src/vendor.rs
+// `soroban-registry vendor` — download pinned dependency wasm + ABI so builds
+// against remote contracts are reproducible.
+//
+// deps.toml:
+//
+//   [dependencies]
+//   token = { id = "<registry contract id>", version = "^1.2.0" }
+//
+// Resolved versions and checksums are written to deps.lock next to the
+// manifest; later runs keep locked versions while they satisfy the manifest.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use shared::semver::{SemVer, VersionConstraint};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+#[derive(Debug, Deserialize)]
+struct VendorManifest {
+    #[serde(default)]
+    dependencies: BTreeMap<String, DependencySpec>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DependencySpec {
+    /// Registry contract id
+    id: String,
+    /// Version constraint, e.g. `1.2.0`, `^1.2.0`, `~1.2.0`
+    version: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Lockfile {
+    #[serde(default, rename = "package")]
+    packages: Vec<LockedPackage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct LockedPackage {
+    name: String,
+    id: String,
+    version: String,
+    wasm_sha256: String,
+    abi_sha256: String,
+}
+
+fn lockfile_path(manifest: &Path) -> PathBuf {
+    manifest.with_extension("lock")
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Dependency names become directory and file names under the vendor
+/// directory, so they must be a single plain path component.
+fn check_package_name(name: &str) -> Result<()> {
+    let plain = !name.is_empty()
+        && name != "."
+        && !name.contains("..")
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !plain {
+        anyhow::bail!(
+            "Invalid dependency name '{}': use letters, digits, '-', '_' and '.' only",
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Pick the version to vendor: the locked one while it still satisfies the
+/// constraint and exists in the registry, otherwise the highest match.
+fn select_version<'a>(
+    available: &'a [String],
+    constraint: &VersionConstraint,
+    locked: Option<&str>,
+) -> Option<&'a String> {
+    if let Some(locked) = locked {
+        let still_valid = SemVer::parse(locked).is_some_and(|v| constraint.matches(&v));
+        if still_valid {
+            if let Some(v) = available.iter().find(|v| v.as_str() == locked) {
+                return Some(v);
+            }
+        }
+    }
+
+    available
+        .iter()
+        .filter_map(|v| SemVer::parse(v).map(|parsed| (parsed, v)))
+        .filter(|(parsed, _)| constraint.matches(parsed))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, v)| v)
+}
+
+pub async fn vendor(
+    api_url: &str,
+    manifest_path: &str,
+    out_dir: &str,
+    jobs: usize,
+    bindings: bool,
+) -> Result<()> {
+    let manifest_path = Path::new(manifest_path);
+    let content = fs::read_to_string(manifest_path)
+        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+    let manifest: VendorManifest = toml::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+
+    if manifest.dependencies.is_empty() {
+        println!("{}", "No dependencies declared.".yellow());
+        return Ok(());
+    }
+
+    let lock_path = lockfile_path(manifest_path);
+    let lock: Lockfile = match fs::read_to_string(&lock_path) {
+        Ok(content) => toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", lock_path.display()))?,
+        Err(_) => Lockfile::default(),
+    };
+
+    println!("\n{}", "Vendoring Dependencies:".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+
+    let client = crate::credentials::api_client(api_url)?;
+    let out_dir = PathBuf::from(out_dir);
+    let permits = Arc::new(Semaphore::new(jobs.max(1)));
+    let mut tasks = JoinSet::new();
+
+    for (name, spec) in manifest.dependencies {
+        let constraint = VersionConstraint::parse(&spec.version).with_context(|| {
+            format!("Invalid version constraint for {}: {}", name, spec.version)
+        })?;
+        let locked = lock
+            .packages
+            .iter()
+            .find(|p| p.name == name && p.id == spec.id)
+            .cloned();
+
+        let client = client.clone();
+        let api_url = api_url.to_string();
+        let out_dir = out_dir.clone();
+        let permits = permits.clone();
+        tasks.spawn(async move {
+            let _permit = permits.acquire_owned().await?;
+            let result = fetch_dependency(
+                &client, &api_url, &name, &spec, &constraint, locked.as_ref(), &out_dir, bindings,
+            )
+            .await;
+            result.with_context(|| format!("Failed to vendor {}", name))
+        });
+    }
+
+    let mut packages = Vec::new();
+    let mut failures = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        match joined.context("Vendor task panicked")? {
+            Ok(package) => {
+                println!(
+                    "  {} {} {} {}",
+                    "✓".green(),
+                    package.name.bold(),
+                    package.version,
+                    package.wasm_sha256[..12].bright_black()
+                );
+                packages.push(package);
+            }
+            Err(err) => {
+                println!("  {} {:#}", "✗".red(), err);
+                failures.push(err);
+            }
+        }
+    }
+
+    println!("\n{}", "=".repeat(80).cyan());
+
+    if !failures.is_empty() {
+        anyhow::bail!("{} dependency(ies) failed; lockfile not updated", failures.len());
+    }
+
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    let lock = Lockfile { packages };
+    fs::write(&lock_path, toml::to_string_pretty(&lock)?)
+        .with_context(|| format!("Failed to write {}", lock_path.display()))?;
+
+    println!(
+        "Vendored {} package(s) into {} (lockfile: {})\n",
+        lock.packages.len(),
+        out_dir.display(),
+        lock_path.display()
+    );
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn fetch_dependency(
+    client: &reqwest::Client,
+    api_url: &str,
+    name: &str,
+    spec: &DependencySpec,
+    constraint: &VersionConstraint,
+    locked: Option<&LockedPackage>,
+    out_dir: &Path,
+    bindings: bool,
+) -> Result<LockedPackage> {
+    check_package_name(name)?;
+
+    let versions: Vec<serde_json::Value> = client
+        .get(format!("{}/api/contracts/{}/versions", api_url, spec.id))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let available: Vec<String> = versions
+        .iter()
+        .filter_map(|v| v["version"].as_str().map(str::to_string))
+        .collect();
+    let version = select_version(&available, constraint, locked.map(|p| p.version.as_str()))
+        .with_context(|| format!("No published version matches {}", constraint))?
+        .clone();
+    let wasm_hash = versions
+        .iter()
+        .find(|v| v["version"].as_str() == Some(version.as_str()))
+        .and_then(|v| v["wasm_hash"].as_str())
+        .context("Version has no wasm hash")?
+        .to_ascii_lowercase();
+
+    // The pre-signed URL goes straight to object storage, so it is fetched
+    // without the registry credentials
+    let presigned: serde_json::Value = client
+        .get(format!("{}/api/artifacts/wasm/{}", api_url, wasm_hash))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let mut download = reqwest::Client::new()
+        .get(presigned["url"].as_str().context("Malformed pre-signed URL")?);
+    if let Some(headers) = presigned["headers"].as_object() {
+        for (key, value) in headers {
+            download = download.header(key.as_str(), value.as_str().unwrap_or_default());
+        }
+    }
+    let wasm = download.send().await?.error_for_status()?.bytes().await?;
+
+    let wasm_sha256 = sha256_hex(&wasm);
+    if wasm_sha256 != wasm_hash {
+        anyhow::bail!(
+            "wasm checksum mismatch: registry says {}, downloaded {}",
+            wasm_hash,
+            wasm_sha256
+        );
+    }
+
+    let abi: serde_json::Value = client
+        .get(format!(
+            "{}/api/contracts/{}/versions/{}/abi",
+            api_url, spec.id, version
+        ))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let abi_json = serde_json::to_string_pretty(&abi)?;
+    let abi_sha256 = sha256_hex(abi_json.as_bytes());
+
+    if let Some(locked) = locked.filter(|p| p.version == version) {
+        if locked.wasm_sha256 != wasm_sha256 || locked.abi_sha256 != abi_sha256 {
+            anyhow::bail!(
+                "{} {} no longer matches the lockfile checksums",
+                name,
+                version
+            );
+        }
+    }
+
+    let dir = out_dir.join(name);
+    fs::create_dir_all(&dir)?;
+    let wasm_path = dir.join(format!("{}.wasm", name));
+    fs::write(&wasm_path, &wasm)?;
+    fs::write(dir.join("abi.json"), &abi_json)?;
+
+    if bindings {
+        let rust = generate_bindings(&wasm_path)?;
+        fs::write(dir.join("bindings.rs"), rust)?;
+    }
+
+    Ok(LockedPackage {
+        name: name.to_string(),
+        id: spec.id.clone(),
+        version,
+        wasm_sha256,
+        abi_sha256,
+    })
+}
+
+/// Generate Rust client bindings with the Stellar (or legacy Soroban) CLI.
+fn generate_bindings(wasm_path: &Path) -> Result<String> {
+    let wasm = wasm_path.to_string_lossy();
+    let args = ["contract", "bindings", "rust", "--wasm", wasm.as_ref()];
+    let output = Command::new("stellar")
+        .args(args)
+        .output()
+        .or_else(|_| Command::new("soroban").args(args).output())
+        .context("Generating bindings needs `stellar` or `soroban` on PATH")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Binding generation failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions(list: &[&str]) -> Vec<String> {
+        list.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn rejects_names_that_leave_the_vendor_directory() {
+        assert!(check_package_name("token").is_ok());
+        assert!(check_package_name("amm-pool_v2.1").is_ok());
+        for name in ["", ".", "..", "../evil", "a/b", "a\\b", "/abs", "x..y"] {
+            assert!(check_package_name(name).is_err(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn selects_highest_matching_version() {
+        let available = versions(&["1.0.0", "1.4.2", "2.0.0", "1.10.0"]);
+        let constraint = VersionConstraint::parse("^1.0.0").unwrap();
+
+        assert_eq!(select_version(&available, &constraint, None).unwrap(), "1.10.0");
+    }
+
+    #[test]
+    fn keeps_locked_version_while_it_satisfies_the_manifest() {
+        let available = versions(&["1.0.0", "1.4.2", "1.10.0"]);
+        let constraint = VersionConstraint::parse("^1.0.0").unwrap();
+        assert_eq!(
+            select_version(&available, &constraint, Some("1.4.2")).unwrap(),
+            "1.4.2"
+        );
+
+        // Manifest moved past the locked version
+        let constraint = VersionConstraint::parse("^1.5.0").unwrap();
+        assert_eq!(
+            select_version(&available, &constraint, Some("1.4.2")).unwrap(),
+            "1.10.0"
+        );
+    }
+
+    #[test]
+    fn lockfile_round_trips() {
+        let lock = Lockfile {
+            packages: vec![LockedPackage {
+                name: "token".into(),
+                id: "6f1c1d0e-8a50-4c44-9f1e-2f0f5f9f2a11".into(),
+                version: "1.4.2".into(),
+                wasm_sha256: sha256_hex(b"wasm"),
+                abi_sha256: sha256_hex(b"[]"),
+            }],
+        };
+
+        let parsed: Lockfile = toml::from_str(&toml::to_string_pretty(&lock).unwrap()).unwrap();
+        assert_eq!(parsed.packages, lock.packages);
+    }
+}
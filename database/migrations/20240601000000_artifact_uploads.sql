@@ -0,0 +1,36 @@
+-- Direct-to-bucket artifact uploads.
+-- Large wasm / source bundles are uploaded and downloaded through pre-signed
+-- object storage URLs; this table tracks each upload until the API has
+-- confirmed the stored object matches the declared SHA-256.
+
+DO $$ BEGIN
+    CREATE TYPE artifact_kind AS ENUM ('wasm', 'source_bundle');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END $$;
+
+DO $$ BEGIN
+    CREATE TYPE artifact_upload_status AS ENUM ('pending', 'completed', 'failed');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END $$;
+
+CREATE TABLE IF NOT EXISTS artifact_uploads (
+    id            UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id   UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    kind          artifact_kind NOT NULL,
+    object_key    VARCHAR(512) NOT NULL,
+    sha256        VARCHAR(64) NOT NULL,
+    size_bytes    BIGINT NOT NULL CHECK (size_bytes > 0),
+    status        artifact_upload_status NOT NULL DEFAULT 'pending',
+    error_message TEXT,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at  TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_artifact_uploads_contract_id
+    ON artifact_uploads(contract_id);
+
+-- Downloads look artifacts up by content hash
+CREATE INDEX IF NOT EXISTS idx_artifact_uploads_sha256_completed
+    ON artifact_uploads(kind, sha256) WHERE status = 'completed';
@@ -0,0 +1,23 @@
+-- Typed, category-specific contract metadata.
+-- Admins register a JSON Schema per category; contracts carry a `metadata`
+-- JSONB document validated against their category's schema by the API.
+
+CREATE TABLE IF NOT EXISTS category_metadata_schemas (
+    category    VARCHAR(100) PRIMARY KEY,
+    schema      JSONB NOT NULL,
+    updated_by  VARCHAR(255),
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+DROP TRIGGER IF EXISTS update_category_metadata_schemas_updated_at ON category_metadata_schemas;
+CREATE TRIGGER update_category_metadata_schemas_updated_at
+    BEFORE UPDATE ON category_metadata_schemas
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+ALTER TABLE contracts
+    ADD COLUMN IF NOT EXISTS metadata JSONB NOT NULL DEFAULT '{}'::jsonb;
+
+-- Supports `metadata @> '{...}'` search filters
+CREATE INDEX IF NOT EXISTS idx_contracts_metadata
+    ON contracts USING GIN (metadata jsonb_path_ops);
@@ -0,0 +1,73 @@
+-- Graduated verification tiers.
+--   source – L1: submitted source matched the deployed wasm
+--   build  – L2: reproducible build attested
+--   audit  – L3: security audit on file
+-- Declaration order matters: comparisons like `>= 'build'` rely on it.
+
+DO $$ BEGIN
+    CREATE TYPE verification_tier AS ENUM ('unverified', 'source', 'build', 'audit');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END $$;
+
+ALTER TABLE contract_versions
+    ADD COLUMN IF NOT EXISTS verification_tier verification_tier NOT NULL DEFAULT 'unverified';
+
+-- Denormalised tier of the latest version, used by search filters
+ALTER TABLE contracts
+    ADD COLUMN IF NOT EXISTS verification_tier verification_tier NOT NULL DEFAULT 'unverified';
+
+-- Existing verified contracts were source-matched, as was their latest version
+UPDATE contracts SET verification_tier = 'source'
+ WHERE is_verified = TRUE AND verification_tier = 'unverified';
+
+UPDATE contract_versions v SET verification_tier = 'source'
+  FROM contracts c
+ WHERE c.id = v.contract_id AND c.is_verified = TRUE
+   AND v.verification_tier = 'unverified'
+   AND v.created_at = (SELECT MAX(created_at) FROM contract_versions WHERE contract_id = c.id);
+
+CREATE INDEX IF NOT EXISTS idx_contracts_verification_tier
+    ON contracts(verification_tier);
+
+-- History of tier attestations per version
+CREATE TABLE IF NOT EXISTS verification_tier_attestations (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_version_id UUID NOT NULL REFERENCES contract_versions(id) ON DELETE CASCADE,
+    tier                verification_tier NOT NULL,
+    attested_by         VARCHAR(255) NOT NULL,
+    evidence_url        VARCHAR(500),
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_tier_attestations_version
+    ON verification_tier_attestations(contract_version_id, created_at DESC);
+
+-- security_audits / audit_checks back the checklist-based audit system.  The
+-- trust score reads the latest audit, and L3 needs one on file.
+CREATE TABLE IF NOT EXISTS security_audits (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id     UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    contract_source TEXT,
+    auditor         VARCHAR(255) NOT NULL,
+    audit_date      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    overall_score   DOUBLE PRECISION NOT NULL DEFAULT 0,
+    summary         TEXT,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_security_audits_contract
+    ON security_audits(contract_id, audit_date DESC);
+
+CREATE TABLE IF NOT EXISTS audit_checks (
+    id            UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    audit_id      UUID NOT NULL REFERENCES security_audits(id) ON DELETE CASCADE,
+    check_id      VARCHAR(64) NOT NULL,
+    status        TEXT NOT NULL DEFAULT 'pending',
+    notes         TEXT,
+    auto_detected BOOLEAN NOT NULL DEFAULT FALSE,
+    evidence      TEXT,
+    updated_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (audit_id, check_id)
+);
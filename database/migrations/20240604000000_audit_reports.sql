@@ -0,0 +1,30 @@
+-- Third-party audit report intake.
+-- Registered auditors upload a PDF (as an `audit_report` artifact) plus
+-- structured findings; findings become a security audit whose audit_checks
+-- they populate (both tables are created with the verification tiers).
+
+ALTER TYPE artifact_kind ADD VALUE IF NOT EXISTS 'audit_report';
+
+CREATE TABLE IF NOT EXISTS auditors (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name            VARCHAR(255) NOT NULL,
+    stellar_address VARCHAR(56),
+    website         VARCHAR(500),
+    api_key_hash    VARCHAR(64) NOT NULL UNIQUE,
+    revoked_at      TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS audit_reports (
+    id                 UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id        UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    audit_id           UUID NOT NULL REFERENCES security_audits(id) ON DELETE CASCADE,
+    auditor_id         UUID NOT NULL REFERENCES auditors(id),
+    artifact_upload_id UUID NOT NULL REFERENCES artifact_uploads(id),
+    findings           JSONB NOT NULL,
+    schema_version     VARCHAR(16) NOT NULL,
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_reports_contract
+    ON audit_reports(contract_id, created_at DESC);
@@ -0,0 +1,64 @@
+-- Deployment approval workflow (two-person rule) for blue/green switches.
+-- When a contract has an approval policy, switch and rollback requests are
+-- queued until K distinct approvers (other than the requester) approve.
+
+DO $$ BEGIN
+    CREATE TYPE deployment_action AS ENUM ('switch', 'rollback');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END $$;
+
+DO $$ BEGIN
+    CREATE TYPE switch_request_status AS ENUM ('pending', 'executed', 'rejected', 'expired', 'failed');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END $$;
+
+-- Either a named approver list or an existing multisig policy as approver set
+CREATE TABLE IF NOT EXISTS deployment_approval_policies (
+    contract_id        UUID PRIMARY KEY REFERENCES contracts(id) ON DELETE CASCADE,
+    approvers          TEXT[],
+    required_approvals INT CHECK (required_approvals >= 1),
+    multisig_policy_id UUID REFERENCES multisig_policies(id) ON DELETE RESTRICT,
+    expiry_seconds     INT NOT NULL DEFAULT 86400 CHECK (expiry_seconds >= 60),
+    created_by         VARCHAR(56) NOT NULL,
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (
+        multisig_policy_id IS NOT NULL
+        OR (approvers IS NOT NULL
+            AND required_approvals IS NOT NULL
+            AND array_length(approvers, 1) >= required_approvals)
+    )
+);
+
+CREATE TABLE IF NOT EXISTS deployment_switch_requests (
+    id                 UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id        UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    action             deployment_action NOT NULL,
+    force              BOOLEAN NOT NULL DEFAULT FALSE,
+    requested_by       VARCHAR(56) NOT NULL,
+    -- Approver set and threshold are snapshotted when the request is queued
+    approvers          TEXT[] NOT NULL,
+    required_approvals INT NOT NULL,
+    status             switch_request_status NOT NULL DEFAULT 'pending',
+    error_message      TEXT,
+    expires_at         TIMESTAMPTZ NOT NULL,
+    resolved_at        TIMESTAMPTZ,
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_switch_requests_contract
+    ON deployment_switch_requests(contract_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_switch_requests_status
+    ON deployment_switch_requests(status);
+
+CREATE TABLE IF NOT EXISTS deployment_switch_decisions (
+    id         UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    request_id UUID NOT NULL REFERENCES deployment_switch_requests(id) ON DELETE CASCADE,
+    approver   VARCHAR(56) NOT NULL,
+    approved   BOOLEAN NOT NULL,
+    comment    TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (request_id, approver)
+);
@@ -0,0 +1,40 @@
+-- Per-contract resource usage budgets.
+-- Indexed invocation costs are recorded as performance_metrics rows with the
+-- new metric types below; the budget monitor compares them against budgets
+-- and raises one alert per contract, budget kind and UTC day.
+
+ALTER TYPE metric_type ADD VALUE IF NOT EXISTS 'cpu_instructions';
+ALTER TYPE metric_type ADD VALUE IF NOT EXISTS 'invocation_fee';
+
+DO $$ BEGIN
+    CREATE TYPE budget_kind AS ENUM ('cpu_instructions', 'fee_per_invocation', 'daily_fees');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END $$;
+
+CREATE TABLE IF NOT EXISTS contract_budgets (
+    contract_id            UUID PRIMARY KEY REFERENCES contracts(id) ON DELETE CASCADE,
+    max_cpu_instructions   BIGINT CHECK (max_cpu_instructions > 0),
+    -- Fees are in stroops
+    max_fee_per_invocation BIGINT CHECK (max_fee_per_invocation > 0),
+    max_daily_fees         BIGINT CHECK (max_daily_fees > 0),
+    webhook_url            VARCHAR(500),
+    updated_by             VARCHAR(56) NOT NULL,
+    created_at             TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at             TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS budget_alerts (
+    id             UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id    UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    kind           budget_kind NOT NULL,
+    limit_value    BIGINT NOT NULL,
+    observed_value BIGINT NOT NULL,
+    window_start   DATE NOT NULL,
+    notified_at    TIMESTAMPTZ,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (contract_id, kind, window_start)
+);
+
+CREATE INDEX IF NOT EXISTS idx_budget_alerts_contract
+    ON budget_alerts(contract_id, created_at DESC);
@@ -0,0 +1,18 @@
+-- Event schema registry.
+-- Publishers describe the topic/data layout of their contract events per
+-- version; schemas are validated against the version's ABI on registration.
+
+CREATE TABLE IF NOT EXISTS contract_event_schemas (
+    id             UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id    UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    version        VARCHAR(50) NOT NULL,
+    event_name     VARCHAR(64) NOT NULL,
+    prefix_topics  TEXT[] NOT NULL DEFAULT '{}',
+    fields         JSONB NOT NULL,
+    registered_by  VARCHAR(56) NOT NULL,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(contract_id, version, event_name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_contract_event_schemas_contract_version
+    ON contract_event_schemas(contract_id, version);
@@ -0,0 +1,11 @@
+-- Trigram indexes for search-as-you-type and typo-tolerant search.
+-- Autocomplete matches name/tag prefixes and falls back to pg_trgm word
+-- similarity; the main search uses similarity to tolerate small typos.
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS idx_contracts_name_trgm
+    ON contracts USING GIN (lower(name) gin_trgm_ops);
+
+CREATE INDEX IF NOT EXISTS idx_tags_name_trgm
+    ON tags USING GIN (lower(name) gin_trgm_ops);
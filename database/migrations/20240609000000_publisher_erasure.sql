@@ -0,0 +1,44 @@
+-- Publisher data export and right-to-erasure workflow.
+-- Erasure replaces the publisher's address with a stable pseudonym and clears
+-- profile fields; contracts, versions and their hashes are kept so provenance
+-- stays verifiable.  Every step is recorded in publisher_erasure_audit.
+
+ALTER TABLE publishers ADD COLUMN IF NOT EXISTS erased_at TIMESTAMPTZ;
+
+DO $$ BEGIN
+    CREATE TYPE erasure_request_status AS ENUM ('pending', 'completed', 'rejected');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END $$;
+
+CREATE TABLE IF NOT EXISTS publisher_erasure_requests (
+    id            UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    publisher_id  UUID NOT NULL REFERENCES publishers(id),
+    requested_by  VARCHAR(56) NOT NULL,
+    reason        TEXT,
+    status        erasure_request_status NOT NULL DEFAULT 'pending',
+    reviewed_by   VARCHAR(255),
+    review_note   TEXT,
+    reviewed_at   TIMESTAMPTZ,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- At most one open request per publisher
+CREATE UNIQUE INDEX IF NOT EXISTS idx_erasure_requests_one_pending
+    ON publisher_erasure_requests(publisher_id) WHERE status = 'pending';
+
+CREATE INDEX IF NOT EXISTS idx_erasure_requests_status
+    ON publisher_erasure_requests(status, created_at);
+
+-- Append-only trail of every step
+CREATE TABLE IF NOT EXISTS publisher_erasure_audit (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    request_id  UUID NOT NULL REFERENCES publisher_erasure_requests(id),
+    action      VARCHAR(20) NOT NULL,
+    actor       VARCHAR(255) NOT NULL,
+    detail      JSONB,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_erasure_audit_request
+    ON publisher_erasure_audit(request_id, created_at);
@@ -0,0 +1,28 @@
+-- External login (OIDC / GitHub) for the web UI.
+-- Identities are linked to publishers directly, or gain publish rights through
+-- an org grant (e.g. every member of a GitHub org).
+
+CREATE TABLE IF NOT EXISTS external_identities (
+    id             UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    provider       VARCHAR(50) NOT NULL,
+    subject        VARCHAR(255) NOT NULL,
+    username       VARCHAR(255),
+    email          VARCHAR(255),
+    orgs           TEXT[] NOT NULL DEFAULT '{}',
+    publisher_id   UUID REFERENCES publishers(id) ON DELETE SET NULL,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_login_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(provider, subject)
+);
+
+CREATE INDEX IF NOT EXISTS idx_external_identities_publisher
+    ON external_identities(publisher_id);
+
+CREATE TABLE IF NOT EXISTS publisher_org_grants (
+    publisher_id  UUID NOT NULL REFERENCES publishers(id) ON DELETE CASCADE,
+    provider      VARCHAR(50) NOT NULL,
+    org           VARCHAR(255) NOT NULL,
+    granted_by    VARCHAR(56) NOT NULL,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (publisher_id, provider, org)
+);
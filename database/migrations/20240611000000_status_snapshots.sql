@@ -0,0 +1,27 @@
+-- Public status page data.
+-- The API's status aggregator writes one snapshot per minute; a missing
+-- snapshot counts as downtime when uptime percentages are computed.
+
+CREATE TABLE IF NOT EXISTS status_snapshots (
+    id                               UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    taken_at                         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    db_latency_ms                    INTEGER NOT NULL,
+    uptime_24h                       DOUBLE PRECISION NOT NULL,
+    uptime_7d                        DOUBLE PRECISION NOT NULL,
+    uptime_30d                       DOUBLE PRECISION NOT NULL,
+    verification_queue_depth         BIGINT NOT NULL,
+    -- Mean submission → verified time over the last 7 days
+    avg_verification_turnaround_secs DOUBLE PRECISION
+);
+
+CREATE INDEX IF NOT EXISTS idx_status_snapshots_taken_at
+    ON status_snapshots(taken_at DESC);
+
+CREATE TABLE IF NOT EXISTS status_snapshot_networks (
+    snapshot_id     UUID NOT NULL REFERENCES status_snapshots(id) ON DELETE CASCADE,
+    network         network_type NOT NULL,
+    -- Newest indexed interaction on this network
+    last_indexed_at TIMESTAMPTZ,
+    lag_seconds     BIGINT,
+    PRIMARY KEY (snapshot_id, network)
+);
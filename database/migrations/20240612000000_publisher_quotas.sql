@@ -0,0 +1,29 @@
+-- Per-publisher publishing quotas.
+-- Every publisher is on a tier (`free` unless an admin assigns another one);
+-- individual limits can be overridden per publisher.
+
+CREATE TABLE IF NOT EXISTS quota_tiers (
+    name                   VARCHAR(50) PRIMARY KEY,
+    max_contracts          INTEGER NOT NULL CHECK (max_contracts > 0),
+    max_versions_per_day   INTEGER NOT NULL CHECK (max_versions_per_day > 0),
+    max_wasm_storage_bytes BIGINT NOT NULL CHECK (max_wasm_storage_bytes > 0),
+    updated_at             TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO quota_tiers (name, max_contracts, max_versions_per_day, max_wasm_storage_bytes)
+VALUES
+    ('free',     25,   20,   52428800),    -- 50 MiB
+    ('verified', 250,  200,  1073741824),  -- 1 GiB
+    ('partner',  2500, 1000, 10737418240)  -- 10 GiB
+ON CONFLICT (name) DO NOTHING;
+
+CREATE TABLE IF NOT EXISTS publisher_quotas (
+    publisher_id           UUID PRIMARY KEY REFERENCES publishers(id) ON DELETE CASCADE,
+    tier                   VARCHAR(50) NOT NULL REFERENCES quota_tiers(name),
+    -- Overrides; NULL means "use the tier's limit"
+    max_contracts          INTEGER CHECK (max_contracts > 0),
+    max_versions_per_day   INTEGER CHECK (max_versions_per_day > 0),
+    max_wasm_storage_bytes BIGINT CHECK (max_wasm_storage_bytes > 0),
+    note                   TEXT,
+    updated_at             TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,28 @@
+-- Contract deprecation.
+-- Deprecating a contract records a notice for every contract that declares it
+-- as a dependency; the health monitor also lists deprecated dependencies in
+-- each dependent's trust-score breakdown.
+
+CREATE TABLE IF NOT EXISTS contract_deprecations (
+    contract_id             UUID PRIMARY KEY REFERENCES contracts(id) ON DELETE CASCADE,
+    reason                  TEXT NOT NULL,
+    replacement_contract_id UUID REFERENCES contracts(id) ON DELETE SET NULL,
+    deprecated_by           VARCHAR(56) NOT NULL,
+    created_at              TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS deprecation_notices (
+    id                     UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    dependent_contract_id  UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    deprecated_contract_id UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    dependency_name        VARCHAR(255) NOT NULL,
+    created_at             TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    acknowledged_at        TIMESTAMPTZ,
+    UNIQUE (dependent_contract_id, deprecated_contract_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_deprecation_notices_dependent
+    ON deprecation_notices(dependent_contract_id, created_at DESC);
+
+ALTER TABLE contract_health
+    ADD COLUMN IF NOT EXISTS deprecated_dependencies JSONB NOT NULL DEFAULT '[]';
@@ -0,0 +1,29 @@
+-- Wasm feature detection and minimum protocol reporting.
+-- Uploaded wasm is analysed once per content hash; the minimum protocol is
+-- mirrored onto the versions (and contracts) that use that wasm.
+
+CREATE TABLE IF NOT EXISTS wasm_features (
+    wasm_sha256          VARCHAR(64) PRIMARY KEY,
+    min_protocol_version INTEGER,
+    pre_release          INTEGER,
+    host_functions       TEXT[] NOT NULL DEFAULT '{}',
+    features             TEXT[] NOT NULL DEFAULT '{}',
+    analyzed_at          TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE contract_versions ADD COLUMN IF NOT EXISTS min_protocol_version INTEGER;
+ALTER TABLE contracts ADD COLUMN IF NOT EXISTS min_protocol_version INTEGER;
+
+-- Highest protocol each network runs; admins bump it after network upgrades.
+CREATE TABLE IF NOT EXISTS network_protocols (
+    network          network_type PRIMARY KEY,
+    protocol_version INTEGER NOT NULL CHECK (protocol_version > 0),
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO network_protocols (network, protocol_version)
+VALUES
+    ('mainnet',   23),
+    ('testnet',   23),
+    ('futurenet', 23)
+ON CONFLICT (network) DO NOTHING;
@@ -0,0 +1,32 @@
+-- Admin impersonation of publishers.
+-- Sessions are short-lived and read-only unless the admin asks otherwise;
+-- every request made with an impersonation token is logged with both the
+-- admin and the impersonated publisher.
+
+CREATE TABLE IF NOT EXISTS impersonation_sessions (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    publisher_id UUID NOT NULL REFERENCES publishers(id) ON DELETE CASCADE,
+    admin_name   VARCHAR(255) NOT NULL,
+    reason       TEXT NOT NULL,
+    read_only    BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at   TIMESTAMPTZ NOT NULL,
+    revoked_at   TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_impersonation_sessions_active
+    ON impersonation_sessions(expires_at) WHERE revoked_at IS NULL;
+
+CREATE TABLE IF NOT EXISTS impersonation_audit_log (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    session_id   UUID NOT NULL REFERENCES impersonation_sessions(id) ON DELETE CASCADE,
+    admin_name   VARCHAR(255) NOT NULL,
+    publisher_id UUID NOT NULL,
+    method       VARCHAR(10) NOT NULL,
+    path         TEXT NOT NULL,
+    status_code  INTEGER NOT NULL,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_impersonation_audit_log_session
+    ON impersonation_audit_log(session_id, created_at);
@@ -0,0 +1,35 @@
+-- Contract name reservations and squatting flags.
+-- Verified publishers may reserve a name (or a whole namespace, e.g.
+-- `soroswap` covering `soroswap-router`) before publishing.  Names that look
+-- confusingly like a reservation or a popular contract are still accepted
+-- but queued for moderation in name_flags.
+
+CREATE TABLE IF NOT EXISTS name_reservations (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    publisher_id UUID NOT NULL REFERENCES publishers(id) ON DELETE CASCADE,
+    name         VARCHAR(255) NOT NULL,
+    -- shared::naming::skeleton(name); one reservation per skeleton
+    skeleton     VARCHAR(255) NOT NULL UNIQUE,
+    namespace    BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_name_reservations_publisher
+    ON name_reservations(publisher_id);
+
+CREATE TABLE IF NOT EXISTS name_flags (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id  UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    publisher_id UUID NOT NULL REFERENCES publishers(id) ON DELETE CASCADE,
+    name         VARCHAR(255) NOT NULL,
+    -- [{ "name", "contract_id", "reservation_id", "confusion" }]
+    matches      JSONB NOT NULL DEFAULT '[]',
+    status       TEXT NOT NULL DEFAULT 'pending'
+                 CHECK (status IN ('pending', 'dismissed', 'confirmed')),
+    review_note  TEXT,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    reviewed_at  TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_name_flags_pending
+    ON name_flags(created_at) WHERE status = 'pending';
@@ -0,0 +1,30 @@
+-- Asynchronous source verification jobs.
+-- A job is queued by the API, claimed by a verifier worker and moves through
+-- building -> comparing before it finishes.  `succeeded` means the pipeline
+-- ran to the end; whether the source matched is in `report.status`.
+-- `failed` means it could not run (bad metadata, toolchain install, ...).
+
+CREATE TABLE IF NOT EXISTS verification_jobs (
+    id                 UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id        UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    status             TEXT NOT NULL DEFAULT 'queued'
+                       CHECK (status IN ('queued', 'building', 'comparing',
+                                         'succeeded', 'failed', 'cancelled')),
+    source_code        TEXT NOT NULL,
+    compiler_version   VARCHAR(50) NOT NULL,
+    build_params       JSONB NOT NULL DEFAULT '{}',
+    deployed_wasm_hash VARCHAR(64) NOT NULL,
+    -- verifier::VerificationReport
+    report             JSONB,
+    error_message      TEXT,
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at         TIMESTAMPTZ,
+    finished_at        TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_verification_jobs_contract
+    ON verification_jobs(contract_id, created_at DESC);
+
+-- Workers claim the oldest queued job
+CREATE INDEX IF NOT EXISTS idx_verification_jobs_queued
+    ON verification_jobs(created_at) WHERE status = 'queued';
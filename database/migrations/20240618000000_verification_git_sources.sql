@@ -0,0 +1,13 @@
+-- Verification jobs built from a git repository instead of inline source.
+-- The repository, commit and crate path are kept as the job's provenance.
+
+ALTER TABLE verification_jobs
+    ALTER COLUMN source_code DROP NOT NULL,
+    ADD COLUMN IF NOT EXISTS source_repository TEXT,
+    ADD COLUMN IF NOT EXISTS source_commit     VARCHAR(64),
+    ADD COLUMN IF NOT EXISTS source_path       TEXT;
+
+ALTER TABLE verification_jobs
+    ADD CONSTRAINT verification_jobs_source_check
+    CHECK (source_code IS NOT NULL
+           OR (source_repository IS NOT NULL AND source_commit IS NOT NULL));
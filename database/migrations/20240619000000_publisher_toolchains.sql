@@ -0,0 +1,11 @@
+-- Toolchain that last reproduced a publisher's contract.  Verifications
+-- with an unknown compiler version try it before the rest of the matrix.
+
+CREATE TABLE IF NOT EXISTS publisher_toolchains (
+    publisher_id UUID PRIMARY KEY REFERENCES publishers(id) ON DELETE CASCADE,
+    rustc        VARCHAR(50) NOT NULL,
+    soroban_sdk  VARCHAR(50) NOT NULL,
+    -- Contract whose verification established it
+    contract_id  UUID REFERENCES contracts(id) ON DELETE SET NULL,
+    updated_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,5 @@
+-- Deployed module supplied with a verification job.  When the rebuild does
+-- not match, the report lists which wasm sections differ from it.
+
+ALTER TABLE verification_jobs
+    ADD COLUMN IF NOT EXISTS deployed_wasm BYTEA;
@@ -0,0 +1,21 @@
+-- Signed verification attestations.
+-- Written by the verifier after a successful verification; the signature
+-- covers shared::attestation::message(onchain_contract_id, network,
+-- wasm_hash, source_commit, signed_at).
+
+CREATE TABLE IF NOT EXISTS verification_attestations (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id         UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    job_id              UUID REFERENCES verification_jobs(id) ON DELETE SET NULL,
+    onchain_contract_id VARCHAR(56) NOT NULL,
+    network             TEXT NOT NULL,
+    wasm_hash           VARCHAR(64) NOT NULL,
+    source_commit       VARCHAR(64),
+    -- Whole seconds: the signed timestamp is a unix time
+    signed_at           TIMESTAMPTZ NOT NULL,
+    public_key          VARCHAR(64) NOT NULL,
+    signature           VARCHAR(128) NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_verification_attestations_contract
+    ON verification_attestations(contract_id, signed_at DESC);
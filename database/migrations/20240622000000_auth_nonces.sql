@@ -0,0 +1,14 @@
+-- One-time nonces of OAuth login states and Stellar key challenges.  A nonce
+-- is kept until its token expires so each can be redeemed only once.
+
+CREATE TABLE IF NOT EXISTS used_auth_nonces (
+    nonce       UUID PRIMARY KEY,
+    expires_at  TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_used_auth_nonces_expires
+    ON used_auth_nonces(expires_at);
+
+-- Grants now record the granting identity (`provider:username`) rather than
+-- a Stellar address
+ALTER TABLE publisher_org_grants ALTER COLUMN granted_by TYPE VARCHAR(255);
@@ -0,0 +1,18 @@
+-- Contract audit entries made through admin impersonation name the session
+-- and the admin, so the contract's own history shows who really acted.
+-- Owner changes to contract settings (budgets, approval policies, ...) are
+-- recorded in the same log.
+
+ALTER TYPE audit_action_type ADD VALUE IF NOT EXISTS 'settings_changed';
+
+ALTER TABLE contract_audit_log
+    ADD COLUMN IF NOT EXISTS impersonation_session_id UUID,
+    ADD COLUMN IF NOT EXISTS impersonated_by VARCHAR(255);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_impersonation
+    ON contract_audit_log(impersonation_session_id)
+    WHERE impersonation_session_id IS NOT NULL;
+
+-- Rollbacks and settings changes may be made by named admins
+-- (`provider:username`)
+ALTER TABLE contract_audit_log ALTER COLUMN changed_by TYPE VARCHAR(255);
@@ -0,0 +1,10 @@
+-- Webhook delivery state of budget alerts.  Undelivered alerts are retried
+-- with backoff until delivered or out of attempts.
+
+ALTER TABLE budget_alerts
+    ADD COLUMN IF NOT EXISTS delivery_attempts INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS last_delivery_error TEXT,
+    ADD COLUMN IF NOT EXISTS next_delivery_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_budget_alerts_undelivered
+    ON budget_alerts(next_delivery_at) WHERE notified_at IS NULL;
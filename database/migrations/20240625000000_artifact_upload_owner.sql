@@ -0,0 +1,7 @@
+-- Who started each artifact upload.  Wasm and source bundles are uploaded by
+-- the contract's publisher, audit report PDFs by the auditor filing them;
+-- only that uploader may complete the upload or, for a report, submit it.
+
+ALTER TABLE artifact_uploads
+    ADD COLUMN IF NOT EXISTS publisher_id UUID REFERENCES publishers(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS auditor_id   UUID REFERENCES auditors(id);
@@ -0,0 +1,13 @@
+-- shared::naming::skeleton now folds `i` to `l` as it already did `I`, so a
+-- name and its upper-case spelling share one skeleton.  Rewrite the stored
+-- skeletons to match; where two reservations now collide, the older one
+-- keeps the name.
+
+DELETE FROM name_reservations r
+ USING name_reservations older
+ WHERE replace(r.skeleton, 'i', 'l') = replace(older.skeleton, 'i', 'l')
+   AND (older.created_at, older.id) < (r.created_at, r.id);
+
+UPDATE name_reservations
+   SET skeleton = replace(skeleton, 'i', 'l')
+ WHERE skeleton LIKE '%i%';
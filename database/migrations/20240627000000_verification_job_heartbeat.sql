@@ -0,0 +1,10 @@
+-- Liveness of running verification jobs.  The worker building a job
+-- refreshes heartbeat_at every few seconds; a building or comparing job whose
+-- heartbeat has gone quiet is reclaimed by another worker.
+
+ALTER TABLE verification_jobs
+    ADD COLUMN IF NOT EXISTS heartbeat_at TIMESTAMPTZ;
+
+UPDATE verification_jobs
+   SET heartbeat_at = started_at
+ WHERE heartbeat_at IS NULL AND status IN ('building', 'comparing');
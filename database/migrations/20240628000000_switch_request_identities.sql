@@ -0,0 +1,15 @@
+-- Login identities behind switch requests and decisions.  One session can
+-- prove several Stellar addresses, so the two-person rule is also enforced
+-- per identity: the requester's identity may not decide, and each identity
+-- decides at most once per request.
+
+ALTER TABLE deployment_switch_requests
+    ADD COLUMN IF NOT EXISTS requested_by_identity UUID
+        REFERENCES external_identities(id) ON DELETE SET NULL;
+
+ALTER TABLE deployment_switch_decisions
+    ADD COLUMN IF NOT EXISTS identity_id UUID
+        REFERENCES external_identities(id) ON DELETE SET NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_switch_decisions_identity
+    ON deployment_switch_decisions(request_id, identity_id);
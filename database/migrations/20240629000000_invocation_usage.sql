@@ -0,0 +1,15 @@
+-- Invocation costs recorded by the indexer (indexer/src/usage.rs) as
+-- `cpu_instructions` and `invocation_fee` performance metrics, which the
+-- budget monitor compares against contract budgets.  Each row carries its
+-- transaction hash so re-reading a page records nothing twice; the cursor
+-- remembers where the last ingestion pass stopped.
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_performance_metrics_tx
+    ON performance_metrics (contract_id, metric_type, (metadata ->> 'tx_hash'))
+    WHERE metadata ->> 'tx_hash' IS NOT NULL;
+
+CREATE TABLE IF NOT EXISTS indexer_cursors (
+    name       TEXT PRIMARY KEY,
+    cursor     TEXT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
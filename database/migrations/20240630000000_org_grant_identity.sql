@@ -0,0 +1,16 @@
+-- Org grants record the granting identity by id instead of a
+-- `provider:username` label, so the grantor's username is not copied into
+-- another table and the reference clears when the identity is erased.
+
+ALTER TABLE publisher_org_grants
+    ADD COLUMN IF NOT EXISTS granted_by_identity UUID
+        REFERENCES external_identities(id) ON DELETE SET NULL;
+
+UPDATE publisher_org_grants g
+   SET granted_by_identity = i.id
+  FROM external_identities i
+ WHERE g.granted_by_identity IS NULL
+   AND g.granted_by = i.provider || ':' || COALESCE(i.username, i.subject);
+
+ALTER TABLE publisher_org_grants DROP COLUMN IF EXISTS granted_by;
+ALTER TABLE publisher_org_grants RENAME COLUMN granted_by_identity TO granted_by;
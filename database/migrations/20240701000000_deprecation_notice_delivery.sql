@@ -0,0 +1,6 @@
+-- Deprecation notices are pushed to the dependent owner's webhook (the one
+-- configured with the contract's budget); record the outcome per notice.
+
+ALTER TABLE deprecation_notices
+    ADD COLUMN IF NOT EXISTS notified_at         TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS last_delivery_error TEXT;
@@ -0,0 +1,6 @@
+-- When each upload's pre-signed PUT stops working.  A pending wasm upload
+-- counts against the publisher's storage quota only until then; uploads
+-- from before this column never expire into the quota.
+
+ALTER TABLE artifact_uploads
+    ADD COLUMN IF NOT EXISTS upload_expires_at TIMESTAMPTZ;
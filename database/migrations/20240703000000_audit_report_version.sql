@@ -0,0 +1,9 @@
+-- The contract version each audit report covers.  The audit tier of a
+-- version is only granted on a report filed against that version; reports
+-- from before this column cover no version.
+
+ALTER TABLE audit_reports
+    ADD COLUMN IF NOT EXISTS contract_version_id UUID REFERENCES contract_versions(id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_audit_reports_version
+    ON audit_reports(contract_version_id, created_at DESC);
@@ -0,0 +1,19 @@
+-- At most one pending switch request per contract, enforced by the database
+-- so two concurrent requests cannot both be queued.  Requests past their
+-- expiry are only marked expired when next touched, so close those (and any
+-- live duplicates but the newest) before adding the index.
+
+UPDATE deployment_switch_requests
+   SET status = 'expired', resolved_at = NOW()
+ WHERE status = 'pending' AND expires_at <= NOW();
+
+UPDATE deployment_switch_requests r
+   SET status = 'expired', resolved_at = NOW()
+ WHERE status = 'pending'
+   AND EXISTS (SELECT 1 FROM deployment_switch_requests newer
+                WHERE newer.contract_id = r.contract_id
+                  AND newer.status = 'pending'
+                  AND newer.created_at > r.created_at);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_switch_requests_one_pending
+    ON deployment_switch_requests(contract_id) WHERE status = 'pending';
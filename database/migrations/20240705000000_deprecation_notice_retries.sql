@@ -0,0 +1,9 @@
+-- Failed deprecation notice deliveries are retried with the same backoff
+-- as budget alerts (see budget_monitor.rs).
+
+ALTER TABLE deprecation_notices
+    ADD COLUMN IF NOT EXISTS delivery_attempts INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS next_delivery_at  TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_deprecation_notices_undelivered
+    ON deprecation_notices(next_delivery_at) WHERE notified_at IS NULL;
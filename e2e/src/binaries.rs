@@ -0,0 +1,47 @@
+//! Building the binaries under test.
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+
+static BUILT: OnceLock<Mutex<HashMap<(PathBuf, String), PathBuf>>> = OnceLock::new();
+
+/// Build `bin` of `package` in the workspace at `manifest` and return the
+/// executable's path.  Each binary is built once per test process.
+pub fn cargo_bin(manifest: &Path, package: &str, bin: &str) -> Result<PathBuf> {
+    let mut built = BUILT.get_or_init(Default::default).lock().unwrap();
+    let key = (manifest.to_path_buf(), bin.to_string());
+    if let Some(path) = built.get(&key) {
+        return Ok(path.clone());
+    }
+
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(cargo)
+        .args([
+            "build",
+            "--quiet",
+            "--message-format=json-render-diagnostics",
+        ])
+        .arg("--manifest-path")
+        .arg(manifest)
+        .args(["-p", package, "--bin", bin])
+        .stderr(Stdio::inherit())
+        .output()
+        .with_context(|| format!("failed to run cargo build for {}", bin))?;
+    if !output.status.success() {
+        bail!("building {} ({}) failed", bin, manifest.display());
+    }
+
+    let path = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|msg| msg["reason"] == "compiler-artifact" && msg["target"]["name"] == bin)
+        .find_map(|msg| msg["executable"].as_str().map(PathBuf::from))
+        .with_context(|| format!("cargo did not report an executable for {}", bin))?;
+
+    built.insert(key, path.clone());
+    Ok(path)
+}
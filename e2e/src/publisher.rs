@@ -0,0 +1,79 @@
+//! Publisher accounts whose keys the tests hold, so they can sign in with
+//! the key the way a wallet would.
+
+use ring::signature::{Ed25519KeyPair, KeyPair};
+
+/// Version byte of a `G...` account id (ed25519 public key) strkey.
+const ACCOUNT_ID_VERSION: u8 = 6 << 3;
+
+/// A Stellar account with a known key.
+pub struct TestPublisher {
+    pair: Ed25519KeyPair,
+    address: String,
+}
+
+impl TestPublisher {
+    /// Deterministic account for `seed`; give each publisher in a test its
+    /// own seed.
+    pub fn from_seed(seed: u8) -> Self {
+        let pair = Ed25519KeyPair::from_seed_unchecked(&[seed; 32])
+            .expect("a 32-byte seed is a valid Ed25519 key");
+        let mut raw = vec![ACCOUNT_ID_VERSION];
+        raw.extend_from_slice(pair.public_key().as_ref());
+        let checksum = crc16_xmodem(&raw);
+        raw.extend_from_slice(&checksum.to_le_bytes());
+        Self {
+            pair,
+            address: base32_encode(&raw),
+        }
+    }
+
+    /// The account's `G...` address.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Hex Ed25519 signature over the UTF-8 bytes of `message`.
+    pub fn sign(&self, message: &str) -> String {
+        self.pair
+            .sign(message.as_bytes())
+            .as_ref()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+}
+
+fn base32_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+    let mut out = String::new();
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for byte in data {
+        buffer = (buffer << 8) | u32::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        out.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
+
+fn crc16_xmodem(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for byte in data {
+        crc ^= u16::from(*byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
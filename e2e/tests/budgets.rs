@@ -0,0 +1,121 @@
+//! Budget alerts raised from usage the indexer ingested.  Needs Docker, so
+//! ignored by default: run with `cargo test -- --ignored`.
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+use registry_e2e::{Harness, TestPublisher, BUDGET_CHECK_INTERVAL_SECONDS};
+use serde_json::{json, Value};
+
+const CONTRACT_ID: &str = "CCJZ5DGASBWQXR5MPFCJXMBI333XE5U3FSJTNQU7RIKE3P5GN2K2WYD5";
+
+/// A successful `transfer` invocation of `CONTRACT_ID`, as `getTransactions`
+/// returns it with `xdrFormat: json`.
+fn invocation(cpu_instructions: u32, fee_charged: i64) -> Value {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    json!({
+        "status": "SUCCESS",
+        "txHash": "cd".repeat(32),
+        "ledger": 1_000,
+        "createdAt": now,
+        "envelopeJson": { "tx": { "tx": {
+            "operations": [{ "body": { "invoke_host_function": {
+                "host_function": { "invoke_contract": {
+                    "contract_address": CONTRACT_ID,
+                    "function_name": "transfer",
+                    "args": [],
+                }},
+                "auth": [],
+            }}}],
+            "ext": { "v1": { "resources": { "instructions": cpu_instructions } } },
+        }, "signatures": [] }},
+        "resultJson": { "fee_charged": fee_charged.to_string() },
+    })
+}
+
+#[tokio::test]
+#[ignore = "needs Docker"]
+async fn ingested_usage_raises_a_budget_alert() -> Result<()> {
+    let registry = Harness::start().await?;
+    let publisher = TestPublisher::from_seed(1);
+    let token = registry.sign_in(&publisher).await?;
+    registry.cli_login(&token).await?;
+
+    registry
+        .cli([
+            "--network",
+            "testnet",
+            "publish",
+            "--contract-id",
+            CONTRACT_ID,
+            "--name",
+            "e2e-budgeted",
+            "--category",
+            "token",
+            "--publisher",
+            publisher.address(),
+        ])
+        .await?
+        .success()?;
+    let (_, list) = registry.get("/api/contracts?query=e2e-budgeted").await?;
+    let id = list["contracts"][0]["id"]
+        .as_str()
+        .with_context(|| format!("published contract not listed: {}", list))?
+        .to_string();
+
+    // Setting a budget needs a publisher session; seed it directly
+    let db = sqlx::PgPool::connect(&registry.database_url).await?;
+    sqlx::query(
+        "INSERT INTO contract_budgets (contract_id, max_cpu_instructions, webhook_url, updated_by)
+         VALUES ($1::uuid, 1000000, 'https://hooks.example.com/budget', $2)",
+    )
+    .bind(&id)
+    .bind(publisher.address())
+    .execute(&db)
+    .await?;
+
+    registry.rpc.respond(
+        "getTransactions",
+        json!({
+            "transactions": [invocation(2_500_000, 100_000)],
+            "latestLedger": 1_000,
+            "cursor": "4294967296000",
+        }),
+    );
+    let indexer = registry.run_indexer().await?;
+    assert!(
+        indexer.status.success(),
+        "indexer failed: {}",
+        String::from_utf8_lossy(&indexer.stderr)
+    );
+
+    let deadline = Instant::now() + Duration::from_secs(BUDGET_CHECK_INTERVAL_SECONDS * 10);
+    let status = loop {
+        let (_, status) = registry
+            .get(&format!("/api/contracts/{}/budget/status", id))
+            .await?;
+        if status["recent_alerts"]
+            .as_array()
+            .is_some_and(|a| !a.is_empty())
+        {
+            break status;
+        }
+        if Instant::now() > deadline {
+            bail!("no budget alert raised: {}\n{}", status, registry.api_log());
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    };
+
+    let alert = &status["recent_alerts"][0];
+    assert_eq!(alert["kind"], "cpu_instructions", "{}", status);
+    assert_eq!(alert["observed_value"], 2_500_000, "{}", status);
+
+    // The owner's webhook endpoint is not part of the public status
+    assert_eq!(status["webhook_configured"], true, "{}", status);
+    assert!(status["budget"].get("webhook_url").is_none(), "{}", status);
+
+    Ok(())
+}
@@ -0,0 +1,185 @@
+//! Cross-service flows.  They need Docker for Postgres, so they are ignored
+//! by default: run them with `cargo test -- --ignored`.
+
+use anyhow::{Context, Result};
+use registry_e2e::{Harness, TestPublisher};
+use serde_json::json;
+
+const CONTRACT_ID: &str = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC";
+
+#[tokio::test]
+#[ignore = "needs Docker"]
+async fn publish_index_verify_deploy_switch() -> Result<()> {
+    let registry = Harness::start().await?;
+    let publisher = TestPublisher::from_seed(1);
+    let token = registry.sign_in(&publisher).await?;
+    registry.cli_login(&token).await?;
+
+    // Publish through the CLI
+    registry
+        .cli([
+            "--network",
+            "testnet",
+            "publish",
+            "--contract-id",
+            CONTRACT_ID,
+            "--name",
+            "e2e-token",
+            "--category",
+            "token",
+            "--publisher",
+            publisher.address(),
+        ])
+        .await?
+        .success()?;
+
+    let (status, list) = registry.get("/api/contracts?query=e2e-token").await?;
+    assert!(status.is_success(), "{}", list);
+    let id = list["contracts"][0]["id"]
+        .as_str()
+        .with_context(|| format!("published contract not listed: {}", list))?
+        .to_string();
+
+    let info = registry
+        .cli(["--network", "testnet", "info", &id])
+        .await?
+        .success()?;
+    assert!(info.stdout.contains("e2e-token"), "{}", info.stdout);
+
+    // Index against the mock RPC
+    let indexer = registry.run_indexer().await?;
+    assert!(
+        indexer.status.success(),
+        "indexer failed: {}",
+        String::from_utf8_lossy(&indexer.stderr)
+    );
+
+    // Verify
+    let (status, body) = registry
+        .post(
+            "/api/contracts/verify",
+            json!({
+                "contract_id": CONTRACT_ID,
+                "source_code": "#![no_std]",
+                "build_params": {},
+                "compiler_version": "1.79.0",
+            }),
+        )
+        .await?;
+    assert!(status.is_success(), "{}", body);
+    assert_eq!(body["status"], "pending");
+
+    // Deploy to green, pass its health checks and switch over
+    let (status, body) = registry
+        .post(
+            "/api/deployments/green",
+            json!({ "contract_id": CONTRACT_ID, "wasm_hash": "ab".repeat(32) }),
+        )
+        .await?;
+    assert!(status.is_success(), "{}", body);
+
+    for _ in 0..3 {
+        let (status, body) = registry
+            .post(
+                "/api/deployments/health",
+                json!({ "contract_id": CONTRACT_ID, "environment": "Green", "passed": true }),
+            )
+            .await?;
+        assert!(status.is_success(), "{}", body);
+    }
+
+    let (status, body) = registry
+        .post(
+            "/api/deployments/switch",
+            json!({ "contract_id": CONTRACT_ID }),
+        )
+        .await?;
+    assert!(status.is_success(), "{}", body);
+
+    let (_, deployments) = registry
+        .get(&format!(
+            "/api/contracts/{}/deployments/status",
+            CONTRACT_ID
+        ))
+        .await?;
+    assert_eq!(
+        deployments["active"]["environment"], "Green",
+        "{}",
+        deployments
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs Docker"]
+async fn cli_reports_api_errors() -> Result<()> {
+    let registry = Harness::start().await?;
+
+    let output = registry
+        .cli([
+            "--network",
+            "testnet",
+            "info",
+            "00000000-0000-0000-0000-000000000000",
+        ])
+        .await?;
+    assert!(!output.status.success());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs Docker"]
+async fn publishing_needs_the_publisher_key() -> Result<()> {
+    let registry = Harness::start().await?;
+    let owner = TestPublisher::from_seed(1);
+    let other = TestPublisher::from_seed(2);
+    let owner_token = registry.sign_in(&owner).await?;
+    let other_token = registry.sign_in(&other).await?;
+
+    let body = json!({
+        "contract_id": CONTRACT_ID,
+        "name": "e2e-owned",
+        "network": "testnet",
+        "tags": [],
+        "publisher_address": owner.address(),
+    });
+
+    // No session: the body address proves nothing
+    let (status, response) = registry
+        .post_as("not-a-session", "/api/contracts", body.clone())
+        .await?;
+    assert_eq!(status.as_u16(), 401, "{}", response);
+
+    // Another publisher's session
+    let (status, response) = registry
+        .post_as(&other_token, "/api/contracts", body.clone())
+        .await?;
+    assert_eq!(status.as_u16(), 403, "{}", response);
+    assert_eq!(response["error"], "NotAuthorizedPublisher", "{}", response);
+
+    // Addresses nobody signed in with are not registered on the fly
+    let stranger = TestPublisher::from_seed(3);
+    let (status, response) = registry
+        .post_as(
+            &owner_token,
+            "/api/contracts",
+            json!({
+                "contract_id": CONTRACT_ID,
+                "name": "e2e-stranger",
+                "network": "testnet",
+                "tags": [],
+                "publisher_address": stranger.address(),
+            }),
+        )
+        .await?;
+    assert_eq!(status.as_u16(), 404, "{}", response);
+
+    let (status, response) = registry
+        .post_as(&owner_token, "/api/contracts", body)
+        .await?;
+    assert!(status.is_success(), "{}", response);
+
+    Ok(())
+}
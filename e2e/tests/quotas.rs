@@ -0,0 +1,59 @@
+//! Publisher quotas under concurrent publishes.  Needs Docker, so ignored by
+//! default: run with `cargo test -- --ignored`.
+
+use anyhow::{Context, Result};
+use registry_e2e::{Harness, TestPublisher};
+use serde_json::{json, Value};
+
+fn publish_body(publisher: &TestPublisher, n: usize) -> Value {
+    json!({
+        "contract_id": format!("C{:0>55}", n),
+        "name": format!("e2e-quota-{}", n),
+        "network": "testnet",
+        "tags": [],
+        "publisher_address": publisher.address(),
+    })
+}
+
+#[tokio::test]
+#[ignore = "needs Docker"]
+async fn concurrent_publishes_cannot_overrun_the_contract_quota() -> Result<()> {
+    let registry = Harness::start().await?;
+    let publisher = TestPublisher::from_seed(1);
+    let token = registry.sign_in(&publisher).await?;
+    let publish = |n| registry.post_as(&token, "/api/contracts", publish_body(&publisher, n));
+
+    let (status, first) = publish(0).await?;
+    assert!(status.is_success(), "{}", first);
+    let publisher_id = first["publisher_id"]
+        .as_str()
+        .with_context(|| format!("publish response has no publisher_id: {}", first))?;
+
+    let (status, body) = registry
+        .put(
+            &format!("/api/publishers/{}/quota", publisher_id),
+            json!({ "tier": "free", "max_contracts": 2 }),
+        )
+        .await?;
+    assert!(status.is_success(), "{}", body);
+
+    // One slot left; four publishes race for it
+    let results = tokio::join!(publish(1), publish(2), publish(3), publish(4));
+    let statuses = [results.0?, results.1?, results.2?, results.3?];
+    let published = statuses
+        .iter()
+        .filter(|(status, _)| status.is_success())
+        .count();
+    assert_eq!(published, 1, "{:?}", statuses);
+    for (status, body) in statuses.iter().filter(|(status, _)| !status.is_success()) {
+        assert_eq!(status.as_u16(), 403, "{}", body);
+        assert_eq!(body["error"], "QuotaExceeded", "{}", body);
+    }
+
+    let (_, quota) = registry
+        .get(&format!("/api/publishers/{}/quota", publisher_id))
+        .await?;
+    assert_eq!(quota["usage"]["contracts"], 2, "{}", quota);
+
+    Ok(())
+}
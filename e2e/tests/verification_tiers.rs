@@ -0,0 +1,77 @@
+//! Verification tier attestation against a real database.  Needs Docker, so
+//! ignored by default: run with `cargo test -- --ignored`.
+
+use anyhow::{Context, Result};
+use registry_e2e::{Harness, TestPublisher, NAMED_ADMIN_SEED};
+use serde_json::json;
+
+const CONTRACT_ID: &str = "CBQHNAXSI55GX2GN6D67GK7BHVPSLJUGZQEU7WJ5LKR5PNUCGLIMAO4K";
+
+#[tokio::test]
+#[ignore = "needs Docker"]
+async fn bare_security_audit_does_not_unlock_the_audit_tier() -> Result<()> {
+    let registry = Harness::start().await?;
+    let publisher = TestPublisher::from_seed(1);
+    let token = registry.sign_in(&publisher).await?;
+    registry.cli_login(&token).await?;
+
+    registry
+        .cli([
+            "--network",
+            "testnet",
+            "publish",
+            "--contract-id",
+            CONTRACT_ID,
+            "--name",
+            "e2e-audited",
+            "--category",
+            "token",
+            "--publisher",
+            publisher.address(),
+        ])
+        .await?
+        .success()?;
+
+    let (_, list) = registry.get("/api/contracts?query=e2e-audited").await?;
+    let id = list["contracts"][0]["id"]
+        .as_str()
+        .with_context(|| format!("published contract not listed: {}", list))?
+        .to_string();
+
+    // The API has no endpoint to register versions; seed one directly
+    let db = sqlx::PgPool::connect(&registry.database_url).await?;
+    sqlx::query(
+        "INSERT INTO contract_versions (contract_id, version, wasm_hash)
+         VALUES ($1::uuid, '1.0.0', $2)",
+    )
+    .bind(&id)
+    .bind("0".repeat(64))
+    .execute(&db)
+    .await?;
+
+    // Anyone can open a security audit; it is not an auditor's report
+    let (status, body) = registry
+        .post(
+            &format!("/api/contracts/{}/security-audit", id),
+            json!({ "auditor": "self-proclaimed auditor" }),
+        )
+        .await?;
+    assert!(status.is_success(), "{}", body);
+
+    // Attestations are attributed to a named admin, not the shared token
+    let path = format!("/api/contracts/{}/versions/1.0.0/verification-tier", id);
+    let (status, body) = registry.put(&path, json!({ "tier": "audit" })).await?;
+    assert_eq!(status.as_u16(), 403, "{}", body);
+    assert_eq!(body["error"], "NamedAdminRequired", "{}", body);
+
+    let admin = registry
+        .sign_in(&TestPublisher::from_seed(NAMED_ADMIN_SEED))
+        .await?;
+    let (status, body) = registry
+        .put_as(&admin, &path, json!({ "tier": "audit" }))
+        .await?;
+    assert_eq!(status.as_u16(), 400, "{}", body);
+    assert_eq!(body["error"], "AuditRequired", "{}", body);
+
+    Ok(())
+}
@@ -57,6 +57,20 @@ export default function HealthWidget({ contract }: HealthWidgetProps) {
         </div>
       </div>
 
+      {health.deprecated_dependencies && health.deprecated_dependencies.length > 0 && (
+        <div className="mt-2 text-xs border-t border-black/10 dark:border-white/10 pt-2 opacity-90">
+          <p className="font-semibold mb-1">Deprecated dependencies:</p>
+          <ul className="list-disc list-inside space-y-1">
+            {health.deprecated_dependencies.map((dep) => (
+              <li key={dep.contract_id}>
+                <span className="font-medium">{dep.dependency_name}</span>: {dep.reason}
+                {dep.replacement_name && <> (use {dep.replacement_name})</>}
+              </li>
+            ))}
+          </ul>
+        </div>
+      )}
+
       {health.recommendations && health.recommendations.length > 0 && (
         <div className="mt-2 text-xs border-t border-black/10 dark:border-white/10 pt-2 opacity-90">
           <p className="font-semibold mb-1">Recommendations:</p>
@@ -23,9 +23,19 @@ export interface ContractHealth {
   audit_date?: string;
   total_score: number;
   recommendations: string[];
+  deprecated_dependencies: DeprecatedDependency[];
   updated_at: string;
 }
 
+export interface DeprecatedDependency {
+  dependency_name: string;
+  contract_id: string;
+  reason: string;
+  replacement_contract_id?: string;
+  replacement_name?: string;
+  deprecated_at: string;
+}
+
 export interface ContractVersion {
   id: string;
   contract_id: string;